@@ -4,7 +4,7 @@ use pep508_rs::MarkerTree;
 use pixi_cli::cli_config::GitRev;
 use pixi_consts::consts;
 use pixi_core::DependencyType;
-use pixi_manifest::{FeaturesExt, SpecType};
+use pixi_manifest::{FeaturesExt, ManifestKind, SolveStrategy, SpecType};
 use pixi_pypi_spec::{PixiPypiSource, PixiPypiSpec, PypiPackageName, VersionOrStar};
 use rattler_conda_types::{PackageName, Platform};
 use tempfile::TempDir;
@@ -133,6 +133,61 @@ async fn add_with_channel() {
     );
 }
 
+/// Test adding a conda dependency to a workspace that is defined in a
+/// `pyproject.toml` and reading it back.
+#[tokio::test]
+async fn add_conda_dependency_to_pyproject() {
+    setup_tracing();
+
+    // A pyproject based workspace always depends on `python`
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("python", "3.12").finish());
+    package_database.add_package(Package::build("bar", "1").finish());
+    let local_channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    fs_err::write(
+        pixi.workspace_path().join(consts::PYPROJECT_MANIFEST),
+        format!(
+            r#"[project]
+name = "pyproject-workspace"
+version = "0.1.0"
+
+[tool.pixi.workspace]
+channels = ["{channel}"]
+platforms = ["{platform}"]
+"#,
+            channel = local_channel.url(),
+            platform = Platform::current()
+        ),
+    )
+    .unwrap();
+
+    pixi.add("bar").await.unwrap();
+
+    // The dependency should end up in the `tool.pixi` table of the pyproject
+    let manifest = pixi.manifest_contents().unwrap();
+    assert!(manifest.contains("[tool.pixi.dependencies]"));
+    assert!(manifest.contains("bar = \">=1,<2\""));
+
+    // Loading the workspace again should pick up the dependency
+    let workspace = pixi.workspace().unwrap();
+    assert_eq!(workspace.workspace.provenance.kind, ManifestKind::Pyproject);
+    assert!(
+        workspace
+            .default_environment()
+            .combined_dependencies(None)
+            .contains_key(&PackageName::try_from("bar").unwrap())
+    );
+
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "bar ==1"
+    ));
+}
+
 /// Test that we get the union of all packages in the lock file for the run,
 /// build and host
 #[tokio::test]
@@ -562,6 +617,9 @@ index-url = "{index_url}"
                             index: None,
                         },
                         env_markers: MarkerTree::default(),
+                        no_build_isolation: false,
+                        no_build: false,
+                        no_binary: false,
                     }
                 );
             }
@@ -650,6 +708,326 @@ async fn add_unconstrained_dependency() {
     "###);
 }
 
+/// Test that a build string constraint is written to the manifest and honored
+/// by the solver.
+#[tokio::test]
+async fn add_dependency_with_build_string() {
+    setup_tracing();
+
+    // Without a build constraint the solver prefers the higher build number.
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(
+        Package::build("numpy", "1.26.0")
+            .with_build("mkl_0")
+            .with_build_number(0)
+            .finish(),
+    );
+    package_database.add_package(
+        Package::build("numpy", "1.26.0")
+            .with_build("openblas_1")
+            .with_build_number(1)
+            .finish(),
+    );
+    let local_channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(local_channel.url()).await.unwrap();
+
+    let numpy_spec = |pixi: &PixiControl| {
+        pixi.workspace()
+            .unwrap()
+            .workspace
+            .value
+            .default_feature()
+            .combined_dependencies(None)
+            .unwrap_or_default()
+            .get_single("numpy")
+            .unwrap()
+            .unwrap()
+            .clone()
+            .to_toml_value()
+            .to_string()
+    };
+
+    pixi.add("numpy=1.26=*mkl*").await.unwrap();
+    assert_eq!(
+        numpy_spec(&pixi),
+        r#"{ version = "1.26.*", build = "*mkl*" }"#
+    );
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "numpy 1.26.0 mkl_0"
+    ));
+
+    // A build constraint without a version keeps the build glob when the
+    // version is pinned.
+    pixi.add("numpy[build=*mkl*]").await.unwrap();
+    let spec = numpy_spec(&pixi);
+    assert!(spec.contains(r#"build = "*mkl*""#), "{spec}");
+    assert!(spec.contains("version = "), "{spec}");
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(!lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "numpy[build=openblas_1]"
+    ));
+}
+
+/// Test that `--feature` writes the dependency to the feature table and
+/// creates that table if the feature does not exist yet.
+#[tokio::test]
+async fn add_dependency_to_feature() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("bar", "1").finish());
+    let local_channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(local_channel.url()).await.unwrap();
+
+    pixi.add("bar").with_feature("test").await.unwrap();
+
+    let manifest = pixi.manifest_contents().unwrap();
+    assert!(
+        manifest.contains("[feature.test.dependencies]"),
+        "expected a feature table in:\n{manifest}"
+    );
+
+    let workspace = pixi.workspace().unwrap();
+    let bar = PackageName::try_from("bar").unwrap();
+    assert!(
+        workspace
+            .workspace
+            .value
+            .feature("test")
+            .expect("feature 'test' is missing")
+            .combined_dependencies(None)
+            .unwrap_or_default()
+            .contains_key(&bar)
+    );
+    assert!(
+        !workspace
+            .workspace
+            .value
+            .default_feature()
+            .combined_dependencies(None)
+            .unwrap_or_default()
+            .contains_key(&bar),
+        "`bar` should not be added to the default feature"
+    );
+}
+
+/// Test that adding a package keeps the locked versions of unrelated packages,
+/// unless `--no-preserve` is passed.
+#[tokio::test]
+async fn add_preserves_locked_versions() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("bar", "1").finish());
+
+    // Write the repodata to disk
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+
+    // Add `bar` without an upper bound so a newer version could be selected
+    pixi.add("bar >=1").await.unwrap();
+
+    // Publish a newer version of `bar` and some new packages
+    package_database.add_package(Package::build("bar", "2").finish());
+    package_database.add_package(Package::build("foo", "1").finish());
+    package_database.add_package(Package::build("baz", "1").finish());
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    // Adding an unrelated package should not bump `bar`
+    pixi.add("foo").await.unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(
+        lock.contains_match_spec(
+            consts::DEFAULT_ENVIRONMENT_NAME,
+            Platform::current(),
+            "bar ==1"
+        ),
+        "expected `bar` to stay on the locked version"
+    );
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "foo ==1"
+    ));
+
+    // With `--no-preserve` the environment is re-solved from scratch
+    pixi.add("baz").with_no_preserve(true).await.unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(
+        lock.contains_match_spec(
+            consts::DEFAULT_ENVIRONMENT_NAME,
+            Platform::current(),
+            "bar ==2"
+        ),
+        "expected `bar` to be updated because the locked versions were not preserved"
+    );
+}
+
+/// Test that a conflicting `pixi add` names the conflicting specs and the
+/// package they disagree on.
+#[tokio::test]
+async fn add_explains_conflict() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("shared", "1").finish());
+    package_database.add_package(Package::build("shared", "2").finish());
+    package_database.add_package(
+        Package::build("foo", "1")
+            .with_dependency("shared ==1")
+            .finish(),
+    );
+    package_database.add_package(
+        Package::build("bar", "1")
+            .with_dependency("shared ==2")
+            .finish(),
+    );
+    let channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(channel.url()).await.unwrap();
+    pixi.add("foo").with_install(false).await.unwrap();
+
+    let error = pixi.add("bar").with_install(false).await.unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("'foo"), "{message}");
+    assert!(message.contains("'bar"), "{message}");
+    assert!(message.contains("'shared'"), "{message}");
+    let report = format!("{error:?}");
+    assert!(
+        !report.contains("The following packages are incompatible"),
+        "the full solver output should only be shown with `--explain`: {report}"
+    );
+    assert!(
+        report.contains("failed to solve requirements of environment"),
+        "the chain of errors up to the failed solve should be kept: {report}"
+    );
+
+    let error = pixi
+        .add("bar")
+        .with_install(false)
+        .with_explain(true)
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{error:?}").contains("The following packages are incompatible"),
+        "{error:?}"
+    );
+}
+
+/// Test that `--build-string` constrains the build of the added package, and
+/// that a glob that matches no build is rejected.
+#[tokio::test]
+async fn add_with_build_string() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("foo", "1").with_build("cpu_0").finish());
+    package_database.add_package(
+        Package::build("foo", "1")
+            .with_build("cuda_0")
+            .with_build_number(1)
+            .finish(),
+    );
+    let channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(channel.url()).await.unwrap();
+    let manifest = pixi.manifest_contents().unwrap();
+
+    pixi.add("foo")
+        .with_build_string("*gpu*")
+        .with_install(false)
+        .await
+        .unwrap_err();
+    assert_eq!(pixi.manifest_contents().unwrap(), manifest);
+
+    pixi.add("foo")
+        .with_build_string("*cpu*")
+        .with_install(false)
+        .await
+        .unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "foo ==1 cpu_0"
+    ));
+    assert!(
+        pixi.manifest_contents().unwrap().contains("*cpu*"),
+        "the build string should be written to the manifest"
+    );
+}
+
+#[tokio::test]
+async fn add_with_lowest_direct_solve_strategy() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("bar", "1").finish());
+    package_database.add_package(Package::build("bar", "2").finish());
+    let channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(channel.url()).await.unwrap();
+
+    pixi.add("bar>=1")
+        .with_solve_strategy(SolveStrategy::LowestDirect)
+        .with_install(false)
+        .await
+        .unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "bar ==1"
+    ));
+    assert!(
+        !pixi.manifest_contents().unwrap().contains("solve-strategy"),
+        "the manifest should not be changed"
+    );
+
+    // A frozen install keeps the versions that were solved with the override.
+    pixi.install().with_frozen().await.unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "bar ==1"
+    ));
+
+    // Without the override the lock file is out of date, and it is re-solved
+    // with the strategy of the manifest.
+    pixi.install().await.unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_match_spec(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "bar ==2"
+    ));
+}
+
 #[tokio::test]
 async fn pinning_dependency() {
     setup_tracing();
@@ -1289,3 +1667,138 @@ async fn add_pypi_with_index() {
     // asserting index flag
     assert_eq!(spec.source.index(), Some(&pypi_index.index_url()));
 }
+
+/// Test that the dependencies and tasks of a workspace can be queried through
+/// the public API.
+#[tokio::test]
+async fn add_dependency_is_queryable() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("rattler", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+    pixi.add("rattler==1").await.unwrap();
+
+    let manifest = pixi.manifest_contents().unwrap();
+    pixi.update_manifest(&format!(
+        "{manifest}\n[pypi-dependencies]\nboltons = \"*\"\n\n[tasks]\nhello = \"echo hello\"\n"
+    ))
+    .unwrap();
+
+    let workspace = pixi.workspace().unwrap();
+    let dependencies = workspace.dependencies(None).collect::<Vec<_>>();
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(dependencies[0].0, PackageName::from_str("rattler").unwrap());
+    assert_eq!(
+        dependencies[0].1.as_version_spec().unwrap().to_string(),
+        "==1"
+    );
+
+    let pypi_dependencies = workspace.pypi_dependencies(None).collect::<Vec<_>>();
+    assert_eq!(pypi_dependencies.len(), 1);
+    assert_eq!(
+        pypi_dependencies[0].0,
+        PypiPackageName::from_str("boltons").unwrap()
+    );
+
+    let tasks = workspace.tasks(None).unwrap();
+    assert!(tasks.keys().any(|name| name.as_str() == "hello"));
+}
+
+/// Test that `pixi add` keeps the dependency table sorted, unless `--no-sort`
+/// is passed.
+#[tokio::test]
+async fn add_sorts_dependency_table() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("zlib", "1").finish());
+    package_database.add_package(Package::build("bar", "1").finish());
+    package_database.add_package(Package::build("abc", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+    pixi.add("zlib").with_install(false).await.unwrap();
+    pixi.add("bar").with_install(false).await.unwrap();
+
+    let manifest = pixi.manifest_contents().unwrap();
+    let position = |name: &str| {
+        manifest
+            .find(&format!("\n{name} = "))
+            .unwrap_or_else(|| panic!("expected `{name}` in the manifest"))
+    };
+    assert!(position("bar") < position("zlib"));
+
+    // Without sorting the new dependency is appended to the table.
+    pixi.add("abc")
+        .with_install(false)
+        .with_no_sort(true)
+        .await
+        .unwrap();
+    let manifest = pixi.manifest_contents().unwrap();
+    let position = |name: &str| manifest.find(&format!("\n{name} = ")).unwrap();
+    assert!(position("bar") < position("zlib"));
+    assert!(position("zlib") < position("abc"));
+}
+
+/// Test that `--no-lock-update` only edits the manifest, so that several
+/// edits can be locked by a single install.
+#[tokio::test]
+async fn add_with_no_lock_update() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("foo", "1").with_materialize(true).finish());
+    package_database.add_package(Package::build("bar", "1").with_materialize(true).finish());
+    package_database.add_package(Package::build("baz", "1").with_materialize(true).finish());
+    let channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(channel.url()).await.unwrap();
+    pixi.add("foo").await.unwrap();
+    let original_lock_file =
+        fs_err::read_to_string(pixi.workspace_path().join("pixi.lock")).unwrap();
+
+    pixi.add("bar").with_no_lock_update().await.unwrap();
+    // The deprecated `--no-lockfile-update` behaves the same.
+    let mut builder = pixi.add("baz");
+    builder.args.lock_file_update_config.no_lock_file_update = true;
+    builder.await.unwrap();
+
+    let manifest = pixi.manifest_contents().unwrap();
+    assert!(manifest.contains("bar = "));
+    assert!(manifest.contains("baz = "));
+    assert_eq!(
+        fs_err::read_to_string(pixi.workspace_path().join("pixi.lock")).unwrap(),
+        original_lock_file,
+        "the lock file should not be touched by `--no-lock-update`"
+    );
+
+    pixi.install().await.unwrap();
+    let lock = pixi.lock_file().await.unwrap();
+    for name in ["foo", "bar", "baz"] {
+        assert!(lock.contains_conda_package(
+            consts::DEFAULT_ENVIRONMENT_NAME,
+            Platform::current(),
+            name
+        ));
+    }
+}
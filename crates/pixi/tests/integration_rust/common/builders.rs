@@ -31,6 +31,7 @@ use pixi_cli::{
     global, init, install, lock, remove, search, task, update, workspace,
 };
 use pixi_core::DependencyType;
+use pixi_manifest::SolveStrategy;
 
 use super::isolated_config_source;
 use std::{
@@ -103,6 +104,11 @@ impl InitBuilder {
         self.args.platforms = platforms.into_iter().map(|p| p.to_string()).collect();
         self
     }
+    /// Replace an existing `[tool.pixi]` section
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.args.force = force;
+        self
+    }
 }
 
 impl IntoFuture for InitBuilder {
@@ -164,6 +170,8 @@ pub trait HasDependencyConfig: Sized {
             git: Default::default(),
             rev: Default::default(),
             subdir: Default::default(),
+            no_sort: false,
+            no_lock_update: false,
         }
     }
 
@@ -172,6 +180,18 @@ pub trait HasDependencyConfig: Sized {
         self
     }
 
+    /// Don't sort the dependency tables of the manifest
+    fn with_no_sort(mut self, no_sort: bool) -> Self {
+        self.dependency_config().no_sort = no_sort;
+        self
+    }
+
+    /// Only modify the manifest, don't update the lock file
+    fn with_no_lock_update(mut self) -> Self {
+        self.dependency_config().no_lock_update = true;
+        self
+    }
+
     /// Set as a host
     fn set_type(mut self, t: DependencyType) -> Self {
         match t {
@@ -267,6 +287,26 @@ impl AddBuilder {
         self.args.index = index;
         self
     }
+
+    pub fn with_no_preserve(mut self, no_preserve: bool) -> Self {
+        self.args.no_preserve = no_preserve;
+        self
+    }
+
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.args.explain = explain;
+        self
+    }
+
+    pub fn with_build_string(mut self, build_string: &str) -> Self {
+        self.args.build_string = Some(build_string.parse().unwrap());
+        self
+    }
+
+    pub fn with_solve_strategy(mut self, strategy: SolveStrategy) -> Self {
+        self.args.solve_strategy_config.solve_strategy = Some(strategy);
+        self
+    }
 }
 
 impl HasDependencyConfig for AddBuilder {
@@ -352,12 +392,24 @@ impl TaskAddBuilder {
         self
     }
 
+    /// Execute these commands in sequence
+    pub fn with_sequence(mut self, commands: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.args.cmd = string_from_iter(commands);
+        self
+    }
+
     /// Depends on these commands
     pub fn with_depends_on(mut self, depends: Vec<Dependency>) -> Self {
         self.args.depends_on = Some(depends);
         self
     }
 
+    /// Allow depending on tasks that do not exist
+    pub fn with_allow_missing(mut self, allow_missing: bool) -> Self {
+        self.args.allow_missing = allow_missing;
+        self
+    }
+
     /// With this working directory
     pub fn with_cwd(mut self, cwd: PathBuf) -> Self {
         self.args.cwd = Some(cwd);
@@ -370,6 +422,12 @@ impl TaskAddBuilder {
         self
     }
 
+    /// Replace an existing task with the same name
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.args.force = force;
+        self
+    }
+
     /// Execute the CLI command
     pub async fn execute(self) -> miette::Result<()> {
         task::execute(task::Args {
@@ -396,6 +454,12 @@ impl TaskAliasBuilder {
         self
     }
 
+    /// Allow depending on tasks that do not exist
+    pub fn with_allow_missing(mut self, allow_missing: bool) -> Self {
+        self.args.allow_missing = allow_missing;
+        self
+    }
+
     /// Execute the CLI command
     pub async fn execute(self) -> miette::Result<()> {
         task::execute(task::Args {
@@ -521,6 +585,11 @@ impl InstallBuilder {
         self
     }
 
+    pub fn with_download_only(mut self) -> Self {
+        self.args.download_only = true;
+        self
+    }
+
     pub fn with_environment(mut self, env: Vec<String>) -> Self {
         self.args.environment = Some(env);
         self
@@ -530,6 +599,21 @@ impl InstallBuilder {
         self.args.all = all;
         self
     }
+
+    pub fn with_concurrent_downloads(mut self, concurrent_downloads: usize) -> Self {
+        self.args.config.concurrent_downloads = Some(concurrent_downloads);
+        self
+    }
+
+    pub fn with_allow_clobber(mut self) -> Self {
+        self.args.allow_clobber = true;
+        self
+    }
+
+    pub fn with_json_summary(mut self) -> Self {
+        self.args.json_summary = true;
+        self
+    }
 }
 
 impl IntoFuture for InstallBuilder {
@@ -662,6 +746,17 @@ impl LockBuilder {
         self.args.check = check;
         self
     }
+
+    /// Roll back to a previous lock file instead of updating it.
+    pub fn with_rollback(mut self, to: Option<String>) -> Self {
+        self.args.command = Some(lock::Command::Rollback(lock::RollbackArgs {
+            config_source: self.args.config_source.clone(),
+            workspace_config: self.args.workspace_config.clone(),
+            no_install_config: NoInstallConfig { no_install: true },
+            to,
+        }));
+        self
+    }
 }
 
 impl IntoFuture for LockBuilder {
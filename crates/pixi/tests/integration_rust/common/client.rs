@@ -1,7 +1,10 @@
 /// Inspired from uv: https://github.com/astral-sh/uv/blob/ccdf2d793bbc2401c891b799772f615a28607e79/crates/uv-client/src/middleware.rs#L33
 /// and used to verify that we don't do any requests during the tests.
 use http::Extensions;
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
 
 use reqwest::{Request, Response};
 use reqwest_middleware::{Middleware, Next};
@@ -47,3 +50,52 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that adds a header to every request.
+pub(crate) struct HeaderMiddleware {
+    name: &'static str,
+    value: &'static str,
+}
+
+impl HeaderMiddleware {
+    pub(crate) fn new(name: &'static str, value: &'static str) -> Self {
+        Self { name, value }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for HeaderMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        req.headers_mut()
+            .insert(self.name, http::HeaderValue::from_static(self.value));
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that records the url and headers of every request and then
+/// fails it like the [`OfflineMiddleware`].
+#[derive(Default)]
+pub(crate) struct RecordingMiddleware {
+    pub(crate) requests: Arc<Mutex<Vec<(Url, http::HeaderMap)>>>,
+}
+
+#[async_trait::async_trait]
+impl Middleware for RecordingMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((req.url().clone(), req.headers().clone()));
+        OfflineMiddleware.handle(req, extensions, next).await
+    }
+}
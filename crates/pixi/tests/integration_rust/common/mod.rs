@@ -443,6 +443,9 @@ impl PixiControl {
                 pyproject_toml: false,
                 scm: Some(GitAttributes::Github),
                 conda_pypi_map: None,
+                force: false,
+                template: None,
+                list_templates: false,
             },
         }
     }
@@ -462,6 +465,9 @@ impl PixiControl {
                 pyproject_toml: false,
                 scm: Some(GitAttributes::Github),
                 conda_pypi_map: None,
+                force: false,
+                template: None,
+                list_templates: false,
             },
         }
     }
@@ -494,10 +500,18 @@ impl PixiControl {
                     no_lock_file_update: false,
                     lock_file_usage: LockFileUsageConfig::default(),
                 },
+                solve_strategy_config: Default::default(),
                 config: Default::default(),
                 config_source: isolated_config_source(),
                 editable: false,
                 index: None,
+                no_build: false,
+                no_binary: false,
+                no_preserve: false,
+                explain: false,
+                no_pin_warning: false,
+                build_string: None,
+                dry_run: false,
             },
         }
     }
@@ -518,6 +532,8 @@ impl PixiControl {
                 limit_packages: 10,
                 json: false,
                 channels: ChannelsConfig::default(),
+                pypi: false,
+                index: None,
             },
         }
     }
@@ -688,6 +704,8 @@ impl PixiControl {
                 .map(|(k, v)| (OsString::from(k), OsString::from(v)))
                 .collect();
 
+            task.check_requirements(lock_file.as_lock_file())
+                .map_err(TaskExecutionError::from)?;
             let output = task.execute_with_pipes(&task_env, None).await?;
             result.stdout.push_str(&output.stdout);
             result.stderr.push_str(&output.stderr);
@@ -715,6 +733,7 @@ impl PixiControl {
                     frozen: false,
                     locked: false,
                 },
+                solve_strategy_config: Default::default(),
                 config: Default::default(),
                 config_source: isolated_config_source(),
                 all: false,
@@ -722,6 +741,11 @@ impl PixiControl {
                 skip: None,
                 skip_with_deps: None,
                 only: None,
+                download_only: false,
+                verify_lock_hash: None,
+                prefix: None,
+                json_summary: false,
+                allow_clobber: false,
             },
         }
     }
@@ -748,8 +772,10 @@ impl PixiControl {
                 },
                 no_install: true,
                 dry_run: false,
+                solve_strategy_config: Default::default(),
                 specs: Default::default(),
                 json: false,
+                allow_clobber: false,
             },
         }
     }
@@ -789,9 +815,11 @@ impl PixiControl {
                     workspace: None,
                 },
                 no_install_config: NoInstallConfig { no_install: false },
+                solve_strategy_config: Default::default(),
                 check: false,
                 json: false,
                 dry_run: false,
+                command: None,
             },
         }
     }
@@ -838,7 +866,9 @@ impl TasksControl<'_> {
             args: AddArgs {
                 name,
                 commands: vec![],
+                cmd: vec![],
                 depends_on: None,
+                allow_missing: false,
                 platform: platform.map(Into::into),
                 feature: feature_name.non_default().map(str::to_owned),
                 cwd: None,
@@ -846,7 +876,10 @@ impl TasksControl<'_> {
                 env: Default::default(),
                 description: None,
                 clean_env: false,
+                shell: None,
+                requires: vec![],
                 args: None,
+                force: false,
             },
         }
     }
@@ -881,7 +914,9 @@ impl TasksControl<'_> {
                 platform: platform.map(Into::into),
                 alias: name,
                 depends_on: vec![],
+                allow_missing: false,
                 description: None,
+                force: false,
             },
         }
     }
@@ -920,3 +955,54 @@ enum RunError {
     #[error("the task executed with a non-zero exit code {0}")]
     NonZeroExitCode(i32),
 }
+
+/// Serves the files of a directory over http. If `authorization` is set, only
+/// requests with that `Authorization` header are served.
+pub async fn serve_directory(dir: PathBuf, authorization: Option<&'static str>) -> url::Url {
+    use axum::{
+        Router,
+        extract::Path,
+        http::{
+            HeaderMap, StatusCode,
+            header::{AUTHORIZATION, CONTENT_TYPE},
+        },
+        response::IntoResponse,
+        routing::get,
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = Router::new().route(
+        "/{*path}",
+        get(move |Path(path): Path<String>, headers: HeaderMap| {
+            let dir = dir.clone();
+            async move {
+                if authorization.is_some()
+                    && headers
+                        .get(AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        != authorization
+                {
+                    return StatusCode::UNAUTHORIZED.into_response();
+                }
+                // Index pages need a content type for the registry client to
+                // parse them.
+                let is_index = path.ends_with('/');
+                let path = if is_index {
+                    dir.join(path).join("index.html")
+                } else {
+                    dir.join(path)
+                };
+                match fs_err::tokio::read(path).await {
+                    Ok(contents) if is_index => {
+                        ([(CONTENT_TYPE, "text/html")], contents).into_response()
+                    }
+                    Ok(contents) => contents.into_response(),
+                    Err(_) => StatusCode::NOT_FOUND.into_response(),
+                }
+            }
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    url::Url::parse(&format!("http://{addr}/")).unwrap()
+}
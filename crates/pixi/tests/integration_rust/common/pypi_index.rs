@@ -48,6 +48,8 @@ pub struct PyPIPackage {
     pub requires_python: Option<String>,
     pub summary: Option<String>,
     pub timestamp: Option<DateTime<Utc>>,
+    /// Extra files of the wheel, relative to the root of the wheel.
+    pub files: Vec<(String, Vec<u8>)>,
 }
 
 impl PyPIPackage {
@@ -61,6 +63,7 @@ impl PyPIPackage {
             requires_python: None,
             summary: None,
             timestamp: None,
+            files: vec![],
         }
     }
 
@@ -97,6 +100,13 @@ impl PyPIPackage {
         self.timestamp = Some(timestamp);
         self
     }
+
+    /// Adds a file to the wheel, the path is relative to the root of the
+    /// wheel.
+    pub fn with_file(mut self, path: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        self.files.push((path.into(), content.into()));
+        self
+    }
 }
 
 /// A collection of packages that can be materialized as either flat or simple indexes.
@@ -401,6 +411,9 @@ fn write_wheel(out_dir: &Path, pkg: &PyPIPackage) -> miette::Result<PathBuf> {
     let (module_path, module_bytes) = build_module(pkg);
     entries.push((module_path, module_bytes));
 
+    // Extra files
+    entries.extend(pkg.files.iter().cloned());
+
     // METADATA
     let metadata_path = format!("{dist_info}/METADATA");
     entries.push((metadata_path.clone(), build_metadata(pkg).into_bytes()));
@@ -102,6 +102,51 @@ async fn init_from_existing_pyproject_toml() {
     );
 }
 
+// Test that an existing `[tool.pixi]` section is only replaced with `--force`
+#[tokio::test]
+async fn init_pyproject_with_existing_pixi_table() {
+    setup_tracing();
+
+    let pixi = PixiControl::new().unwrap();
+
+    let pyproject_toml = pixi.workspace_path().join("pyproject.toml");
+    fs_err::write(
+        &pyproject_toml,
+        r#"[project]
+name = "existing"
+version = "0.1.0"
+
+[tool.pixi.workspace]
+channels = ["original"]
+platforms = ["linux-64"]
+"#,
+    )
+    .unwrap();
+
+    // Without `--force` the existing section is left alone
+    pixi.init()
+        .with_format(pixi_cli::init::ManifestFormat::Pyproject)
+        .with_channel("replacement")
+        .await
+        .unwrap();
+    let contents = pixi.manifest_contents().unwrap();
+    assert!(contents.contains("\"original\""));
+    assert!(!contents.contains("\"replacement\""));
+
+    // With `--force` the section is replaced while the rest is preserved
+    pixi.init()
+        .with_format(pixi_cli::init::ManifestFormat::Pyproject)
+        .with_channel("replacement")
+        .with_force(true)
+        .await
+        .unwrap();
+    let contents = pixi.manifest_contents().unwrap();
+    assert!(contents.contains("name = \"existing\""));
+    assert!(contents.contains("[tool.pixi.workspace]"));
+    assert!(contents.contains("\"replacement\""));
+    assert!(!contents.contains("\"original\""));
+}
+
 // TODO: enable and fix this test when we fix the global config loading
 // #[tokio::test]
 // async fn default_pypi_config() {
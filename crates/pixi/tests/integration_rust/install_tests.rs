@@ -856,16 +856,9 @@ async fn test_v6_local_archive_path_upgrade() {
     fs_err::write(&lock_path, &original_lock).unwrap();
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-#[cfg_attr(
-    any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
-    ignore
-)]
-async fn test_no_build_isolation() {
-    setup_tracing();
-
-    let current_platform = Platform::current();
-    let setup_py = r#"
+/// A `setup.py` that imports `boltons`, so it only builds without build
+/// isolation in an environment that contains it.
+const NO_BUILD_ISOLATION_SETUP_PY: &str = r#"
 from setuptools import setup, find_packages
 # custom import
 import boltons
@@ -891,7 +884,17 @@ setup(
         ],
     },
 )
-    "#;
+"#;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[cfg_attr(
+    any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
+    ignore
+)]
+async fn test_no_build_isolation() {
+    setup_tracing();
+
+    let current_platform = Platform::current();
 
     let manifest = format!(
         r#"
@@ -919,7 +922,7 @@ setup(
     // Write setup.py to a my-pkg folder
     let my_pkg = project_path.join("my-pkg");
     fs_err::create_dir_all(&my_pkg).unwrap();
-    fs_err::write(my_pkg.join("setup.py"), setup_py).unwrap();
+    fs_err::write(my_pkg.join("setup.py"), NO_BUILD_ISOLATION_SETUP_PY).unwrap();
 
     let has_pkg = pixi
         .workspace()
@@ -948,6 +951,61 @@ setup(
     .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[cfg_attr(
+    any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
+    ignore
+)]
+async fn test_no_build_isolation_per_dependency() {
+    setup_tracing();
+
+    let current_platform = Platform::current();
+    let manifest = format!(
+        r#"
+    [project]
+    name = "no-build-isolation"
+    channels = ["https://prefix.dev/conda-forge"]
+    platforms = ["{current_platform}"]
+
+    [dependencies]
+    python = "3.12.*"
+    setuptools = ">=72,<73"
+    boltons = ">=24,<25"
+
+    [pypi-dependencies.my-pkg]
+    path = "./my-pkg"
+    no-build-isolation = true
+    "#,
+    );
+
+    let pixi = PixiControl::from_manifest(&manifest).expect("cannot instantiate pixi project");
+
+    let my_pkg = pixi.workspace_path().join("my-pkg");
+    fs_err::create_dir_all(&my_pkg).unwrap();
+    fs_err::write(my_pkg.join("setup.py"), NO_BUILD_ISOLATION_SETUP_PY).unwrap();
+
+    let has_pkg = pixi
+        .workspace()
+        .unwrap()
+        .default_environment()
+        .pypi_options()
+        .no_build_isolation
+        .contains(&"my-pkg".parse().unwrap());
+    assert!(has_pkg, "my-pkg is not in no-build-isolation list");
+
+    let tmp_dir = tempdir().unwrap();
+    temp_env::async_with_vars(
+        [(
+            "PIXI_CACHE_PYPI_WHEELS_DIR",
+            Some(tmp_dir.path().to_str().unwrap()),
+        )],
+        async {
+            pixi.install().await.expect("cannot install project");
+        },
+    )
+    .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[cfg_attr(
     any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
@@ -1359,6 +1417,66 @@ async fn conda_pypi_override_correct_per_platform() {
     ));
 }
 
+/// A PyPI package that ships a file that python already installed may only
+/// overwrite it silently with `--allow-clobber`, and the overwritten file is
+/// recorded in the install summary.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[cfg_attr(
+    any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
+    ignore
+)]
+async fn test_allow_clobber_records_clobbered_files() {
+    setup_tracing();
+
+    // conda-forge python installs `site-packages/README.txt`.
+    let pypi_index = crate::common::pypi_index::Database::new()
+        .with(PyPIPackage::new("clobber", "1.0.0").with_file("README.txt", "clobbered\n"))
+        .into_simple_index()
+        .unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "allow-clobber"
+        channels = ["https://prefix.dev/conda-forge"]
+        platforms = ["{platform}"]
+
+        [dependencies]
+        python = "3.12.*"
+
+        [pypi-dependencies]
+        clobber = "*"
+
+        [pypi-options]
+        index-url = "{index_url}"
+        allow-clobber = false
+        "#,
+        platform = Platform::current(),
+        index_url = pypi_index.index_url(),
+    ))
+    .unwrap();
+
+    // The flag takes precedence over the `allow-clobber = false` of the
+    // manifest.
+    pixi.install()
+        .with_allow_clobber()
+        .with_json_summary()
+        .await
+        .unwrap();
+
+    let summary = pixi_progress::install_summary::recorded();
+    let readme = summary
+        .clobbered_files
+        .iter()
+        .map(Path::new)
+        .find(|path| path.ends_with("site-packages/README.txt"))
+        .expect("the README.txt of python should be recorded as clobbered");
+    assert_eq!(
+        fs_err::read_to_string(pixi.default_env_path().unwrap().join(readme)).unwrap(),
+        "clobbered\n"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[cfg_attr(
     any(not(feature = "online_tests"), not(feature = "slow_integration_tests")),
@@ -2051,3 +2169,158 @@ async fn install_all_skips_unsupported_environments() {
             "install -e other should fail because it does not support the current platform",
         );
 }
+
+/// Test that installing with a single concurrent download still installs all
+/// the packages.
+#[tokio::test]
+async fn install_with_single_concurrent_download() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    for name in ["foo", "bar", "baz"] {
+        package_database.add_package(Package::build(name, "1").finish());
+    }
+    let channel = package_database.into_channel().await.unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().with_channel(channel.url()).await.unwrap();
+    pixi.add_multiple(vec!["foo", "bar", "baz"])
+        .with_install(false)
+        .await
+        .unwrap();
+
+    pixi.install().with_concurrent_downloads(1).await.unwrap();
+
+    let prefix = pixi_utils::prefix::Prefix::new(pixi.default_env_path().unwrap());
+    for name in ["foo", "bar", "baz"] {
+        let package_name = rattler_conda_types::PackageName::try_from(name).unwrap();
+        assert!(
+            prefix.find_designated_package(&package_name).await.is_ok(),
+            "{name} should be installed"
+        );
+    }
+}
+
+/// `pixi install --platform` for a platform that doesn't run on this machine
+/// locks and downloads the packages, but doesn't create a prefix.
+#[tokio::test]
+async fn test_install_download_only_for_other_platform() {
+    setup_tracing();
+
+    let current_platform = Platform::current();
+    let other_platform = if current_platform.is_linux() {
+        Platform::Osx64
+    } else {
+        Platform::Linux64
+    };
+
+    let mut db = MockRepoData::default();
+    db.add_package(
+        Package::build("foo", "1")
+            .with_subdir(other_platform)
+            .with_materialize(true)
+            .finish(),
+    );
+    let channel = db.into_channel().await.unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "test-download-only"
+        channels = ["{channel}"]
+        platforms = ["{other_platform}"]
+
+        [dependencies]
+        foo = "*"
+        "#,
+        channel = channel.url(),
+    ))
+    .unwrap();
+
+    let cache_dir = tempdir().unwrap();
+    temp_env::async_with_vars(
+        [("PIXI_CACHE_DIR", Some(cache_dir.path().to_str().unwrap()))],
+        async {
+            pixi.install().with_platform(other_platform).await.unwrap();
+        },
+    )
+    .await;
+
+    let lock = pixi.lock_file().await.unwrap();
+    assert!(lock.contains_conda_package(consts::DEFAULT_ENVIRONMENT_NAME, other_platform, "foo"));
+
+    let package_cache = cache_dir.path().join(consts::CONDA_PACKAGE_CACHE_DIR);
+    assert!(
+        fs_err::read_dir(&package_cache)
+            .unwrap()
+            .filter_map(Result::ok)
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("foo-1")),
+        "expected foo to be downloaded into the package cache"
+    );
+
+    assert!(!pixi.default_env_path().unwrap().exists());
+}
+
+/// `pixi install --download-only --skip` doesn't download the skipped
+/// packages.
+#[tokio::test]
+async fn test_install_download_only_applies_filters() {
+    setup_tracing();
+
+    let platform = Platform::current();
+    let mut db = MockRepoData::default();
+    for name in ["foo", "bar"] {
+        db.add_package(
+            Package::build(name, "1")
+                .with_subdir(platform)
+                .with_materialize(true)
+                .finish(),
+        );
+    }
+    let channel = db.into_channel().await.unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "test-download-only-filters"
+        channels = ["{channel}"]
+        platforms = ["{platform}"]
+
+        [dependencies]
+        foo = "*"
+        bar = "*"
+        "#,
+        channel = channel.url(),
+    ))
+    .unwrap();
+
+    let cache_dir = tempdir().unwrap();
+    temp_env::async_with_vars(
+        [("PIXI_CACHE_DIR", Some(cache_dir.path().to_str().unwrap()))],
+        async {
+            pixi.install()
+                .with_download_only()
+                .with_skipped(vec!["bar".to_string()])
+                .await
+                .unwrap();
+        },
+    )
+    .await;
+
+    let package_cache = cache_dir.path().join(consts::CONDA_PACKAGE_CACHE_DIR);
+    let cached = fs_err::read_dir(&package_cache)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(
+        cached.iter().any(|name| name.starts_with("foo-1")),
+        "expected foo in the package cache, found {cached:?}"
+    );
+    assert!(
+        !cached.iter().any(|name| name.starts_with("bar-1")),
+        "expected the skipped bar to not be downloaded, found {cached:?}"
+    );
+
+    assert!(!pixi.default_env_path().unwrap().exists());
+}
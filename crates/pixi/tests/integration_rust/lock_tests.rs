@@ -1,8 +1,59 @@
 use crate::common::{LockFileExt, PixiControl};
+use pixi_consts::consts;
+use pixi_core::lock_file::{LockFileMetadata, UnsatisfiedSpec, find_unsatisfied_specs};
 use pixi_test_utils::{MockRepoData, Package};
 use rattler_conda_types::Platform;
 use tempfile::TempDir;
 
+/// Test that `pixi lock rollback` restores the lock file from before the last
+/// update.
+#[tokio::test]
+async fn test_lock_rollback_restores_previous_lock_file() {
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(
+        Package::build("python", "3.11.0")
+            .with_subdir(Platform::current())
+            .finish(),
+    );
+    package_database.add_package(
+        Package::build("numpy", "1.24.0")
+            .with_subdir(Platform::current())
+            .finish(),
+    );
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+    pixi.add("python").await.unwrap();
+    let lock_file_path = pixi.workspace_path().join(consts::PROJECT_LOCK_FILE);
+    let first_lock_file = fs_err::read_to_string(&lock_file_path).unwrap();
+
+    pixi.add("numpy").await.unwrap();
+    let second_lock_file = fs_err::read_to_string(&lock_file_path).unwrap();
+    assert_ne!(second_lock_file, first_lock_file);
+
+    pixi.lock().with_rollback(None).await.unwrap();
+    assert_eq!(
+        fs_err::read_to_string(&lock_file_path).unwrap(),
+        first_lock_file
+    );
+
+    // The lock file replaced by the rollback was archived, so rolling back
+    // again undoes the rollback.
+    pixi.lock().with_rollback(None).await.unwrap();
+    assert_eq!(
+        fs_err::read_to_string(&lock_file_path).unwrap(),
+        second_lock_file
+    );
+}
+
 /// Test that `pixi lock --dry-run` doesn't modify the lock file on disk
 #[tokio::test]
 async fn test_lock_dry_run_doesnt_modify_lock_file() {
@@ -145,3 +196,342 @@ async fn test_lock_dry_run_implies_no_install() {
         "Environment should not be created with --dry-run"
     );
 }
+
+/// Test that the unsatisfied specs are reported without solving when the
+/// manifest changes.
+#[tokio::test]
+async fn test_find_unsatisfied_specs() {
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("bar", "1").finish());
+    package_database.add_package(Package::build("foo", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+    pixi.add("bar").await.unwrap();
+
+    // The lock file satisfies the manifest
+    let lock_file = pixi.lock_file().await.unwrap();
+    let workspace = pixi.workspace().unwrap();
+    assert_eq!(find_unsatisfied_specs(&workspace, &lock_file), vec![]);
+
+    // Change an existing dependency and add a new one without updating the lock
+    // file
+    let manifest_content = pixi.manifest_contents().unwrap();
+    let updated_manifest =
+        manifest_content.replace("bar = \">=1,<2\"", "bar = \">=2\"\nfoo = \"*\"");
+    pixi.update_manifest(&updated_manifest).unwrap();
+
+    let workspace = pixi.workspace().unwrap();
+    let mut unsatisfied = find_unsatisfied_specs(&workspace, &lock_file)
+        .into_iter()
+        .map(|spec| match spec {
+            UnsatisfiedSpec::Conda { spec, .. } => {
+                spec.split_whitespace().next().unwrap().to_string()
+            }
+            other => panic!("unexpected unsatisfied spec: {other}"),
+        })
+        .collect::<Vec<_>>();
+    unsatisfied.sort();
+    assert_eq!(unsatisfied, vec!["bar", "foo"]);
+}
+
+/// Test that a PyPI dependency is reported when the locked version does not
+/// match its specifiers.
+#[tokio::test]
+async fn test_find_unsatisfied_pypi_specs() {
+    let manifest = |requirement: &str| {
+        format!(
+            r#"
+    [workspace]
+    name = "pypi-unsatisfied"
+    channels = ["conda-forge"]
+    platforms = ["win-64"]
+
+    [dependencies]
+    python = "*"
+
+    [pypi-dependencies]
+    my-dep = "{requirement}"
+    "#
+        )
+    };
+    let lock_file = rattler_lock::LockFile::from_str_with_base_directory(
+        r#"
+version: 7
+platforms:
+- name: win-64
+environments:
+  default:
+    channels:
+    - url: https://conda.anaconda.org/conda-forge/
+    indexes:
+    - https://pypi.org/simple
+    packages:
+      win-64:
+      - conda: https://conda.anaconda.org/conda-forge/win-64/python-3.12.3-h2628c8c_0_cpython.conda
+      - pypi: https://files.pythonhosted.org/packages/my_dep-1.0.0-py3-none-any.whl
+packages:
+- conda: https://conda.anaconda.org/conda-forge/win-64/python-3.12.3-h2628c8c_0_cpython.conda
+  sha256: 1a95494abe572a8819c933f978df89f00bde72ea9432d46a70632599e8029ea4
+  md5: f07c8c5dd98767f9a652de5d039b284e
+  depends: []
+- pypi: https://files.pythonhosted.org/packages/my_dep-1.0.0-py3-none-any.whl
+  name: my-dep
+  version: 1.0.0
+  sha256: 0000000000000000000000000000000000000000000000000000000000000000
+"#,
+        None,
+    )
+    .unwrap();
+
+    let pixi = PixiControl::from_manifest(&manifest(">=1")).unwrap();
+    let workspace = pixi.workspace().unwrap();
+    assert_eq!(find_unsatisfied_specs(&workspace, &lock_file), vec![]);
+
+    let pixi = PixiControl::from_manifest(&manifest(">=2")).unwrap();
+    let workspace = pixi.workspace().unwrap();
+    let unsatisfied = find_unsatisfied_specs(&workspace, &lock_file);
+    assert!(
+        matches!(
+            unsatisfied.as_slice(),
+            [UnsatisfiedSpec::Pypi { spec, .. }] if spec.starts_with("my-dep")
+        ),
+        "unexpected unsatisfied specs: {unsatisfied:?}"
+    );
+}
+
+/// Test that the dependencies of a feature only take part in the solve of the
+/// environments that include that feature.
+#[tokio::test]
+async fn test_feature_dependencies_only_in_including_environments() {
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("numpy", "1").finish());
+    package_database.add_package(Package::build("cudatoolkit", "12").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let channel = url::Url::from_file_path(channel_dir.path()).unwrap();
+    let platform = Platform::current();
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "optional-gpu"
+    channels = ["{channel}"]
+    platforms = ["{platform}"]
+
+    [dependencies]
+    numpy = "*"
+
+    [feature.gpu.dependencies]
+    cudatoolkit = "*"
+
+    [environments]
+    gpu = ["gpu"]
+    "#
+    ))
+    .unwrap();
+
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert!(lock_file.contains_conda_package("default", platform, "numpy"));
+    assert!(
+        !lock_file.contains_conda_package("default", platform, "cudatoolkit"),
+        "the default environment does not include the gpu feature"
+    );
+    assert!(lock_file.contains_conda_package("gpu", platform, "numpy"));
+    assert!(lock_file.contains_conda_package("gpu", platform, "cudatoolkit"));
+}
+
+/// Test that a dependency pinned to a channel is locked from that channel,
+/// even when a channel with a higher priority also provides it.
+#[tokio::test]
+async fn test_dependency_channel_overrides_channel_priority() {
+    let mut preferred_database = MockRepoData::default();
+    preferred_database.add_package(Package::build("bar", "1").finish());
+    let preferred_dir = TempDir::new().unwrap();
+    preferred_database
+        .write_repodata(preferred_dir.path())
+        .await
+        .unwrap();
+
+    let mut pinned_database = MockRepoData::default();
+    pinned_database.add_package(Package::build("bar", "2").finish());
+    let pinned_dir = TempDir::new().unwrap();
+    pinned_database
+        .write_repodata(pinned_dir.path())
+        .await
+        .unwrap();
+
+    let preferred = url::Url::from_file_path(preferred_dir.path()).unwrap();
+    let pinned = url::Url::from_file_path(pinned_dir.path()).unwrap();
+    let platform = Platform::current();
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "channel-per-dependency"
+    channels = ["{preferred}", "{pinned}"]
+    platforms = ["{platform}"]
+
+    [dependencies]
+    bar = {{ version = "*", channel = "{pinned}" }}
+    "#
+    ))
+    .unwrap();
+
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    let environment = lock_file.environment("default").unwrap();
+    let lock_platform = lock_file.platform(&platform.to_string()).unwrap();
+    let bar = environment
+        .conda_packages(lock_platform)
+        .unwrap()
+        .find(|package| package.name().as_normalized() == "bar")
+        .expect("bar should be locked");
+    assert!(
+        bar.location().as_str().starts_with(pinned.as_str()),
+        "bar should be locked from the pinned channel, got {}",
+        bar.location()
+    );
+    assert!(lock_file.contains_match_spec("default", platform, "bar ==2"));
+}
+
+/// Test that a channel with `platforms` is only fetched for those subdirs.
+#[tokio::test]
+async fn test_channel_platforms_restrict_fetched_subdirs() {
+    let platform = Platform::current();
+    let mut database = MockRepoData::default();
+    database.add_package(Package::build("bar", "1").with_subdir(platform).finish());
+    database.add_package(
+        Package::build("bar", "2")
+            .with_subdir(Platform::NoArch)
+            .finish(),
+    );
+    let channel_dir = TempDir::new().unwrap();
+    database.write_repodata(channel_dir.path()).await.unwrap();
+    let channel = url::Url::from_file_path(channel_dir.path()).unwrap();
+
+    let manifest = |channel: String| {
+        format!(
+            r#"
+    [workspace]
+    name = "channel-subdirs"
+    channels = [{channel}]
+    platforms = ["{platform}"]
+
+    [dependencies]
+    bar = "*"
+    "#
+        )
+    };
+
+    // Without a restriction both subdirs are fetched.
+    let pixi = PixiControl::from_manifest(&manifest(format!(r#""{channel}""#))).unwrap();
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert!(lock_file.contains_match_spec("default", platform, "bar ==2"));
+
+    // Restricting the channel makes the lock file out of date, and the newer
+    // noarch package is no longer fetched.
+    pixi.update_manifest(&manifest(format!(
+        r#"{{ channel = "{channel}", platforms = ["{platform}"] }}"#
+    )))
+    .unwrap();
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert!(lock_file.contains_match_spec("default", platform, "bar ==1"));
+
+    // An environment that lists the channel again with other platforms is
+    // fetched for the union of them, other environments keep their own.
+    let pixi = PixiControl::from_manifest(&format!(
+        "{}\n[feature.extra]\nchannels = [{{ channel = \"{channel}\", platforms = [\"noarch\"] }}]\n\n[environments]\nextra = [\"extra\"]\n",
+        manifest(format!(
+            r#"{{ channel = "{channel}", platforms = ["{platform}"] }}"#
+        ))
+    ))
+    .unwrap();
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert!(lock_file.contains_match_spec("default", platform, "bar ==1"));
+    assert!(lock_file.contains_match_spec("extra", platform, "bar ==2"));
+}
+
+/// Test that every platform is only solved with the listed subdirs that are
+/// that platform or `noarch`.
+#[tokio::test]
+async fn test_channel_platforms_multiple_platforms() {
+    let mut database = MockRepoData::default();
+    database.add_package(
+        Package::build("bar", "1")
+            .with_subdir(Platform::Linux64)
+            .finish(),
+    );
+    database.add_package(
+        Package::build("bar", "2")
+            .with_subdir(Platform::OsxArm64)
+            .finish(),
+    );
+    database.add_package(
+        Package::build("bar", "3")
+            .with_subdir(Platform::NoArch)
+            .finish(),
+    );
+    let channel_dir = TempDir::new().unwrap();
+    database.write_repodata(channel_dir.path()).await.unwrap();
+    let channel = url::Url::from_file_path(channel_dir.path()).unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "channel-subdirs"
+    channels = [{{ channel = "{channel}", platforms = ["linux-64", "osx-arm64"] }}]
+    platforms = ["linux-64", "osx-arm64"]
+
+    [dependencies]
+    bar = "*"
+    "#
+    ))
+    .unwrap();
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert!(lock_file.contains_match_spec("default", Platform::Linux64, "bar ==1"));
+    assert!(lock_file.contains_match_spec("default", Platform::OsxArm64, "bar ==2"));
+}
+
+/// Test that a written lock file records the version of pixi that wrote it.
+#[tokio::test]
+async fn test_lock_file_records_pixi_version() {
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("foo", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init()
+        .with_local_channel(channel_dir.path())
+        .await
+        .unwrap();
+    pixi.add("foo").await.unwrap();
+
+    let lock_file_path = pixi.workspace().unwrap().lock_file_path();
+    let metadata = LockFileMetadata::from_path(&lock_file_path).unwrap();
+    assert_eq!(metadata.pixi_version.as_deref(), Some(consts::PIXI_VERSION));
+    assert_eq!(metadata.channel_priority.as_deref(), Some("strict"));
+    assert!(
+        metadata
+            .platforms
+            .contains(&Platform::current().to_string())
+    );
+    assert!(metadata.generated_at.is_some());
+
+    // The metadata does not prevent reading the lock file.
+    let lock_file = pixi.lock_file().await.unwrap();
+    assert!(lock_file.contains_conda_package("default", Platform::current(), "foo"));
+}
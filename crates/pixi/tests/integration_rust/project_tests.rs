@@ -1,14 +1,21 @@
 use std::collections::BTreeSet;
 use std::path::PathBuf;
 
-use crate::common::PixiControl;
+use crate::common::{
+    LockFileExt, PixiControl,
+    client::{HeaderMiddleware, RecordingMiddleware},
+    pypi_index::{Database as PyPIDatabase, PyPIPackage},
+    serve_directory,
+};
 use crate::setup_tracing;
 use insta::assert_debug_snapshot;
 use pixi_config::Config;
-use pixi_core::Workspace;
+use pixi_consts::consts;
+use pixi_core::{UpdateLockFileOptions, Workspace, lock_file::LockFileMetadata};
 use pixi_manifest::FeaturesExt;
-use pixi_test_utils::MockRepoData;
+use pixi_test_utils::{MockRepoData, Package};
 use rattler_conda_types::{NamedChannelOrUrl, Platform};
+use reqwest_middleware::ClientBuilder;
 use tempfile::TempDir;
 use url::Url;
 
@@ -189,3 +196,253 @@ fn parse_valid_docs_configs() {
         }
     }
 }
+
+/// Test that a client given to `Workspace::with_client` is used for the
+/// requests of the workspace instead of the client pixi builds itself.
+#[tokio::test]
+async fn test_injected_client_is_used_for_requests() {
+    setup_tracing();
+
+    let pixi = PixiControl::from_manifest(
+        r#"
+    [workspace]
+    name = "injected-client"
+    channels = ["https://repo.example.com/my-channel"]
+    platforms = ["linux-64"]
+
+    [dependencies]
+    foo = "*"
+    "#,
+    )
+    .unwrap();
+
+    let recording = RecordingMiddleware::default();
+    let requests = recording.requests.clone();
+    let client = ClientBuilder::new(reqwest::Client::new())
+        .with(HeaderMiddleware::new("x-embedder", "pixi-tests"))
+        .with(recording)
+        .build();
+    let workspace = pixi.workspace().unwrap().with_client(client);
+
+    // The recording middleware fails every request, so the solve fails.
+    assert!(
+        workspace
+            .update_lock_file(None, UpdateLockFileOptions::default())
+            .await
+            .is_err()
+    );
+
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests.iter().any(|(url, _)| url
+            .as_str()
+            .starts_with("https://repo.example.com/my-channel/")),
+        "expected a request to the channel, got {requests:?}"
+    );
+    for (url, headers) in requests.iter() {
+        assert_eq!(
+            headers
+                .get("x-embedder")
+                .and_then(|value| value.to_str().ok()),
+            Some("pixi-tests"),
+            "the injected header is missing on the request to {url}"
+        );
+    }
+}
+
+/// Test that the repodata of a channel is fetched through the mirror that is
+/// configured for it.
+#[tokio::test]
+async fn test_channel_mirror_is_used_for_repodata() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("foo", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+    let mirror_url = serve_directory(channel_dir.path().to_path_buf(), None).await;
+
+    // The channel itself does not exist, it can only be reached through the
+    // mirror.
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "mirrored-channel"
+    channels = ["https://conda.example.com/my-channel"]
+    platforms = ["{platform}"]
+
+    [dependencies]
+    foo = "*"
+    "#,
+        platform = Platform::current()
+    ))
+    .unwrap();
+
+    let mut config = Config::default();
+    config.mirrors.insert(
+        Url::parse("https://conda.example.com/my-channel").unwrap(),
+        vec![mirror_url],
+    );
+    let workspace = pixi.workspace().unwrap().with_cli_config(config);
+
+    let (derived, _) = workspace
+        .update_lock_file(
+            None,
+            UpdateLockFileOptions {
+                no_install: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let lock_file = derived.into_lock_file();
+    assert!(lock_file.contains_conda_package(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "foo"
+    ));
+}
+
+/// Test that the lock file records the mirror url a package is downloaded
+/// from, and that the recorded url is used to install the package when no
+/// mirror is configured.
+#[tokio::test]
+async fn test_lock_file_records_channel_mirror() {
+    setup_tracing();
+
+    let mut package_database = MockRepoData::default();
+    package_database.add_package(Package::build("foo", "1").finish());
+    let channel_dir = TempDir::new().unwrap();
+    package_database
+        .write_repodata(channel_dir.path())
+        .await
+        .unwrap();
+    let mirror_url = serve_directory(channel_dir.path().to_path_buf(), None).await;
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "mirrored-channel"
+    channels = ["https://conda.example.com/my-channel"]
+    platforms = ["{platform}"]
+
+    [dependencies]
+    foo = "*"
+    "#,
+        platform = Platform::current()
+    ))
+    .unwrap();
+
+    let channel_url = Url::parse("https://conda.example.com/my-channel/").unwrap();
+    let mut config = Config::default();
+    config
+        .mirrors
+        .insert(channel_url.clone(), vec![mirror_url.clone()]);
+    let workspace = pixi.workspace().unwrap().with_cli_config(config);
+    workspace
+        .update_lock_file(
+            None,
+            UpdateLockFileOptions {
+                no_install: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let metadata = LockFileMetadata::from_path(&workspace.lock_file_path()).unwrap();
+    let [(url, download_url)] = metadata.download_urls.as_slice() else {
+        panic!(
+            "expected one download url, got {:?}",
+            metadata.download_urls
+        );
+    };
+    let package_path = url.as_str().strip_prefix(channel_url.as_str()).unwrap();
+    assert_eq!(download_url, &mirror_url.join(package_path).unwrap());
+
+    // The lock file itself still contains the urls of the channel.
+    let lock_file = pixi.lock_file().await.unwrap();
+    assert!(lock_file.contains_conda_package(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        Platform::current(),
+        "foo"
+    ));
+
+    // The recorded urls don't change the mirror configuration.
+    let workspace = pixi.workspace().unwrap();
+    assert!(workspace.config().mirror_map().get(&channel_url).is_none());
+
+    // Without a configured mirror the package is installed from the recorded
+    // url, the channel itself doesn't exist.
+    pixi.install().with_frozen().await.unwrap();
+}
+
+/// Test that the client given to `Workspace::with_client` is not used for
+/// PyPI requests, uv builds its own client from the configuration.
+#[tokio::test]
+async fn test_injected_client_is_not_used_for_pypi_requests() {
+    setup_tracing();
+
+    let platform = Platform::current();
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.12.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+    let index = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .into_simple_index()
+        .unwrap();
+    let index_url = serve_directory(index.index_path().to_path_buf(), None).await;
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "injected-client-pypi"
+    channels = ["{channel_url}"]
+    platforms = ["{platform}"]
+    conda-pypi-map = {{}}
+
+    [dependencies]
+    python = "==3.12.0"
+
+    [pypi-dependencies]
+    foo = "*"
+
+    [pypi-options]
+    index-url = "{index_url}"
+    "#,
+        channel_url = channel.url(),
+    ))
+    .unwrap();
+
+    // The recording middleware fails every request that goes through it. The
+    // conda channel is local, so only the PyPI index is reached over http.
+    let recording = RecordingMiddleware::default();
+    let requests = recording.requests.clone();
+    let client = ClientBuilder::new(reqwest::Client::new())
+        .with(recording)
+        .build();
+    let workspace = pixi.workspace().unwrap().with_client(client);
+
+    let (derived, _) = workspace
+        .update_lock_file(None, UpdateLockFileOptions::default())
+        .await
+        .unwrap();
+    assert!(derived.lock_file.contains_pypi_package(
+        consts::DEFAULT_ENVIRONMENT_NAME,
+        platform,
+        "foo"
+    ));
+
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests.is_empty(),
+        "expected no requests through the injected client, got {requests:?}"
+    );
+}
@@ -14,7 +14,7 @@ use tempfile::tempdir;
 use typed_path::Utf8TypedPath;
 
 use crate::common::pypi_index::{Database as PyPIDatabase, PyPIPackage};
-use crate::common::{LockFileExt, PixiControl};
+use crate::common::{LockFileExt, PixiControl, serve_directory};
 use crate::setup_tracing;
 use pixi_test_utils::{MockRepoData, Package};
 
@@ -1897,6 +1897,121 @@ async fn test_prerelease_mode_allow() {
     );
 }
 
+/// Tests that wheels that require a newer python than the one of the conda
+/// solve are rejected, with an error that names the python version.
+#[tokio::test]
+async fn test_requires_python_of_wheel_is_honored() {
+    setup_tracing();
+
+    let simple = PyPIDatabase::new()
+        .with(PyPIPackage::new("newpkg", "1.0.0").with_requires_python(">=3.10"))
+        .into_simple_index()
+        .expect("failed to create local simple index");
+
+    let platform = Platform::current();
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.9.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "requires-python-test"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}} # Disable mapping
+
+        [dependencies]
+        python = "==3.9.0"
+
+        [pypi-dependencies]
+        newpkg = "*"
+
+        [pypi-options]
+        index-url = "{index_url}"
+        "#,
+        platform = platform,
+        channel_url = channel.url(),
+        index_url = simple.index_url(),
+    ))
+    .unwrap();
+
+    let error = pixi.update_lock_file().await.unwrap_err();
+    let error_msg = format!("{error:?}");
+    assert!(error_msg.contains("newpkg"), "{error_msg}");
+    assert!(
+        error_msg.contains("resolved for Python 3.9.0"),
+        "{error_msg}"
+    );
+}
+
+/// Tests that a constraint in the pypi-options narrows the version of a
+/// transitive dependency, without it being a direct dependency.
+#[tokio::test]
+async fn test_pypi_constraints_on_transitive_dependency() {
+    setup_tracing();
+
+    let simple = PyPIDatabase::new()
+        .with(PyPIPackage::new("app", "1.0.0").with_requires_dist(["lib"]))
+        .with(PyPIPackage::new("lib", "1.0.0"))
+        .with(PyPIPackage::new("lib", "2.0.0"))
+        .with(PyPIPackage::new("unused", "1.0.0"))
+        .into_simple_index()
+        .expect("failed to create local simple index");
+
+    let platform = Platform::current();
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.12.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "pypi-constraints"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}} # Disable mapping
+
+        [dependencies]
+        python = "==3.12.0"
+
+        [pypi-dependencies]
+        app = "*"
+
+        [pypi-options]
+        index-url = "{index_url}"
+
+        [pypi-options.constraints]
+        lib = "<2"
+        unused = "*"
+        "#,
+        platform = platform,
+        channel_url = channel.url(),
+        index_url = simple.index_url(),
+    ))
+    .unwrap();
+
+    let lock_file = pixi.update_lock_file().await.unwrap();
+    assert_eq!(
+        lock_file.get_pypi_package_version("default", platform, "lib"),
+        Some("1.0.0".to_string())
+    );
+    // A constraint does not add the package to the environment.
+    assert!(
+        lock_file
+            .get_pypi_package_version("default", platform, "unused")
+            .is_none()
+    );
+}
+
 /// Tests that prerelease-mode = "disallow" prevents pre-release versions from being resolved.
 #[tokio::test]
 async fn test_prerelease_mode_disallow() {
@@ -2527,3 +2642,260 @@ async fn test_index_url_omitted_for_default_pypi() {
         "lock file content should be identical after round-trip"
     );
 }
+
+/// The token of an index is read from `PIXI_PYPI_TOKEN_<HOST>` and is not
+/// written to the lock file.
+#[tokio::test]
+async fn test_index_token_from_env() {
+    setup_tracing();
+
+    let platform = Platform::current();
+
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.12.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+
+    let simple = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .into_simple_index()
+        .expect("failed to create simple index");
+    // The base64 encoding of `__token__:secret`.
+    let index_url = serve_directory(
+        simple.index_path().to_path_buf(),
+        Some("Basic X190b2tlbl9fOnNlY3JldA=="),
+    )
+    .await;
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "pypi-index-token"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}}
+
+        [dependencies]
+        python = "==3.12.0"
+
+        [pypi-dependencies]
+        foo = "*"
+
+        [pypi-options]
+        index-url = "{index_url}"
+        "#,
+        channel_url = channel.url(),
+    ))
+    .unwrap();
+
+    // Without the token the index rejects the requests
+    temp_env::async_with_vars([("PIXI_PYPI_TOKEN_127_0_0_1", None::<&str>)], async {
+        pixi.update_lock_file()
+            .await
+            .expect_err("the index requires a token");
+    })
+    .await;
+
+    let lock_file = temp_env::async_with_vars(
+        [("PIXI_PYPI_TOKEN_127_0_0_1", Some("secret"))],
+        pixi.update_lock_file(),
+    )
+    .await
+    .unwrap();
+
+    assert!(lock_file.contains_pypi_package("default", platform, "foo"));
+    assert!(!lock_file.render_to_string().unwrap().contains("secret"));
+}
+
+/// Updating the lock file to a new Python minor version reports the change.
+#[tokio::test]
+async fn test_python_abi_change_reinstalls_pypi_packages() {
+    setup_tracing();
+
+    let platform = Platform::current();
+
+    let mut package_db = MockRepoData::default();
+    for version in ["3.12.0", "3.13.0"] {
+        package_db.add_package(
+            Package::build("python", version)
+                .with_subdir(platform)
+                .finish(),
+        );
+    }
+    let channel = package_db.into_channel().await.unwrap();
+
+    let simple = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .into_simple_index()
+        .unwrap();
+
+    let manifest = |python: &str| {
+        format!(
+            r#"
+        [workspace]
+        name = "python-abi-change"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}}
+
+        [dependencies]
+        python = "=={python}"
+
+        [pypi-dependencies]
+        foo = "*"
+
+        [pypi-options]
+        index-url = "{index_url}"
+        "#,
+            channel_url = channel.url(),
+            index_url = simple.index_url(),
+        )
+    };
+
+    let pixi = PixiControl::from_manifest(&manifest("3.12.0")).unwrap();
+    let workspace = pixi.workspace().unwrap();
+    let (derived, _) = workspace
+        .update_lock_file(None, UpdateLockFileOptions::default())
+        .await
+        .unwrap();
+    assert!(derived.python_abi_changes.is_empty());
+
+    pixi.update_manifest(&manifest("3.13.0")).unwrap();
+    let workspace = pixi.workspace().unwrap();
+    let (derived, updated) = workspace
+        .update_lock_file(None, UpdateLockFileOptions::default())
+        .await
+        .unwrap();
+    assert!(updated);
+    assert!(
+        derived
+            .lock_file
+            .contains_pypi_package("default", platform, "foo")
+    );
+    assert_eq!(
+        derived
+            .python_abi_changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![format!(
+            "the Python version of the 'default' environment on {platform} changed from 3.12.0 to 3.13.0"
+        )]
+    );
+}
+
+/// A wheel at a direct URL is resolved from that URL, and the URL is recorded
+/// in the lock file.
+#[tokio::test]
+async fn test_direct_url_wheel() {
+    setup_tracing();
+
+    let platform = Platform::current();
+
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.12.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+
+    let flat = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .into_flat_index()
+        .unwrap();
+    let wheel_name = "foo-1.0.0-py3-none-any.whl";
+    let sha256 =
+        rattler_digest::compute_file_digest::<rattler_digest::Sha256>(flat.path().join(wheel_name))
+            .unwrap();
+    let wheel_url = serve_directory(flat.path().to_path_buf(), None)
+        .await
+        .join(wheel_name)
+        .unwrap();
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "pypi-direct-url"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}}
+
+        [dependencies]
+        python = "==3.12.0"
+
+        [pypi-dependencies]
+        foo = {{ url = "{wheel_url}#sha256={sha256:x}" }}
+        "#,
+        channel_url = channel.url(),
+    ))
+    .unwrap();
+    let lock_file = pixi.update_lock_file().await.unwrap();
+
+    let mut locked_url = lock_file
+        .get_pypi_package_url("default", platform, "foo")
+        .unwrap()
+        .as_url()
+        .unwrap()
+        .clone();
+    locked_url.set_fragment(None);
+    assert_eq!(locked_url, wheel_url);
+}
+
+/// A wheel at a direct URL whose `#sha256=` fragment doesn't match the
+/// contents of the wheel is rejected.
+#[tokio::test]
+async fn test_direct_url_wheel_hash_mismatch() {
+    setup_tracing();
+
+    let platform = Platform::current();
+
+    let mut package_db = MockRepoData::default();
+    package_db.add_package(
+        Package::build("python", "3.12.0")
+            .with_subdir(platform)
+            .finish(),
+    );
+    let channel = package_db.into_channel().await.unwrap();
+
+    let flat = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .into_flat_index()
+        .unwrap();
+    let wheel_url = serve_directory(flat.path().to_path_buf(), None)
+        .await
+        .join("foo-1.0.0-py3-none-any.whl")
+        .unwrap();
+    let wrong_sha256 = "0".repeat(64);
+
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+        [workspace]
+        name = "pypi-direct-url"
+        platforms = ["{platform}"]
+        channels = ["{channel_url}"]
+        conda-pypi-map = {{}}
+
+        [dependencies]
+        python = "==3.12.0"
+
+        [pypi-dependencies]
+        foo = {{ url = "{wheel_url}#sha256={wrong_sha256}" }}
+        "#,
+        channel_url = channel.url(),
+    ))
+    .unwrap();
+
+    let err = pixi
+        .update_lock_file()
+        .await
+        .expect_err("a wheel with the wrong hash should be rejected");
+    let rendered = format!("{err:?}");
+    assert!(
+        rendered.contains("Hash mismatch"),
+        "expected a hash mismatch error, got:\n{rendered}"
+    );
+}
@@ -6,7 +6,8 @@ use serde_json::Value;
 use tempfile::TempDir;
 use url::Url;
 
-use crate::common::PixiControl;
+use crate::common::pypi_index::{Database as PyPIDatabase, PyPIPackage};
+use crate::common::{PixiControl, serve_directory};
 use crate::setup_tracing;
 use pixi_test_utils::{MockRepoData, Package};
 
@@ -437,3 +438,68 @@ async fn test_search_json_output() {
     assert!(first_record.contains_key("depends"));
     assert!(first_record.contains_key("fn"));
 }
+
+/// Test that a PyPI search queries all configured indexes, labels every
+/// distribution with its index and lists identical wheels only once.
+#[tokio::test]
+async fn test_search_pypi_labels_results_by_index() {
+    setup_tracing();
+
+    let primary = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .with(PyPIPackage::new("foo", "2.0.0"))
+        .into_simple_index()
+        .unwrap();
+    let extra = PyPIDatabase::new()
+        .with(PyPIPackage::new("foo", "1.0.0"))
+        .with(PyPIPackage::new("foo", "3.0.0"))
+        .into_simple_index()
+        .unwrap();
+    let primary_url = serve_directory(primary.index_path().to_path_buf(), None).await;
+    let extra_url = serve_directory(extra.index_path().to_path_buf(), None).await;
+
+    let platform = Platform::current();
+    let pixi = PixiControl::from_manifest(&format!(
+        r#"
+    [workspace]
+    name = "test-search-pypi"
+    channels = []
+    platforms = ["{platform}"]
+
+    [pypi-options]
+    index-url = "{primary_url}"
+    extra-index-urls = ["{extra_url}"]
+    "#
+    ))
+    .unwrap();
+
+    let mut out = Vec::new();
+    let mut builder = pixi.search("foo".to_string());
+    builder.args.pypi = true;
+    search::execute_impl(builder.args, &mut out).await.unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+    assert_eq!(
+        output.trim(),
+        format!(
+            "foo\n  \
+             3.0.0 foo-3.0.0-py3-none-any.whl {extra_url}\n  \
+             2.0.0 foo-2.0.0-py3-none-any.whl {primary_url}\n  \
+             1.0.0 foo-1.0.0-py3-none-any.whl {primary_url}"
+        )
+    );
+
+    // `--index` restricts the search to a single index.
+    let mut out = Vec::new();
+    let mut builder = pixi.search("foo".to_string());
+    builder.args.index = Some(extra_url.clone());
+    search::execute_impl(builder.args, &mut out).await.unwrap();
+    let output = strip_ansi(&String::from_utf8(out).unwrap());
+    assert_eq!(
+        output.trim(),
+        format!(
+            "foo\n  \
+             3.0.0 foo-3.0.0-py3-none-any.whl {extra_url}\n  \
+             1.0.0 foo-1.0.0-py3-none-any.whl {extra_url}"
+        )
+    );
+}
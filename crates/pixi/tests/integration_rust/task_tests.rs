@@ -93,10 +93,11 @@ pub async fn add_command_types() {
         "\"echo hello\" \"echo bonjour\""
     );
 
-    // Create an alias
+    // Create an alias, `test3` does not exist yet
     pixi.tasks()
         .alias("testing".into(), None)
         .with_depends_on(vec!["test".into(), "test3".into()])
+        .with_allow_missing(true)
         .execute()
         .await
         .unwrap();
@@ -152,6 +153,141 @@ async fn test_alias() {
     assert_eq!(result.stdout, "hello\nworld\n");
 }
 
+#[tokio::test]
+async fn sequence_stops_at_first_failure() {
+    setup_tracing();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().without_channels().await.unwrap();
+
+    pixi.tasks()
+        .add("sequence".into(), None, FeatureName::default())
+        .with_sequence(["exit 3", "echo second > second.txt"])
+        .execute()
+        .await
+        .unwrap();
+
+    let project = pixi.workspace().unwrap();
+    let tasks = project.default_environment().tasks(None).unwrap();
+    let task = tasks.get(&<TaskName>::from("sequence")).unwrap();
+    assert!(matches!(
+        task,
+        Task::Execute(execute)
+            if matches!(&execute.cmd, CmdArgs::Sequence(commands) if commands.len() == 2)
+    ));
+    assert_eq!(
+        task.as_single_command_no_render().unwrap().unwrap(),
+        "exit 3 && echo second > second.txt"
+    );
+
+    let result = pixi
+        .run(Args {
+            task: vec!["sequence".to_string()],
+            ..Default::default()
+        })
+        .await;
+
+    assert!(result.is_err(), "the task should fail");
+    assert!(
+        !pixi.workspace_path().join("second.txt").exists(),
+        "the second command should not run after the first failed"
+    );
+}
+
+#[tokio::test]
+async fn add_existing_task_requires_force() {
+    setup_tracing();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().without_channels().await.unwrap();
+
+    pixi.tasks()
+        .add("test".into(), None, FeatureName::default())
+        .with_commands(["echo hello"])
+        .execute()
+        .await
+        .unwrap();
+
+    // Adding a task or an alias with the same name fails without `--force`
+    let err = pixi
+        .tasks()
+        .add("test".into(), None, FeatureName::default())
+        .with_commands(["echo bye"])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+    let err = pixi
+        .tasks()
+        .alias("test".into(), None)
+        .with_depends_on(vec!["other".into()])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+
+    pixi.tasks()
+        .add("test".into(), None, FeatureName::default())
+        .with_commands(["echo bye"])
+        .with_force(true)
+        .execute()
+        .await
+        .unwrap();
+
+    let project = pixi.workspace().unwrap();
+    let tasks = project.default_environment().tasks(None).unwrap();
+    let task = tasks.get(&<TaskName>::from("test")).unwrap();
+    assert!(matches!(task, Task::Plain(s) if *s == TemplateString::from("echo bye")));
+}
+
+#[tokio::test]
+async fn add_task_with_missing_dependency() {
+    setup_tracing();
+
+    let pixi = PixiControl::new().unwrap();
+    pixi.init().without_channels().await.unwrap();
+
+    pixi.tasks()
+        .add("build".into(), None, FeatureName::default())
+        .with_commands(["echo build"])
+        .execute()
+        .await
+        .unwrap();
+
+    // Depending on a task that does not exist fails when the task is added
+    let err = pixi
+        .tasks()
+        .add("test".into(), None, FeatureName::default())
+        .with_commands(["echo test"])
+        .with_depends_on(vec!["build".into(), "biuld".into()])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("biuld"), "{err}");
+    let err = pixi
+        .tasks()
+        .alias("all".into(), None)
+        .with_depends_on(vec!["biuld".into()])
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("biuld"), "{err}");
+
+    let project = pixi.workspace().unwrap();
+    let tasks = project.default_environment().tasks(None).unwrap();
+    assert!(!tasks.contains_key(&<TaskName>::from("test")));
+    assert!(!tasks.contains_key(&<TaskName>::from("all")));
+
+    // Unless missing tasks are allowed
+    pixi.tasks()
+        .alias("all".into(), None)
+        .with_depends_on(vec!["biuld".into()])
+        .with_allow_missing(true)
+        .execute()
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 pub async fn add_remove_target_specific_task() {
     setup_tracing();
@@ -394,24 +530,20 @@ async fn test_clean_env() {
         .await
         .unwrap();
 
-    let run = pixi.run(Args {
-        task: vec!["env-test".to_string()],
-        workspace_config: WorkspaceConfig {
-            manifest_path: None,
+    let result = pixi
+        .run(Args {
+            task: vec!["env-test".to_string()],
+            workspace_config: WorkspaceConfig {
+                manifest_path: None,
+                ..Default::default()
+            },
+            clean_env: true,
             ..Default::default()
-        },
-        clean_env: true,
-        ..Default::default()
-    });
-
-    if cfg!(windows) {
-        // Clean env running not supported on windows.
-        run.await.unwrap_err();
-    } else {
-        let result = run.await.unwrap();
-        assert_eq!(result.exit_code, 0);
-        assert_eq!(result.stdout, "Hello is:\n");
-    }
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(result.stdout, "Hello is:\n");
 
     let result = pixi
         .run(Args {
@@ -15,11 +15,13 @@ use pixi_spec::PixiSpec;
 use rattler_conda_types::{
     Channel, MatchSpec, NamedChannelOrUrl, PackageName, Platform, RepoDataRecord,
 };
+use url::Url;
 
 use crate::interface::Interface;
 use crate::workspace::add::GitOptions;
 use crate::workspace::{
-    ChannelOptions, DependencyOptions, InitOptions, Package, ReinstallOptions, RemoveError,
+    ChannelOptions, DependencyOptions, InitOptions, Package, PypiSearchResult, ReinstallOptions,
+    RemoveError,
 };
 
 pub struct DefaultContext<I: Interface> {
@@ -42,6 +44,20 @@ impl<I: Interface> DefaultContext<I> {
     ) -> miette::Result<Vec<RepoDataRecord>> {
         crate::workspace::search::search(None, matchspec, channels, platforms).await
     }
+
+    /// Search the PyPI indexes of the global configuration, or only `index`
+    /// if it is given, for the distributions of a package
+    pub async fn search_pypi(
+        &self,
+        package: &PypiPackageName,
+        index: Option<Url>,
+    ) -> miette::Result<Vec<PypiSearchResult>> {
+        let indexes = match index {
+            Some(index) => vec![index],
+            None => crate::workspace::search::pypi_indexes(None),
+        };
+        crate::workspace::search::search_pypi(None, package, &indexes).await
+    }
 }
 
 pub struct WorkspaceContext<I: Interface> {
@@ -413,6 +429,8 @@ impl<I: Interface> WorkspaceContext<I> {
         task: Task,
         feature: FeatureName,
         platform: Option<PixiPlatformName>,
+        force: bool,
+        allow_missing: bool,
     ) -> miette::Result<()> {
         crate::workspace::task::add_task(
             &self.interface,
@@ -421,6 +439,8 @@ impl<I: Interface> WorkspaceContext<I> {
             task,
             feature,
             platform,
+            force,
+            allow_missing,
         )
         .await
     }
@@ -430,6 +450,8 @@ impl<I: Interface> WorkspaceContext<I> {
         name: TaskName,
         task: Task,
         platform: Option<PixiPlatformName>,
+        force: bool,
+        allow_missing: bool,
     ) -> miette::Result<()> {
         crate::workspace::task::alias_task(
             &self.interface,
@@ -437,10 +459,21 @@ impl<I: Interface> WorkspaceContext<I> {
             name,
             task,
             platform,
+            force,
+            allow_missing,
         )
         .await
     }
 
+    pub async fn import_tasks(
+        &self,
+        tasks: Vec<(TaskName, Task, FeatureName, Option<PixiPlatformName>)>,
+        force: bool,
+    ) -> miette::Result<()> {
+        crate::workspace::task::import_tasks(&self.interface, self.workspace_mut()?, tasks, force)
+            .await
+    }
+
     pub async fn remove_task(
         &self,
         names: Vec<TaskName>,
@@ -467,4 +500,18 @@ impl<I: Interface> WorkspaceContext<I> {
         crate::workspace::search::search(Some(&self.workspace), matchspec, channels, platforms)
             .await
     }
+
+    /// Search the PyPI indexes of the workspace, or only `index` if it is
+    /// given, for the distributions of a package
+    pub async fn search_pypi(
+        &self,
+        package: &PypiPackageName,
+        index: Option<Url>,
+    ) -> miette::Result<Vec<PypiSearchResult>> {
+        let indexes = match index {
+            Some(index) => vec![index],
+            None => crate::workspace::search::pypi_indexes(Some(&self.workspace)),
+        };
+        crate::workspace::search::search_pypi(Some(&self.workspace), package, &indexes).await
+    }
 }
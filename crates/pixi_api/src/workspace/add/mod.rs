@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use miette::IntoDiagnostic;
+use pixi_consts::consts;
 use pixi_core::{
     environment::sanity_check_workspace,
     workspace::{PypiDeps, UpdateDeps, WorkspaceMut},
@@ -84,9 +85,6 @@ pub async fn add_conda_dep(
         match_specs = passed_specs;
     }
 
-    // TODO: add dry_run logic to add
-    let dry_run = false;
-
     let targets = workspace.target_selectors_for_platforms(&dep_options.platforms);
     let update_deps = match Box::pin(workspace.update_dependencies(
         match_specs,
@@ -97,11 +95,24 @@ pub async fn add_conda_dep(
         &dep_options.feature,
         &targets,
         false,
-        dry_run,
+        dep_options.dry_run,
+        dep_options.no_preserve,
     ))
     .await
     {
+        Ok(update_deps) if dep_options.dry_run => {
+            // Restore the manifest, only the solve result is of interest
+            workspace.revert().await.into_diagnostic()?;
+            update_deps
+        }
         Ok(update_deps) => {
+            if !dep_options.no_sort {
+                workspace.manifest().sort_dependency_table(
+                    spec_type.name(),
+                    &dep_options.platforms,
+                    &dep_options.feature,
+                );
+            }
             // Write the updated manifest
             workspace.save().await.into_diagnostic()?;
             update_deps
@@ -136,9 +147,6 @@ pub async fn add_pypi_dep(
         .manifest()
         .add_platforms(pixi_platforms.iter(), &FeatureName::DEFAULT)?;
 
-    // TODO: add dry_run logic to add
-    let dry_run = false;
-
     let targets = workspace.target_selectors_for_platforms(&options.platforms);
     let update_deps = match Box::pin(workspace.update_dependencies(
         IndexMap::default(),
@@ -149,11 +157,24 @@ pub async fn add_pypi_dep(
         &options.feature,
         &targets,
         editable,
-        dry_run,
+        options.dry_run,
+        options.no_preserve,
     ))
     .await
     {
+        Ok(update_deps) if options.dry_run => {
+            // Restore the manifest, only the solve result is of interest
+            workspace.revert().await.into_diagnostic()?;
+            update_deps
+        }
         Ok(update_deps) => {
+            if !options.no_sort {
+                workspace.manifest().sort_dependency_table(
+                    consts::PYPI_DEPENDENCIES,
+                    &options.platforms,
+                    &options.feature,
+                );
+            }
             // Write the updated manifest
             workspace.save().await.into_diagnostic()?;
             update_deps
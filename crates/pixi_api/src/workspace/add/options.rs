@@ -13,6 +13,17 @@ pub struct DependencyOptions {
     /// Don't modify the environment, only modify the lock file.
     pub no_install: bool,
     pub lock_file_usage: LockFileUsage,
+    /// Re-solve the affected environments from scratch instead of preferring
+    /// the versions that are already locked.
+    #[serde(default)]
+    pub no_preserve: bool,
+    /// Don't sort the dependency tables of the manifest after modifying them.
+    #[serde(default)]
+    pub no_sort: bool,
+    /// Solve as if the dependencies were added, without writing the manifest
+    /// or the lock file.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -12,20 +12,27 @@ use minijinja::{Environment, context};
 use pixi_config::{Config, get_default_author, pixi_home};
 use pixi_consts::consts;
 use pixi_core::{Workspace, workspace::WorkspaceMut};
-use pixi_manifest::{FeatureName, pyproject::PyProjectManifest};
+use pixi_manifest::{
+    FeatureName, Task, TaskName, normalize_local_channel, pyproject::PyProjectManifest,
+};
 use pixi_utils::conda_environment_file::CondaEnvFile;
-use rattler_conda_types::{NamedChannelOrUrl, Platform};
+use rattler_conda_types::{MatchSpec, NamedChannelOrUrl, ParseStrictness, Platform};
 use same_file::is_same_file;
 use tokio::fs::OpenOptions;
 use url::Url;
 use uv_normalize::PackageName;
+use uv_requirements_txt::RequirementsTxt;
 
-use crate::interface::Interface;
+use crate::{
+    interface::Interface, workspace::requirements_txt::convert_requirements_txt_to_pep508,
+};
 
 mod options;
+mod preset;
 mod template;
 
 pub use options::{GitAttributes, InitOptions, ManifestFormat};
+pub use preset::{InitTemplate, InitTemplateSource, init_templates, template_dirs};
 
 pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::Result<Workspace> {
     let env = Environment::new();
@@ -55,11 +62,33 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
         );
     }
 
+    let template = options
+        .template
+        .as_deref()
+        .map(preset::load_init_template)
+        .transpose()?;
+    if template.is_some() && options.env_file.is_some() {
+        miette::bail!("a template cannot be combined with importing an environment file");
+    }
+
+    // Relative channel paths are given relative to `cwd`, but the manifest
+    // resolves them relative to the workspace.
+    let cwd = options.cwd.as_deref().unwrap_or(&dir);
+    let cli_channels = options.channels.map(|channels| {
+        channels
+            .into_iter()
+            .map(|channel| normalize_local_channel(channel, cwd, &dir))
+            .collect_vec()
+    });
+
     let default_name = get_name_from_dir(&dir).unwrap_or_else(|_| String::from("new_workspace"));
     let version = "0.1.0";
     let author = get_default_author();
     let platforms = if options.platforms.is_empty() {
-        vec![Platform::current().to_string()]
+        template
+            .as_ref()
+            .and_then(|template| template.platforms.clone())
+            .unwrap_or_else(|| vec![Platform::current().to_string()])
     } else {
         // Dedup so a repeated `--platform` (or one matching the current
         // platform) doesn't write a manifest the parser then rejects.
@@ -70,7 +99,7 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
     let extra_index_urls = config.pypi_config.extra_index_urls.clone();
 
     // Create a 'pixi.toml' manifest and populate it by importing a conda
-    // environment file
+    // environment file or a pip requirements file
     let workspace = if let Some(env_file_path) = options.env_file {
         // Check if the 'pixi.toml' file doesn't already exist. We don't want to
         // overwrite it.
@@ -78,17 +107,39 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
             miette::bail!("{} already exists", consts::WORKSPACE_MANIFEST);
         }
 
-        let env_file = CondaEnvFile::from_path(&env_file_path)?;
-        let name = env_file
-            .name()
-            .unwrap_or(default_name.clone().as_str())
-            .to_string();
-
-        let env_vars = env_file.variables();
-
-        // TODO: Improve this:
-        //  - Use .condarc as channel config
-        let (conda_deps, pypi_deps, channels) = env_file.to_manifest(&config)?;
+        let (name, env_vars, conda_deps, pypi_deps, channels) =
+            if is_requirements_file(&env_file_path) {
+                let reqs_txt = RequirementsTxt::parse(&env_file_path, &dir)
+                    .await
+                    .into_diagnostic()?;
+                let pypi_deps = convert_requirements_txt_to_pep508(reqs_txt)?;
+
+                // The pypi dependencies need a python interpreter from conda
+                let python = MatchSpec::from_str("python", ParseStrictness::Strict)
+                    .expect("python is a valid match spec");
+
+                let channels = cli_channels.unwrap_or_else(|| config.default_channels().to_vec());
+                (
+                    default_name.clone(),
+                    HashMap::new(),
+                    vec![python],
+                    pypi_deps,
+                    channels,
+                )
+            } else {
+                let env_file = CondaEnvFile::from_path(&env_file_path)?;
+                let name = env_file
+                    .name()
+                    .unwrap_or(default_name.clone().as_str())
+                    .to_string();
+
+                let env_vars = env_file.variables();
+
+                // TODO: Improve this:
+                //  - Use .condarc as channel config
+                let (conda_deps, pypi_deps, channels) = env_file.to_manifest(&config)?;
+                (name, env_vars, conda_deps, pypi_deps, channels)
+            };
         let rendered_workspace_template = render_workspace(
             &env,
             name,
@@ -118,7 +169,12 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
 
         workspace
     } else {
-        let channels = if let Some(channels) = options.channels {
+        let channels = if let Some(channels) = cli_channels {
+            channels
+        } else if let Some(channels) = template
+            .as_ref()
+            .and_then(|template| template.channels.clone())
+        {
             channels
         } else {
             config.default_channels().to_vec()
@@ -142,13 +198,18 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
         // Inject a tool.pixi.workspace section into an existing pyproject.toml file if
         // there is one without '[tool.pixi.workspace]'
         if pyproject && pyproject_manifest_path.is_file() {
-            let pyproject = PyProjectManifest::from_path(&pyproject_manifest_path)?;
+            let mut pyproject = PyProjectManifest::from_path(&pyproject_manifest_path)?;
 
-            // Early exit if 'pyproject.toml' already contains a '[tool.pixi.workspace]' table
+            // Early exit if 'pyproject.toml' already contains a '[tool.pixi.workspace]' table,
+            // unless the user explicitly asked to replace it.
             if pyproject.has_pixi_table() {
-                interface.info("Nothing to do here: 'pyproject.toml' already contains a '[tool.pixi.workspace]' section.").await;
-                let workspace = Workspace::from_path(&pyproject_manifest_path)?;
-                return Ok(workspace);
+                if !options.force {
+                    interface.info("Nothing to do here: 'pyproject.toml' already contains a '[tool.pixi.workspace]' section. Use '--force' to replace it.").await;
+                    let workspace = Workspace::from_path(&pyproject_manifest_path)?;
+                    return Ok(workspace);
+                }
+                remove_pixi_table(&pyproject_manifest_path)?;
+                pyproject = PyProjectManifest::from_path(&pyproject_manifest_path)?;
             }
 
             let (name, pixi_name) = match pyproject.name() {
@@ -298,6 +359,11 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
         }
     };
 
+    let workspace = match &template {
+        Some(template) => apply_template(workspace, template).await?,
+        None => workspace,
+    };
+
     // create a .gitignore if one is missing
     if let Err(e) =
         create_or_append_file(&gitignore_path, template::GITIGNORE_TEMPLATE.trim_start())
@@ -323,6 +389,62 @@ pub async fn init<I: Interface>(interface: &I, options: InitOptions) -> miette::
     Ok(workspace)
 }
 
+/// Adds the dependencies and tasks of a template to a newly created workspace.
+async fn apply_template(
+    workspace: Workspace,
+    template: &InitTemplate,
+) -> miette::Result<Workspace> {
+    if template.dependencies.is_empty()
+        && template.pypi_dependencies.is_empty()
+        && template.tasks.is_empty()
+    {
+        return Ok(workspace);
+    }
+
+    let mut workspace = workspace.modify()?;
+    workspace.add_specs(
+        template.conda_specs()?,
+        template.pypi_requirements()?,
+        &[],
+        &FeatureName::DEFAULT,
+    )?;
+    for (name, cmd) in &template.tasks {
+        workspace.manifest().add_task(
+            TaskName::from(name.as_str()),
+            Task::Plain(cmd.as_str().into()),
+            None,
+            &FeatureName::DEFAULT,
+        )?;
+    }
+    workspace.save().await.into_diagnostic()
+}
+
+/// Removes the `[tool.pixi]` table from the "pyproject.toml" at the given path,
+/// keeping the rest of the document and its formatting intact.
+fn remove_pixi_table(path: &Path) -> miette::Result<()> {
+    let contents = fs_err::read_to_string(path).into_diagnostic()?;
+    let mut document = toml_edit::DocumentMut::from_str(&contents).into_diagnostic()?;
+    if let Some(tool) = document
+        .get_mut("tool")
+        .and_then(|tool| tool.as_table_like_mut())
+    {
+        tool.remove("pixi");
+        // Don't leave an empty `[tool]` table behind
+        if tool.is_empty() {
+            document.remove("tool");
+        }
+    }
+    fs_err::write(path, document.to_string()).into_diagnostic()
+}
+
+/// Returns true if the file to import looks like a pip requirements file
+/// (e.g. `requirements.txt` or `requirements.in`) instead of a conda
+/// environment file.
+fn is_requirements_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt") || ext.eq_ignore_ascii_case("in"))
+}
+
 fn is_init_dir_equal_to_pixi_home_parent(init_dir: &Path) -> bool {
     pixi_home()
         .as_ref()
@@ -11,10 +11,15 @@ pub struct InitOptions {
     /// Channel to use in the workspace. Defaults to conda-forge when empty.
     pub channels: Option<Vec<NamedChannelOrUrl>>,
 
+    /// The directory that relative channel paths are given relative to, e.g.
+    /// the current directory of the CLI. Defaults to the workspace directory.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+
     /// Platforms that the workspace supports. Defaults to currently used platform when empty.
     pub platforms: Vec<String>,
 
-    /// Environment.yml file to bootstrap the workspace.
+    /// Environment.yml or requirements.txt file to bootstrap the workspace.
     pub env_file: Option<PathBuf>,
 
     /// The manifest format to create. Defaults to [ManifestFormat::Pixi] or asks the user when a "pyproject.toml" file already exists.
@@ -25,6 +30,13 @@ pub struct InitOptions {
 
     /// The conda-pypi-mapping
     pub conda_pypi_mapping: Option<HashMap<NamedChannelOrUrl, String>>,
+
+    /// Replace an existing `[tool.pixi]` section of a "pyproject.toml".
+    #[serde(default)]
+    pub force: bool,
+    /// The name of the template that pre-fills the manifest.
+    #[serde(default)]
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,196 @@
+//! Named templates that pre-fill the manifest created by `pixi init`.
+//!
+//! A template is a TOML file in one of the [`template_dirs`], named after the
+//! template, e.g. `~/.pixi/templates/python.toml`:
+//!
+//! ```toml
+//! channels = ["conda-forge"]
+//! platforms = ["linux-64", "osx-arm64"]
+//!
+//! [dependencies]
+//! python = ">=3.12"
+//!
+//! [pypi-dependencies]
+//! rich = "*"
+//!
+//! [tasks]
+//! default = "python main.py"
+//! ```
+//!
+//! User templates take precedence over the built-in templates with the same
+//! name.
+
+use std::{path::PathBuf, str::FromStr};
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+use pep508_rs::Requirement;
+use pixi_config::pixi_home;
+use pixi_consts::consts;
+use rattler_conda_types::{MatchSpec, NamedChannelOrUrl, ParseStrictness};
+use serde::Deserialize;
+
+/// The extension of a template file.
+const TEMPLATE_EXTENSION: &str = "toml";
+
+/// The templates that are shipped with pixi.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[(
+    "python",
+    r#"channels = ["conda-forge"]
+
+[dependencies]
+python = "*"
+
+[tasks]
+default = "python main.py"
+"#,
+)];
+
+/// The contents of a template.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct InitTemplate {
+    /// The channels of the workspace, unless channels are given explicitly.
+    pub channels: Option<Vec<NamedChannelOrUrl>>,
+
+    /// The platforms of the workspace, unless platforms are given explicitly.
+    pub platforms: Option<Vec<String>>,
+
+    /// The conda dependencies, mapping a package name to its spec.
+    #[serde(default)]
+    pub dependencies: IndexMap<String, String>,
+
+    /// The PyPI dependencies, mapping a package name to its specifiers.
+    #[serde(default)]
+    pub pypi_dependencies: IndexMap<String, String>,
+
+    /// The tasks, mapping a task name to its command.
+    #[serde(default)]
+    pub tasks: IndexMap<String, String>,
+}
+
+impl InitTemplate {
+    /// Returns the conda dependencies of the template as match specs.
+    pub(crate) fn conda_specs(&self) -> miette::Result<Vec<MatchSpec>> {
+        self.dependencies
+            .iter()
+            .map(|(name, spec)| {
+                MatchSpec::from_str(&format!("{name} {spec}"), ParseStrictness::Lenient)
+                    .into_diagnostic()
+                    .with_context(|| format!("invalid spec for '{name}' in template"))
+            })
+            .collect()
+    }
+
+    /// Returns the PyPI dependencies of the template as requirements.
+    pub(crate) fn pypi_requirements(&self) -> miette::Result<Vec<Requirement>> {
+        self.pypi_dependencies
+            .iter()
+            .map(|(name, specifiers)| {
+                let requirement = if specifiers == "*" {
+                    name.clone()
+                } else {
+                    format!("{name}{specifiers}")
+                };
+                Requirement::from_str(&requirement)
+                    .into_diagnostic()
+                    .with_context(|| format!("invalid requirement for '{name}' in template"))
+            })
+            .collect()
+    }
+}
+
+/// A template that is available to `pixi init`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitTemplateSource {
+    /// The name of the template.
+    pub name: String,
+
+    /// The file the template is read from, or `None` for a built-in template.
+    pub path: Option<PathBuf>,
+}
+
+/// Returns the directories that contain user templates, in order of
+/// increasing precedence.
+pub fn template_dirs() -> Vec<PathBuf> {
+    [
+        dirs::config_dir().map(|dir| dir.join(consts::CONFIG_DIR).join("templates")),
+        pixi_home().map(|dir| dir.join("templates")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Returns all available templates sorted by name.
+pub fn init_templates() -> Vec<InitTemplateSource> {
+    let mut templates: IndexMap<String, Option<PathBuf>> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (name.to_string(), None))
+        .collect();
+
+    for dir in template_dirs() {
+        let Ok(entries) = fs_err::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.extension().and_then(|ext| ext.to_str()) != Some(TEMPLATE_EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                templates.insert(name.to_string(), Some(path.clone()));
+            }
+        }
+    }
+
+    templates
+        .into_iter()
+        .map(|(name, path)| InitTemplateSource { name, path })
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect()
+}
+
+/// Loads the template with the given name.
+pub(crate) fn load_init_template(name: &str) -> miette::Result<InitTemplate> {
+    let templates = init_templates();
+    let Some(template) = templates.iter().find(|template| template.name == name) else {
+        miette::bail!(
+            help = format!(
+                "available templates are: {}",
+                templates.iter().map(|template| &template.name).join(", ")
+            ),
+            "unknown template '{name}'"
+        );
+    };
+
+    let contents = match &template.path {
+        Some(path) => fs_err::read_to_string(path).into_diagnostic()?,
+        None => BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, contents)| contents.to_string())
+            .expect("the template is a built-in template"),
+    };
+    toml_edit::de::from_str(&contents)
+        .into_diagnostic()
+        .with_context(|| format!("failed to parse template '{name}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_parse() {
+        for (name, contents) in BUILTIN_TEMPLATES {
+            let template: InitTemplate = toml_edit::de::from_str(contents)
+                .unwrap_or_else(|err| panic!("template '{name}' is invalid: {err}"));
+            template.conda_specs().unwrap();
+            template.pypi_requirements().unwrap();
+        }
+    }
+}
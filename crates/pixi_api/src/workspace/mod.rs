@@ -5,11 +5,16 @@ pub mod platforms;
 pub(crate) mod add;
 pub use add::{DependencyOptions, GitOptions};
 
+pub mod requirements_txt;
+
 pub(crate) mod remove;
 pub use remove::RemoveError;
 
 pub(crate) mod init;
-pub use init::{GitAttributes, InitOptions, ManifestFormat};
+pub use init::{
+    GitAttributes, InitOptions, InitTemplate, InitTemplateSource, ManifestFormat, init_templates,
+    template_dirs,
+};
 
 pub(crate) mod list;
 pub use list::{Package, PackageKind};
@@ -18,6 +23,7 @@ pub(crate) mod reinstall;
 pub use reinstall::ReinstallOptions;
 
 pub(crate) mod search;
+pub use search::{PypiSearchResult, environment_pypi_indexes, search_pypi};
 
 pub(crate) mod task;
 
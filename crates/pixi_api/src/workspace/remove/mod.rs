@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use miette::Diagnostic;
+use pixi_consts::consts;
 use pixi_core::{
     InstallFilter, UpdateLockFileOptions,
     environment::{LockFileUsage, get_update_lock_file_and_prefix},
@@ -85,6 +86,13 @@ pub async fn remove_conda_deps(
             &options.feature,
         )?;
     }
+    if !options.no_sort {
+        workspace.manifest().sort_dependency_table(
+            spec_type.name(),
+            &options.platforms,
+            &options.feature,
+        );
+    }
     let workspace = workspace.save().await.map_err(RemoveError::Save)?;
 
     // TODO: update all environments touched by this feature defined.
@@ -120,6 +128,13 @@ pub async fn remove_pypi_deps(
             .manifest()
             .remove_pypi_dependency(name, &options.platforms, &options.feature)?;
     }
+    if !options.no_sort {
+        workspace.manifest().sort_dependency_table(
+            consts::PYPI_DEPENDENCIES,
+            &options.platforms,
+            &options.feature,
+        );
+    }
 
     let workspace = workspace.save().await.map_err(RemoveError::Save)?;
 
@@ -171,6 +186,9 @@ mod tests {
             platforms: vec![],
             no_install: true,
             lock_file_usage: LockFileUsage::Frozen,
+            no_preserve: false,
+            no_sort: false,
+            dry_run: false,
         }
     }
 
@@ -0,0 +1,30 @@
+use miette::IntoDiagnostic;
+use pixi_uv_conversions::convert_uv_requirements_to_pep508;
+use uv_requirements_txt::{RequirementsTxt, RequirementsTxtRequirement};
+
+/// Converts the requirements of a parsed `requirements.txt` file into pep508
+/// requirements.
+///
+/// Unnamed requirements are not supported and result in an error, constraints
+/// are ignored with a warning.
+pub fn convert_requirements_txt_to_pep508(
+    reqs_txt: RequirementsTxt,
+) -> miette::Result<Vec<pep508_rs::Requirement>> {
+    let uv_requirements: Vec<uv_pep508::Requirement<uv_pypi_types::VerbatimParsedUrl>> = reqs_txt
+        .requirements
+        .into_iter()
+        .map(|r| match r.requirement {
+            RequirementsTxtRequirement::Named(req) => Ok(req),
+            RequirementsTxtRequirement::Unnamed(_) => Err(miette::miette!(
+                "Error parsing input file: unnamed requirements are currently unsupported."
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    if !reqs_txt.constraints.is_empty() {
+        tracing::warn!(
+            "Constraints detected in input file, but these are currently unsupported. Continuing without applying constraints..."
+        )
+    }
+
+    convert_uv_requirements_to_pep508(uv_requirements.iter()).into_diagnostic()
+}
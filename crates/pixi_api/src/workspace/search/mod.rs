@@ -5,6 +5,9 @@ use pixi_core::Workspace;
 use pixi_utils::reqwest::build_lazy_reqwest_clients;
 use rattler_conda_types::{Channel, MatchSpec, Platform, RepoDataRecord};
 
+mod pypi;
+pub use pypi::{PypiSearchResult, environment_pypi_indexes, pypi_indexes, search_pypi};
+
 pub async fn search(
     workspace: Option<&Workspace>,
     matchspec: MatchSpec,
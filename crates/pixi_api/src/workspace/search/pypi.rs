@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+use pixi_config::Config;
+use pixi_consts::consts;
+use pixi_core::{Workspace, workspace::Environment};
+use pixi_manifest::FeaturesExt;
+use pixi_pypi_spec::PypiPackageName;
+use pixi_utils::reqwest::build_lazy_reqwest_clients;
+use pixi_uv_context::UvResolutionContext;
+use pixi_uv_conversions::configure_insecure_hosts_for_tls_bypass;
+use tokio::sync::Semaphore;
+use url::Url;
+use uv_client::{Connectivity, IndexMetadataRef, MetadataFormat, OwnedArchive};
+use uv_configuration::IndexStrategy;
+use uv_distribution_types::{Index, IndexLocations, IndexUrl};
+use uv_pep440::Version;
+use uv_pep508::VerbatimUrl;
+use uv_redacted::DisplaySafeUrl;
+
+/// A distribution of a PyPI package that was found on one of the indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PypiSearchResult {
+    /// The filename of the wheel or source distribution.
+    pub filename: String,
+    /// The version of the distribution.
+    pub version: Option<Version>,
+    /// The url the distribution can be downloaded from.
+    pub url: Url,
+    /// The index the distribution was found on.
+    pub index: Url,
+}
+
+/// Returns the PyPI indexes that are searched: the index and the extra indexes
+/// of the default environment of the workspace, or of the global
+/// configuration if there is no workspace. Falls back to PyPI.
+pub fn pypi_indexes(workspace: Option<&Workspace>) -> Vec<Url> {
+    let (index_url, extra_index_urls) = match workspace {
+        Some(workspace) => return environment_pypi_indexes(&workspace.default_environment()),
+        None => {
+            let config = Config::load_global().pypi_config;
+            (config.index_url, config.extra_index_urls)
+        }
+    };
+    std::iter::once(index_url.unwrap_or_else(|| consts::DEFAULT_PYPI_INDEX_URL.clone()))
+        .chain(extra_index_urls)
+        .collect()
+}
+
+/// Returns the index and the extra indexes of an environment. Falls back to
+/// PyPI.
+pub fn environment_pypi_indexes(environment: &Environment<'_>) -> Vec<Url> {
+    let options = environment.pypi_options();
+    std::iter::once(
+        options
+            .index_url
+            .unwrap_or_else(|| consts::DEFAULT_PYPI_INDEX_URL.clone()),
+    )
+    .chain(options.extra_index_urls.unwrap_or_default())
+    .collect()
+}
+
+/// Searches the given PyPI indexes for the distributions of a package.
+///
+/// The indexes are queried in order through the uv registry client, and every
+/// distribution is labelled with the index it was found on. A distribution
+/// that is served by several indexes with the same filename and hashes is
+/// only listed for the first one. The results are sorted from the newest to
+/// the oldest version.
+pub async fn search_pypi(
+    workspace: Option<&Workspace>,
+    package: &PypiPackageName,
+    indexes: &[Url],
+) -> miette::Result<Vec<PypiSearchResult>> {
+    let uv_context = match workspace {
+        Some(workspace) => {
+            UvResolutionContext::from_config(workspace.config(), workspace.client()?.clone())?
+        }
+        None => {
+            let config = Config::load_global();
+            let client = build_lazy_reqwest_clients(Some(&config), None)?.0;
+            UvResolutionContext::from_config(&config, client)?
+        }
+    };
+
+    let index_urls = indexes
+        .iter()
+        .map(|index| {
+            IndexUrl::from(VerbatimUrl::from_url(DisplaySafeUrl::from_url(
+                index.clone(),
+            )))
+        })
+        .collect_vec();
+    let index_locations = IndexLocations::new(
+        index_urls
+            .iter()
+            .cloned()
+            .map(Index::from_extra_index_url)
+            .collect(),
+        Vec::new(),
+        false,
+    );
+    let allow_insecure_hosts = configure_insecure_hosts_for_tls_bypass(
+        uv_context.allow_insecure_host.clone(),
+        uv_context.tls_no_verify,
+        &index_locations,
+    );
+    let registry_client = uv_context.build_registry_client(
+        allow_insecure_hosts,
+        &index_locations,
+        IndexStrategy::default(),
+        None,
+        Connectivity::Online,
+    )?;
+    let download_concurrency = Semaphore::new(uv_context.concurrency.downloads);
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for (index, index_url) in indexes.iter().zip(&index_urls) {
+        let archives = match registry_client
+            .simple_detail(
+                package.as_normalized(),
+                Some(IndexMetadataRef::from(index_url)),
+                &uv_context.capabilities,
+                &download_concurrency,
+            )
+            .await
+        {
+            Ok(archives) => archives,
+            Err(err) if matches!(err.kind(), uv_client::ErrorKind::RemotePackageNotFound(_)) => {
+                tracing::debug!("'{}' is not available on {index}", package.as_source());
+                continue;
+            }
+            Err(err) => {
+                return Err(err)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("failed to query the index {index}"));
+            }
+        };
+
+        for (_, archive) in archives {
+            let MetadataFormat::Simple(archive) = archive else {
+                continue;
+            };
+            let metadata = OwnedArchive::deserialize(&archive);
+            for datum in metadata.iter() {
+                for (filename, file) in datum.files.clone().all() {
+                    let hashes = file.hashes.iter().map(ToString::to_string).collect_vec();
+                    if !seen.insert((file.filename.to_string(), hashes)) {
+                        continue;
+                    }
+                    let Ok(url) = file.url.to_url() else {
+                        continue;
+                    };
+                    results.push(PypiSearchResult {
+                        filename: file.filename.to_string(),
+                        version: Some(filename.version().clone()),
+                        url: Url::clone(&url),
+                        index: index.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if results.is_empty() {
+        return Err(miette::miette!(
+            help = format!("searched {}", indexes.iter().map(Url::as_str).join(", ")),
+            "No distributions found for '{}' on the PyPI indexes",
+            package.as_source()
+        ));
+    }
+
+    // The sort is stable, so files of the same version keep the order of the
+    // indexes.
+    results.sort_by(|a, b| b.version.cmp(&a.version));
+
+    Ok(results)
+}
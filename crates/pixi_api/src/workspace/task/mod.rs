@@ -11,7 +11,7 @@ use pixi_core::{
 };
 use pixi_manifest::{
     EnvironmentName, FeatureName, HasWorkspaceManifest, PixiPlatform, PixiPlatformName, Task,
-    TaskName,
+    TaskName, WorkspaceManifest,
 };
 
 use crate::interface::Interface;
@@ -37,16 +37,48 @@ fn resolve_task_platform(
     )
 }
 
+/// Returns an error for the first `depends-on` entry of `task` that is not a
+/// task of any feature or target of the workspace.
+fn ensure_dependencies_exist(
+    manifest: &WorkspaceManifest,
+    name: &TaskName,
+    task: &Task,
+) -> miette::Result<()> {
+    let existing: HashSet<&TaskName> = manifest
+        .features
+        .values()
+        .flat_map(|feature| feature.targets.iter())
+        .flat_map(|(target, _)| target.tasks.keys())
+        .collect();
+    if let Some(missing) = task
+        .depends_on()
+        .iter()
+        .find(|dependency| !existing.contains(&dependency.task_name))
+    {
+        miette::bail!(
+            help = "add the task first, or use `--allow-missing` to add it anyway",
+            "task '{}' depends on '{}', which does not exist",
+            name.fancy_display(),
+            missing.task_name.fancy_display()
+        );
+    }
+    Ok(())
+}
+
 /// Resolve `platform`, auto-declare it on the default feature when it's a
 /// subdir pixi hasn't seen yet (matching `pixi add --platform`), then add the
 /// task under `feature`. Shared by `add_task`/`alias_task` so their resolution
-/// rules stay in lock-step. Does not save the workspace.
+/// rules stay in lock-step. An existing task with the same name is only
+/// replaced if `force` is set, and the tasks it depends on have to exist
+/// unless `allow_missing` is set. Does not save the workspace.
 fn declare_platform_and_add_task(
     workspace: &mut WorkspaceMut,
     name: &TaskName,
     task: &Task,
     feature: &FeatureName,
     platform: Option<&PixiPlatformName>,
+    force: bool,
+    allow_missing: bool,
 ) -> miette::Result<()> {
     let pixi_platform = resolve_task_platform(workspace.workspace(), platform)?;
     // The auto-declare is idempotent on already-declared entries.
@@ -55,9 +87,27 @@ fn declare_platform_and_add_task(
             .manifest()
             .add_platforms(std::slice::from_ref(p).iter(), &FeatureName::DEFAULT)?;
     }
-    workspace
-        .manifest()
-        .add_task(name.clone(), task.clone(), pixi_platform.as_ref(), feature)?;
+    let mut manifest = workspace.manifest();
+    if !force
+        && manifest
+            .workspace
+            .tasks(pixi_platform.as_ref(), feature)
+            .is_ok_and(|tasks| tasks.contains_key(name))
+    {
+        miette::bail!(
+            help = "use `--force` to replace it",
+            "task '{}' already exists",
+            name.fancy_display()
+        );
+    }
+    if !allow_missing {
+        ensure_dependencies_exist(manifest.workspace, name, task)?;
+    }
+    if force {
+        manifest.replace_task(name.clone(), task.clone(), pixi_platform.as_ref(), feature)?;
+    } else {
+        manifest.add_task(name.clone(), task.clone(), pixi_platform.as_ref(), feature)?;
+    }
     Ok(())
 }
 
@@ -125,8 +175,18 @@ pub async fn add_task<I: Interface>(
     task: Task,
     feature: FeatureName,
     platform: Option<PixiPlatformName>,
+    force: bool,
+    allow_missing: bool,
 ) -> miette::Result<()> {
-    declare_platform_and_add_task(&mut workspace, &name, &task, &feature, platform.as_ref())?;
+    declare_platform_and_add_task(
+        &mut workspace,
+        &name,
+        &task,
+        &feature,
+        platform.as_ref(),
+        force,
+        allow_missing,
+    )?;
     workspace.save().await.into_diagnostic()?;
 
     interface
@@ -146,6 +206,8 @@ pub async fn alias_task<I: Interface>(
     name: TaskName,
     task: Task,
     platform: Option<PixiPlatformName>,
+    force: bool,
+    allow_missing: bool,
 ) -> miette::Result<()> {
     declare_platform_and_add_task(
         &mut workspace,
@@ -153,6 +215,8 @@ pub async fn alias_task<I: Interface>(
         &task,
         &FeatureName::DEFAULT,
         platform.as_ref(),
+        force,
+        allow_missing,
     )?;
     workspace.save().await.into_diagnostic()?;
 
@@ -167,6 +231,57 @@ pub async fn alias_task<I: Interface>(
     Ok(())
 }
 
+/// Adds the given tasks to the workspace. A task that already exists is
+/// skipped, unless `force` is set in which case it is replaced. The tasks may
+/// depend on each other, so their dependencies are not validated.
+pub async fn import_tasks<I: Interface>(
+    interface: &I,
+    mut workspace: WorkspaceMut,
+    tasks: Vec<(TaskName, Task, FeatureName, Option<PixiPlatformName>)>,
+    force: bool,
+) -> miette::Result<()> {
+    let mut imported = 0;
+    let mut skipped = Vec::new();
+    for (name, task, feature, platform) in tasks {
+        let pixi_platform = resolve_task_platform(workspace.workspace(), platform.as_ref())?;
+        let exists = workspace
+            .workspace()
+            .workspace
+            .value
+            .tasks(pixi_platform.as_ref(), &feature)
+            .is_ok_and(|tasks| tasks.contains_key(&name));
+        if exists && !force {
+            skipped.push(name);
+            continue;
+        }
+        declare_platform_and_add_task(
+            &mut workspace,
+            &name,
+            &task,
+            &feature,
+            platform.as_ref(),
+            true,
+            true,
+        )?;
+        imported += 1;
+    }
+    workspace.save().await.into_diagnostic()?;
+
+    for name in skipped {
+        interface
+            .warning(&format!(
+                "Skipped task `{}` because it already exists, use `--force` to replace it",
+                name.fancy_display().bold(),
+            ))
+            .await;
+    }
+    interface
+        .success(&format!("Imported {imported} task(s)"))
+        .await;
+
+    Ok(())
+}
+
 pub async fn remove_tasks<I: Interface>(
     interface: &I,
     mut workspace: WorkspaceMut,
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
 
 use miette::IntoDiagnostic;
 use pixi_core::{
@@ -8,8 +11,9 @@ use pixi_core::{
     workspace::WorkspaceMut,
 };
 use pixi_manifest::FeaturesExt;
-use pixi_manifest::{EnvironmentName, FeatureName, PrioritizedChannel};
+use pixi_manifest::{EnvironmentName, FeatureName, PrioritizedChannel, normalize_local_channel};
 use rattler_conda_types::{ChannelConfig, NamedChannelOrUrl};
+use rattler_lock::{CondaPackageData, LockFile, LockedPackage};
 use serde::{Deserialize, Serialize};
 
 use crate::Interface;
@@ -17,6 +21,10 @@ use crate::Interface;
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct ChannelOptions {
     pub channels: Vec<NamedChannelOrUrl>,
+    /// The directory that relative channel paths are given relative to, e.g.
+    /// the current directory of the CLI. Defaults to the workspace root.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
     pub feature: Option<String>,
     pub no_install: bool,
     pub lock_file_usage: LockFileUsage,
@@ -38,10 +46,12 @@ pub async fn list(workspace: &Workspace) -> HashMap<EnvironmentName, Vec<NamedCh
 pub async fn add<I: Interface>(
     interface: &I,
     mut workspace: WorkspaceMut,
-    options: ChannelOptions,
+    mut options: ChannelOptions,
     priority: Option<i32>,
     prepend: bool,
 ) -> miette::Result<()> {
+    normalize_channels(workspace.workspace(), &mut options);
+
     // Add the channels to the manifest
     workspace.manifest().add_channels(
         prioritized_channels(&options.channels, priority),
@@ -83,9 +93,38 @@ pub async fn add<I: Interface>(
 pub async fn remove<I: Interface>(
     interface: &I,
     mut workspace: WorkspaceMut,
-    options: ChannelOptions,
+    mut options: ChannelOptions,
     priority: Option<i32>,
 ) -> miette::Result<()> {
+    normalize_channels(workspace.workspace(), &mut options);
+
+    // Warn about the locked packages that come from the removed channels,
+    // they will have to be resolved from the remaining channels. The warning
+    // is best-effort, a lock file that can't be read doesn't stop the removal.
+    let channel_config = workspace.workspace().channel_config();
+    let removed_channels: Vec<String> = options
+        .channels
+        .iter()
+        .filter_map(|channel| channel.clone().into_base_url(&channel_config).ok())
+        .map(|url| url.to_string())
+        .collect();
+    let lock_file = match workspace.workspace().load_lock_file().await {
+        Ok(lock_file) => lock_file.into_lock_file_or_empty(),
+        Err(err) => {
+            tracing::debug!("not checking the locked packages of the removed channels: {err}");
+            LockFile::default()
+        }
+    };
+    let dependent_packages = packages_from_channels(&lock_file, &removed_channels);
+    if !dependent_packages.is_empty() {
+        interface
+            .warning(&format!(
+                "The following locked packages come from the removed channel(s) and will be resolved from the remaining channels: {}",
+                dependent_packages.into_iter().collect::<Vec<_>>().join(", ")
+            ))
+            .await;
+    }
+
     // Remove the channels from the manifest
     workspace.manifest().remove_channels(
         prioritized_channels(&options.channels, priority),
@@ -125,8 +164,10 @@ pub async fn remove<I: Interface>(
 pub async fn set<I: Interface>(
     interface: &I,
     mut workspace: WorkspaceMut,
-    options: ChannelOptions,
+    mut options: ChannelOptions,
 ) -> miette::Result<()> {
+    normalize_channels(workspace.workspace(), &mut options);
+
     // Set the channels in the manifest (this replaces all existing channels)
     workspace.manifest().set_channels(
         prioritized_channels(&options.channels, None),
@@ -164,6 +205,39 @@ pub async fn set<I: Interface>(
     Ok(())
 }
 
+/// Returns the names of the locked conda packages whose url starts with one of
+/// the given channel urls.
+fn packages_from_channels(lock_file: &LockFile, channels: &[String]) -> BTreeSet<String> {
+    let mut packages = BTreeSet::new();
+    for (_, environment) in lock_file.environments() {
+        for (_, locked_packages) in environment.packages_by_platform() {
+            for package in locked_packages {
+                let LockedPackage::Conda(CondaPackageData::Binary(binary)) = package else {
+                    continue;
+                };
+                let location = binary.location.to_string();
+                if channels
+                    .iter()
+                    .any(|channel| location.starts_with(channel.as_str()))
+                {
+                    packages.insert(binary.package_record.name.as_normalized().to_string());
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// Rebases the relative channel paths, which are given relative to the `cwd`
+/// of the options, onto the workspace root the manifest resolves them from.
+fn normalize_channels(workspace: &Workspace, options: &mut ChannelOptions) {
+    let cwd = options.cwd.as_deref().unwrap_or(workspace.root());
+    options.channels = std::mem::take(&mut options.channels)
+        .into_iter()
+        .map(|channel| normalize_local_channel(channel, cwd, workspace.root()))
+        .collect();
+}
+
 fn feature_name(feature: &Option<String>) -> FeatureName {
     feature
         .clone()
@@ -230,6 +304,41 @@ mod tests {
         ChannelConfig::default_with_root_dir(PathBuf::from("/"))
     }
 
+    #[test]
+    fn test_packages_from_channels() {
+        let lock_file = LockFile::from_str_with_base_directory(
+            r#"
+version: 6
+environments:
+  default:
+    channels:
+    - url: https://conda.anaconda.org/conda-forge/
+    - url: https://prefix.dev/other/
+    packages:
+      linux-64:
+      - conda: https://conda.anaconda.org/conda-forge/linux-64/foo-1.0-h0_0.conda
+      - conda: https://prefix.dev/other/noarch/bar-1.0-pyh0_0.conda
+packages:
+- conda: https://conda.anaconda.org/conda-forge/linux-64/foo-1.0-h0_0.conda
+  sha256: fe51de6107f9edc7aa4f786a70f4a883943bc9d39b3bb7307c04c41410990726
+  md5: d7c89558ba9fa0495403155b64376d81
+- conda: https://prefix.dev/other/noarch/bar-1.0-pyh0_0.conda
+  sha256: fbe2c5e56a653bebb982eda4876a9178aedfc2b545f25d0ce9c4c0b508253d22
+  md5: 73aaf86a425cc6e73fcf236a5a46396d
+"#,
+            None,
+        )
+        .unwrap();
+
+        let packages =
+            packages_from_channels(&lock_file, &["https://prefix.dev/other/".to_string()]);
+        assert_eq!(packages, BTreeSet::from(["bar".to_string()]));
+        assert!(
+            packages_from_channels(&lock_file, &["https://prefix.dev/unused/".to_string()])
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_format_named_channel_without_priority() {
         let channel = NamedChannelOrUrl::Name("conda-forge".into());
@@ -1,16 +1,28 @@
 use clap::Parser;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
 use pep508_rs::Requirement;
 use pixi_api::{
     WorkspaceContext,
     workspace::{DependencyOptions, GitOptions},
 };
 use pixi_config::ConfigCli;
-use pixi_core::{DependencyType, WorkspaceLocator, workspace::PypiDeps};
+use pixi_consts::consts;
+use pixi_core::{
+    DependencyType, WorkspaceLocator,
+    lock_file::{ConflictExplanation, SolveCondaEnvironmentError},
+    workspace::{PypiDeps, UpdateDeps},
+};
 use pixi_pypi_spec::{PixiPypiSource, PixiPypiSpec, PypiPackageName};
+use rattler_conda_types::{MatchSpec, PackageName, StringMatcher};
 use url::Url;
 
 use crate::{
-    cli_config::{DependencyConfig, LockFileUpdateConfig, NoInstallConfig, WorkspaceConfig},
+    cli_config::{
+        DependencyConfig, LockFileUpdateConfig, NoInstallConfig, SolveStrategyConfig,
+        WorkspaceConfig,
+    },
     cli_interface::CliInterface,
     has_specs::HasSpecs,
 };
@@ -63,9 +75,10 @@ use crate::{
 /// - `pixi add --pypi --editable 'boto3 @ file://absolute/path/to/boto3'` will add
 ///   the local editable `boto3` to the `pypi-dependencies` array
 ///
-/// Note that if `--platform` or `--editable` are specified, the pypi dependency
-/// will be added to the `tool.pixi.pypi-dependencies` table instead as native
-/// arrays have no support for platform-specific or editable dependencies.
+/// Note that if `--platform`, `--editable`, `--no-build` or `--no-binary` are
+/// specified, the pypi dependency will be added to the
+/// `tool.pixi.pypi-dependencies` table instead as native arrays have no support
+/// for platform-specific, editable or build options of dependencies.
 ///
 /// These dependencies will then be read by pixi as if they had been added to
 /// the pixi `pypi-dependencies` tables of the default or of a named feature.
@@ -75,6 +88,14 @@ use crate::{
 /// packages that are not following the semver versioning scheme but will use
 /// the minor version by default:
 /// Python, Rust, Julia, GCC, GXX, GFortran, NodeJS, Deno, R, R-Base, Perl
+///
+/// Strategies that don't set an upper bound, `latest-up` and `no-pin`, print
+/// a warning that suggests a bounded constraint instead.
+///
+/// The `--build-string` option only allows the builds whose build string
+/// matches a glob, e.g. to pick the CPU or GPU build of a package:
+///
+/// - `pixi add pytorch --build-string "*cpu*"`
 #[derive(Parser, Debug, Default)]
 #[clap(arg_required_else_help = true, verbatim_doc_comment)]
 pub struct Args {
@@ -90,6 +111,9 @@ pub struct Args {
     #[clap(flatten)]
     pub lock_file_update_config: LockFileUpdateConfig,
 
+    #[clap(flatten)]
+    pub solve_strategy_config: SolveStrategyConfig,
+
     #[clap(flatten)]
     pub config: ConfigCli,
 
@@ -104,6 +128,46 @@ pub struct Args {
     /// Only applicable when adding pypi dependencies.
     #[clap(long, requires = "pypi", conflicts_with = "git")]
     pub index: Option<Url>,
+
+    /// Only install the pypi dependency from a pre-built wheel, fail instead
+    /// of building it from source.
+    #[arg(long, visible_alias = "only-binary", requires = "pypi")]
+    pub no_build: bool,
+
+    /// Always build the pypi dependency from source instead of installing a
+    /// pre-built wheel.
+    #[arg(long, requires = "pypi", conflicts_with = "no_build")]
+    pub no_binary: bool,
+
+    /// Don't prefer the versions that are already locked, re-solve the
+    /// affected environments from scratch instead.
+    #[arg(long, help_heading = consts::CLAP_UPDATE_OPTIONS)]
+    pub no_preserve: bool,
+
+    /// Show the full output of the solver when the dependencies conflict,
+    /// instead of only the requested specs that cause the conflict.
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Don't warn when a dependency is added without an upper bound because
+    /// of the pinning strategy.
+    #[arg(long)]
+    pub no_pin_warning: bool,
+
+    /// Only allow the builds of the conda dependencies whose build string
+    /// matches the given glob, e.g. `"*cpu*"`.
+    #[arg(long, value_name = "BUILD_STRING", conflicts_with_all = ["pypi", "git"])]
+    pub build_string: Option<StringMatcher>,
+
+    /// Only show the changes to the lock file that adding the dependencies
+    /// would make, without modifying the manifest, lock file, or environment.
+    #[arg(
+        short = 'n',
+        long,
+        help_heading = consts::CLAP_UPDATE_OPTIONS,
+        conflicts_with_all = ["frozen", "locked", "no_lock_update"]
+    )]
+    pub dry_run: bool,
 }
 
 impl TryFrom<&Args> for DependencyOptions {
@@ -113,8 +177,13 @@ impl TryFrom<&Args> for DependencyOptions {
         Ok(DependencyOptions {
             feature: args.dependency_config.feature.clone(),
             platforms: args.dependency_config.platforms.clone(),
-            no_install: args.no_install_config.no_install,
-            lock_file_usage: args.lock_file_update_config.lock_file_usage()?,
+            no_install: args.dependency_config.no_install(&args.no_install_config),
+            lock_file_usage: args
+                .dependency_config
+                .lock_file_usage(&args.lock_file_update_config)?,
+            no_preserve: args.no_preserve,
+            no_sort: args.dependency_config.no_sort,
+            dry_run: args.dry_run,
         })
     }
 }
@@ -134,27 +203,34 @@ impl From<&Args> for GitOptions {
     }
 }
 
-fn map_pypi_requirements_with_index(
+fn map_pypi_requirements(
     requirements: impl Iterator<Item = (PypiPackageName, Requirement)>,
     index: Option<&Url>,
+    no_build: bool,
+    no_binary: bool,
 ) -> miette::Result<PypiDeps> {
     requirements
         .map(|(name, req)| {
-            let pixi_spec = if let Some(index_url) = index {
+            let pixi_spec = if index.is_some() || no_build || no_binary {
                 // Create spec from requirement
                 let mut spec = PixiPypiSpec::try_from(req.clone())
                     .map_err(|e| miette::miette!("failed to convert requirement: {}", e))?;
+                spec.no_build = no_build;
+                spec.no_binary = no_binary;
 
                 // Only apply index if this is a Registry source
-                match spec.source_mut() {
-                    PixiPypiSource::Registry { index, .. } => {
+                let index_applied = match (spec.source_mut(), index) {
+                    (PixiPypiSource::Registry { index, .. }, Some(index_url)) => {
                         *index = Some(index_url.clone());
-                        Some(spec) // Return spec only when index was actually applied
+                        true
                     }
-                    _ => None, // For Git, Path, etc. - index doesn't apply
-                }
+                    _ => false, // For Git, Path, etc. - index doesn't apply
+                };
+
+                // Return spec only when something was actually applied
+                (index_applied || no_build || no_binary).then_some(spec)
             } else {
-                None // No index provided
+                None // No index or build options provided
             };
 
             Ok((name, (req, pixi_spec, None)))
@@ -162,7 +238,131 @@ fn map_pypi_requirements_with_index(
         .collect()
 }
 
-pub async fn execute(args: Args) -> miette::Result<()> {
+/// Constrains the build string of the specs to the `--build-string` glob.
+fn with_build_string(
+    specs: IndexMap<PackageName, MatchSpec>,
+    build_string: Option<&StringMatcher>,
+) -> miette::Result<IndexMap<PackageName, MatchSpec>> {
+    let Some(build_string) = build_string else {
+        return Ok(specs);
+    };
+    specs
+        .into_iter()
+        .map(|(name, mut spec)| {
+            if spec.build.is_some() {
+                return Err(miette::miette!(
+                    "'{spec}' already specifies a build string, which can't be combined with `--build-string`"
+                ));
+            }
+            spec.build = Some(build_string.clone());
+            Ok((name, spec))
+        })
+        .collect()
+}
+
+/// An unsatisfiable solve with the concise explanation of the conflict instead
+/// of the full solver output.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error("{message}\n\n{explanation}")]
+#[diagnostic(help("run the command again with `--explain` to see the full output of the solver"))]
+struct ConciseSolveError {
+    message: String,
+    explanation: ConflictExplanation,
+    #[source]
+    source: Option<ErrorMessage>,
+}
+
+/// The message of an error in the chain of sources of a [`ConciseSolveError`].
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+struct ErrorMessage {
+    message: String,
+    #[source]
+    source: Option<Box<ErrorMessage>>,
+}
+
+/// Replaces the full solver output of an unsatisfiable solve with the concise
+/// explanation of the conflict. The solve error can be wrapped in the errors
+/// of the lock file update, so the whole chain of errors is searched. The
+/// chain is kept up to the failed solve, its sources are the solver output.
+fn concise_solve_error(err: miette::Report) -> miette::Report {
+    let mut messages = Vec::new();
+    let mut explanation = None;
+    for source in err.chain() {
+        messages.push(source.to_string());
+        if let Some(SolveCondaEnvironmentError::SolveFailed {
+            explanation: solve_explanation,
+            ..
+        }) = source.downcast_ref()
+        {
+            explanation = solve_explanation.clone();
+            break;
+        }
+    }
+    let Some(explanation) = explanation else {
+        return err;
+    };
+
+    let source = messages.drain(1..).rev().fold(None, |source, message| {
+        Some(ErrorMessage {
+            message,
+            source: source.map(Box::new),
+        })
+    });
+    ConciseSolveError {
+        message: messages.remove(0),
+        explanation,
+        source,
+    }
+    .into()
+}
+
+/// Warns about the dependencies that were added without an upper bound, and
+/// suggests a bounded constraint instead.
+fn warn_unbounded_constraints(args: &Args, update_deps: &UpdateDeps) {
+    let pypi_flag = if args.dependency_config.pypi {
+        " --pypi"
+    } else {
+        ""
+    };
+    for (name, bounded) in update_deps.unbounded_constraints.iter().sorted() {
+        let constraint = update_deps
+            .implicit_constraints
+            .get(name)
+            .map_or("*", String::as_str);
+        tracing::warn!(
+            "'{name}' was added as '{constraint}' which has no upper bound, so any future version may be installed. Consider adding a bound with `pixi add{pypi_flag} \"{name}{bounded}\"`, or pass `--no-pin-warning` to silence this warning."
+        );
+    }
+}
+
+/// Prints the changes to the lock file that adding the dependencies would make.
+fn print_dry_run(update_deps: Option<UpdateDeps>) -> miette::Result<()> {
+    match update_deps {
+        Some(update_deps) if !update_deps.lock_file_diff.is_empty() => {
+            eprintln!(
+                "{}Dry-run: the lock file would be updated (nothing was written to disk)",
+                console::style(console::Emoji("i ", "i ")).blue()
+            );
+            update_deps
+                .lock_file_diff
+                .print()
+                .into_diagnostic()
+                .context("failed to print lock file diff")
+        }
+        _ => {
+            eprintln!(
+                "{}Dry-run: the lock file would not change",
+                console::style(console::Emoji("i ", "i ")).blue()
+            );
+            Ok(())
+        }
+    }
+}
+
+pub async fn execute(mut args: Args) -> miette::Result<()> {
+    args.dependency_config
+        .take_deprecated_no_lock_file_update(&mut args.lock_file_update_config);
     let mut workspace = WorkspaceLocator::for_cli()
         .with_global_config_source(args.config_source.source())
         .with_search_start(args.workspace_config.workspace_locator_start())
@@ -173,31 +373,26 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     if let Some(backend_override) = args.workspace_config.backend_override.clone() {
         workspace = workspace.with_backend_override(backend_override);
     }
+    workspace = args.solve_strategy_config.apply(workspace);
 
     let workspace_ctx = WorkspaceContext::new(CliInterface {}, workspace.clone());
 
-    let update_deps = match args.dependency_config.dependency_type() {
-        DependencyType::CondaDependency(spec_type) => {
-            let git_options = GitOptions {
-                git: args.dependency_config.git.clone(),
-                reference: args
-                    .dependency_config
-                    .rev
-                    .clone()
-                    .unwrap_or_default()
-                    .into(),
-                subdir: args.dependency_config.subdir.clone(),
-            };
-
-            workspace_ctx
-                .add_conda_deps(
-                    args.dependency_config.specs()?,
-                    spec_type,
-                    (&args).try_into()?,
-                    git_options,
-                )
-                .await?
-        }
+    let update_deps = match args.dependency_config.dependency_type()? {
+        DependencyType::CondaDependency(spec_type) => workspace_ctx
+            .add_conda_deps(
+                with_build_string(args.dependency_config.specs()?, args.build_string.as_ref())?,
+                spec_type,
+                (&args).try_into()?,
+                GitOptions::from(&args),
+            )
+            .await
+            .map_err(|err| {
+                if args.explain {
+                    err
+                } else {
+                    concise_solve_error(err)
+                }
+            })?,
         DependencyType::PypiDependency => {
             let requirements_iter = match args
                 .dependency_config
@@ -208,8 +403,12 @@ pub async fn execute(args: Args) -> miette::Result<()> {
                 None => args.dependency_config.pypi_deps(&workspace)?.into_iter(),
             };
 
-            let pypi_deps =
-                map_pypi_requirements_with_index(requirements_iter, args.index.as_ref())?;
+            let pypi_deps = map_pypi_requirements(
+                requirements_iter,
+                args.index.as_ref(),
+                args.no_build,
+                args.no_binary,
+            )?;
 
             workspace_ctx
                 .add_pypi_deps(pypi_deps, args.editable, (&args).try_into()?)
@@ -217,11 +416,23 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         }
     };
 
+    if args.dry_run {
+        return print_dry_run(update_deps);
+    }
+
     if let Some(update_deps) = update_deps {
+        if !args.no_pin_warning {
+            warn_unbounded_constraints(&args, &update_deps);
+        }
+
         // Notify the user we succeeded
         args.dependency_config
             .display_success("Added", update_deps.implicit_constraints);
+    } else if args.dependency_config.no_lock_update {
+        args.dependency_config
+            .display_success("Added", Default::default());
     }
+    args.dependency_config.print_stale_lock_file_reminder();
 
     Ok(())
 }
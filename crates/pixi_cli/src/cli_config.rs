@@ -1,5 +1,6 @@
 use crate::has_specs::HasSpecs;
 use clap::Parser;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use indexmap::IndexMap;
 use indexmap::IndexSet;
 use itertools::Itertools;
@@ -12,8 +13,9 @@ use pixi_core::DependencyType;
 use pixi_core::Workspace;
 use pixi_core::environment::LockFileUsage;
 use pixi_core::workspace::DiscoveryStart;
+use pixi_core::workspace::errors::ConflictingDependencyTypesError;
 use pixi_manifest::FeaturesExt;
-use pixi_manifest::{FeatureName, PixiPlatformName, SpecType};
+use pixi_manifest::{FeatureName, PixiPlatformName, SolveStrategy, SpecType};
 use pixi_spec::GitReference;
 use rattler_conda_types::ChannelConfig;
 use rattler_conda_types::{Channel, NamedChannelOrUrl};
@@ -23,6 +25,7 @@ use url::Url;
 
 use pixi_git::GIT_URL_QUERY_REV_TYPE;
 use pixi_pypi_spec::PypiPackageName;
+use strum::VariantNames;
 
 /// Workspace configuration
 #[derive(Parser, Debug, Default, Clone)]
@@ -156,6 +159,36 @@ impl NoInstallConfig {
     }
 }
 
+/// Configuration for overriding the solve strategy of the manifest
+#[derive(Parser, Debug, Default, Clone)]
+pub struct SolveStrategyConfig {
+    /// Solve the conda and PyPI packages with this strategy instead of the
+    /// `solve-strategy` of the manifest, one of `highest`, `lowest` or
+    /// `lowest-direct`. `lowest-direct` selects the lowest versions of the
+    /// direct dependencies, which surfaces bounds that are too loose. The lock
+    /// file records the strategy, so a later command without this option
+    /// re-solves with the `solve-strategy` of the manifest, use `--frozen` to
+    /// keep the locked versions.
+    #[arg(
+        long,
+        value_name = "STRATEGY",
+        value_parser = PossibleValuesParser::new(SolveStrategy::VARIANTS)
+            .map(|strategy| strategy.parse::<SolveStrategy>().expect("a valid strategy")),
+        help_heading = consts::CLAP_UPDATE_OPTIONS
+    )]
+    pub solve_strategy: Option<SolveStrategy>,
+}
+
+impl SolveStrategyConfig {
+    /// Applies the strategy to the workspace, if one was given.
+    pub fn apply(&self, workspace: Workspace) -> Workspace {
+        match self.solve_strategy {
+            Some(strategy) => workspace.with_solve_strategy_override(strategy),
+            None => workspace,
+        }
+    }
+}
+
 /// Lock file and installation configuration with --as-is support
 /// Used by shell, shell-hook, and run commands
 #[derive(Parser, Debug, Default, Clone)]
@@ -315,18 +348,70 @@ pub struct DependencyConfig {
     /// The subdirectory of the git repository to use
     #[clap(long, short, requires = "git", help_heading = consts::CLAP_GIT_OPTIONS)]
     pub subdir: Option<String>,
+
+    /// Don't sort the edited dependency table of the manifest alphabetically
+    #[arg(long)]
+    pub no_sort: bool,
+
+    /// Only modify the manifest, don't update the lock file or the
+    /// environment. The same as `--frozen --no-install`, useful to make several
+    /// edits before a single `pixi install`.
+    #[arg(
+        long,
+        help_heading = consts::CLAP_UPDATE_OPTIONS,
+        conflicts_with_all = ["frozen", "locked"]
+    )]
+    pub no_lock_update: bool,
 }
 
 impl DependencyConfig {
-    pub(crate) fn dependency_type(&self) -> DependencyType {
-        if self.pypi {
-            DependencyType::PypiDependency
-        } else if self.host {
-            DependencyType::CondaDependency(SpecType::Host)
-        } else if self.build {
-            DependencyType::CondaDependency(SpecType::Build)
+    pub(crate) fn dependency_type(
+        &self,
+    ) -> Result<DependencyType, ConflictingDependencyTypesError> {
+        DependencyType::from_args(self.host, self.build, self.pypi)
+    }
+
+    /// Treats the deprecated `--no-lockfile-update` as `--no-lock-update`,
+    /// with a deprecation warning, instead of refusing it.
+    pub(crate) fn take_deprecated_no_lock_file_update(
+        &mut self,
+        lock_file_update_config: &mut LockFileUpdateConfig,
+    ) {
+        if std::mem::take(&mut lock_file_update_config.no_lock_file_update) {
+            tracing::warn!(
+                "The '--no-lockfile-update' flag is deprecated and will be removed in a future version, use '--no-lock-update' instead."
+            );
+            self.no_lock_update = true;
+        }
+    }
+
+    /// Returns how the lock file is used, `--no-lock-update` leaves the lock
+    /// file as is like `--frozen`.
+    pub(crate) fn lock_file_usage(
+        &self,
+        lock_file_update_config: &LockFileUpdateConfig,
+    ) -> miette::Result<LockFileUsage> {
+        if self.no_lock_update {
+            Ok(LockFileUsage::Frozen)
         } else {
-            DependencyType::CondaDependency(SpecType::Run)
+            lock_file_update_config.lock_file_usage()
+        }
+    }
+
+    /// Returns whether the environment is left alone, which `--no-lock-update`
+    /// implies.
+    pub(crate) fn no_install(&self, no_install_config: &NoInstallConfig) -> bool {
+        self.no_lock_update || no_install_config.no_install
+    }
+
+    /// Reminds the user that the lock file no longer matches the manifest
+    /// after `--no-lock-update`.
+    pub(crate) fn print_stale_lock_file_reminder(&self) {
+        if self.no_lock_update {
+            eprintln!(
+                "{}The lock file was not updated and is now out of date, run `pixi install` to update it",
+                console::style(console::Emoji("⚠ ", "! ")).yellow()
+            );
         }
     }
 
@@ -336,7 +421,7 @@ impl DependencyConfig {
         implicit_constraints: HashMap<String, String>,
     ) {
         for package in self.specs.clone() {
-            eprintln!(
+            pixi_progress::println_status!(
                 "{}{operation} {}{}",
                 console::style(console::Emoji("✔ ", "")).green(),
                 console::style(&package).bold(),
@@ -349,12 +434,10 @@ impl DependencyConfig {
         }
 
         // Print if it is something different from host and dep
-        let dependency_type = self.dependency_type();
-        if !matches!(
-            dependency_type,
-            DependencyType::CondaDependency(SpecType::Run)
-        ) {
-            eprintln!(
+        if let Ok(dependency_type) = self.dependency_type()
+            && dependency_type != DependencyType::CondaDependency(SpecType::Run)
+        {
+            pixi_progress::println_status!(
                 "{operation} these as {}.",
                 console::style(dependency_type.name()).bold()
             );
@@ -362,7 +445,7 @@ impl DependencyConfig {
 
         // Print something if we've modified for platforms
         if !self.platforms.is_empty() {
-            eprintln!(
+            pixi_progress::println_status!(
                 "{operation} these only for platform(s): {}",
                 console::style(self.platforms.iter().join(", ")).bold()
             )
@@ -370,7 +453,7 @@ impl DependencyConfig {
         // Print something if we've modified for features
         if let Some(feature) = self.feature.non_default() {
             {
-                eprintln!(
+                pixi_progress::println_status!(
                     "{operation} these only for feature: {}",
                     consts::FEATURE_STYLE.apply_to(feature)
                 )
@@ -3,16 +3,16 @@ use std::str::FromStr;
 
 use clap::{Parser, ValueEnum};
 use indexmap::IndexSet;
-use pixi_api::workspace::platforms::resolve_platforms;
+use pixi_api::workspace::{
+    platforms::resolve_platforms, requirements_txt::convert_requirements_txt_to_pep508,
+};
 use pixi_config::ConfigCli;
 use pixi_core::{WorkspaceLocator, environment::sanity_check_workspace};
 use pixi_manifest::{
     EnvironmentName, FeatureName, HasFeaturesIter, PixiPlatformName, PrioritizedChannel,
 };
 use pixi_utils::conda_environment_file::CondaEnvFile;
-use pixi_uv_conversions::convert_uv_requirements_to_pep508;
 
-use tracing::warn;
 use uv_requirements_txt::RequirementsTxt;
 
 use miette::{Diagnostic, IntoDiagnostic, Result};
@@ -109,31 +109,6 @@ fn get_feature_and_environment(
     ))
 }
 
-fn convert_uv_requirements_txt_to_pep508(
-    reqs_txt: uv_requirements_txt::RequirementsTxt,
-) -> Result<Vec<pep508_rs::Requirement>, miette::Error> {
-    let uv_requirements: Vec<uv_pep508::Requirement<uv_pypi_types::VerbatimParsedUrl>> = reqs_txt
-        .requirements
-        .into_iter()
-        .map(|r| match r.requirement {
-            uv_requirements_txt::RequirementsTxtRequirement::Named(req) => Ok(req),
-            uv_requirements_txt::RequirementsTxtRequirement::Unnamed(_) => Err(miette::miette!(
-                "Error parsing input file: unnamed requirements are currently unsupported."
-            )),
-        })
-        .collect::<Result<_, _>>()?;
-    if !reqs_txt.constraints.is_empty() {
-        warn!(
-            "Constraints detected in input file, but these are currently unsupported. Continuing without applying constraints..."
-        )
-    }
-
-    let requirements =
-        convert_uv_requirements_to_pep508(uv_requirements.iter()).into_diagnostic()?;
-
-    Ok(requirements)
-}
-
 async fn import(args: Args, format: &ImportFileFormat) -> miette::Result<()> {
     let source = args.config_source.source();
     let (input_file, platforms, workspace_config) =
@@ -217,7 +192,7 @@ async fn import(args: Args, format: &ImportFileFormat) -> miette::Result<()> {
             let reqs_txt = RequirementsTxt::parse(&input_file, workspace.workspace().root())
                 .await
                 .into_diagnostic()?;
-            let pypi_deps = convert_uv_requirements_txt_to_pep508(reqs_txt)?;
+            let pypi_deps = convert_requirements_txt_to_pep508(reqs_txt)?;
 
             (vec![], pypi_deps)
         }
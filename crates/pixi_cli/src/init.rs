@@ -14,7 +14,8 @@ use crate::cli_interface::CliInterface;
 /// As pixi can both work with `pixi.toml` and `pyproject.toml` files, the user
 /// can choose which one to use with `--format`.
 ///
-/// You can import an existing conda environment file with the `--import` flag.
+/// You can import an existing conda environment file or a pip requirements
+/// file with the `--import` flag.
 #[derive(Parser, Debug)]
 pub struct Args {
     /// Where to place the workspace (defaults to current path)
@@ -34,7 +35,7 @@ pub struct Args {
     #[arg(short, long = "platform", id = "PLATFORM")]
     pub platforms: Vec<String>,
 
-    /// Environment.yml file to bootstrap the workspace.
+    /// Environment.yml or requirements.txt file to bootstrap the workspace.
     #[arg(short = 'i', long = "import", id = "ENVIRONMENT_FILE")]
     pub env_file: Option<PathBuf>,
 
@@ -54,6 +55,22 @@ pub struct Args {
     /// Set a mapping between conda channels and pypi channels.
     #[arg(long = "conda-pypi-map", value_parser = parse_conda_pypi_mapping, value_delimiter = ',')]
     pub conda_pypi_map: Option<Vec<(NamedChannelOrUrl, String)>>,
+
+    /// Replace the `[tool.pixi]` section of an existing pyproject.toml
+    #[arg(long)]
+    pub force: bool,
+
+    /// Pre-fill the manifest with the channels, platforms, dependencies and
+    /// tasks of a template.
+    ///
+    /// Templates are read from `<name>.toml` files in the `templates`
+    /// directory of the pixi config directory or of `PIXI_HOME`.
+    #[arg(long, value_name = "NAME", conflicts_with = "ENVIRONMENT_FILE")]
+    pub template: Option<String>,
+
+    /// List the available templates
+    #[arg(long)]
+    pub list_templates: bool,
 }
 
 fn parse_conda_pypi_mapping(s: &str) -> Result<(NamedChannelOrUrl, String), String> {
@@ -98,16 +115,29 @@ impl From<Args> for InitOptions {
         InitOptions {
             path: args.path,
             channels: args.channels,
+            cwd: std::env::current_dir().ok(),
             platforms: args.platforms,
             env_file: args.env_file,
             format,
             scm,
             conda_pypi_mapping: args.conda_pypi_map.map(|map| map.into_iter().collect()),
+            force: args.force,
+            template: args.template,
         }
     }
 }
 
 pub async fn execute(args: Args) -> miette::Result<()> {
+    if args.list_templates {
+        for template in pixi_api::workspace::init_templates() {
+            match template.path {
+                Some(path) => println!("{} ({})", template.name, path.display()),
+                None => println!("{} (built-in)", template.name),
+            }
+        }
+        return Ok(());
+    }
+
     let uses_deprecated_pyproject_flag = args.pyproject_toml;
     let mut options: InitOptions = args.into();
 
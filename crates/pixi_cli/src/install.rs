@@ -1,18 +1,23 @@
 use clap::Parser;
 use fancy_display::FancyDisplay;
+use futures::{StreamExt, TryStreamExt, stream};
 use itertools::Itertools;
+use miette::IntoDiagnostic;
 use pixi_config::ConfigCli;
 use pixi_core::{
-    UpdateLockFileOptions, WorkspaceLocator,
-    environment::{InstallFilter, get_update_lock_file_and_prefixes},
-    lock_file::{LockFileDerivedData, PackageFilterNames, ReinstallPackages, UpdateMode},
-    workspace::{HasWorkspaceRef, PlatformOverrides, PlatformSource},
+    UpdateLockFileOptions, Workspace, WorkspaceLocator,
+    environment::{InstallFilter, LockFileUsage, get_update_lock_file_and_prefixes},
+    lock_file::{
+        LockFileDerivedData, PackageFilterNames, ReinstallPackages, UpdateMode, lock_file_hash,
+    },
+    workspace::{Environment, HasWorkspaceRef, PlatformOverrides, PlatformSource},
 };
 use pixi_manifest::PixiPlatformName;
-use std::fmt::Write;
+use std::{fmt::Write, path::PathBuf, time::Instant};
 
-use crate::cli_config::WorkspaceConfig;
-use crate::shared::install_platform::resolve_install_platform;
+use crate::cli_config::{SolveStrategyConfig, WorkspaceConfig};
+use crate::shared::install_platform::{resolve_install_platform, runs_on_host};
+use crate::verify_lock::verify_installed_lock_file_hash;
 
 /// Install an environment, both updating the lock file and installing the
 /// environment.
@@ -44,6 +49,9 @@ pub struct Args {
     #[clap(flatten)]
     pub lock_file_usage: crate::LockFileUsageConfig,
 
+    #[clap(flatten)]
+    pub solve_strategy_config: SolveStrategyConfig,
+
     /// The environment to install
     #[arg(long, short)]
     pub environment: Option<Vec<String>>,
@@ -55,8 +63,8 @@ pub struct Args {
     #[arg(long, short, conflicts_with = "environment")]
     pub all: bool,
 
-    /// Install for the given platform; a warning is printed when it
-    /// doesn't run on this machine.
+    /// Install for the given platform. When it doesn't run on this machine,
+    /// the packages are only downloaded, as with `--download-only`.
     #[arg(long, short)]
     pub platform: Option<PixiPlatformName>,
 
@@ -76,11 +84,46 @@ pub struct Args {
     /// passed multiple times.
     #[arg(long)]
     pub only: Option<Vec<String>>,
+
+    /// Only update the lock file and download the conda packages into the
+    /// package cache, without installing the environment. Implied by a
+    /// `--platform` that doesn't run on this machine. `--skip`,
+    /// `--skip-with-deps` and `--only` limit the packages that are downloaded.
+    #[arg(long)]
+    pub download_only: bool,
+
+    /// Fail unless the lock file has the given sha256 hash, and the installed
+    /// environments were installed from it. Complements `--frozen`.
+    #[arg(long, value_name = "HASH", conflicts_with = "download_only")]
+    pub verify_lock_hash: Option<String>,
+
+    /// Install the environment in the given prefix instead of the `.pixi`
+    /// directory. A relative path is resolved against the workspace root. Use
+    /// the same `--prefix` with `pixi run` to run tasks in it.
+    #[arg(long, conflicts_with_all = ["all", "download_only"])]
+    pub prefix: Option<PathBuf>,
+
+    /// Print a json object with the number of conda packages that were added,
+    /// removed and updated, the bytes downloaded, the packages taken from the
+    /// package cache and the duration of the install to stdout when done.
+    #[arg(long, conflicts_with = "download_only")]
+    pub json_summary: bool,
+
+    /// Allow PyPI packages to overwrite files installed by conda packages.
+    /// Takes precedence over the `allow-clobber` pypi-option and config. The
+    /// overwritten files are listed in the `--json-summary`.
+    #[arg(long)]
+    pub allow_clobber: bool,
 }
 
 const SKIP_CUTOFF: usize = 5;
 
 pub async fn execute(args: Args) -> miette::Result<()> {
+    let start = Instant::now();
+    if args.json_summary {
+        pixi_progress::install_summary::enable();
+    }
+
     let mut workspace = WorkspaceLocator::for_cli()
         .with_global_config_source(args.config_source.source())
         .with_search_start(args.workspace_config.workspace_locator_start())
@@ -91,6 +134,10 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     if let Some(backend_override) = args.workspace_config.backend_override.clone() {
         workspace = workspace.with_backend_override(backend_override);
     }
+    workspace = args.solve_strategy_config.apply(workspace);
+    if args.allow_clobber {
+        workspace = workspace.with_allow_clobber();
+    }
 
     let target_platform = resolve_install_platform(&workspace, args.platform.as_ref())?;
 
@@ -111,6 +158,17 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         vec![workspace.default_environment().name().to_string()]
     };
 
+    if let Some(prefix) = &args.prefix {
+        let [name] = envs.as_slice() else {
+            miette::bail!("`--prefix` can only be used when installing a single environment");
+        };
+        let name = workspace
+            .environment_from_name_or_env_var(Some(name.clone()))?
+            .name()
+            .clone();
+        workspace = workspace.with_prefix_override(name, prefix);
+    }
+
     // Get the environments by name
     let mut environments = envs
         .into_iter()
@@ -138,14 +196,59 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         environments = supported;
     }
 
+    // The packages of a platform that this machine can't run can't be linked
+    // either, so they are only downloaded.
+    let download_only_platform = target_platform
+        .as_ref()
+        .filter(|name| !runs_on_host(&workspace, name));
+    if let Some(name) = download_only_platform {
+        tracing::info!(
+            "platform '{name}' doesn't run on this machine, only downloading its packages"
+        );
+    }
+
     // Build the install filter from CLI args
     let filter = InstallFilter::new()
         .skip_direct(args.skip.clone().unwrap_or_default())
         .skip_with_deps(args.skip_with_deps.clone().unwrap_or_default())
         .target_packages(args.only.clone().unwrap_or_default());
 
+    if args.download_only || download_only_platform.is_some() {
+        return download_only(
+            &workspace,
+            &environments,
+            target_platform.as_ref(),
+            args.lock_file_usage.to_usage(),
+            &filter,
+        )
+        .await;
+    }
+
+    // Refuse to install from a lock file other than the expected one.
+    if let Some(expected_hash) = &args.verify_lock_hash {
+        let hash = lock_file_hash(&workspace.lock_file_path())?;
+        if !hash
+            .as_ref()
+            .is_some_and(|hash| hash.eq_ignore_ascii_case(expected_hash))
+        {
+            miette::bail!(
+                help = "the lock file changed since the hash was recorded, restore it or pass the new hash",
+                "the lock file has hash {}, expected {expected_hash}",
+                hash.as_deref().unwrap_or("<missing>"),
+            );
+        }
+    }
+
     // Update the prefixes by installing all packages
-    let (LockFileDerivedData { lock_file, .. }, _) = get_update_lock_file_and_prefixes(
+    let (
+        LockFileDerivedData {
+            lock_file,
+            workspace_unchanged,
+            updated_pypi_prefixes,
+            ..
+        },
+        _,
+    ) = get_update_lock_file_and_prefixes(
         &environments,
         target_platform.as_ref(),
         Some(pixi_reporters::TopLevelProgress::from_global()),
@@ -161,6 +264,13 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     )
     .await?;
 
+    // The lock file may have been updated during the install.
+    if let Some(expected_hash) = &args.verify_lock_hash {
+        for environment in &environments {
+            verify_installed_lock_file_hash(environment, expected_hash)?;
+        }
+    }
+
     // Message what's installed
     let mut message = console::style(console::Emoji("✔ ", "")).green().to_string();
 
@@ -168,11 +278,19 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         || args.skip_with_deps.is_some()
         || args.only.as_ref().is_some_and(|v| !v.is_empty());
 
+    // Neither the lock file nor any of the prefixes had to be verified.
+    let up_to_date = workspace_unchanged && updated_pypi_prefixes.is_empty();
+
     if let Ok(Some(environment)) = environments.iter().at_most_one() {
         write!(
             &mut message,
-            "The {} environment has been installed",
+            "The {} environment {}",
             environment.name().fancy_display(),
+            if up_to_date {
+                "is already up to date"
+            } else {
+                "has been installed"
+            }
         )
         .expect("failed to write into message buffer");
 
@@ -249,12 +367,25 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             .format_with(", ", |e, f| f(&e.name().fancy_display()));
         write!(
             &mut message,
-            "The following environments have been installed: {env}",
+            "The following environments {}: {env}",
+            if up_to_date {
+                "are already up to date"
+            } else {
+                "have been installed"
+            }
         )
         .expect("failed to write into message buffer");
     }
 
-    if let Ok(Some(path)) = workspace.config().detached_environments_dir() {
+    let location = match &args.prefix {
+        Some(_) => environments.first().map(|environment| environment.dir()),
+        None => workspace
+            .config()
+            .detached_environments_dir()
+            .ok()
+            .flatten(),
+    };
+    if let Some(path) = location {
         write!(
             &mut message,
             " in '{}'",
@@ -263,7 +394,101 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         .expect("failed to write into message buffer");
     }
 
-    eprintln!("{message}.");
+    pixi_progress::println_status!("{message}.");
+
+    if args.json_summary {
+        println!(
+            "{}",
+            pixi_progress::install_summary::recorded().to_json(start.elapsed())
+        );
+    }
+
+    Ok(())
+}
+
+/// Updates the lock file and downloads the conda packages of the environments
+/// into the package cache, without installing them. Packages that `filter`
+/// excludes are not downloaded.
+async fn download_only(
+    workspace: &Workspace,
+    environments: &[Environment<'_>],
+    target_platform: Option<&PixiPlatformName>,
+    lock_file_usage: LockFileUsage,
+    filter: &InstallFilter,
+) -> miette::Result<()> {
+    let derived = workspace
+        .update_lock_file(
+            Some(pixi_reporters::TopLevelProgress::from_global()),
+            UpdateLockFileOptions {
+                lock_file_usage,
+                no_install: true,
+                max_concurrent_solves: workspace.config().max_concurrent_solves(),
+                ..Default::default()
+            },
+        )
+        .await?
+        .0;
+    let client = derived.command_dispatcher.download_client().clone();
+
+    for environment in environments {
+        let platform = environment
+            .named_or_best_declared_platform(target_platform)
+            .ok_or_else(|| {
+                miette::miette!(
+                    "environment '{}' does not support the current platform",
+                    environment.name()
+                )
+            })?;
+        let records = match (
+            derived.lock_file.environment(environment.name().as_str()),
+            derived.lock_file.platform(platform.name().as_str()),
+        ) {
+            (Some(locked_env), Some(lock_platform)) => {
+                let records = locked_env
+                    .conda_repodata_records(lock_platform)
+                    .into_diagnostic()?
+                    .unwrap_or_default();
+                if filter.filter_active() {
+                    let names =
+                        PackageFilterNames::new(filter, locked_env, platform).unwrap_or_default();
+                    records
+                        .into_iter()
+                        .filter(|record| {
+                            names
+                                .retained
+                                .iter()
+                                .any(|name| name == record.package_record.name.as_normalized())
+                        })
+                        .collect()
+                } else {
+                    records
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        stream::iter(records.iter())
+            .map(|record| {
+                derived.package_cache.get_or_fetch_from_url(
+                    &record.package_record,
+                    record.url.clone(),
+                    client.clone(),
+                    None,
+                )
+            })
+            .buffer_unordered(workspace.config().max_concurrent_downloads())
+            .try_collect::<Vec<_>>()
+            .await
+            .into_diagnostic()?;
+
+        pixi_progress::println_status!(
+            "{}Downloaded {} packages of the {} environment for '{}', the environment was not installed.",
+            console::style(console::Emoji("✔ ", "")).green(),
+            records.len(),
+            environment.name().fancy_display(),
+            platform.name(),
+        );
+    }
 
     Ok(())
 }
@@ -16,7 +16,7 @@ use pixi_consts::consts;
 use pixi_core::environment::LockFileUsage;
 use pixi_progress::global_multi_progress;
 
-use std::{env, io::IsTerminal};
+use std::{env, io::IsTerminal, path::PathBuf};
 use tracing::level_filters::LevelFilter;
 
 pub mod add;
@@ -38,6 +38,7 @@ pub mod list;
 pub mod lock;
 pub(crate) mod match_spec_or_path;
 mod process_exit;
+pub mod prune;
 pub mod publish;
 pub mod reinstall;
 pub mod remove;
@@ -52,6 +53,8 @@ pub mod tree;
 pub mod update;
 pub mod upgrade;
 pub mod upload;
+pub mod verify;
+pub mod verify_lock;
 pub mod workspace;
 
 #[derive(Parser, Debug)]
@@ -111,7 +114,7 @@ pub struct GlobalOptions {
     #[clap(short, long, action = clap::ArgAction::Count, global = true, help_heading = consts::CLAP_GLOBAL_OPTIONS)]
     verbose: u8,
 
-    /// Decrease logging verbosity (quiet mode)
+    /// Decrease logging verbosity (quiet mode), this also hides the progress bars and status messages
     #[clap(short, long, action = clap::ArgAction::Count, global = true, help_heading = consts::CLAP_GLOBAL_OPTIONS)]
     quiet: u8,
 
@@ -122,6 +125,26 @@ pub struct GlobalOptions {
     /// Hide all progress bars, always turned on if stderr is not a terminal.
     #[clap(long, default_value = "false", global = true, env = "PIXI_NO_PROGRESS", help_heading = consts::CLAP_GLOBAL_OPTIONS)]
     no_progress: bool,
+
+    /// How to report progress, `json` writes a json object per finished step to stderr instead of showing progress bars.
+    #[clap(long, default_value = "bars", global = true, env = "PIXI_PROGRESS", help_heading = consts::CLAP_GLOBAL_OPTIONS)]
+    progress: ProgressOutput,
+
+    /// Store all caches in this directory for this invocation, it is created if it doesn't exist. Takes precedence over `PIXI_CACHE_DIR` and the `[cache]` configuration.
+    #[clap(long, global = true, value_name = "DIR", help_heading = consts::CLAP_GLOBAL_OPTIONS)]
+    cache_dir: Option<PathBuf>,
+
+    /// Print how long solving, downloading and linking took when the command finishes, unless running in quiet mode.
+    #[clap(long, global = true, help_heading = consts::CLAP_GLOBAL_OPTIONS)]
+    timing: bool,
+}
+
+impl GlobalOptions {
+    /// Whether to print the summary of the timings, quiet mode takes
+    /// precedence over `--timing`.
+    fn print_timing(&self) -> bool {
+        self.timing && self.quiet == 0
+    }
 }
 
 impl Args {
@@ -130,10 +153,21 @@ impl Args {
         if !std::io::stderr().is_terminal() {
             true
         } else {
-            self.global_options.no_progress
+            self.progress_disabled_by_flags()
         }
     }
 
+    /// Whether the user asked to hide the progress bars, either explicitly,
+    /// by running in quiet mode or by requesting json progress.
+    fn progress_disabled_by_flags(&self) -> bool {
+        self.global_options.no_progress || self.global_options.quiet > 0 || self.json_progress()
+    }
+
+    /// Whether progress should be reported as json lines.
+    fn json_progress(&self) -> bool {
+        matches!(self.global_options.progress, ProgressOutput::Json)
+    }
+
     /// Determine the log level filter based on verbose and quiet counts.
     #[allow(unused)]
     fn log_level_filter(&self) -> LevelFilter {
@@ -174,6 +208,7 @@ pub enum Command {
     #[clap(visible_alias = "ls")]
     List(list::Args),
     Lock(lock::Args),
+    Prune(prune::Args),
     Reinstall(reinstall::Args),
     Publish(publish::Args),
     #[clap(visible_alias = "rm")]
@@ -193,6 +228,8 @@ pub enum Command {
     Update(update::Args),
     Upgrade(upgrade::Args),
     Upload(upload::Args),
+    Verify(verify::Args),
+    VerifyLock(verify_lock::Args),
     #[clap(alias = "project")]
     Workspace(workspace::Args),
     #[command(external_subcommand)]
@@ -260,6 +297,18 @@ pub async fn execute() -> miette::Result<()> {
     if no_progress {
         global_multi_progress().set_draw_target(ProgressDrawTarget::hidden());
     }
+    if args.json_progress() {
+        pixi_progress::json::enable();
+    }
+    if args.global_options.quiet > 0 {
+        pixi_progress::quiet::enable();
+    }
+    if args.global_options.print_timing() {
+        pixi_progress::timing::enable();
+    }
+    if let Some(cache_dir) = &args.global_options.cache_dir {
+        pixi_config::set_cache_dir_override(cache_dir)?;
+    }
 
     // Handle `--list`: print installed commands and exit 0
     if args.list {
@@ -276,7 +325,14 @@ pub async fn execute() -> miette::Result<()> {
     };
 
     // Execute the command
-    execute_command(command, &global_options).await
+    let result = execute_command(command, &global_options).await;
+    if global_options.print_timing() {
+        eprint!(
+            "{}",
+            pixi_progress::timing::summary(&pixi_progress::timing::recorded())
+        );
+    }
+    result
 }
 
 #[cfg(feature = "console-subscriber")]
@@ -371,9 +427,12 @@ pub async fn execute_command(
         Command::Task(cmd) => task::execute(cmd).await,
         Command::Info(cmd) => info::execute(cmd).await,
         Command::Import(cmd) => import::execute(cmd).await,
+        Command::Prune(cmd) => prune::execute(cmd).await,
         Command::Publish(cmd) => publish::execute(cmd).await,
         Command::Upload(cmd) => upload::execute(cmd).await,
         Command::Search(cmd) => search::execute(cmd).await,
+        Command::Verify(cmd) => verify::execute(cmd).await,
+        Command::VerifyLock(cmd) => verify_lock::execute(cmd).await,
         Command::Workspace(cmd) => workspace::execute(cmd).await,
         Command::Remove(cmd) => remove::execute(cmd).await,
         #[cfg(feature = "self_update")]
@@ -391,6 +450,17 @@ pub async fn execute_command(
     }
 }
 
+/// How progress is reported.
+#[derive(clap::ValueEnum, Debug, Clone, Default)]
+pub enum ProgressOutput {
+    /// Show progress bars.
+    #[default]
+    Bars,
+
+    /// Write a json object per finished solve, download and install step.
+    Json,
+}
+
 /// Whether to use colored log format.
 /// Option `Auto` enables color output only if the logging is done to a terminal
 /// and  `NO_COLOR` environment variable is not set.
@@ -529,6 +599,37 @@ fn print_installed_commands() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_quiet_disables_progress() {
+        let args = Args::parse_from(["pixi", "info"]);
+        assert!(!args.progress_disabled_by_flags());
+
+        let args = Args::parse_from(["pixi", "--quiet", "info"]);
+        assert!(args.progress_disabled_by_flags());
+
+        let args = Args::parse_from(["pixi", "--no-progress", "info"]);
+        assert!(args.progress_disabled_by_flags());
+    }
+
+    #[test]
+    fn test_quiet_hides_timing() {
+        let args = Args::parse_from(["pixi", "--timing", "info"]);
+        assert!(args.global_options.print_timing());
+
+        let args = Args::parse_from(["pixi", "--timing", "--quiet", "info"]);
+        assert!(!args.global_options.print_timing());
+    }
+
+    #[test]
+    fn test_json_progress_hides_progress_bars() {
+        let args = Args::parse_from(["pixi", "info"]);
+        assert!(!args.json_progress());
+
+        let args = Args::parse_from(["pixi", "info", "--progress", "json"]);
+        assert!(args.json_progress());
+        assert!(args.progress_disabled_by_flags());
+    }
+
     #[test]
     fn test_clap_boolean_env_var_behavior() {
         // Test PIXI_FROZEN=true
@@ -7,10 +7,10 @@ use fancy_display::FancyDisplay;
 use itertools::Itertools;
 use pixi_api::{
     WorkspaceContext,
-    workspace::{Package, PackageKind},
+    workspace::{Package, PackageKind, platforms::resolve_platforms},
 };
 use pixi_consts::consts;
-use pixi_core::WorkspaceLocator;
+use pixi_core::{WorkspaceLocator, workspace::Environment};
 use pixi_manifest::PixiPlatformName;
 use rattler_conda_types::Platform;
 use serde::Serialize;
@@ -18,6 +18,10 @@ use serde::Serialize;
 use crate::{
     cli_config::{LockFileUpdateConfig, NoInstallConfig, WorkspaceConfig},
     cli_interface::CliInterface,
+    shared::{
+        installed_prefix::installed_prefix,
+        outdated::{OutdatedPackage, is_outdated, outdated_packages},
+    },
 };
 
 // an enum to sort by size or name
@@ -199,6 +203,18 @@ pub struct Args {
     /// Only list packages that are explicitly defined in the workspace.
     #[arg(short = 'x', long)]
     pub explicit: bool,
+
+    /// Report how much disk space the installed conda packages of the
+    /// environment use instead, from large to small. Files that are hard
+    /// linked within the environment are counted once.
+    #[arg(long, conflicts_with_all = ["sort_by", "fields", "explicit", "platform"])]
+    pub size: bool,
+
+    /// Only list packages for which a newer version is available in the
+    /// channels or PyPI indexes, together with the newest version allowed by
+    /// the manifest and the newest version overall.
+    #[arg(long, conflicts_with_all = ["sort_by", "fields", "size"])]
+    pub outdated: bool,
 }
 
 pub async fn execute(args: Args) -> miette::Result<()> {
@@ -217,11 +233,15 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             .unwrap_or_else(|| Platform::current().to_string()),
     };
 
+    if args.size {
+        return print_disk_usage(&environment, args.regex.as_deref(), args.json);
+    }
+
     let workspace_ctx = WorkspaceContext::new(CliInterface {}, workspace.clone());
     let mut packages_to_output = workspace_ctx
         .list_packages(
             args.regex,
-            args.platform,
+            args.platform.clone(),
             args.environment,
             args.explicit,
             args.no_install_config.no_install,
@@ -250,6 +270,30 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         );
     }
 
+    if args.outdated {
+        let platform = match &args.platform {
+            Some(name) => resolve_platforms(
+                &workspace.workspace_manifest().workspace.platforms,
+                std::slice::from_ref(name),
+            )?
+            .into_iter()
+            .next(),
+            None => environment.best_declared_platform().cloned(),
+        }
+        .ok_or_else(|| {
+            miette::miette!(
+                "no platform supported by environment '{}' matches the current system",
+                environment.name()
+            )
+        })?;
+        let outdated = outdated_packages(&environment, &platform, &packages_to_output)
+            .await?
+            .into_iter()
+            .filter(is_outdated)
+            .collect_vec();
+        return print_outdated(&environment, &outdated, args.json);
+    }
+
     // Print as table string or JSON
     if args.json {
         // print packages as json
@@ -368,3 +412,156 @@ fn json_packages(packages: &Vec<Package>) {
         serde_json::to_string_pretty(&packages).expect("Cannot serialize packages to JSON");
     println!("{json_string}");
 }
+
+fn print_outdated(
+    environment: &Environment<'_>,
+    packages: &[OutdatedPackage],
+    json: bool,
+) -> miette::Result<()> {
+    if json {
+        let json_string = serde_json::to_string_pretty(&packages)
+            .expect("Cannot serialize outdated packages to JSON");
+        println!("{json_string}");
+        return Ok(());
+    }
+
+    if packages.is_empty() {
+        eprintln!(
+            "{}All packages of the {} environment are up-to-date",
+            console::style(console::Emoji("✔ ", "")).green(),
+            environment.name().fancy_display()
+        );
+        return Ok(());
+    }
+
+    if !environment.is_default() {
+        eprintln!("Environment: {}", environment.name().fancy_display());
+    }
+    let header_style = Style::new().bold().cyan();
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header(
+            ["Name", "Current", "Wanted", "Latest", "Kind"]
+                .map(|header| Cell::new(header_style.apply_to(header))),
+        );
+    let unknown = || Style::new().yellow().apply_to("unknown").to_string();
+    for package in packages {
+        let kind = match package.kind {
+            PackageKind::Conda => consts::CONDA_PACKAGE_STYLE.apply_to("conda"),
+            PackageKind::Pypi => consts::PYPI_PACKAGE_STYLE.apply_to("pypi"),
+        };
+        table.add_row([
+            Cell::new(&package.name),
+            Cell::new(package.current.as_deref().unwrap_or_default()),
+            // Packages that are not in the manifest have no wanted version.
+            Cell::new(match (&package.wanted, &package.latest) {
+                (Some(wanted), _) => wanted.clone(),
+                (None, Some(_)) => String::new(),
+                (None, None) => unknown(),
+            }),
+            Cell::new(package.latest.clone().unwrap_or_else(unknown)),
+            Cell::new(kind),
+        ]);
+    }
+
+    println!(
+        "{}",
+        table
+            .lines()
+            .map(|line| line.trim().to_string())
+            .format("\n")
+    );
+    Ok(())
+}
+
+/// The disk usage of an installed package, as reported by `pixi list --size`.
+#[derive(Serialize)]
+struct PackageDiskUsage {
+    name: String,
+    size_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct DiskUsage {
+    packages: Vec<PackageDiskUsage>,
+    total_bytes: u64,
+}
+
+fn print_disk_usage(
+    environment: &Environment<'_>,
+    regex: Option<&str>,
+    json: bool,
+) -> miette::Result<()> {
+    let prefix = installed_prefix(environment)?;
+    let regex = regex
+        .map(regex::Regex::new)
+        .transpose()
+        .map_err(|_| miette::miette!("Invalid regex"))?;
+
+    let records = prefix.find_installed_packages()?;
+    let packages = prefix
+        .disk_usage(&records)
+        .into_iter()
+        .map(|usage| PackageDiskUsage {
+            name: usage.name.as_normalized().to_string(),
+            size_bytes: usage.size_in_bytes,
+        })
+        .filter(|usage| {
+            regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&usage.name))
+        })
+        .sorted_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.name.cmp(&b.name)))
+        .collect_vec();
+    let usage = DiskUsage {
+        total_bytes: packages.iter().map(|package| package.size_bytes).sum(),
+        packages,
+    };
+
+    if json {
+        let json_string =
+            serde_json::to_string_pretty(&usage).expect("Cannot serialize disk usage to JSON");
+        println!("{json_string}");
+        return Ok(());
+    }
+
+    if !environment.is_default() {
+        eprintln!("Environment: {}", environment.name().fancy_display());
+    }
+    let header_style = Style::new().bold().cyan();
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header([
+            Cell::new(header_style.apply_to("Name")),
+            Cell::new(header_style.apply_to("Size")).set_alignment(CellAlignment::Right),
+        ]);
+    for package in &usage.packages {
+        table.add_row([
+            Cell::new(&package.name),
+            Cell::new(indicatif::HumanBytes(package.size_bytes))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+    table.add_row([
+        Cell::new(Style::new().bold().apply_to("Total")),
+        Cell::new(
+            Style::new()
+                .bold()
+                .apply_to(indicatif::HumanBytes(usage.total_bytes)),
+        )
+        .set_alignment(CellAlignment::Right),
+    ]);
+
+    println!(
+        "{}",
+        table
+            .lines()
+            .map(|line| line.trim().to_string())
+            .format("\n")
+    );
+    Ok(())
+}
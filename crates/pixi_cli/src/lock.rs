@@ -1,13 +1,18 @@
 use clap::Parser;
+use fancy_display::FancyDisplay;
+use itertools::Itertools;
 use miette::{Context, IntoDiagnostic};
 use pixi_core::{
-    WorkspaceLocator,
-    environment::LockFileUsage,
-    lock_file::{LockFileDerivedData, UpdateLockFileOptions},
+    UpdateLockFileOptions, WorkspaceLocator,
+    environment::{InstallFilter, LockFileUsage, get_update_lock_file_and_prefixes},
+    lock_file::{
+        LockFileDerivedData, LockFileHistory, ReinstallPackages, UpdateMode, find_unsatisfied_specs,
+    },
 };
 use pixi_diff::{LockFileDiff, LockFileJsonDiff};
 
 use crate::cli_config::NoInstallConfig;
+use crate::cli_config::SolveStrategyConfig;
 use crate::cli_config::WorkspaceConfig;
 
 /// Solve environment and update the lock file without installing the
@@ -24,12 +29,18 @@ pub struct Args {
     #[clap(flatten)]
     pub no_install_config: NoInstallConfig,
 
+    #[clap(flatten)]
+    pub solve_strategy_config: SolveStrategyConfig,
+
     /// Output the changes in JSON format.
     #[clap(long)]
     pub json: bool,
 
     /// Check if any changes have been made to the lock file.
     /// If yes, exit with a non-zero code.
+    ///
+    /// Exits without solving when a dependency is not satisfied by the locked
+    /// packages.
     #[clap(long)]
     pub check: bool,
 
@@ -37,9 +48,43 @@ pub struct Args {
     /// Implies --no-install
     #[clap(long)]
     pub dry_run: bool,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Restore a previous version of the lock file and reinstall the
+    /// environments.
+    ///
+    /// Every update of the lock file archives the version it replaces in
+    /// `.pixi/lock-history`. The number of versions that are kept is set with
+    /// the `lock-history-depth` configuration.
+    Rollback(RollbackArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct RollbackArgs {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    #[clap(flatten)]
+    pub no_install_config: NoInstallConfig,
+
+    /// The revision to restore. Defaults to the most recent revision.
+    #[arg(long)]
+    pub to: Option<String>,
 }
 
 pub async fn execute(args: Args) -> miette::Result<()> {
+    if let Some(Command::Rollback(args)) = args.command {
+        return rollback(args).await;
+    }
+
     let mut workspace = WorkspaceLocator::for_cli()
         .with_global_config_source(args.config_source.source())
         .with_search_start(args.workspace_config.workspace_locator_start())
@@ -49,11 +94,32 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     if let Some(backend_override) = args.workspace_config.backend_override.clone() {
         workspace = workspace.with_backend_override(backend_override);
     }
+    workspace = args.solve_strategy_config.apply(workspace);
 
     // Update the lock file, and extract it from the derived data to drop additional resources
     // created for the solve.
     // Use the silent version here since update_lock_file() will display the warning.
     let original_lock_file = workspace.load_lock_file().await?.into_lock_file_or_empty();
+
+    // A requirement that is not satisfied by the locked packages means the lock
+    // file is out of date. This is cheap to find out because it does not require
+    // solving, so `--check` fails without a solve in that case.
+    if args.check && !args.json {
+        let unsatisfied = find_unsatisfied_specs(&workspace, &original_lock_file);
+        if !unsatisfied.is_empty() {
+            for spec in unsatisfied {
+                eprintln!(
+                    "{}{spec}",
+                    console::style(console::Emoji("⚠ ", "! ")).yellow()
+                );
+            }
+            eprintln!(
+                "{}Lock-file is not up-to-date",
+                console::style(console::Emoji("✘ ", "")).red()
+            );
+            std::process::exit(1);
+        }
+    }
     let progress = pixi_reporters::TopLevelProgress::from_global();
     let (LockFileDerivedData { lock_file, .. }, lock_updated) = workspace
         .update_lock_file(
@@ -118,3 +184,90 @@ pub async fn execute(args: Args) -> miette::Result<()> {
 
     Ok(())
 }
+
+async fn rollback(args: RollbackArgs) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?;
+
+    let history = LockFileHistory::from_workspace(&workspace);
+    let revisions = history.revisions()?;
+    let revision = match &args.to {
+        Some(id) => revisions.iter().find(|revision| &revision.id == id),
+        None => revisions.first(),
+    };
+    let Some(revision) = revision else {
+        if revisions.is_empty() {
+            miette::bail!("there is no previous lock file to roll back to");
+        }
+        miette::bail!(
+            help = format!(
+                "available revisions are: {}",
+                revisions.iter().map(|revision| &revision.id).format(", ")
+            ),
+            "the lock file history contains no revision '{}'",
+            args.to.unwrap_or_default()
+        );
+    };
+
+    history.restore(
+        revision,
+        &workspace.lock_file_path(),
+        workspace.config().lock_history_depth(),
+    )?;
+    eprintln!(
+        "{}Restored the lock file of {}",
+        console::style(console::Emoji("✔ ", "")).green(),
+        revision.id
+    );
+
+    // The manifest may have changed since the restored lock file was written.
+    let lock_file = workspace.load_lock_file().await?.into_lock_file_or_empty();
+    let unsatisfied = find_unsatisfied_specs(&workspace, &lock_file);
+    if !unsatisfied.is_empty() {
+        tracing::warn!(
+            "the restored lock file does not match the manifest, it will be updated by the next command that uses it:\n{}",
+            unsatisfied.iter().format("\n")
+        );
+    }
+
+    if args.no_install_config.no_install {
+        return Ok(());
+    }
+
+    // Reinstall the environments that were installed before.
+    let environments = workspace
+        .environments()
+        .into_iter()
+        .filter(|environment| environment.dir().is_dir())
+        .collect::<Vec<_>>();
+    if environments.is_empty() {
+        return Ok(());
+    }
+    get_update_lock_file_and_prefixes(
+        &environments,
+        None,
+        Some(pixi_reporters::TopLevelProgress::from_global()),
+        UpdateMode::Revalidate,
+        UpdateLockFileOptions {
+            lock_file_usage: LockFileUsage::Frozen,
+            no_install: false,
+            max_concurrent_solves: workspace.config().max_concurrent_solves(),
+            ..Default::default()
+        },
+        ReinstallPackages::default(),
+        &InstallFilter::default(),
+    )
+    .await?;
+    eprintln!(
+        "{}Reinstalled {}",
+        console::style(console::Emoji("✔ ", "")).green(),
+        environments
+            .iter()
+            .map(|environment| environment.name().fancy_display())
+            .format(", ")
+    );
+
+    Ok(())
+}
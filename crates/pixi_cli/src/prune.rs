@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use clap::Parser;
+use fancy_display::FancyDisplay;
+use itertools::Itertools;
+use pixi_core::WorkspaceLocator;
+use rattler_conda_types::PrefixRecord;
+
+use crate::{cli_config::WorkspaceConfig, shared::installed_prefix::installed_prefix};
+
+/// Remove installed packages that are no longer part of the lock file.
+///
+/// Compares the conda packages installed in the environment with the
+/// packages the environment is locked to, and removes the packages that are
+/// no longer locked together with their files. Files that are shared with a
+/// package that is still locked are kept.
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// The environment to prune.
+    #[arg(long, short)]
+    pub environment: Option<String>,
+
+    /// Only list the packages that would be removed.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?;
+
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let prefix = installed_prefix(&environment)?;
+
+    // The conda packages the environment is locked to for the current system.
+    let lock_file = workspace
+        .load_lock_file()
+        .await?
+        .into_lock_file_or_empty_with_warning();
+    let Some(locked_packages) = environment.best_declared_platform().and_then(|platform| {
+        let locked_environment = lock_file.environment(environment.name().as_str())?;
+        let lock_platform = lock_file.platform(platform.name().as_str())?;
+        locked_environment.conda_packages(lock_platform)
+    }) else {
+        miette::bail!(
+            help = "run `pixi lock` to update the lock file",
+            "the {} environment is not locked for the current platform, refusing to prune it",
+            environment.name()
+        );
+    };
+    let locked_names: HashSet<String> = locked_packages
+        .map(|package| package.record().name.as_normalized().to_string())
+        .collect();
+
+    let (orphaned, kept): (Vec<PrefixRecord>, Vec<PrefixRecord>) = prefix
+        .find_installed_packages()?
+        .into_iter()
+        .partition(|record| {
+            !locked_names.contains(record.repodata_record.package_record.name.as_normalized())
+        });
+
+    if orphaned.is_empty() {
+        eprintln!(
+            "{}The {} environment contains no packages that are not locked",
+            console::style(console::Emoji("✔ ", "")).green(),
+            environment.name().fancy_display()
+        );
+        return Ok(());
+    }
+
+    let orphaned_names = orphaned
+        .iter()
+        .map(|record| {
+            let package_record = &record.repodata_record.package_record;
+            format!(
+                "{} {} {}",
+                package_record.name.as_normalized(),
+                package_record.version,
+                package_record.build
+            )
+        })
+        .sorted()
+        .collect_vec();
+
+    if args.dry_run {
+        eprintln!(
+            "The following packages would be removed from the {} environment:",
+            environment.name().fancy_display()
+        );
+        for name in &orphaned_names {
+            eprintln!("  {name}");
+        }
+        return Ok(());
+    }
+
+    prefix.remove_packages(&orphaned, &kept)?;
+    for name in &orphaned_names {
+        eprintln!("{}Removed {name}", console::style("- ").red());
+    }
+    eprintln!(
+        "{}Pruned {} package(s) from the {} environment",
+        console::style(console::Emoji("✔ ", "")).green(),
+        orphaned.len(),
+        environment.name().fancy_display()
+    );
+
+    Ok(())
+}
@@ -646,6 +646,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         manifest_source.to_string(),
         EnvironmentSpec {
             channels: channels.clone(),
+            channel_subdirs: Default::default(),
             build_environment: build_environment.clone(),
             variants: pixi_utils::variants::VariantConfig {
                 variant_configuration: variant_configuration.clone(),
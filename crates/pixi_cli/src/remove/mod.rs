@@ -56,13 +56,20 @@ impl TryFrom<&Args> for DependencyOptions {
         Ok(DependencyOptions {
             feature: args.dependency_config.feature.clone(),
             platforms: args.dependency_config.platforms.clone(),
-            no_install: args.no_install_config.no_install,
-            lock_file_usage: args.lock_file_update_config.lock_file_usage()?,
+            no_install: args.dependency_config.no_install(&args.no_install_config),
+            lock_file_usage: args
+                .dependency_config
+                .lock_file_usage(&args.lock_file_update_config)?,
+            no_preserve: false,
+            no_sort: args.dependency_config.no_sort,
+            dry_run: false,
         })
     }
 }
 
-pub async fn execute(args: Args) -> miette::Result<()> {
+pub async fn execute(mut args: Args) -> miette::Result<()> {
+    args.dependency_config
+        .take_deprecated_no_lock_file_update(&mut args.lock_file_update_config);
     let workspace = WorkspaceLocator::for_cli()
         .with_global_config_source(args.config_source.source())
         .with_search_start(args.workspace_config.workspace_locator_start())
@@ -71,7 +78,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
 
     let workspace_ctx = WorkspaceContext::new(CliInterface {}, workspace.clone());
 
-    let dependency_type = args.dependency_config.dependency_type();
+    let dependency_type = args.dependency_config.dependency_type()?;
     let feature = args.dependency_config.feature.clone();
     let platforms = args.dependency_config.platforms.clone();
 
@@ -112,6 +119,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         (Ok(()), _) => {
             args.dependency_config
                 .display_success("Removed", Default::default());
+            args.dependency_config.print_stale_lock_file_reminder();
             Ok(())
         }
         (Err(RemoveError::NotFound { name: missing }), typed_names) => {
@@ -2,7 +2,9 @@ use std::{
     collections::{HashMap, HashSet, hash_map::Entry},
     convert::identity,
     ffi::OsString,
+    path::PathBuf,
     string::String,
+    sync::atomic::{AtomicBool, AtomicI32, Ordering},
 };
 
 #[cfg(unix)]
@@ -34,8 +36,10 @@ use pixi_progress::global_multi_progress;
 use pixi_task::{
     AmbiguousTask, CanSkip, ExecutableTask, FailedToParseShellScript, InvalidWorkingDirectory,
     PreferExecutable, SearchEnvironments, TaskAndEnvironment, TaskGraph, get_task_env,
+    get_unactivated_task_env,
 };
 use rattler_conda_types::Platform;
+use rattler_lock::LockFile;
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 use tracing::Level;
@@ -68,6 +72,14 @@ pub struct Args {
     #[arg(long = "executable", short = 'x')]
     pub executable: bool,
 
+    /// Run a command as if it was a task in the manifest, without adding it.
+    ///
+    /// The command is rendered as a template like the `cmd` of a task, so
+    /// `{{ args }}` is replaced by the arguments that follow it. Otherwise the
+    /// arguments are appended to the command.
+    #[arg(long, value_name = "COMMAND", conflicts_with = "executable")]
+    pub cmd: Option<String>,
+
     #[clap(flatten)]
     pub workspace_config: WorkspaceConfig,
 
@@ -84,6 +96,12 @@ pub struct Args {
     #[arg(long, short)]
     pub environment: Option<String>,
 
+    /// Use the environment that is installed in the given prefix instead of
+    /// the `.pixi` directory. A relative path is resolved against the
+    /// workspace root.
+    #[arg(long)]
+    pub prefix: Option<PathBuf>,
+
     /// Install and run in the environment for the given platform; a warning is
     /// printed when it doesn't run on this machine. Accepts a workspace
     /// platform name; a bare conda subdir (e.g. `linux-64`) is also accepted.
@@ -97,6 +115,14 @@ pub struct Args {
     #[arg(long)]
     pub clean_env: bool,
 
+    /// Don't activate the environment, only add its executables to the PATH.
+    ///
+    /// Skips the activation scripts and the environment variables pixi sets
+    /// during activation, which makes starting a task faster. Tasks can opt
+    /// out of activation individually with `activation = false`.
+    #[arg(long)]
+    pub no_activation: bool,
+
     /// Don't run the dependencies of the task ('depends-on' field in the task
     /// definition)
     #[arg(long)]
@@ -114,6 +140,25 @@ pub struct Args {
     #[clap(short = 'n', long)]
     pub dry_run: bool,
 
+    /// Prefix every line of output of a task with the name of the task.
+    ///
+    /// Useful to tell apart the output of tasks that are run as dependencies
+    /// of another task. The output of the tasks is no longer connected to the
+    /// terminal directly when this is enabled.
+    #[arg(long)]
+    pub prefix_output: bool,
+
+    /// Keep running the remaining tasks when a task fails.
+    ///
+    /// Tasks that depend on a failed task are still skipped. Pixi exits with
+    /// the exit code of the first task that failed.
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// List the available tasks instead of running one.
+    #[arg(long, conflicts_with = "task")]
+    pub list: bool,
+
     #[clap(long, action = clap::ArgAction::HelpLong)]
     pub help: Option<bool>,
 
@@ -136,12 +181,20 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         .merge_config(args.config.clone().into());
 
     // Load the workspace
-    let workspace = WorkspaceLocator::for_cli()
+    let mut workspace = WorkspaceLocator::for_cli()
         .with_global_config_source(args.config_source.source())
         .with_search_start(args.workspace_config.workspace_locator_start())
         .locate()?
         .with_cli_config(cli_config);
 
+    if let Some(prefix) = &args.prefix {
+        let name = workspace
+            .environment_from_name_or_env_var(args.environment.clone())?
+            .name()
+            .clone();
+        workspace = workspace.with_prefix_override(name, prefix);
+    }
+
     // Extract the passed in environment name.
     let environment = workspace.environment_from_name_or_env_var(args.environment.clone())?;
 
@@ -153,8 +206,8 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     };
 
     // Print all available tasks if no task is provided
-    if args.task.is_empty() {
-        command_not_found(&workspace, explicit_environment);
+    if args.list || (args.task.is_empty() && args.cmd.is_none()) {
+        command_not_found(&workspace, explicit_environment, None);
         return Ok(());
     }
 
@@ -224,7 +277,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     let progress = pixi_reporters::TopLevelProgress::from_global();
 
     // Ensure that the lock file is up-to-date.
-    let mut lock_file = workspace
+    let (mut lock_file, lock_file_updated) = workspace
         .update_lock_file(
             Some(progress.clone()),
             UpdateLockFileOptions {
@@ -234,19 +287,21 @@ pub async fn execute(args: Args) -> miette::Result<()> {
                 ..Default::default()
             },
         )
-        .await?
-        .0;
+        .await?;
+    if lock_file_updated {
+        pixi_progress::println!(
+            "{}The lock file is stale, updating it to match the workspace manifest",
+            console::style(console::Emoji("✔ ", "")).green(),
+        );
+    }
 
     // Pin the run platform (explicit `--platform`, else the auto-upgraded
     // resolved platform) so the on-demand prefix install below targets it.
     lock_file.target_platform = run_platform.clone();
 
-    // Spawn a task that listens for ctrl+c and resets the cursor.
-    tokio::spawn(async {
-        if tokio::signal::ctrl_c().await.is_ok() {
-            reset_cursor();
-        }
-    });
+    // Listen for ctrl+c and `SIGTERM`, so that the remaining tasks are not
+    // started after the running task is interrupted.
+    listen_for_interrupts();
 
     // Construct a task graph from the input arguments.
     // Pin the search only to an explicit `--platform`; otherwise each
@@ -265,18 +320,29 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         SearchEnvironments::from_opt_env(&workspace, explicit_environment.clone(), search_platform)
             .with_disambiguate_fn(disambiguate_task_interactive);
 
-    let task_graph = TaskGraph::from_cmd_args(
-        &workspace,
-        &search_environment,
-        args.task,
-        args.skip_deps,
-        if args.executable {
-            PreferExecutable::Always
-        } else {
-            PreferExecutable::TaskFirst
-        },
-        args.templated,
-    )?;
+    // The name of the command, used to suggest a task if it cannot be found.
+    let requested_command = args
+        .task
+        .first()
+        .filter(|_| args.cmd.is_none())
+        .and_then(|arg| arg.split_whitespace().next())
+        .map(ToOwned::to_owned);
+
+    let task_graph = match args.cmd {
+        Some(cmd) => TaskGraph::from_inline_cmd(&workspace, &search_environment, cmd, args.task)?,
+        None => TaskGraph::from_cmd_args(
+            &workspace,
+            &search_environment,
+            args.task,
+            args.skip_deps,
+            if args.executable {
+                PreferExecutable::Always
+            } else {
+                PreferExecutable::TaskFirst
+            },
+            args.templated,
+        )?,
+    };
     tracing::debug!("Task graph: {}", task_graph);
 
     // Print dry-run message if dry-run mode is enabled
@@ -298,8 +364,21 @@ pub async fn execute(args: Args) -> miette::Result<()> {
     // make sure that child processes are killed when pixi stops
     let _drop_guard = signal.clone().drop_guard();
 
+    // The tasks that failed when running with `--keep-going`.
+    let mut failed_tasks = Vec::new();
+    let mut failed_task_ids = HashSet::new();
+
     let init_cwd = std::env::current_dir().ok();
     for task_id in task_graph.topological_order() {
+        // A task that depends on a failed task cannot run either, unless the
+        // dependency is marked as `continue-on-error`.
+        if task_graph[task_id].dependencies.iter().any(|dependency| {
+            failed_task_ids.contains(&dependency.task_id()) && !dependency.continue_on_error()
+        }) {
+            failed_task_ids.insert(task_id);
+            continue;
+        }
+
         let executable_task =
             ExecutableTask::from_task_graph(&task_graph, task_id, init_cwd.clone());
 
@@ -402,7 +481,9 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         // If we don't have a command environment yet, we need to compute it. We lazily
         // compute the task environment because we only need the environment if
         // a task is actually executed.
-        let task_env: &_ = match task_envs.entry(executable_task.run_environment.clone()) {
+        let activation = !args.no_activation && executable_task.task().activation();
+        let task_env_key = (executable_task.run_environment.clone(), activation);
+        let task_env: &_ = match task_envs.entry(task_env_key) {
             Entry::Occupied(env) => env.into_mut(),
             Entry::Vacant(entry) => {
                 // Check if we allow installs
@@ -432,14 +513,19 @@ pub async fn execute(args: Args) -> miette::Result<()> {
                 // Clear caches based on the filesystem. The tasks might change files on disk.
                 lock_file.command_dispatcher.clear_filesystem_caches().await;
 
-                let command_env = get_task_env(
-                    &executable_task.run_environment,
-                    args.clean_env || executable_task.task().clean_env(),
-                    Some(lock_file.as_lock_file()),
-                    workspace.config().force_activate(),
-                    workspace.config().experimental_activation_cache_usage(),
-                )
-                .await?;
+                let clean_env = args.clean_env || executable_task.task().clean_env();
+                let command_env = if activation {
+                    get_task_env(
+                        &executable_task.run_environment,
+                        clean_env,
+                        Some(lock_file.as_lock_file()),
+                        workspace.config().force_activate(),
+                        workspace.config().experimental_activation_cache_usage(),
+                    )
+                    .await?
+                } else {
+                    get_unactivated_task_env(&executable_task.run_environment, clean_env)?
+                };
                 entry.insert(command_env)
             }
         };
@@ -452,15 +538,62 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         // Execute the task itself within the command environment. If one of the tasks
         // failed with a non-zero exit code, we exit this parent process with
         // the same code.
-        match execute_task(&executable_task, &task_env, signal.clone()).await {
+        TASK_RUNNING.store(true, Ordering::SeqCst);
+        let result = execute_task(
+            &executable_task,
+            lock_file.as_lock_file(),
+            &task_env,
+            signal.clone(),
+            args.prefix_output,
+        )
+        .await;
+        TASK_RUNNING.store(false, Ordering::SeqCst);
+
+        // The remaining tasks are never started after an interrupt, also when
+        // failures are tolerated. The exit code of the task is kept in case it
+        // handled the signal itself.
+        if let Some(signal) = received_interrupt() {
+            match &result {
+                Err(TaskExecutionError::NonZeroExitCode(code)) => {
+                    process_exit::exit_with_code(*code)
+                }
+                _ => exit_on_interrupt(signal),
+            }
+        }
+
+        match result {
             Ok(_) => {
                 task_idx += 1;
             }
             Err(TaskExecutionError::NonZeroExitCode(code)) => {
                 if code == 127 {
-                    command_not_found(&workspace, explicit_environment.clone());
+                    // Only a custom command can be a misspelled task name.
+                    let command = executable_task
+                        .name()
+                        .is_none()
+                        .then_some(requested_command.as_deref())
+                        .flatten();
+                    command_not_found(&workspace, explicit_environment.clone(), command);
+                }
+                if task_graph.failure_is_tolerated(task_id) {
+                    tracing::warn!(
+                        "task '{}' failed with exit code {code}, continuing because it is marked as `continue-on-error`",
+                        executable_task.name().unwrap_or("unnamed")
+                    );
+                    failed_task_ids.insert(task_id);
+                    task_idx += 1;
+                    continue;
                 }
-                process_exit::exit_with_code(code);
+                if !args.keep_going {
+                    process_exit::exit_with_code(code);
+                }
+                failed_task_ids.insert(task_id);
+                failed_tasks.push((
+                    executable_task.name().unwrap_or("unnamed").to_string(),
+                    code,
+                ));
+                task_idx += 1;
+                continue;
             }
             Err(err) => return Err(err.into()),
         }
@@ -479,11 +612,32 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             .into_diagnostic()?;
     }
 
+    if let Some((_, code)) = failed_tasks.first() {
+        pixi_progress::println!(
+            "\n{}{} {}",
+            console::Emoji("❌ ", ""),
+            console::style("Failed tasks:").red().bold(),
+            failed_tasks
+                .iter()
+                .format_with(", ", |(name, code), f| f(&format_args!(
+                    "{} (exit code {code})",
+                    console::style(name).bold()
+                )))
+        );
+        process_exit::exit_with_code(*code);
+    }
+
     Ok(())
 }
 
-/// Called when a command was not found.
-fn command_not_found<'p>(workspace: &'p Workspace, explicit_environment: Option<Environment<'p>>) {
+/// Called when a command was not found, prints the available tasks and the
+/// ones that are similar to the command.
+fn command_not_found<'p>(
+    workspace: &'p Workspace,
+    explicit_environment: Option<Environment<'p>>,
+    command: Option<&str>,
+) {
+    let explicit_environment_name = explicit_environment.as_ref().map(|env| env.name().clone());
     let available_tasks: HashSet<TaskName> =
         if let Some(explicit_environment) = explicit_environment {
             explicit_environment.get_filtered_tasks()
@@ -499,7 +653,7 @@ fn command_not_found<'p>(workspace: &'p Workspace, explicit_environment: Option<
         pixi_progress::println!(
             "\nAvailable tasks:\n{}",
             available_tasks
-                .into_iter()
+                .iter()
                 .sorted()
                 .format_with("\n", |name, f| {
                     f(&format_args!("\t{}", name.fancy_display().bold()))
@@ -507,6 +661,45 @@ fn command_not_found<'p>(workspace: &'p Workspace, explicit_environment: Option<
         );
     }
 
+    if let Some(command) = command {
+        let similar = similar_task_names(command, &available_tasks);
+        if !similar.is_empty() {
+            pixi_progress::println!(
+                "\nDid you mean {}?",
+                similar
+                    .iter()
+                    .format_with(" or ", |name, f| f(&format_args!(
+                        "'{}'",
+                        name.fancy_display().bold()
+                    )))
+            );
+        }
+
+        // The task may exist, just not in the environment that was selected.
+        if let Some(explicit_environment_name) = explicit_environment_name {
+            let task_name = TaskName::from(command);
+            let other_environments = workspace
+                .environments()
+                .into_iter()
+                .filter(|env| env.name() != &explicit_environment_name)
+                .filter(|env| env.get_filtered_tasks().contains(&task_name))
+                .map(|env| env.name().clone())
+                .collect_vec();
+            if let Some(first) = other_environments.first() {
+                pixi_progress::println!(
+                    "\nHelp: task '{}' is not available in the {} environment, it is defined in {}. Run it with `pixi run -e {} {}`",
+                    task_name.fancy_display().bold(),
+                    explicit_environment_name.fancy_display(),
+                    other_environments
+                        .iter()
+                        .format_with(", ", |name, f| f(&name.fancy_display())),
+                    first.as_str(),
+                    command
+                );
+            }
+        }
+    }
+
     // Help user when there is no task available because the platform is not
     // supported
     if workspace
@@ -522,6 +715,22 @@ fn command_not_found<'p>(workspace: &'p Workspace, explicit_environment: Option<
     }
 }
 
+/// Returns the names of the tasks that are similar to the given command, most
+/// similar first.
+fn similar_task_names<'a>(command: &str, tasks: &'a HashSet<TaskName>) -> Vec<&'a TaskName> {
+    tasks
+        .iter()
+        .filter_map(|name| {
+            let similarity = strsim::jaro(command, name.as_str());
+            // A high threshold, short names are otherwise similar to almost anything.
+            (similarity > 0.8).then_some((similarity, name))
+        })
+        .sorted_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal))
+        .take(3)
+        .map(|(_, name)| name)
+        .collect()
+}
+
 #[derive(Debug, Error, Diagnostic)]
 enum TaskExecutionError {
     #[error("the script exited with a non-zero exit code {0}")]
@@ -536,6 +745,10 @@ enum TaskExecutionError {
 
     #[error(transparent)]
     UnsupportedPlatformError(#[from] UnsupportedPlatformError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ExecutionError(#[from] pixi_task::TaskExecutionError),
 }
 
 /// Called to execute a single command.
@@ -543,16 +756,37 @@ enum TaskExecutionError {
 /// This function is called from [`execute`].
 async fn execute_task(
     task: &ExecutableTask<'_>,
+    lock_file: &LockFile,
     command_env: &HashMap<OsString, OsString>,
     kill_signal: KillSignal,
+    prefix_output: bool,
 ) -> Result<(), TaskExecutionError> {
+    task.check_requirements(lock_file)
+        .map_err(pixi_task::TaskExecutionError::from)?;
+    if prefix_output {
+        let execute_future = task.execute_with_prefixed_output(
+            command_env,
+            kill_signal.clone(),
+            std::io::stdout(),
+            std::io::stderr(),
+        );
+        let status_code = run_future_forwarding_signals(kill_signal, execute_future).await?;
+        if status_code != 0 {
+            return Err(TaskExecutionError::NonZeroExitCode(status_code));
+        }
+        return Ok(());
+    }
+
     let Some(script) = task.as_deno_script()? else {
         return Ok(());
     };
     let cwd = task.working_directory()?;
+    let command_env = task
+        .command_env_with_dotenv(command_env)
+        .map_err(pixi_task::TaskExecutionError::from)?;
     let execute_future = deno_task_shell::execute(
         script,
-        command_env.clone(),
+        command_env,
         cwd,
         Default::default(),
         kill_signal.clone(),
@@ -624,16 +858,66 @@ fn reset_cursor() {
     let _ = term.show_cursor();
 }
 
-// /// Exit the process with the appropriate exit code for a SIGINT.
-// fn exit_process_on_sigint() {
-//     // https://learn.microsoft.com/en-us/cpp/c-runtime-library/signal-constants
-//     #[cfg(target_os = "windows")]
-//     std::process::exit(3);
-//
-//     // POSIX compliant OSs: 128 + SIGINT (2)
-//     #[cfg(not(target_os = "windows"))]
-//     std::process::exit(130);
-// }
+/// The signal number of the last interrupt pixi received, or 0.
+static INTERRUPT_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// Whether a task is running. The running task receives the interrupt itself,
+/// otherwise pixi exits right away.
+static TASK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// The signal number of `SIGINT`, which is also used for ctrl+c on Windows.
+const SIGINT: i32 = 2;
+
+/// Returns the signal number of the interrupt pixi received, if any.
+fn received_interrupt() -> Option<i32> {
+    Some(INTERRUPT_SIGNAL.load(Ordering::SeqCst)).filter(|&signal| signal != 0)
+}
+
+/// Exit the process with the conventional exit code for an interrupt,
+/// 128 + the signal number.
+fn exit_on_interrupt(signal: i32) -> ! {
+    process_exit::exit_with_code(128 + signal)
+}
+
+/// Records the interrupt and exits when no task is running.
+fn on_interrupt(signal: i32) {
+    reset_cursor();
+    INTERRUPT_SIGNAL.store(signal, Ordering::SeqCst);
+    if !TASK_RUNNING.load(Ordering::SeqCst) {
+        exit_on_interrupt(signal);
+    }
+}
+
+/// Spawns a task that listens for `SIGINT` and `SIGTERM`, or the ctrl+c
+/// console control event on Windows. The signals themselves are forwarded to
+/// the running task by [`run_future_forwarding_signals`].
+fn listen_for_interrupts() {
+    #[cfg(unix)]
+    tokio::spawn(async {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let (Ok(mut interrupt), Ok(mut terminate)) = (
+            signal(SignalKind::interrupt()),
+            signal(SignalKind::terminate()),
+        ) else {
+            return;
+        };
+        loop {
+            tokio::select! {
+                Some(()) = interrupt.recv() => on_interrupt(SIGINT),
+                Some(()) = terminate.recv() => on_interrupt(libc::SIGTERM),
+                else => break,
+            }
+        }
+    });
+
+    #[cfg(windows)]
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            on_interrupt(SIGINT);
+        }
+    });
+}
 
 /// Runs a task future forwarding any signals received to the process.
 ///
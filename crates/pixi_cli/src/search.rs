@@ -8,11 +8,12 @@ use indexmap::IndexMap;
 use indexmap::IndexSet;
 use miette::{IntoDiagnostic, Report};
 use pixi_api::workspace::platforms::resolve_platforms;
-use pixi_api::{DefaultContext, WorkspaceContext};
+use pixi_api::{DefaultContext, WorkspaceContext, workspace::PypiSearchResult};
 use pixi_config::default_channel_config;
-use pixi_core::{WorkspaceLocator, workspace::WorkspaceLocatorError};
+use pixi_core::{Workspace, WorkspaceLocator, workspace::WorkspaceLocatorError};
 use pixi_manifest::{FeaturesExt, HasWorkspaceManifest, PixiPlatformName};
 use pixi_progress::await_in_progress;
+use pixi_pypi_spec::PypiPackageName;
 use rattler_conda_types::{
     MatchSpec, PackageName, ParseStrictness, ParseStrictnessWithNameMatcher, Platform,
     RepoDataRecord,
@@ -67,6 +68,17 @@ pub struct Args {
     /// Output in JSON format
     #[arg(long, conflicts_with_all = ["limit", "limit_packages"])]
     pub json: bool,
+
+    /// Search the PyPI indexes of the workspace instead of the conda channels
+    ///
+    /// The package has to be given by its exact name. Every distribution is
+    /// labelled with the index it was found on.
+    #[arg(long)]
+    pub pypi: bool,
+
+    /// Only search this PyPI index, implies `--pypi`
+    #[arg(long, value_name = "URL")]
+    pub index: Option<Url>,
 }
 
 fn build_json_output(packages: &[RepoDataRecord]) -> IndexMap<String, Vec<&RepoDataRecord>> {
@@ -104,6 +116,11 @@ pub async fn execute_impl<W: Write>(
         }
     };
 
+    if args.pypi || args.index.is_some() {
+        search_pypi(workspace, &args, out).await?;
+        return Ok(Vec::new());
+    }
+
     // Resolve channels from project / CLI args
     let channels = args.channels.resolve_from_project(workspace.as_ref())?;
     eprintln!(
@@ -202,6 +219,106 @@ pub async fn execute_impl<W: Write>(
     Ok(packages)
 }
 
+/// Searches the PyPI indexes for the package of `args` and prints the
+/// distributions that were found, labelled with their index.
+async fn search_pypi<W: Write>(
+    workspace: Option<Workspace>,
+    args: &Args,
+    out: &mut W,
+) -> miette::Result<()> {
+    let package = PypiPackageName::from_str(&args.package).into_diagnostic()?;
+    let results = if let Some(workspace) = workspace {
+        let context = WorkspaceContext::new(CliInterface {}, workspace);
+        await_in_progress("searching packages...", |_| {
+            context.search_pypi(&package, args.index.clone())
+        })
+        .await?
+    } else {
+        let context = DefaultContext::new(CliInterface {});
+        await_in_progress("searching packages...", |_| {
+            context.search_pypi(&package, args.index.clone())
+        })
+        .await?
+    };
+
+    let result = if args.json {
+        let json_output = results
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "filename": result.filename,
+                    "version": result.version.as_ref().map(ToString::to_string),
+                    "url": result.url.as_str(),
+                    "index": result.index.as_str(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let json_str = serde_json::to_string_pretty(&json_output).into_diagnostic()?;
+        writeln!(out, "{json_str}")
+    } else {
+        let limit_versions = usize::try_from(args.limit).ok();
+        print_pypi_search_results(&package, &results, out, limit_versions)
+    };
+    if let Err(e) = result
+        && e.kind() != std::io::ErrorKind::BrokenPipe
+    {
+        return Err(e).into_diagnostic();
+    }
+    Ok(())
+}
+
+fn print_pypi_search_results<W: Write>(
+    package: &PypiPackageName,
+    results: &[PypiSearchResult],
+    out: &mut W,
+    limit_versions: Option<usize>,
+) -> io::Result<()> {
+    // The results are sorted from the newest version, group them by version.
+    let mut by_version: IndexMap<Option<String>, Vec<&PypiSearchResult>> = IndexMap::new();
+    for result in results {
+        by_version
+            .entry(result.version.as_ref().map(ToString::to_string))
+            .or_default()
+            .push(result);
+    }
+
+    writeln!(
+        out,
+        "{}",
+        console::style(package.as_source()).green().bold()
+    )?;
+    let shown = by_version.len().min(limit_versions.unwrap_or(usize::MAX));
+    for (version, files) in by_version.iter().take(shown) {
+        for file in files {
+            writeln!(
+                out,
+                "  {} {} {}",
+                console::style(version.as_deref().unwrap_or("<unknown>")).cyan(),
+                file.filename,
+                console::style(&file.index).dim(),
+            )?;
+        }
+    }
+
+    let remaining_versions = by_version.len() - shown;
+    if remaining_versions > 0 {
+        let label = if remaining_versions == 1 {
+            "version"
+        } else {
+            "versions"
+        };
+        writeln!(
+            out,
+            "  {}",
+            console::style(format!(
+                "... and {remaining_versions} more {label} (use -l to show more)"
+            ))
+            .dim(),
+        )?;
+    }
+    Ok(())
+}
+
 pub async fn execute(args: Args) -> miette::Result<()> {
     let mut out = io::stdout();
     execute_impl(args, &mut out).await?;
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::io::{Seek, Write};
+use std::path::Path;
 
 use flate2::read::GzDecoder;
 use tar::Archive;
@@ -9,11 +10,13 @@ use pixi_config::Config;
 use pixi_consts::consts;
 use pixi_utils::reqwest::{build_reqwest_clients, reqwest_client_builder};
 use reqwest::redirect::Policy;
+use reqwest_middleware::ClientWithMiddleware;
 
 use tempfile::{NamedTempFile, TempDir};
 use url::Url;
 
 use rattler_conda_types::Version;
+use rattler_digest::{Sha256, Sha256Hash};
 use std::str::FromStr;
 
 use crate::GlobalOptions;
@@ -86,6 +89,62 @@ fn default_archive_name() -> Option<String> {
     }
 }
 
+/// Returns the url of the release archive of the given version, or of the
+/// latest release if no version is given.
+fn download_url(target_version: Option<&Version>, archive_name: &str) -> String {
+    match target_version {
+        Some(target_version) => format!(
+            "{}/download/v{}/{}",
+            consts::RELEASES_URL,
+            target_version,
+            archive_name
+        ),
+        None => format!("{}/latest/download/{}", consts::RELEASES_URL, archive_name),
+    }
+}
+
+/// Parses the contents of the `.sha256` file that is published next to every
+/// release archive, e.g. `<hash>  pixi-x86_64-unknown-linux-musl.tar.gz`.
+fn parse_checksum(contents: &str) -> Option<Sha256Hash> {
+    contents
+        .split_whitespace()
+        .next()
+        .and_then(rattler_digest::parse_digest_from_hex::<Sha256>)
+}
+
+/// Returns the package manager that manages the pixi binary at the given
+/// path, if any. Such an installation should be updated through that package
+/// manager instead.
+fn installing_package_manager(binary: &Path) -> Option<&'static str> {
+    const MANAGED_PATHS: &[(&str, &str)] = &[
+        ("/opt/homebrew/", "Homebrew"),
+        ("/usr/local/Cellar/", "Homebrew"),
+        ("/home/linuxbrew/", "Homebrew"),
+        ("/nix/store/", "Nix"),
+        ("/snap/", "snap"),
+        ("/usr/bin/", "your system package manager"),
+        ("/usr/sbin/", "your system package manager"),
+    ];
+
+    let path = binary.to_string_lossy();
+    if let Some((_, manager)) = MANAGED_PATHS
+        .iter()
+        .find(|(prefix, _)| path.starts_with(prefix))
+    {
+        return Some(manager);
+    }
+    if path.to_lowercase().contains("\\scoop\\apps\\") {
+        return Some("Scoop");
+    }
+
+    // A pixi installed in a conda environment, `<prefix>/bin/pixi`.
+    binary
+        .parent()
+        .and_then(Path::parent)
+        .is_some_and(|prefix| prefix.join("conda-meta").is_dir())
+        .then_some("conda")
+}
+
 async fn latest_version() -> miette::Result<Version> {
     // Uses the public Github Releases /latest endpoint to get the latest tag from the URL
     let url = format!("{}/latest", consts::RELEASES_URL);
@@ -183,6 +242,62 @@ async fn fetch_release_notes(version: &Option<Version>) -> miette::Result<String
     }
 }
 
+/// Downloads the release archive at `download_url` into a temporary file and
+/// verifies it against the checksum that is published next to it.
+async fn download_archive(
+    client: &ClientWithMiddleware,
+    download_url: &str,
+) -> miette::Result<NamedTempFile> {
+    // Create a temp file to download the archive
+    let archived_tempfile = NamedTempFile::new().into_diagnostic()?;
+
+    let mut res = client
+        .get(download_url)
+        .header("User-Agent", user_agent())
+        .send()
+        .await
+        .into_diagnostic()?;
+
+    if res.status() != reqwest::StatusCode::OK {
+        miette::bail!(format!("URL {} returned {}", download_url, res.status()));
+    } else {
+        // Download the archive
+        while let Some(chunk) = res.chunk().await.into_diagnostic()? {
+            archived_tempfile
+                .as_file()
+                .write_all(&chunk)
+                .into_diagnostic()?;
+        }
+    }
+
+    // Verify the archive against the checksum that is published next to it
+    let checksum_url = format!("{download_url}.sha256");
+    let res = client
+        .get(&checksum_url)
+        .header("User-Agent", user_agent())
+        .send()
+        .await
+        .into_diagnostic()?;
+    if res.status() != reqwest::StatusCode::OK {
+        miette::bail!("URL {} returned {}", checksum_url, res.status());
+    }
+    let checksum = res.text().await.into_diagnostic()?;
+    let expected_sha256 = parse_checksum(&checksum)
+        .ok_or_else(|| miette::miette!("URL {} does not contain a sha256 hash", checksum_url))?;
+    let sha256 = rattler_digest::compute_file_digest::<Sha256>(archived_tempfile.path())
+        .into_diagnostic()?;
+    if sha256 != expected_sha256 {
+        miette::bail!(
+            "the checksum of {} does not match, expected {:x} but got {:x}",
+            download_url,
+            expected_sha256,
+            sha256
+        );
+    }
+
+    Ok(archived_tempfile)
+}
+
 /// Executes the self-update command.
 ///
 /// # Arguments
@@ -190,6 +305,18 @@ async fn fetch_release_notes(version: &Option<Version>) -> miette::Result<String
 /// * `global_options` - Reference to the global CLI options.
 pub async fn execute(args: Args, global_options: &GlobalOptions) -> miette::Result<()> {
     let is_quiet = global_options.quiet > 0;
+
+    // Refuse to replace a binary that is managed by a package manager
+    let current_exe = std::env::current_exe().into_diagnostic()?;
+    let current_exe = fs_err::canonicalize(&current_exe).unwrap_or(current_exe);
+    if let Some(manager) = installing_package_manager(&current_exe) {
+        miette::bail!(
+            help = format!("use {manager} to update pixi instead"),
+            "pixi at '{}' was installed by {manager}",
+            current_exe.display()
+        );
+    }
+
     // Get the target version, without 'v' prefix, None for force latest version
     let target_version = match &args.version {
         Some(version) => {
@@ -318,39 +445,10 @@ pub async fn execute(args: Args, global_options: &GlobalOptions) -> miette::Resu
     let archive_name = default_archive_name()
         .expect("Could not find the default archive name for the current platform");
 
-    let download_url = if let Some(ref target_version) = target_version {
-        format!(
-            "{}/download/v{}/{}",
-            consts::RELEASES_URL,
-            target_version,
-            archive_name
-        )
-    } else {
-        format!("{}/latest/download/{}", consts::RELEASES_URL, archive_name)
-    };
-
-    // Create a temp file to download the archive
-    let mut archived_tempfile = NamedTempFile::new().into_diagnostic()?;
+    let download_url = download_url(target_version.as_ref(), &archive_name);
 
     let client = build_reqwest_clients(None, None)?.1;
-    let mut res = client
-        .get(&download_url)
-        .header("User-Agent", user_agent())
-        .send()
-        .await
-        .expect("Failed to download the archive");
-
-    if res.status() != reqwest::StatusCode::OK {
-        miette::bail!(format!("URL {} returned {}", download_url, res.status()));
-    } else {
-        // Download the archive
-        while let Some(chunk) = res.chunk().await.into_diagnostic()? {
-            archived_tempfile
-                .as_file()
-                .write_all(&chunk)
-                .into_diagnostic()?;
-        }
-    }
+    let mut archived_tempfile = download_archive(&client, &download_url).await?;
 
     if !is_quiet {
         eprintln!(
@@ -474,6 +572,97 @@ pub async fn execute_stub(_: Args, _: &GlobalOptions) -> miette::Result<()> {
 mod tests {
     use std::path::PathBuf;
 
+    use super::*;
+
+    #[test]
+    fn test_download_url() {
+        let archive_name = default_archive_name().unwrap();
+        let version = Version::from_str("0.46.0").unwrap();
+        assert_eq!(
+            download_url(Some(&version), &archive_name),
+            format!("https://github.com/prefix-dev/pixi/releases/download/v0.46.0/{archive_name}")
+        );
+        assert_eq!(
+            download_url(None, "pixi-x86_64-unknown-linux-musl.tar.gz"),
+            "https://github.com/prefix-dev/pixi/releases/latest/download/pixi-x86_64-unknown-linux-musl.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "2db1733f4b644575dbbdd7994a8f338e6ef937f5ebdb74acd557e9dda0211709";
+        let checksum =
+            parse_checksum(&format!("{hash}  pixi-x86_64-unknown-linux-musl.tar.gz\n")).unwrap();
+        assert_eq!(format!("{checksum:x}"), hash);
+        assert!(parse_checksum("not a hash").is_none());
+    }
+
+    /// Serves `archive` and the given contents of its `.sha256` file, returns
+    /// the url of the archive.
+    async fn serve_release(archive: &'static [u8], checksum: String) -> String {
+        use axum::{Router, routing::get};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = Router::new()
+            .route("/pixi.tar.gz", get(move || async move { archive }))
+            .route("/pixi.tar.gz.sha256", get(move || async move { checksum }));
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}/pixi.tar.gz")
+    }
+
+    #[tokio::test]
+    async fn test_download_archive_verifies_checksum() {
+        const ARCHIVE: &[u8] = b"pixi release archive";
+        let client = build_reqwest_clients(None, None).unwrap().1;
+
+        // A matching checksum is accepted.
+        let sha256 = rattler_digest::compute_bytes_digest::<Sha256>(ARCHIVE);
+        let url = serve_release(ARCHIVE, format!("{sha256:x}  pixi.tar.gz\n")).await;
+        let archive = download_archive(&client, &url).await.unwrap();
+        assert_eq!(fs_err::read(archive.path()).unwrap(), ARCHIVE);
+
+        // A mismatching checksum is refused.
+        let other = rattler_digest::compute_bytes_digest::<Sha256>(b"something else");
+        let url = serve_release(ARCHIVE, format!("{other:x}  pixi.tar.gz\n")).await;
+        let err = download_archive(&client, &url).await.unwrap_err();
+        assert!(
+            err.to_string().contains("does not match"),
+            "unexpected error: {err}"
+        );
+
+        // A checksum file without a hash is refused.
+        let url = serve_release(ARCHIVE, "not a hash".to_string()).await;
+        let err = download_archive(&client, &url).await.unwrap_err();
+        assert!(
+            err.to_string().contains("does not contain a sha256 hash"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_installing_package_manager() {
+        assert_eq!(
+            installing_package_manager(Path::new("/opt/homebrew/Cellar/pixi/0.46.0/bin/pixi")),
+            Some("Homebrew")
+        );
+        assert_eq!(
+            installing_package_manager(Path::new("/usr/bin/pixi")),
+            Some("your system package manager")
+        );
+        assert_eq!(
+            installing_package_manager(Path::new("/home/user/.pixi/bin/pixi")),
+            None
+        );
+
+        let prefix = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(prefix.path().join("conda-meta")).unwrap();
+        assert_eq!(
+            installing_package_manager(&prefix.path().join("bin").join("pixi")),
+            Some("conda")
+        );
+    }
+
     #[test]
     pub fn test_unarchive_flat_structure() {
         // This archive contains a single file named "a_file"
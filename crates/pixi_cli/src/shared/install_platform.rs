@@ -6,6 +6,7 @@
 use pixi_api::workspace::platforms::resolve_platforms;
 use pixi_core::Workspace;
 use pixi_manifest::{HasWorkspaceManifest, PixiPlatformName};
+use rattler_conda_types::Platform;
 
 /// Resolve `--platform` to its canonical workspace platform name. Returns
 /// `Ok(None)` when the flag was unset; the caller threads the result down
@@ -25,12 +26,27 @@ pub(crate) fn resolve_install_platform(
     Ok(Some(resolved.name().clone()))
 }
 
+/// Returns whether this machine can run the packages of the given workspace
+/// platform. Platforms the workspace doesn't declare are assumed to run here,
+/// the install path reports them.
+pub(crate) fn runs_on_host(workspace: &Workspace, name: &PixiPlatformName) -> bool {
+    let manifest = &workspace.workspace_manifest().workspace;
+    let Some(platform) = manifest
+        .platforms
+        .iter()
+        .find(|platform| platform.name() == name)
+    else {
+        return true;
+    };
+    manifest
+        .candidate_subdirs(Platform::current())
+        .contains(&platform.subdir())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
-    use rattler_conda_types::Platform;
-
     use super::*;
 
     fn workspace_with_platforms(platforms: &[&str]) -> Workspace {
@@ -85,6 +101,20 @@ mod tests {
         assert_eq!(resolved.as_str(), target);
     }
 
+    #[test]
+    fn cross_platform_subdir_does_not_run_on_host() {
+        let workspace = workspace_with_platforms(&["linux-64", "osx-arm64"]);
+        let (host, other) = if Platform::current() == Platform::OsxArm64 {
+            ("osx-arm64", "linux-64")
+        } else {
+            ("linux-64", "osx-arm64")
+        };
+        if Platform::current().as_str() == host {
+            assert!(runs_on_host(&workspace, &host.parse().unwrap()));
+        }
+        assert!(!runs_on_host(&workspace, &other.parse().unwrap()));
+    }
+
     #[test]
     fn invalid_name_errors() {
         let workspace = workspace_with_platforms(&["linux-64"]);
@@ -0,0 +1,18 @@
+//! Shared lookup of the prefix of an installed environment, used by the
+//! commands that inspect or modify the installed packages.
+
+use pixi_core::workspace::Environment;
+use pixi_utils::prefix::Prefix;
+
+/// Returns the prefix of `environment`, or an error if it is not installed.
+pub(crate) fn installed_prefix(environment: &Environment<'_>) -> miette::Result<Prefix> {
+    let prefix = Prefix::new(environment.dir());
+    if !prefix.is_installed() {
+        miette::bail!(
+            help = "run `pixi install` to install it",
+            "environment '{}' is not installed",
+            environment.name()
+        );
+    }
+    Ok(prefix)
+}
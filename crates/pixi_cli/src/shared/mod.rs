@@ -1,5 +1,7 @@
 //! This file contains utilities shared by the implementation of command logic
 
 pub(crate) mod install_platform;
+pub(crate) mod installed_prefix;
+pub(crate) mod outdated;
 pub(crate) mod platform_note;
 pub mod tree;
@@ -0,0 +1,216 @@
+//! Looks up the newest versions of the packages of an environment, for
+//! `pixi list --outdated`.
+
+use std::str::FromStr;
+
+use futures::StreamExt;
+use miette::IntoDiagnostic;
+use pep508_rs::pep440_rs::{Version as PypiVersion, VersionSpecifiers};
+use pixi_api::workspace::{Package, PackageKind, environment_pypi_indexes, search_pypi};
+use pixi_core::{
+    Workspace,
+    repodata::Repodata,
+    workspace::{Environment, HasWorkspaceRef},
+};
+use pixi_manifest::{FeaturesExt, PixiPlatform};
+use pixi_pypi_spec::{PypiPackageName, VersionOrStar};
+use rattler_conda_types::{Channel, MatchSpec, ParseStrictness, Platform, Version, VersionSpec};
+use rattler_repodata_gateway::Gateway;
+use serde::Serialize;
+use url::Url;
+
+/// The current, wanted and latest version of a package. A version that could
+/// not be determined is `None`.
+#[derive(Debug, Serialize)]
+pub(crate) struct OutdatedPackage {
+    pub name: String,
+    pub kind: PackageKind,
+    pub current: Option<String>,
+    /// The newest version that satisfies the spec in the manifest, only set
+    /// for packages that are defined in the manifest.
+    pub wanted: Option<String>,
+    pub latest: Option<String>,
+}
+
+/// The versions of a package that are available in the channels or indexes.
+enum Available<V> {
+    Versions(Vec<V>),
+    Unknown,
+}
+
+impl<V: Ord + Clone + ToString> Available<V> {
+    /// Returns the wanted and the latest version.
+    fn newest(&self, wanted: impl Fn(&V) -> bool) -> (Option<String>, Option<String>) {
+        match self {
+            Available::Versions(versions) => (
+                versions
+                    .iter()
+                    .filter(|version| wanted(version))
+                    .max()
+                    .map(ToString::to_string),
+                versions.iter().max().map(ToString::to_string),
+            ),
+            Available::Unknown => (None, None),
+        }
+    }
+}
+
+/// Returns the newest versions of the given packages. A package whose
+/// versions could not be fetched is reported with unknown versions instead of
+/// failing the whole lookup. At most `max_concurrent_downloads` lookups run at
+/// the same time.
+pub(crate) async fn outdated_packages(
+    environment: &Environment<'_>,
+    platform: &PixiPlatform,
+    packages: &[Package],
+) -> miette::Result<Vec<OutdatedPackage>> {
+    let workspace = environment.workspace();
+    let gateway = workspace.repodata_gateway()?;
+    let channels = environment
+        .channel_urls(&workspace.channel_config())
+        .into_diagnostic()?
+        .into_iter()
+        .map(Channel::from_url)
+        .collect::<Vec<_>>();
+    let conda_dependencies = environment.combined_dependencies(Some(platform));
+    let pypi_dependencies = environment.pypi_dependencies(Some(platform));
+    let indexes = environment_pypi_indexes(environment);
+
+    let lookups = packages.iter().map(|package| {
+        let channels = channels.clone();
+        let indexes = &indexes;
+        let conda_dependencies = &conda_dependencies;
+        let pypi_dependencies = &pypi_dependencies;
+        async move {
+            let (wanted, latest) = match package.kind {
+                PackageKind::Conda => {
+                    let spec = conda_dependencies
+                        .iter()
+                        .find(|(name, _)| name.as_normalized() == package.name)
+                        .map(|(_, specs)| {
+                            specs
+                                .iter()
+                                .find_map(|spec| spec.as_version_spec().cloned())
+                                .unwrap_or(VersionSpec::Any)
+                        });
+                    let available =
+                        conda_versions(gateway, channels, platform.subdir(), &package.name).await;
+                    available.newest(|version: &Version| {
+                        spec.as_ref().is_some_and(|spec| spec.matches(version))
+                    })
+                }
+                PackageKind::Pypi => {
+                    let specifiers = pypi_dependencies
+                        .iter()
+                        .find(|(name, _)| name.as_normalized().to_string() == package.name)
+                        .map(|(_, specs)| {
+                            specs
+                                .iter()
+                                .find_map(|spec| match spec.source.as_version() {
+                                    Some(VersionOrStar::Version(specifiers)) => {
+                                        Some(specifiers.clone())
+                                    }
+                                    _ => None,
+                                })
+                                .unwrap_or_else(VersionSpecifiers::empty)
+                        });
+                    let available = pypi_versions(workspace, indexes, &package.name).await;
+                    available.newest(|version: &PypiVersion| {
+                        specifiers
+                            .as_ref()
+                            .is_some_and(|specifiers| specifiers.contains(version))
+                    })
+                }
+            };
+            OutdatedPackage {
+                name: package.name.clone(),
+                kind: package.kind.clone(),
+                current: package.version.clone(),
+                wanted,
+                latest,
+            }
+        }
+    });
+
+    Ok(futures::stream::iter(lookups)
+        .buffered(workspace.config().max_concurrent_downloads())
+        .collect()
+        .await)
+}
+
+/// Returns true if a newer version than the current one is available, or if
+/// that is unknown.
+pub(crate) fn is_outdated(package: &OutdatedPackage) -> bool {
+    let (Some(current), Some(latest)) = (&package.current, &package.latest) else {
+        return true;
+    };
+    match package.kind {
+        PackageKind::Conda => match (Version::from_str(current), Version::from_str(latest)) {
+            (Ok(current), Ok(latest)) => latest > current,
+            _ => current != latest,
+        },
+        PackageKind::Pypi => match (
+            PypiVersion::from_str(current),
+            PypiVersion::from_str(latest),
+        ) {
+            (Ok(current), Ok(latest)) => latest > current,
+            _ => current != latest,
+        },
+    }
+}
+
+/// The versions of a conda package in the channels of the environment.
+async fn conda_versions(
+    gateway: &Gateway,
+    channels: Vec<Channel>,
+    subdir: Platform,
+    name: &str,
+) -> Available<Version> {
+    let Ok(spec) = MatchSpec::from_str(name, ParseStrictness::Lenient) else {
+        return Available::Unknown;
+    };
+    match gateway
+        .query(channels, [subdir, Platform::NoArch], [spec])
+        .recursive(false)
+        .await
+    {
+        Ok(repodata) => Available::Versions(
+            repodata
+                .iter()
+                .flat_map(|records| records.iter())
+                .map(|record| record.package_record.version.version().clone())
+                .collect(),
+        ),
+        Err(err) => {
+            tracing::warn!("failed to fetch the versions of '{name}': {err}");
+            Available::Unknown
+        }
+    }
+}
+
+/// The versions of a PyPI package on the index and the extra indexes of the
+/// environment. Pre-releases are left out.
+async fn pypi_versions(
+    workspace: &Workspace,
+    indexes: &[Url],
+    name: &str,
+) -> Available<PypiVersion> {
+    let fetch = async {
+        let name = PypiPackageName::from_str(name).into_diagnostic()?;
+        search_pypi(Some(workspace), &name, indexes).await
+    };
+    match fetch.await {
+        Ok(results) => Available::Versions(
+            results
+                .iter()
+                .filter_map(|result| result.version.as_ref())
+                .filter_map(|version| PypiVersion::from_str(&version.to_string()).ok())
+                .filter(|version| !version.any_prerelease())
+                .collect(),
+        ),
+        Err(err) => {
+            tracing::warn!("failed to fetch the versions of '{name}': {err}");
+            Available::Unknown
+        }
+    }
+}
@@ -12,7 +12,7 @@ use which::which;
 use pixi_config::{ConfigCli, ConfigCliActivation, ConfigCliPrompt};
 use pixi_core::{
     UpdateLockFileOptions, WorkspaceLocator,
-    activation::CurrentEnvVarBehavior,
+    activation::{self, CurrentEnvVarBehavior},
     environment::{InstallFilter, get_update_lock_file_and_prefix},
     lock_file::{ReinstallPackages, UpdateMode},
     prompt,
@@ -322,6 +322,9 @@ async fn start_nu_shell(
 }
 
 pub async fn execute(args: Args) -> miette::Result<()> {
+    // Refuse to stack a shell on top of an already activated environment.
+    activation::ensure_not_activated(&std::env::vars().collect())?;
+
     let config = args
         .activation_config
         .merge_config(args.prompt_config.into())
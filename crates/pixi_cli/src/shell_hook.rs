@@ -11,9 +11,10 @@ use rattler_shell::{
 use serde::Serialize;
 use serde_json;
 
+use pixi_consts::consts;
 use pixi_core::{
     UpdateLockFileOptions, Workspace, WorkspaceLocator,
-    activation::{CurrentEnvVarBehavior, get_activator},
+    activation::{CurrentEnvVarBehavior, EnvironmentSnapshot, get_activator, run_activation},
     environment::get_update_lock_file_and_prefix,
     lock_file::{ReinstallPackages, UpdateMode},
     prompt,
@@ -56,6 +57,11 @@ pub struct Args {
     #[clap(long, default_value = "false", conflicts_with = "shell")]
     json: bool,
 
+    /// Print a script that reverts the activation of a previously sourced
+    /// shell-hook, restoring the variables and the prompt to their prior values
+    #[clap(long, default_value = "false", conflicts_with_all = ["json", "environment"])]
+    deactivate: bool,
+
     #[clap(flatten)]
     prompt_config: ConfigCliPrompt,
 }
@@ -72,12 +78,7 @@ async fn generate_activation_script(
     environment: &Environment<'_>,
     project: &Workspace,
 ) -> miette::Result<String> {
-    // Get shell from the arguments or from the current process or use default if
-    // all fails
-    let shell = shell.unwrap_or_else(|| {
-        ShellEnum::from_parent_process()
-            .unwrap_or_else(|| ShellEnum::from_env().unwrap_or_default())
-    });
+    let shell = resolve_shell(shell);
 
     let activator = get_activator(environment, shell.clone())?;
 
@@ -90,6 +91,19 @@ async fn generate_activation_script(
     let conda_prefix = std::env::var("CONDA_PREFIX").ok().map(|p| p.into());
     let current_env = std::env::vars().collect::<HashMap<_, _>>();
 
+    // Remember the values of the variables that the activation changes so
+    // that `--deactivate` can restore them. The activation cache is bypassed,
+    // the snapshot has to match the activation of the script below.
+    let activated_env = run_activation(
+        environment,
+        &CurrentEnvVarBehavior::Include,
+        None,
+        project.config().force_activate(),
+        false,
+    )
+    .await?;
+    let snapshot = EnvironmentSnapshot::from_activation(&current_env, &activated_env);
+
     let mut result = activator
         .activation(ActivationVariables {
             conda_prefix,
@@ -99,6 +113,11 @@ async fn generate_activation_script(
         })
         .into_diagnostic()?;
 
+    result
+        .script
+        .set_env_var(consts::PIXI_DEACTIVATION_ENV, &snapshot.encode()?)
+        .into_diagnostic()?;
+
     if project.config().shell.source_completion_scripts()
         && let Some(completions_dir) = shell.completion_script_location()
     {
@@ -120,6 +139,29 @@ async fn generate_activation_script(
     }
 }
 
+/// Returns the shell from the arguments, from the current process or the
+/// default if all fails.
+fn resolve_shell(shell: Option<ShellEnum>) -> ShellEnum {
+    shell.unwrap_or_else(|| {
+        ShellEnum::from_parent_process()
+            .unwrap_or_else(|| ShellEnum::from_env().unwrap_or_default())
+    })
+}
+
+/// Generates the script that reverts a previous activation.
+fn generate_deactivation_script(shell: Option<ShellEnum>) -> miette::Result<String> {
+    let Some(snapshot) = EnvironmentSnapshot::from_env()? else {
+        miette::bail!(
+            help = "source the output of `pixi shell-hook` first",
+            "no environment was activated by `pixi shell-hook` in this shell"
+        );
+    };
+    let shell = resolve_shell(shell);
+    let script = snapshot.deactivation_script(shell.clone())?;
+    let prompt_reset = prompt::shell_prompt_reset(&shell);
+    Ok([script, prompt_reset].join("\n"))
+}
+
 /// Generates a JSON object describing the changes to the shell environment when
 /// activating the provided pixi environment.
 async fn generate_environment_json(
@@ -155,6 +197,11 @@ async fn generate_environment_json(
 
 /// Prints the activation script to the stdout.
 pub async fn execute(args: Args) -> miette::Result<()> {
+    if args.deactivate {
+        println!("{}", generate_deactivation_script(args.shell)?);
+        return Ok(());
+    }
+
     let config = args
         .activation_config
         .merge_config(args.prompt_config.merge_config(args.config.clone().into()));
@@ -222,6 +269,13 @@ mod tests {
         let project = WorkspaceLocator::default().locate().unwrap();
         let environment = project.default_environment();
 
+        // The prompt is modified unless it is disabled in the configuration.
+        let change_ps1 = project.config().change_ps1();
+        let prompt_name = prompt::prompt_name(project.display_name(), environment.name());
+        let has_prompt = |script: &str, shell: ShellEnum| {
+            script.contains(&prompt::shell_prompt(&shell, &prompt_name))
+        };
+
         let script = generate_activation_script(
             Some(ShellEnum::Bash(Bash::default())),
             &environment,
@@ -231,12 +285,17 @@ mod tests {
         .unwrap();
         assert!(script.contains(&format!("export {path_var_name}=")));
         assert!(script.contains("export CONDA_PREFIX="));
+        assert_eq!(
+            script.contains(&format!("export PS1='({prompt_name}) '")),
+            change_ps1
+        );
 
         let script = generate_activation_script(Some(ShellEnum::Zsh(Zsh)), &environment, &project)
             .await
             .unwrap();
         assert!(script.contains(&format!("export {path_var_name}=")));
         assert!(script.contains("export CONDA_PREFIX="));
+        assert_eq!(has_prompt(&script, ShellEnum::Zsh(Zsh)), change_ps1);
 
         let script =
             generate_activation_script(Some(ShellEnum::Fish(Fish)), &environment, &project)
@@ -244,6 +303,7 @@ mod tests {
                 .unwrap();
         assert!(script.contains(&format!("set -gx {path_var_name} ")));
         assert!(script.contains("set -gx CONDA_PREFIX "));
+        assert_eq!(has_prompt(&script, ShellEnum::Fish(Fish)), change_ps1);
 
         let script =
             generate_activation_script(Some(ShellEnum::Xonsh(Xonsh)), &environment, &project)
@@ -251,6 +311,7 @@ mod tests {
                 .unwrap();
         assert!(script.contains(&format!("${path_var_name} = ")));
         assert!(script.contains("$CONDA_PREFIX = "));
+        assert_eq!(has_prompt(&script, ShellEnum::Xonsh(Xonsh)), change_ps1);
 
         // Powershell is universal so we go with that on UNIX too
         let script = generate_activation_script(
@@ -262,6 +323,10 @@ mod tests {
         .unwrap();
         assert!(script.contains(&format!("${{Env:{path_var_name}}}")));
         assert!(script.contains("${Env:CONDA_PREFIX}"));
+        assert_eq!(
+            has_prompt(&script, ShellEnum::PowerShell(PowerShell::default())),
+            change_ps1
+        );
 
         let script =
             generate_activation_script(Some(ShellEnum::NuShell(NuShell)), &environment, &project)
@@ -269,6 +334,7 @@ mod tests {
                 .unwrap();
         assert!(script.contains(&format!("$env.{path_var_name} = ")));
         assert!(script.contains("$env.CONDA_PREFIX = "));
+        assert_eq!(has_prompt(&script, ShellEnum::NuShell(NuShell)), change_ps1);
     }
 
     #[cfg(target_family = "windows")]
@@ -279,6 +345,13 @@ mod tests {
         let project = WorkspaceLocator::default().locate().unwrap();
         let environment = project.default_environment();
 
+        // The prompt is modified unless it is disabled in the configuration.
+        let change_ps1 = project.config().change_ps1();
+        let prompt_name = prompt::prompt_name(project.display_name(), environment.name());
+        let has_prompt = |script: &str, shell: ShellEnum| {
+            script.contains(&prompt::shell_prompt(&shell, &prompt_name))
+        };
+
         let script = generate_activation_script(
             Some(ShellEnum::PowerShell(PowerShell::default())),
             &environment,
@@ -288,6 +361,10 @@ mod tests {
         .unwrap();
         assert!(script.contains(&format!("${{Env:{path_var_name}}}")));
         assert!(script.contains("${Env:CONDA_PREFIX}"));
+        assert_eq!(
+            has_prompt(&script, ShellEnum::PowerShell(PowerShell::default())),
+            change_ps1
+        );
 
         let script =
             generate_activation_script(Some(ShellEnum::CmdExe(CmdExe)), &environment, &project)
@@ -295,6 +372,7 @@ mod tests {
                 .unwrap();
         assert!(script.contains(&format!("@SET \"{path_var_name}=")));
         assert!(script.contains("@SET \"CONDA_PREFIX="));
+        assert_eq!(has_prompt(&script, ShellEnum::CmdExe(CmdExe)), change_ps1);
 
         let script =
             generate_activation_script(Some(ShellEnum::NuShell(NuShell)), &environment, &project)
@@ -302,5 +380,6 @@ mod tests {
                 .unwrap();
         assert!(script.contains(&format!("$env.{path_var_name} = ")));
         assert!(script.contains("$env.CONDA_PREFIX = "));
+        assert_eq!(has_prompt(&script, ShellEnum::NuShell(NuShell)), change_ps1);
     }
 }
@@ -6,23 +6,28 @@ use std::{
     str::FromStr,
 };
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use fancy_display::FancyDisplay;
 use indexmap::IndexMap;
 use itertools::Itertools;
-use miette::IntoDiagnostic;
+use miette::{Context, IntoDiagnostic};
 use pixi_api::WorkspaceContext;
 use pixi_manifest::{
-    EnvironmentName, FeatureName, PixiPlatformName,
-    task::{Alias, CmdArgs, Dependency, Execute, Task, TaskArg, TaskName, TemplateString, quote},
+    EnvironmentName, FeatureName, PixiPlatformName, TargetSelector, WithWarnings,
+    task::{
+        Alias, CmdArgs, Dependency, Execute, Task, TaskArg, TaskName, TaskShell, TemplateString,
+        quote,
+    },
+    toml::FromTomlStr,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 use pixi_core::{
     Workspace, WorkspaceLocator,
     workspace::{Environment, virtual_packages::EnvironmentRunnability},
 };
+use pixi_task::{SearchEnvironments, TaskDependencyGraph, TaskGraph};
 
 use crate::{cli_config::WorkspaceConfig, cli_interface::CliInterface};
 
@@ -43,6 +48,15 @@ pub enum Operation {
     /// List all tasks in the workspace
     #[clap(visible_alias = "ls", alias = "l")]
     List(ListArgs),
+
+    /// Export the tasks of the workspace
+    Export(ExportArgs),
+
+    /// Import tasks that were exported with `pixi task export`
+    Import(ImportArgs),
+
+    /// Print the dependency graph of the tasks as a Mermaid or Graphviz graph
+    Graph(GraphArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -68,14 +82,23 @@ pub struct AddArgs {
     pub name: TaskName,
 
     /// One or more commands to actually execute.
-    #[clap(required = true, num_args = 1.., id = "COMMAND")]
+    #[clap(required_unless_present = "cmd", num_args = 1.., id = "COMMAND")]
     pub commands: Vec<String>,
 
+    /// A command to execute, use --cmd multiple times to run several commands
+    /// in sequence. A command only runs if the previous one succeeded.
+    #[arg(long = "cmd", id = "cmd", conflicts_with = "COMMAND")]
+    pub cmd: Vec<String>,
+
     /// Depends on these other commands.
     #[clap(long)]
     #[clap(num_args = 1..)]
     pub depends_on: Option<Vec<Dependency>>,
 
+    /// Add the task even if a task it depends on does not exist.
+    #[arg(long, requires = "depends_on")]
+    pub allow_missing: bool,
+
     /// The platform for which the task should be added.
     #[arg(long, short)]
     pub platform: Option<PixiPlatformName>,
@@ -106,9 +129,24 @@ pub struct AddArgs {
     #[arg(long)]
     pub clean_env: bool,
 
+    /// The shell that runs the command, one of `bash`, `cmd`, `powershell` or
+    /// `none`. Defaults to the cross-platform shell of pixi, `none` executes
+    /// the command without a shell.
+    #[arg(long)]
+    pub shell: Option<TaskShell>,
+
+    /// A package or executable that has to be installed in the environment
+    /// to run the task, use --requires multiple times for more than one.
+    #[arg(long, action = clap::ArgAction::Append)]
+    pub requires: Vec<String>,
+
     /// The arguments to pass to the task
     #[arg(long = "arg", action = clap::ArgAction::Append)]
     pub args: Option<Vec<TaskArg>>,
+
+    /// Replace the task if a task with the same name already exists.
+    #[arg(long)]
+    pub force: bool,
 }
 
 /// Parse a single key-value pair
@@ -131,6 +169,10 @@ pub struct AliasArgs {
     #[clap(required = true, num_args = 1..)]
     pub depends_on: Vec<Dependency>,
 
+    /// Add the alias even if a task it depends on does not exist.
+    #[arg(long)]
+    pub allow_missing: bool,
+
     /// The platform for which the alias should be added
     #[arg(long, short)]
     pub platform: Option<PixiPlatformName>,
@@ -138,6 +180,10 @@ pub struct AliasArgs {
     /// The description of the alias task
     #[arg(long)]
     pub description: Option<String>,
+
+    /// Replace the task if a task with the same name already exists.
+    #[arg(long)]
+    pub force: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -163,42 +209,102 @@ pub struct ListArgs {
     pub json: bool,
 }
 
+#[derive(Parser, Debug, Clone)]
+pub struct ExportArgs {
+    /// Export the tasks as json, this is currently the only supported format.
+    #[arg(long, required = true)]
+    pub json: bool,
+}
+
+/// The format of the graph printed by `pixi task graph`.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// A Mermaid flowchart, which can be pasted into Markdown
+    #[default]
+    Mermaid,
+    /// A Graphviz `dot` graph
+    Dot,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct GraphArgs {
+    /// The format of the graph.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: GraphFormat,
+
+    /// The environment whose tasks are drawn.
+    /// If not specified, the default environment is used.
+    #[arg(long, short)]
+    pub environment: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[clap(arg_required_else_help = true)]
+pub struct ImportArgs {
+    /// The file with the exported tasks.
+    pub path: PathBuf,
+
+    /// Replace the tasks that already exist in the workspace. Without this,
+    /// tasks that already exist are skipped.
+    #[arg(long)]
+    pub force: bool,
+}
+
 impl From<AddArgs> for Task {
     fn from(value: AddArgs) -> Self {
         let depends_on = value.depends_on.unwrap_or_default();
         // description or none
         let description = value.description;
-        // Convert the arguments into a single string representation
-        let cmd_args = value
-            .commands
-            .iter()
-            .exactly_one()
-            .map(|c| c.to_string())
-            .unwrap_or_else(|_| {
-                // Simply concatenate all arguments
+        // Every `--cmd` is stored as a separate command of a sequence, the
+        // positional arguments are converted into a single string representation
+        let cmd_args = if !value.cmd.is_empty() {
+            CmdArgs::Sequence(value.cmd.iter().map(|cmd| cmd.trim().into()).collect())
+        } else {
+            CmdArgs::Single(
                 value
                     .commands
                     .iter()
-                    .map(|arg| quote(arg).into_owned())
-                    .join(" ")
-            });
+                    .exactly_one()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|_| {
+                        // Simply concatenate all arguments
+                        value
+                            .commands
+                            .iter()
+                            .map(|arg| quote(arg).into_owned())
+                            .join(" ")
+                    })
+                    .into(),
+            )
+        };
+        let single_cmd = match &cmd_args {
+            CmdArgs::Single(cmd) => Some(cmd.clone()),
+            _ => None,
+        };
 
         // Depending on whether the task has a command, and depends_on or not we create
         // a plain or complex, or alias command.
-        if cmd_args.trim().is_empty() && !depends_on.is_empty() {
+        if single_cmd
+            .as_ref()
+            .is_some_and(|cmd| cmd.source().trim().is_empty())
+            && !depends_on.is_empty()
+        {
             Self::Alias(Alias {
                 depends_on,
                 description,
                 args: value.args,
             })
-        } else if depends_on.is_empty()
+        } else if let Some(cmd) = single_cmd
+            && depends_on.is_empty()
             && value.cwd.is_none()
             && value.env.is_empty()
             && value.default_environment.is_none()
             && description.is_none()
             && value.args.is_none()
+            && value.shell.is_none()
+            && value.requires.is_empty()
         {
-            Self::Plain(cmd_args.into())
+            Self::Plain(cmd)
         } else {
             let clean_env = value.clean_env;
             let cwd = value.cwd;
@@ -215,15 +321,19 @@ impl From<AddArgs> for Task {
             let args = value.args;
 
             Self::Execute(Box::new(Execute {
-                cmd: CmdArgs::Single(cmd_args.into()),
+                cmd: cmd_args,
                 depends_on,
                 inputs: None,
                 outputs: None,
                 cwd,
                 env,
+                dotenv: None,
                 default_environment,
                 description,
                 clean_env,
+                activation: true,
+                shell: value.shell,
+                requires: (!value.requires.is_empty()).then_some(value.requires),
                 args,
             }))
         }
@@ -368,6 +478,9 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         Operation::Remove(args) => remove_tasks(workspace_ctx, args).await,
         Operation::Alias(args) => alias_task(workspace_ctx, args).await,
         Operation::List(args) => list_tasks(workspace_ctx, args).await,
+        Operation::Export(_) => export_tasks(workspace_ctx.workspace()),
+        Operation::Import(args) => import_tasks(workspace_ctx, args).await,
+        Operation::Graph(args) => print_task_graph(workspace_ctx.workspace(), args),
     }
 }
 
@@ -428,6 +541,8 @@ async fn add_task(
             args.clone().into(),
             feature,
             args.platform,
+            args.force,
+            args.allow_missing,
         )
         .await?;
 
@@ -439,7 +554,13 @@ async fn alias_task(
     args: AliasArgs,
 ) -> miette::Result<()> {
     workspace_ctx
-        .alias_task(args.clone().alias, args.clone().into(), args.platform)
+        .alias_task(
+            args.clone().alias,
+            args.clone().into(),
+            args.platform,
+            args.force,
+            args.allow_missing,
+        )
         .await?;
 
     Ok(())
@@ -459,6 +580,144 @@ async fn remove_tasks(
         .await
 }
 
+/// A task as written by `pixi task export` and read by `pixi task import`.
+/// Next to the name, feature and platform, it has the fields of the task
+/// table in the manifest, like `cmd`, `depends-on`, `env`, `cwd` and
+/// `description`. An alias is a task without `cmd` or `commands`.
+#[derive(Serialize, Deserialize, Debug)]
+struct ExportedTask {
+    name: TaskName,
+    feature: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    platform: Option<PixiPlatformName>,
+    #[serde(flatten)]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Returns the fields of the task as they are written to the manifest.
+fn task_fields(task: &Task) -> miette::Result<serde_json::Map<String, serde_json::Value>> {
+    let table = match toml_edit::Item::from(task.clone()) {
+        toml_edit::Item::Value(toml_edit::Value::InlineTable(table)) => table.into_table(),
+        toml_edit::Item::Value(cmd @ toml_edit::Value::String(_)) => {
+            let mut table = toml_edit::Table::new();
+            table.insert("cmd", toml_edit::Item::Value(cmd));
+            table
+        }
+        toml_edit::Item::Value(depends_on @ toml_edit::Value::Array(_)) => {
+            let mut table = toml_edit::Table::new();
+            table.insert("depends-on", toml_edit::Item::Value(depends_on));
+            table
+        }
+        _ => miette::bail!("the task `{task}` can't be written to a manifest"),
+    };
+    toml_edit::de::from_str(&table.to_string()).into_diagnostic()
+}
+
+/// Parses the fields of an exported task like a task table of the manifest.
+fn parse_task_fields(
+    name: &TaskName,
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> miette::Result<Task> {
+    let source = toml_edit::ser::to_string(fields).into_diagnostic()?;
+    <WithWarnings<Task> as FromTomlStr>::from_toml_str(&source)
+        .map(|task| task.value)
+        .into_diagnostic()
+        .with_context(|| format!("failed to parse the task `{}`", name.as_str()))
+}
+
+/// Collects the tasks of all features of the workspace. Tasks of a target
+/// that selects more than a single platform, like `unix`, are skipped because
+/// they cannot be imported again.
+fn build_exported_tasks(workspace: &Workspace) -> miette::Result<Vec<ExportedTask>> {
+    let mut exported = Vec::new();
+    for (feature_name, feature) in &workspace.workspace.value.features {
+        for (target, selector) in feature.targets.iter() {
+            if target.tasks.is_empty() {
+                continue;
+            }
+            let platform = match selector {
+                None => None,
+                Some(TargetSelector::Platform(name)) => Some(name.clone()),
+                Some(TargetSelector::Subdir(subdir)) => Some(PixiPlatformName::from(*subdir)),
+                Some(selector) => {
+                    tracing::warn!(
+                        "skipping the tasks of the `{selector}` target, only tasks of a single platform can be exported"
+                    );
+                    continue;
+                }
+            };
+            for (name, task) in target.tasks.iter().sorted_by_key(|(name, _)| *name) {
+                exported.push(ExportedTask {
+                    name: name.clone(),
+                    feature: feature_name.to_string(),
+                    platform: platform.clone(),
+                    fields: task_fields(task)?,
+                });
+            }
+        }
+    }
+    Ok(exported)
+}
+
+fn export_tasks(workspace: &Workspace) -> miette::Result<()> {
+    let json_string = serde_json::to_string_pretty(&build_exported_tasks(workspace)?)
+        .expect("Failed to serialize tasks");
+    writeln!(std::io::stdout(), "{json_string}")
+        .inspect_err(|e| {
+            if e.kind() == std::io::ErrorKind::BrokenPipe {
+                std::process::exit(0);
+            }
+        })
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+fn print_task_graph(workspace: &Workspace, args: GraphArgs) -> miette::Result<()> {
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let search_envs = SearchEnvironments::from_opt_env(workspace, Some(environment.clone()), None);
+    let task_graph = TaskGraph::from_environment(workspace, &search_envs, &environment)?;
+    let graph = TaskDependencyGraph::from_task_graph(&task_graph, environment.name())?;
+    let rendered = match args.format {
+        GraphFormat::Mermaid => graph.to_mermaid(),
+        GraphFormat::Dot => graph.to_dot(),
+    };
+    write!(std::io::stdout(), "{rendered}")
+        .inspect_err(|e| {
+            if e.kind() == std::io::ErrorKind::BrokenPipe {
+                std::process::exit(0);
+            }
+        })
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+async fn import_tasks(
+    workspace_ctx: WorkspaceContext<CliInterface>,
+    args: ImportArgs,
+) -> miette::Result<()> {
+    let contents = fs_err::read_to_string(&args.path).into_diagnostic()?;
+    let exported: Vec<ExportedTask> = serde_json::from_str(&contents)
+        .into_diagnostic()
+        .with_context(|| format!("failed to parse the tasks in {}", args.path.display()))?;
+
+    let tasks = exported
+        .into_iter()
+        .map(|exported| {
+            let task = parse_task_fields(&exported.name, &exported.fields)?;
+            Ok((
+                exported.name,
+                task,
+                FeatureName::from(exported.feature),
+                exported.platform,
+            ))
+        })
+        .collect::<miette::Result<Vec<_>>>()?;
+
+    workspace_ctx.import_tasks(tasks, args.force).await
+}
+
 fn print_tasks_json(project: &Workspace) -> miette::Result<()> {
     let env_feature_task_map: Vec<EnvTasks> = build_env_feature_task_map(project);
 
@@ -15,7 +15,7 @@ use pixi_diff::{LockFileDiff, LockFileJsonDiff};
 use pixi_manifest::{EnvironmentName, PixiPlatformName};
 use rattler_lock::LockFile;
 
-use crate::cli_config::WorkspaceConfig;
+use crate::cli_config::{SolveStrategyConfig, WorkspaceConfig};
 
 /// The `update` command checks if there are newer versions of the dependencies and updates the `pixi.lock` file and environments accordingly.
 ///
@@ -40,12 +40,20 @@ pub struct Args {
     #[clap(short = 'n', long)]
     pub dry_run: bool,
 
+    #[clap(flatten)]
+    pub solve_strategy_config: SolveStrategyConfig,
+
     #[clap(flatten)]
     pub specs: UpdateSpecsArgs,
 
     /// Output the changes in JSON format.
     #[clap(long)]
     pub json: bool,
+
+    /// Allow PyPI packages to overwrite files installed by conda packages.
+    /// Takes precedence over the `allow-clobber` pypi-option and config.
+    #[clap(long)]
+    pub allow_clobber: bool,
 }
 
 #[derive(Parser, Debug, Default)]
@@ -133,6 +141,10 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         .with_search_start(args.project_config.workspace_locator_start())
         .locate()?
         .with_cli_config(args.config);
+    let mut workspace = args.solve_strategy_config.apply(workspace);
+    if args.allow_clobber {
+        workspace = workspace.with_allow_clobber();
+    }
 
     let specs = UpdateSpecs::from(args.specs);
 
@@ -162,6 +162,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
                     &[],
                     false,
                     args.dry_run,
+                    false,
                 )
                 .await?
         {
@@ -190,6 +191,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
                     std::slice::from_ref(&target),
                     false,
                     args.dry_run,
+                    false,
                 )
                 .await?
             {
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use clap::Parser;
+use fancy_display::FancyDisplay;
+use pixi_api::{WorkspaceContext, workspace::ReinstallOptions};
+use pixi_core::{
+    WorkspaceLocator,
+    environment::LockFileUsage,
+    lock_file::{ReinstallEnvironment, ReinstallPackages},
+};
+use pixi_utils::prefix::PackageVerification;
+
+use crate::{
+    cli_config::WorkspaceConfig, cli_interface::CliInterface,
+    shared::installed_prefix::installed_prefix,
+};
+
+/// Verify the integrity of an installed environment.
+///
+/// Checks that the files of every installed conda package are still present
+/// and, where the package metadata records them, that their size and hash
+/// are unchanged. Packages from the lock file that are not installed are
+/// reported as well.
+///
+/// Use `--repair` to reinstall the packages that failed verification.
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// The environment to verify.
+    #[arg(long, short)]
+    pub environment: Option<String>,
+
+    /// Reinstall the packages that failed verification.
+    #[arg(long)]
+    pub repair: bool,
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?;
+
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let prefix = installed_prefix(&environment)?;
+
+    // The conda packages the environment is locked to for the current system.
+    let lock_file = workspace
+        .load_lock_file()
+        .await?
+        .into_lock_file_or_empty_with_warning();
+    let locked_packages: HashSet<String> = environment
+        .best_declared_platform()
+        .and_then(|platform| {
+            let locked_environment = lock_file.environment(environment.name().as_str())?;
+            let lock_platform = lock_file.platform(platform.name().as_str())?;
+            locked_environment.conda_packages(lock_platform)
+        })
+        .into_iter()
+        .flatten()
+        .filter(|package| package.as_source().is_none())
+        .map(|package| package.record().name.as_normalized().to_string())
+        .collect();
+
+    let installed = prefix.find_installed_packages()?;
+    let installed_names: HashSet<String> = installed
+        .iter()
+        .map(|record| {
+            record
+                .repodata_record
+                .package_record
+                .name
+                .as_normalized()
+                .to_string()
+        })
+        .collect();
+
+    let mut failures: BTreeMap<String, PackageVerification> = BTreeMap::new();
+    for record in &installed {
+        let verification = prefix.verify_package(record);
+        if !verification.is_intact() {
+            failures.insert(
+                record
+                    .repodata_record
+                    .package_record
+                    .name
+                    .as_normalized()
+                    .to_string(),
+                verification,
+            );
+        }
+    }
+    let not_installed = locked_packages
+        .difference(&installed_names)
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    if failures.is_empty() && not_installed.is_empty() {
+        eprintln!(
+            "{}All {} packages of the {} environment are intact",
+            console::style(console::Emoji("✔ ", "")).green(),
+            installed.len(),
+            environment.name().fancy_display()
+        );
+        return Ok(());
+    }
+
+    for (name, verification) in &failures {
+        eprintln!("{}", console::style(name).bold());
+        for path in &verification.missing {
+            eprintln!("  missing:  {}", path.display());
+        }
+        for path in &verification.modified {
+            eprintln!("  modified: {}", path.display());
+        }
+    }
+    for name in &not_installed {
+        eprintln!("{}", console::style(name).bold());
+        eprintln!("  not installed");
+    }
+
+    let failed = failures
+        .into_keys()
+        .chain(not_installed)
+        .collect::<HashSet<_>>();
+    if !args.repair {
+        miette::bail!(
+            help = "run `pixi verify --repair` to reinstall the affected packages",
+            "{} package(s) of the {} environment failed verification",
+            failed.len(),
+            environment.name()
+        );
+    }
+
+    let options = ReinstallOptions {
+        reinstall_packages: ReinstallPackages::Some(failed),
+        reinstall_environments: ReinstallEnvironment::Some(HashSet::from_iter([environment
+            .name()
+            .to_string()])),
+        target_platform: None,
+    };
+    WorkspaceContext::new(CliInterface {}, workspace)
+        .reinstall(options, LockFileUsage::Frozen)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use clap::Parser;
+use fancy_display::FancyDisplay;
+use pixi_core::{WorkspaceLocator, lock_file::lock_file_hash, workspace::Environment};
+
+use crate::cli_config::WorkspaceConfig;
+
+/// Verify that an installed environment was installed from the lock file.
+///
+/// Every install records the hash of `pixi.lock` in the environment. This
+/// command fails if the environment was installed from a lock file with a
+/// different hash than the expected one, which defaults to the hash of the
+/// current `pixi.lock`. The hash of the current `pixi.lock` is printed to
+/// stdout, so it can be passed to `pixi install --verify-lock-hash` later.
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// The environment to verify.
+    #[arg(long, short)]
+    pub environment: Option<String>,
+
+    /// The expected sha256 hash of the lock file, defaults to the hash of
+    /// the current lock file.
+    #[arg(long)]
+    pub hash: Option<String>,
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?;
+
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let Some(current_hash) = lock_file_hash(&workspace.lock_file_path())? else {
+        miette::bail!(
+            help = "run `pixi install` to create it",
+            "the workspace has no lock file"
+        );
+    };
+    println!("{current_hash}");
+
+    let expected_hash = args.hash.unwrap_or(current_hash);
+    verify_installed_lock_file_hash(&environment, &expected_hash)?;
+
+    eprintln!(
+        "{}The {} environment was installed from the lock file with hash {}",
+        console::style(console::Emoji("✔ ", "")).green(),
+        environment.name().fancy_display(),
+        console::style(&expected_hash).bold(),
+    );
+    Ok(())
+}
+
+/// Fails if the lock file hash recorded in the environment doesn't match the
+/// expected hash.
+pub(crate) fn verify_installed_lock_file_hash(
+    environment: &Environment<'_>,
+    expected_hash: &str,
+) -> miette::Result<()> {
+    match environment.installed_lock_file_hash() {
+        Some(hash) if hash.eq_ignore_ascii_case(expected_hash) => Ok(()),
+        Some(hash) => miette::bail!(
+            help = "run `pixi install --frozen` with the expected lock file to install it",
+            "the {} environment was installed from a lock file with hash {hash}, expected {expected_hash}",
+            environment.name().fancy_display(),
+        ),
+        None => miette::bail!(
+            help = "run `pixi install` to install it and record the lock file hash",
+            "the {} environment has no recorded lock file hash",
+            environment.name().fancy_display(),
+        ),
+    }
+}
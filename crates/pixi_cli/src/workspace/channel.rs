@@ -67,6 +67,7 @@ impl TryFrom<&AddRemoveArgs> for ChannelOptions {
     fn try_from(args: &AddRemoveArgs) -> Result<Self, Self::Error> {
         Ok(Self {
             channels: args.channel.clone(),
+            cwd: Some(std::env::current_dir().into_diagnostic()?),
             feature: args.feature.clone(),
             no_install: args.no_install_config.no_install,
             lock_file_usage: args.lock_file_update_config.lock_file_usage()?,
@@ -11,10 +11,11 @@ use pixi_manifest::{
 use pixi_pypi_spec::{PixiPypiSource, PixiPypiSpec, PypiPackageName, VersionOrStar};
 use rattler_conda_types::{
     ChannelConfig, EnvironmentYaml, MatchSpec, MatchSpecOrSubSection, NamedChannelOrUrl,
-    ParseStrictness, Platform,
+    PackageName as CondaPackageName, ParseStrictness, Platform,
 };
 use rattler_lock::{CondaPackageData, LockFile, LockedPackage, PypiPackageData, UrlOrPath};
 
+use super::select_packages;
 use crate::cli_config::WorkspaceConfig;
 
 #[derive(Debug, Default, Parser)]
@@ -50,6 +51,15 @@ pub struct Args {
     /// recreate the same environment without re-running the solver.
     #[arg(long)]
     pub from_lock_file: bool,
+
+    /// Only export the given conda packages. Defaults to all dependencies of
+    /// the environment.
+    ///
+    /// With `--from-lock-file` the dependencies of the given packages are
+    /// exported as well. PyPI dependencies are not exported when packages are
+    /// selected.
+    #[arg(long, num_args = 1.., value_name = "NAME")]
+    pub packages: Option<Vec<CondaPackageName>>,
 }
 
 fn format_pip_extras(extras: &[ExtraName]) -> String {
@@ -145,7 +155,34 @@ fn build_env_yaml(
     environment: &Environment,
     config: &ChannelConfig,
     name: String,
+    packages: Option<&[CondaPackageName]>,
 ) -> miette::Result<EnvironmentYaml> {
+    let dependencies = environment.combined_dependencies(Some(platform));
+    if let Some(packages) = packages {
+        let unknown = packages
+            .iter()
+            .filter(|package| !dependencies.contains_key(*package))
+            .collect_vec();
+        if !unknown.is_empty() {
+            miette::bail!(
+                help = format!(
+                    "the dependencies of environment '{}' are: {}",
+                    environment.name(),
+                    dependencies
+                        .names()
+                        .map(|name| name.as_normalized())
+                        .sorted()
+                        .join(", ")
+                ),
+                "unknown package(s) {}",
+                unknown
+                    .iter()
+                    .map(|name| format!("'{}'", name.as_source()))
+                    .join(", ")
+            );
+        }
+    }
+
     let channels =
         channels_with_nodefaults(environment.channels().into_iter().cloned().collect_vec());
     let mut env_yaml = rattler_conda_types::EnvironmentYaml {
@@ -156,10 +193,10 @@ fn build_env_yaml(
 
     let mut pip_dependencies: Vec<String> = Vec::new();
 
-    for (name, pixi_spec) in environment
-        .combined_dependencies(Some(platform))
-        .into_specs()
-    {
+    for (name, pixi_spec) in dependencies.into_specs() {
+        if packages.is_some_and(|packages| !packages.contains(&name)) {
+            continue;
+        }
         if let Some(nameless_spec) = pixi_spec
             .clone()
             .try_into_nameless_match_spec(config)
@@ -177,7 +214,7 @@ fn build_env_yaml(
         }
     }
 
-    if environment.has_pypi_dependencies() {
+    if packages.is_none() && environment.has_pypi_dependencies() {
         for (name, requirement) in environment.pypi_dependencies(Some(platform)).into_specs() {
             pip_dependencies.push(format_pip_dependency(&name, &requirement));
         }
@@ -268,6 +305,7 @@ fn build_env_yaml_from_lock_file(
     environment: &Environment,
     lock_file: &LockFile,
     name: String,
+    selected_packages: Option<&[CondaPackageName]>,
 ) -> miette::Result<EnvironmentYaml> {
     let env_name = environment.name().as_str();
     let lock_file_env = lock_file.environment(env_name).ok_or_else(|| {
@@ -317,12 +355,31 @@ fn build_env_yaml_from_lock_file(
         .map(|(name, _)| name.as_normalized().clone())
         .collect();
 
+    let packages = packages.collect_vec();
+    let selected = selected_packages
+        .map(|roots| {
+            select_packages(
+                packages.iter().filter_map(|package| match package {
+                    LockedPackage::Conda(CondaPackageData::Binary(p)) => Some(&p.package_record),
+                    _ => None,
+                }),
+                roots,
+            )
+        })
+        .transpose()?;
+
     let mut pip_dependencies: Vec<String> = Vec::new();
 
     for package in packages {
         match package {
             LockedPackage::Conda(CondaPackageData::Binary(p)) => {
                 let pr = &p.package_record;
+                if selected
+                    .as_ref()
+                    .is_some_and(|selected| !selected.contains(&pr.name))
+                {
+                    continue;
+                }
                 let spec_str = format!(
                     "{name} =={version} {build}",
                     name = pr.name.as_source(),
@@ -339,11 +396,18 @@ fn build_env_yaml_from_lock_file(
                     .push(MatchSpecOrSubSection::MatchSpec(Box::new(spec)));
             }
             LockedPackage::Conda(CondaPackageData::Source(source)) => {
+                if selected
+                    .as_ref()
+                    .is_some_and(|selected| !selected.contains(source.name()))
+                {
+                    continue;
+                }
                 tracing::warn!(
                     "Skipping conda source package '{}' since source packages cannot be expressed in a conda environment file.",
                     source.name().as_source()
                 );
             }
+            LockedPackage::Pypi(_) if selected.is_some() => {}
             LockedPackage::Pypi(pypi) => {
                 let is_editable = editable_packages.contains(pypi.name());
                 pip_dependencies.push(format_locked_pypi_dependency(pypi, is_editable));
@@ -459,13 +523,20 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             .with_context(|| {
                 format!("failed to read lock file at '{}'", lock_file_path.display())
             })?;
-        build_env_yaml_from_lock_file(&platform, &environment, &lock_file, name)?
+        build_env_yaml_from_lock_file(
+            &platform,
+            &environment,
+            &lock_file,
+            name,
+            args.packages.as_deref(),
+        )?
     } else {
         build_env_yaml(
             &platform,
             &environment,
             config.global_channel_config(),
             name,
+            args.packages.as_deref(),
         )?
     };
 
@@ -520,6 +591,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -531,6 +603,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml",
@@ -550,6 +623,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -561,6 +635,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_with_pip_extras",
@@ -581,6 +656,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -592,6 +668,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_with_source_editable",
@@ -617,6 +694,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -628,6 +706,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_with_pip_custom_registry",
@@ -648,6 +727,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -659,6 +739,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_with_pip_find_links",
@@ -678,6 +759,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -689,6 +771,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_pyproject_panic",
@@ -716,6 +799,7 @@ mod tests {
             config_source: Default::default(),
             name: None,
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -727,6 +811,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             environment.name().as_str().to_string(),
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_with_defaults",
@@ -769,6 +854,7 @@ mod tests {
                 &environment,
                 &lock_file,
                 environment.name().as_str().to_string(),
+                None,
             )
             .unwrap();
             insta::assert_snapshot!(
@@ -778,6 +864,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_export_conda_env_yaml_from_lock_file_with_packages() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.toml");
+        let workspace = Workspace::from_path(&path).unwrap();
+        let lock_file = LockFile::from_path(&workspace.lock_file_path()).unwrap();
+        let environment = workspace
+            .environment_from_name_or_env_var(Some("default".to_string()))
+            .unwrap();
+
+        let osx64 = pixi_manifest::PixiPlatform::from_subdir(Platform::Osx64);
+        let env_yaml = build_env_yaml_from_lock_file(
+            &osx64,
+            &environment,
+            &lock_file,
+            environment.name().as_str().to_string(),
+            Some(&[CondaPackageName::new_unchecked("openssl")]),
+        )
+        .unwrap();
+        let names = env_yaml
+            .dependencies
+            .iter()
+            .map(|dependency| match dependency {
+                MatchSpecOrSubSection::MatchSpec(spec) => spec
+                    .name
+                    .as_exact()
+                    .expect("locked packages have exact names")
+                    .as_normalized()
+                    .to_string(),
+                MatchSpecOrSubSection::SubSection(name, _) => name.clone(),
+            })
+            .sorted()
+            .collect_vec();
+        assert_eq!(names, vec!["ca-certificates", "openssl"]);
+    }
+
     #[test]
     fn test_export_conda_env_yaml_from_lock_file_unknown_platform() {
         let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
@@ -795,6 +917,7 @@ mod tests {
             &environment,
             &lock_file,
             environment.name().as_str().to_string(),
+            None,
         );
         assert!(result.is_err());
     }
@@ -813,6 +936,7 @@ mod tests {
             config_source: Default::default(),
             name: Some(env_name.clone()),
             from_lock_file: false,
+            packages: None,
         };
         let environment = workspace
             .environment_from_name_or_env_var(args.environment)
@@ -824,6 +948,7 @@ mod tests {
             &environment,
             workspace.config().global_channel_config(),
             env_name,
+            None,
         );
         insta::assert_snapshot!(
             "test_export_conda_env_yaml_custom_name",
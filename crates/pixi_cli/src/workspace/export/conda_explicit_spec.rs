@@ -8,12 +8,27 @@ use miette::{Context, IntoDiagnostic};
 use pixi_config::ConfigCli;
 use pixi_core::{WorkspaceLocator, lock_file::UpdateLockFileOptions};
 use rattler_conda_types::{
-    ExplicitEnvironmentEntry, ExplicitEnvironmentSpec, PackageRecord, Platform, RepoDataRecord,
+    ExplicitEnvironmentEntry, ExplicitEnvironmentSpec, PackageName, PackageRecord, Platform,
+    RepoDataRecord,
 };
 use rattler_lock::{CondaPackageData, Environment, LockedPackage};
+use serde::Serialize;
+use url::Url;
 
+use super::select_packages;
 use crate::cli_config::{LockFileUpdateConfig, NoInstallConfig, WorkspaceConfig};
 
+/// The format of the rendered explicit environment spec files.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExplicitSpecFormat {
+    /// The `@EXPLICIT` text format understood by conda.
+    #[default]
+    Text,
+    /// A JSON file with the url, name, version, build and hashes of every
+    /// package.
+    Json,
+}
+
 #[derive(Debug, Parser)]
 #[clap(arg_required_else_help = false)]
 pub struct Args {
@@ -43,6 +58,15 @@ pub struct Args {
     #[arg(long, default_value = "false")]
     pub ignore_source_errors: bool,
 
+    /// The format of the rendered files.
+    #[arg(long, value_enum, default_value_t = ExplicitSpecFormat::Text)]
+    pub format: ExplicitSpecFormat,
+
+    /// Only export the given packages and their dependencies. Defaults to all
+    /// packages in the environment.
+    #[arg(long, num_args = 1.., value_name = "NAME")]
+    pub packages: Option<Vec<PackageName>>,
+
     #[clap(flatten)]
     pub lock_file_update_config: LockFileUpdateConfig,
 
@@ -61,13 +85,19 @@ fn build_explicit_spec<'a>(
 
     for cp in conda_packages {
         let prec = &cp.package_record;
-        let hash = prec.md5.ok_or(miette::miette!(
-            "Package {} does not contain an md5 hash",
-            prec.name.as_normalized()
-        ))?;
+        // Prefer the sha256 hash, conda understands both `#sha256:<hash>` and
+        // `#<md5>` fragments.
+        let fragment = match (prec.sha256, prec.md5) {
+            (Some(sha256), _) => format!("sha256:{sha256:x}"),
+            (None, Some(md5)) => format!("{md5:x}"),
+            (None, None) => miette::bail!(
+                "Package {} does not contain a sha256 or md5 hash",
+                prec.name.as_normalized()
+            ),
+        };
 
         let mut url = cp.url.clone();
-        url.set_fragment(Some(&format!("{hash:x}")));
+        url.set_fragment(Some(&fragment));
 
         packages.push(ExplicitEnvironmentEntry {
             url: url.to_owned(),
@@ -80,6 +110,29 @@ fn build_explicit_spec<'a>(
     })
 }
 
+/// Removes virtual packages, like `__glibc` or `__cuda`, from the records.
+/// They are provided by the system and cannot be downloaded, so they have no
+/// place in an explicit spec.
+fn without_virtual_packages(
+    records: Vec<RepoDataRecord>,
+    env_name: &str,
+    platform: &Platform,
+) -> Vec<RepoDataRecord> {
+    records
+        .into_iter()
+        .filter(|record| {
+            let name = record.package_record.name.as_normalized();
+            let is_virtual = name.starts_with("__");
+            if is_virtual {
+                tracing::info!(
+                    "skipping virtual package {name} in the explicit spec of env: {env_name} platform: {platform}"
+                );
+            }
+            !is_virtual
+        })
+        .collect()
+}
+
 fn render_explicit_spec(
     target: impl AsRef<Path>,
     exp_env_spec: &ExplicitEnvironmentSpec,
@@ -101,12 +154,76 @@ fn render_explicit_spec(
     Ok(())
 }
 
+/// An explicit environment spec in the JSON format.
+#[derive(Debug, Serialize)]
+struct JsonExplicitSpec {
+    platform: Platform,
+    packages: Vec<JsonExplicitSpecEntry>,
+}
+
+/// A single package of a [`JsonExplicitSpec`].
+#[derive(Debug, Serialize)]
+struct JsonExplicitSpecEntry {
+    url: Url,
+    name: String,
+    version: String,
+    build: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+}
+
+fn build_json_explicit_spec<'a>(
+    platform: &Platform,
+    conda_packages: impl IntoIterator<Item = &'a RepoDataRecord>,
+) -> JsonExplicitSpec {
+    let packages = conda_packages
+        .into_iter()
+        .map(|cp| {
+            let prec = &cp.package_record;
+            JsonExplicitSpecEntry {
+                url: cp.url.clone(),
+                name: prec.name.as_normalized().to_string(),
+                version: prec.version.to_string(),
+                build: prec.build.clone(),
+                md5: prec.md5.map(|hash| format!("{hash:x}")),
+                sha256: prec.sha256.map(|hash| format!("{hash:x}")),
+            }
+        })
+        .collect();
+
+    JsonExplicitSpec {
+        platform: *platform,
+        packages,
+    }
+}
+
+fn render_json_explicit_spec(
+    target: impl AsRef<Path>,
+    spec: &JsonExplicitSpec,
+) -> miette::Result<()> {
+    if spec.packages.is_empty() {
+        return Ok(());
+    }
+
+    let target = target.as_ref();
+    let contents = serde_json::to_string_pretty(spec).into_diagnostic()?;
+    fs_err::write(target, contents)
+        .into_diagnostic()
+        .with_context(|| format!("failed to write environment file: {}", target.display()))?;
+
+    Ok(())
+}
+
 fn render_env_platform(
     output_dir: &Path,
     env_name: &str,
     env: &Environment,
     platform: &Platform,
     ignore_pypi_errors: bool,
+    format: ExplicitSpecFormat,
+    packages: Option<&[PackageName]>,
 ) -> miette::Result<()> {
     let lock_platform = env
         .lock_file()
@@ -159,16 +276,34 @@ fn render_env_platform(
         .into_diagnostic()
         .with_context(|| "Failed to convert conda packages to RepoDataRecords")?;
 
-    let repodata = PackageRecord::sort_topologically(repodata);
+    let repodata = without_virtual_packages(repodata, env_name, platform);
 
-    let ees = build_explicit_spec(platform, &repodata)?;
+    let repodata = match packages {
+        Some(roots) => {
+            let selected = select_packages(repodata.iter().map(|r| &r.package_record), roots)?;
+            repodata
+                .into_iter()
+                .filter(|record| selected.contains(&record.package_record.name))
+                .collect()
+        }
+        None => repodata,
+    };
 
-    tracing::info!("Creating conda explicit spec for env: {env_name} platform: {platform}");
-    let target = output_dir
-        .join(format!("{env_name}_{platform}_conda_spec.txt"))
-        .into_os_string();
+    let repodata = PackageRecord::sort_topologically(repodata);
 
-    render_explicit_spec(target, &ees)?;
+    tracing::info!("Creating conda explicit spec for env: {env_name} platform: {platform}");
+    match format {
+        ExplicitSpecFormat::Text => {
+            let ees = build_explicit_spec(platform, &repodata)?;
+            let target = output_dir.join(format!("{env_name}_{platform}_conda_spec.txt"));
+            render_explicit_spec(target, &ees)?;
+        }
+        ExplicitSpecFormat::Json => {
+            let spec = build_json_explicit_spec(platform, &repodata);
+            let target = output_dir.join(format!("{env_name}_{platform}_conda_spec.json"));
+            render_json_explicit_spec(target, &spec)?;
+        }
+    }
 
     Ok(())
 }
@@ -243,6 +378,8 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             &env,
             &plat,
             args.ignore_pypi_errors,
+            args.format,
+            args.packages.as_deref(),
         )?;
     }
 
@@ -253,6 +390,7 @@ pub async fn execute(args: Args) -> miette::Result<()> {
 mod tests {
     use std::path::Path;
 
+    use itertools::Itertools;
     use rattler_lock::LockFile;
     use tempfile::tempdir;
 
@@ -272,10 +410,27 @@ mod tests {
                 // example contains pypi dependencies so should fail if `ignore_pypi_errors` is
                 // false.
                 assert!(
-                    render_env_platform(output_dir.path(), env_name, &env, &platform, false)
-                        .is_err()
+                    render_env_platform(
+                        output_dir.path(),
+                        env_name,
+                        &env,
+                        &platform,
+                        false,
+                        ExplicitSpecFormat::Text,
+                        None,
+                    )
+                    .is_err()
                 );
-                render_env_platform(output_dir.path(), env_name, &env, &platform, true).unwrap();
+                render_env_platform(
+                    output_dir.path(),
+                    env_name,
+                    &env,
+                    &platform,
+                    true,
+                    ExplicitSpecFormat::Text,
+                    None,
+                )
+                .unwrap();
 
                 let file_path = output_dir
                     .path()
@@ -287,4 +442,119 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_render_conda_explicit_spec_json() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.lock");
+        let lock_file = LockFile::from_path(&path).unwrap();
+        let env = lock_file.environment("default").unwrap();
+
+        let output_dir = tempdir().unwrap();
+        render_env_platform(
+            output_dir.path(),
+            "default",
+            &env,
+            &Platform::OsxArm64,
+            true,
+            ExplicitSpecFormat::Json,
+            None,
+        )
+        .unwrap();
+
+        let file_path = output_dir.path().join("default_osx-arm64_conda_spec.json");
+        insta::assert_snapshot!(fs_err::read_to_string(file_path).unwrap());
+    }
+
+    #[test]
+    fn test_explicit_spec_without_md5() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.lock");
+        let lock_file = LockFile::from_path(&path).unwrap();
+        let env = lock_file.environment("default").unwrap();
+        let lock_platform = lock_file.platform("osx-arm64").unwrap();
+
+        let mut record = env
+            .conda_repodata_records(lock_platform)
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+        record.package_record.md5 = None;
+        let sha256 = record.package_record.sha256.unwrap();
+
+        let spec = build_explicit_spec(&Platform::OsxArm64, [&record]).unwrap();
+        assert_eq!(
+            spec.packages[0].url.fragment(),
+            Some(format!("sha256:{sha256:x}").as_str())
+        );
+
+        record.package_record.sha256 = None;
+        assert!(build_explicit_spec(&Platform::OsxArm64, [&record]).is_err());
+    }
+
+    #[test]
+    fn test_select_packages() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.lock");
+        let lock_file = LockFile::from_path(&path).unwrap();
+        let env = lock_file.environment("default").unwrap();
+        let lock_platform = lock_file.platform("osx-arm64").unwrap();
+        let records = env.conda_repodata_records(lock_platform).unwrap().unwrap();
+
+        let records = records.iter().map(|record| &record.package_record);
+
+        let selected =
+            select_packages(records.clone(), &[PackageName::new_unchecked("openssl")]).unwrap();
+        let names = selected
+            .iter()
+            .map(PackageName::as_normalized)
+            .sorted()
+            .collect_vec();
+        assert_eq!(names, vec!["ca-certificates", "openssl"]);
+
+        let err =
+            select_packages(records, &[PackageName::new_unchecked("does-not-exist")]).unwrap_err();
+        assert!(err.to_string().contains("'does-not-exist'"));
+    }
+
+    #[test]
+    fn test_explicit_spec_skips_virtual_packages() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.lock");
+        let lock_file = LockFile::from_path(&path).unwrap();
+        let env = lock_file.environment("default").unwrap();
+        let lock_platform = lock_file.platform("osx-arm64").unwrap();
+        let mut records = env.conda_repodata_records(lock_platform).unwrap().unwrap();
+        let package_count = records.len();
+
+        // Virtual packages are never downloaded, so they carry no hashes.
+        let mut virtual_package = records[0].clone();
+        virtual_package.package_record.name = "__osx".parse().unwrap();
+        virtual_package.package_record.md5 = None;
+        virtual_package.package_record.sha256 = None;
+        records.push(virtual_package);
+
+        let records = without_virtual_packages(records, "default", &Platform::OsxArm64);
+        assert_eq!(records.len(), package_count);
+
+        let spec = build_explicit_spec(&Platform::OsxArm64, &records).unwrap();
+        assert_eq!(spec.packages.len(), package_count);
+
+        // Noarch packages are referenced by their own url and hash.
+        let noarch = records
+            .iter()
+            .find(|record| record.url.path().contains("/noarch/"))
+            .unwrap();
+        let entry = spec
+            .packages
+            .iter()
+            .find(|entry| entry.url.path() == noarch.url.path())
+            .unwrap();
+        assert_eq!(
+            entry.url.fragment(),
+            Some(format!("sha256:{:x}", noarch.package_record.sha256.unwrap()).as_str())
+        );
+    }
 }
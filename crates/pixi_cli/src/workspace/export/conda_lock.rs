@@ -0,0 +1,402 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+use pep508_rs::VersionOrUrl;
+use pixi_config::ConfigCli;
+use pixi_core::{WorkspaceLocator, lock_file::UpdateLockFileOptions};
+use rattler_conda_types::{Platform, RepoDataRecord};
+use rattler_lock::{
+    CondaPackageData, Environment, LockedPackage, PackageHashes, PypiPackageData, UrlOrPath,
+};
+use serde::Serialize;
+use url::Url;
+
+use crate::cli_config::{LockFileUpdateConfig, NoInstallConfig, WorkspaceConfig};
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// Explicit path to export the lock file to.
+    /// Defaults to `conda-lock.yml` in the current directory.
+    pub output_path: Option<PathBuf>,
+
+    /// The environment to export. Defaults to the default environment.
+    #[arg(short, long)]
+    pub environment: Option<String>,
+
+    /// The platform to export. Can be repeated for multiple platforms.
+    /// Defaults to all platforms the environment is locked for.
+    #[arg(short, long)]
+    pub platform: Option<Vec<Platform>>,
+
+    #[clap(flatten)]
+    pub lock_file_update_config: LockFileUpdateConfig,
+
+    #[clap(flatten)]
+    pub no_install_config: NoInstallConfig,
+
+    #[clap(flatten)]
+    config: ConfigCli,
+}
+
+/// A lock file in the conda-lock v1 format.
+#[derive(Debug, Serialize)]
+struct CondaLock {
+    version: u32,
+    metadata: CondaLockMetadata,
+    package: Vec<CondaLockPackage>,
+}
+
+#[derive(Debug, Serialize)]
+struct CondaLockMetadata {
+    content_hash: BTreeMap<Platform, String>,
+    channels: Vec<CondaLockChannel>,
+    platforms: Vec<Platform>,
+    sources: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CondaLockChannel {
+    url: String,
+    used_env_vars: Vec<String>,
+}
+
+/// The package manager that installs a [`CondaLockPackage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Manager {
+    Conda,
+    Pip,
+}
+
+/// A single locked package for a single platform.
+#[derive(Debug, Serialize)]
+struct CondaLockPackage {
+    name: String,
+    version: String,
+    manager: Manager,
+    platform: Platform,
+    dependencies: BTreeMap<String, String>,
+    url: Url,
+    hash: CondaLockHash,
+    category: String,
+    optional: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CondaLockHash {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+}
+
+impl From<&PackageHashes> for CondaLockHash {
+    fn from(hashes: &PackageHashes) -> Self {
+        match hashes {
+            PackageHashes::Md5(md5) => CondaLockHash {
+                md5: Some(format!("{md5:x}")),
+                sha256: None,
+            },
+            PackageHashes::Sha256(sha256) => CondaLockHash {
+                md5: None,
+                sha256: Some(format!("{sha256:x}")),
+            },
+            PackageHashes::Md5Sha256(md5, sha256) => CondaLockHash {
+                md5: Some(format!("{md5:x}")),
+                sha256: Some(format!("{sha256:x}")),
+            },
+        }
+    }
+}
+
+fn conda_package(record: &RepoDataRecord, platform: Platform) -> CondaLockPackage {
+    let prec = &record.package_record;
+    // conda-lock stores the dependencies of a package as a map of the package
+    // name to its constraint.
+    let dependencies = prec
+        .depends
+        .iter()
+        .map(|dep| match dep.split_once(' ') {
+            Some((name, constraint)) => (name.to_string(), constraint.trim().to_string()),
+            None => (dep.to_string(), "*".to_string()),
+        })
+        .collect();
+
+    CondaLockPackage {
+        name: prec.name.as_normalized().to_string(),
+        version: prec.version.to_string(),
+        manager: Manager::Conda,
+        platform,
+        dependencies,
+        url: record.url.clone(),
+        hash: CondaLockHash {
+            md5: prec.md5.map(|hash| format!("{hash:x}")),
+            sha256: prec.sha256.map(|hash| format!("{hash:x}")),
+        },
+        category: "main".to_string(),
+        optional: false,
+    }
+}
+
+/// Converts a locked PyPI package to a `pip` entry. conda-lock only supports
+/// PyPI packages that are downloaded from a url, `None` is returned for
+/// packages that are installed from a local path.
+fn pip_package(pypi: &PypiPackageData, platform: Platform) -> Option<CondaLockPackage> {
+    let UrlOrPath::Url(url) = pypi.location().inner() else {
+        return None;
+    };
+
+    let dependencies = pypi
+        .requires_dist()
+        .iter()
+        .map(|requirement| {
+            let constraint = match &requirement.version_or_url {
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) if !specifiers.is_empty() => {
+                    specifiers.to_string()
+                }
+                Some(VersionOrUrl::Url(url)) => url.to_string(),
+                _ => "*".to_string(),
+            };
+            (requirement.name.to_string(), constraint)
+        })
+        .collect();
+
+    Some(CondaLockPackage {
+        name: pypi.name().to_string(),
+        version: pypi
+            .version()
+            .map_or_else(|| String::from("*"), ToString::to_string),
+        manager: Manager::Pip,
+        platform,
+        dependencies,
+        url: url.clone(),
+        hash: pypi
+            .as_wheel()
+            .and_then(|wheel| wheel.hash.as_ref())
+            .map(CondaLockHash::from)
+            .unwrap_or_default(),
+        category: "main".to_string(),
+        optional: false,
+    })
+}
+
+fn build_conda_lock(
+    env_name: &str,
+    env: &Environment,
+    platforms: &[Platform],
+    sources: Vec<String>,
+) -> miette::Result<CondaLock> {
+    let mut content_hash = BTreeMap::new();
+    let mut package = Vec::new();
+
+    for platform in platforms {
+        let packages = env
+            .lock_file()
+            .platform(&platform.to_string())
+            .and_then(|lock_platform| env.packages(lock_platform))
+            .ok_or_else(|| miette::miette!("platform '{platform}' not found for env {env_name}"))?;
+
+        let mut platform_packages = Vec::new();
+        for locked_package in packages {
+            match locked_package {
+                LockedPackage::Conda(CondaPackageData::Binary(binary)) => {
+                    let record = RepoDataRecord::try_from(*binary.clone())
+                        .into_diagnostic()
+                        .with_context(|| "Failed to convert conda packages to RepoDataRecords")?;
+                    // Virtual packages are provided by the system.
+                    if record.package_record.name.as_normalized().starts_with("__") {
+                        continue;
+                    }
+                    platform_packages.push(conda_package(&record, *platform));
+                }
+                LockedPackage::Conda(CondaPackageData::Source(source)) => {
+                    tracing::warn!(
+                        "skipping conda source package '{}' since source packages are not supported by conda-lock",
+                        source.name().as_source()
+                    );
+                }
+                LockedPackage::Pypi(pypi) => match pip_package(pypi, *platform) {
+                    Some(pip) => platform_packages.push(pip),
+                    None => tracing::warn!(
+                        "skipping PyPI package '{}' since conda-lock only supports PyPI packages with a url",
+                        pypi.name()
+                    ),
+                },
+            }
+        }
+
+        // conda-lock uses the content hash to detect changed inputs, pixi
+        // has no equivalent so the hash is derived from the locked packages.
+        let urls = platform_packages
+            .iter()
+            .map(|package| package.url.as_str())
+            .sorted()
+            .join("\n");
+        let hash = rattler_digest::compute_bytes_digest::<rattler_digest::Sha256>(urls);
+        content_hash.insert(*platform, format!("{hash:x}"));
+
+        package.extend(platform_packages);
+    }
+
+    Ok(CondaLock {
+        version: 1,
+        metadata: CondaLockMetadata {
+            content_hash,
+            channels: env
+                .channels()
+                .iter()
+                .map(|channel| CondaLockChannel {
+                    url: channel.url.clone(),
+                    used_env_vars: channel.used_env_vars.clone(),
+                })
+                .collect(),
+            platforms: platforms.to_vec(),
+            sources,
+        },
+        package,
+    })
+}
+
+fn render_conda_lock(target: &Path, conda_lock: &CondaLock) -> miette::Result<()> {
+    let mut contents = String::from("# Generated by `pixi workspace export conda-lock`\n");
+    contents.push_str(&serde_yaml::to_string(conda_lock).into_diagnostic()?);
+    fs_err::write(target, contents)
+        .into_diagnostic()
+        .with_context(|| format!("failed to write lock file: {}", target.display()))
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?
+        .with_cli_config(args.config.clone());
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let env_name = environment.name().as_str();
+
+    let lock_file = workspace
+        .update_lock_file(
+            Some(pixi_reporters::TopLevelProgress::from_global()),
+            UpdateLockFileOptions {
+                lock_file_usage: args.lock_file_update_config.lock_file_usage()?,
+                no_install: args.no_install_config.no_install,
+                max_concurrent_solves: workspace.config().max_concurrent_solves(),
+                ..Default::default()
+            },
+        )
+        .await?
+        .0
+        .into_lock_file();
+    let env = lock_file
+        .environment(env_name)
+        .ok_or_else(|| miette::miette!("environment '{env_name}' not found in the lock file"))?;
+
+    let locked_platforms: HashSet<Platform> = env.platforms().map(|p| p.subdir()).collect();
+    let platforms = match args.platform {
+        Some(platforms) => {
+            for platform in &platforms {
+                if !locked_platforms.contains(platform) {
+                    miette::bail!(
+                        "platform '{platform}' is not locked for environment '{env_name}'"
+                    );
+                }
+            }
+            platforms
+        }
+        None => locked_platforms.into_iter().sorted().collect(),
+    };
+
+    let sources = workspace
+        .workspace
+        .provenance
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .into_iter()
+        .collect();
+
+    let conda_lock = build_conda_lock(env_name, &env, &platforms, sources)?;
+    let target = args
+        .output_path
+        .unwrap_or_else(|| PathBuf::from("conda-lock.yml"));
+    render_conda_lock(&target, &conda_lock)?;
+
+    pixi_progress::println_status!(
+        "{}Exported the {} environment to {}",
+        console::style(console::Emoji("✔ ", "")).green(),
+        env_name,
+        target.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use rattler_lock::LockFile;
+
+    use super::*;
+
+    #[test]
+    fn test_build_conda_lock() {
+        let path = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export/pixi.lock");
+        let lock_file = LockFile::from_path(&path).unwrap();
+        let env = lock_file.environment("default").unwrap();
+
+        let conda_lock = build_conda_lock(
+            "default",
+            &env,
+            &[Platform::Linux64],
+            vec!["pixi.toml".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(conda_lock.version, 1);
+        assert_eq!(conda_lock.metadata.platforms, vec![Platform::Linux64]);
+        assert!(
+            conda_lock
+                .metadata
+                .content_hash
+                .contains_key(&Platform::Linux64)
+        );
+
+        let conda_names = conda_lock
+            .package
+            .iter()
+            .filter(|package| package.manager == Manager::Conda)
+            .map(|package| package.name.as_str())
+            .collect_vec();
+        assert!(conda_names.contains(&"python"));
+        assert!(conda_names.contains(&"openssl"));
+        assert!(
+            conda_lock
+                .package
+                .iter()
+                .any(|package| package.manager == Manager::Pip)
+        );
+        assert!(
+            conda_lock
+                .package
+                .iter()
+                .all(|package| package.platform == Platform::Linux64 && package.category == "main")
+        );
+
+        let yaml = serde_yaml::to_string(&conda_lock).unwrap();
+        assert!(yaml.contains("manager: conda"));
+        assert!(yaml.contains("content_hash:"));
+    }
+}
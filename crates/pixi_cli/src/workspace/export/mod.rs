@@ -1,7 +1,13 @@
 pub mod conda_environment;
 pub mod conda_explicit_spec;
+pub mod conda_lock;
+pub mod spack;
+
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use clap::Parser;
+use itertools::Itertools;
+use rattler_conda_types::{MatchSpec, PackageName, PackageRecord, ParseStrictness};
 
 /// Commands to export workspaces to other formats
 #[derive(Parser, Debug)]
@@ -17,12 +23,85 @@ pub enum Command {
     CondaExplicitSpec(conda_explicit_spec::Args),
     /// Export workspace environment to a conda environment.yaml file
     CondaEnvironment(conda_environment::Args),
+    /// Export workspace environment to a conda-lock v1 lock file
+    CondaLock(conda_lock::Args),
+    /// Export workspace environment to a spack environment file
+    Spack(spack::Args),
 }
 
 pub async fn execute(args: Args) -> miette::Result<()> {
     match args.command {
         Command::CondaExplicitSpec(args) => conda_explicit_spec::execute(args).await?,
         Command::CondaEnvironment(args) => conda_environment::execute(args).await?,
+        Command::CondaLock(args) => conda_lock::execute(args).await?,
+        Command::Spack(args) => spack::execute(args).await?,
     };
     Ok(())
 }
+
+/// Returns the names of the given packages and of their transitive
+/// dependencies, as found in the `depends` of the records.
+///
+/// Errors with the list of available packages if one of the given packages is
+/// not among the records.
+pub(crate) fn select_packages<'a>(
+    records: impl IntoIterator<Item = &'a PackageRecord>,
+    roots: &[PackageName],
+) -> miette::Result<HashSet<PackageName>> {
+    let by_name: HashMap<&PackageName, &PackageRecord> = records
+        .into_iter()
+        .map(|record| (&record.name, record))
+        .collect();
+
+    let unknown = roots
+        .iter()
+        .filter(|root| !by_name.contains_key(root))
+        .collect_vec();
+    if !unknown.is_empty() {
+        miette::bail!(
+            help = format!(
+                "available packages are: {}",
+                by_name
+                    .keys()
+                    .map(|name| name.as_normalized())
+                    .sorted()
+                    .join(", ")
+            ),
+            "unknown package(s) {}",
+            unknown
+                .iter()
+                .map(|name| format!("'{}'", name.as_source()))
+                .join(", ")
+        );
+    }
+
+    let mut selected = HashSet::new();
+    let mut queue: VecDeque<&PackageName> = roots.iter().collect();
+    while let Some(name) = queue.pop_front() {
+        if !selected.insert(name.clone()) {
+            continue;
+        }
+        let record = by_name[name];
+        for dependency in &record.depends {
+            match MatchSpec::from_str(dependency, ParseStrictness::Lenient) {
+                Ok(spec) => {
+                    // Only follow dependencies that are part of the records,
+                    // so the queue can borrow their names.
+                    if let Some(name) = spec.name.as_exact()
+                        && let Some((&known, _)) = by_name.get_key_value(name)
+                    {
+                        queue.push_back(known);
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "ignoring the dependency '{dependency}' of '{}': {err}",
+                        record.name.as_source()
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(selected)
+}
@@ -0,0 +1,437 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+use pep508_rs::pep440_rs::{Operator, VersionSpecifiers};
+use pixi_core::{WorkspaceLocator, workspace::Environment};
+use pixi_manifest::{FeaturesExt, HasWorkspaceManifest, PixiPlatform};
+use pixi_pypi_spec::{PixiPypiSource, VersionOrStar};
+use rattler_conda_types::{
+    ChannelConfig, Platform, VersionSpec,
+    version_spec::{EqualityOperator, LogicalOperator, RangeOperator, StrictRangeOperator},
+};
+
+use crate::cli_config::WorkspaceConfig;
+
+#[derive(Debug, Default, Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// Explicit path to export the environment file to.
+    pub output_path: Option<PathBuf>,
+
+    /// The platform to render the environment file for.
+    /// Defaults to the current platform.
+    #[arg(short, long)]
+    pub platform: Option<Platform>,
+
+    /// The environment to render the environment file for.
+    /// Defaults to the default environment.
+    #[arg(short, long)]
+    pub environment: Option<String>,
+}
+
+/// The spack names of conda packages whose spack package is known.
+///
+/// Spack packages of python libraries are prefixed with `py-`, other packages
+/// mostly share their name, but not always.
+const SPACK_PACKAGES: &[(&str, &str)] = &[
+    ("boost", "boost"),
+    ("bzip2", "bzip2"),
+    ("clang", "llvm"),
+    ("cmake", "cmake"),
+    ("cuda-toolkit", "cuda"),
+    ("curl", "curl"),
+    ("cython", "py-cython"),
+    ("eigen", "eigen"),
+    ("fftw", "fftw"),
+    ("gcc", "gcc"),
+    ("gfortran", "gcc"),
+    ("git", "git"),
+    ("gxx", "gcc"),
+    ("h5py", "py-h5py"),
+    ("hdf5", "hdf5"),
+    ("libboost-devel", "boost"),
+    ("libcurl", "curl"),
+    ("libnetcdf", "netcdf-c"),
+    ("make", "gmake"),
+    ("matplotlib", "py-matplotlib"),
+    ("mpi4py", "py-mpi4py"),
+    ("mpich", "mpich"),
+    ("netcdf-fortran", "netcdf-fortran"),
+    ("ninja", "ninja"),
+    ("nodejs", "node-js"),
+    ("numpy", "py-numpy"),
+    ("openblas", "openblas"),
+    ("openmpi", "openmpi"),
+    ("openssl", "openssl"),
+    ("pandas", "py-pandas"),
+    ("perl", "perl"),
+    ("pip", "py-pip"),
+    ("pkg-config", "pkgconf"),
+    ("pytest", "py-pytest"),
+    ("python", "python"),
+    ("r-base", "r"),
+    ("rust", "rust"),
+    ("scipy", "py-scipy"),
+    ("setuptools", "py-setuptools"),
+    ("sqlite", "sqlite"),
+    ("xz", "xz"),
+    ("zlib", "zlib"),
+    ("zstd", "zstd"),
+];
+
+/// Returns the name of the spack package of a conda package, if it is known.
+fn spack_package_name(conda_name: &str) -> Option<&'static str> {
+    SPACK_PACKAGES
+        .iter()
+        .find(|(name, _)| *name == conda_name)
+        .map(|(_, spack_name)| *spack_name)
+}
+
+/// A bound of a version constraint.
+enum Bound {
+    /// Exactly this version.
+    Exact(String),
+    /// This version or any version that starts with it.
+    Prefix(String),
+    /// This version or higher.
+    Lower(String),
+    /// This version, any version that starts with it, or lower.
+    Upper(String),
+}
+
+/// Formats the bounds of a single version range in spack syntax, e.g. `1.2:2`.
+/// Returns `None` if the bounds don't form a range spack can express.
+fn format_range(bounds: Vec<Bound>) -> Option<String> {
+    let (mut lower, mut upper) = (None, None);
+    for bound in bounds {
+        match bound {
+            Bound::Exact(version) if lower.is_none() && upper.is_none() => {
+                return Some(format!("={version}"));
+            }
+            Bound::Prefix(version) if lower.is_none() && upper.is_none() => {
+                return Some(version);
+            }
+            Bound::Lower(version) if lower.is_none() => lower = Some(version),
+            Bound::Upper(version) if upper.is_none() => upper = Some(version),
+            _ => return None,
+        }
+    }
+    Some(format!(
+        "{}:{}",
+        lower.unwrap_or_default(),
+        upper.unwrap_or_default()
+    ))
+}
+
+/// Spack version ranges are inclusive, and an upper bound includes every
+/// version that starts with it. So `<1.27` is `:1.26` in spack, and `<2.0` is
+/// `:1`. Returns `None` for versions that cannot be decremented this way.
+fn exclusive_upper_bound(version: &str) -> Option<String> {
+    let mut segments = version
+        .split('.')
+        .map(|segment| segment.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    while segments.last() == Some(&0) {
+        segments.pop();
+    }
+    let last = segments.last_mut()?;
+    *last -= 1;
+    Some(segments.iter().join("."))
+}
+
+/// Converts a conda version spec to a spack version constraint.
+fn conda_versions(spec: &VersionSpec) -> Option<String> {
+    fn bounds(spec: &VersionSpec) -> Option<Vec<Bound>> {
+        Some(match spec {
+            VersionSpec::Exact(EqualityOperator::Equals, version) => {
+                vec![Bound::Exact(version.to_string())]
+            }
+            VersionSpec::StrictRange(StrictRangeOperator::StartsWith, version) => {
+                vec![Bound::Prefix(version.to_string())]
+            }
+            VersionSpec::Range(RangeOperator::GreaterEquals, version) => {
+                vec![Bound::Lower(version.to_string())]
+            }
+            VersionSpec::Range(RangeOperator::LessEquals, version) => {
+                vec![Bound::Upper(version.to_string())]
+            }
+            VersionSpec::Range(RangeOperator::Less, version) => {
+                vec![Bound::Upper(exclusive_upper_bound(&version.to_string())?)]
+            }
+            VersionSpec::Group(LogicalOperator::And, specs) => specs
+                .iter()
+                .map(bounds)
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+            _ => return None,
+        })
+    }
+
+    match spec {
+        VersionSpec::Any => Some(String::new()),
+        VersionSpec::Group(LogicalOperator::Or, specs) => specs
+            .iter()
+            .map(|spec| bounds(spec).and_then(format_range))
+            .collect::<Option<Vec<_>>>()
+            .map(|ranges| ranges.join(",")),
+        spec => bounds(spec).and_then(format_range),
+    }
+}
+
+/// Converts the version specifiers of a PyPI requirement to a spack version
+/// constraint.
+fn pypi_versions(specifiers: &VersionSpecifiers) -> Option<String> {
+    let bounds = specifiers
+        .iter()
+        .map(|specifier| {
+            let version = specifier.version().to_string();
+            Some(match specifier.operator() {
+                Operator::Equal | Operator::ExactEqual => Bound::Exact(version),
+                Operator::EqualStar => Bound::Prefix(version),
+                Operator::GreaterThanEqual => Bound::Lower(version),
+                Operator::LessThanEqual => Bound::Upper(version),
+                Operator::LessThan => Bound::Upper(exclusive_upper_bound(&version)?),
+                _ => return None,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if bounds.is_empty() {
+        return Some(String::new());
+    }
+    format_range(bounds)
+}
+
+/// Formats a spack spec from a package name and version constraint.
+fn spack_spec(name: &str, versions: Option<String>) -> String {
+    match versions {
+        Some(versions) if !versions.is_empty() => format!("{name}@{versions}"),
+        _ => name.to_string(),
+    }
+}
+
+/// A spack environment with the specs of a pixi environment.
+#[derive(Debug, Default)]
+struct SpackEnvironment {
+    /// A comment that describes where the environment came from.
+    header: String,
+    specs: Vec<String>,
+}
+
+impl SpackEnvironment {
+    /// Adds a spec, unless the environment already has a spec for the same
+    /// spack package.
+    fn push(&mut self, spec: String) {
+        let name = spec.split('@').next().unwrap_or_default();
+        if !self
+            .specs
+            .iter()
+            .any(|existing| existing.split('@').next() == Some(name))
+        {
+            self.specs.push(spec);
+        }
+    }
+
+    /// Renders the environment as a `spack.yaml` file.
+    fn to_yaml_string(&self) -> String {
+        let mut yaml = format!("# {}\nspack:\n  specs:", self.header);
+        if self.specs.is_empty() {
+            yaml.push_str(" []");
+        }
+        for spec in &self.specs {
+            yaml.push_str(&format!("\n  - \"{spec}\""));
+        }
+        yaml.push_str("\n  view: true\n  concretizer:\n    unify: true\n");
+        yaml
+    }
+}
+
+fn build_spack_env(
+    platform: &PixiPlatform,
+    environment: &Environment,
+    config: &ChannelConfig,
+) -> miette::Result<SpackEnvironment> {
+    let mut spack_env = SpackEnvironment {
+        header: format!(
+            "Generated by pixi from the '{}' environment for {}",
+            environment.name(),
+            platform.name()
+        ),
+        ..SpackEnvironment::default()
+    };
+
+    for (name, pixi_spec) in environment
+        .combined_dependencies(Some(platform))
+        .into_specs()
+    {
+        let Some(spack_name) = spack_package_name(name.as_normalized()) else {
+            tracing::warn!(
+                "'{}' has no known spack equivalent. Skipping dependency",
+                name.as_source()
+            );
+            continue;
+        };
+        let Some(spec) = pixi_spec
+            .try_into_nameless_match_spec(config)
+            .into_diagnostic()?
+        else {
+            tracing::warn!(
+                "'{}' is a source dependency, which cannot be exported to spack. Skipping dependency",
+                name.as_source()
+            );
+            continue;
+        };
+        let versions = spec
+            .version
+            .as_ref()
+            .map_or(Some(String::new()), conda_versions);
+        if versions.is_none() {
+            tracing::warn!(
+                "The version of '{}' cannot be expressed in spack. Exporting it without a version",
+                name.as_source()
+            );
+        }
+        spack_env.push(spack_spec(spack_name, versions));
+    }
+
+    for (name, requirement) in environment.pypi_dependencies(Some(platform)).into_specs() {
+        let PixiPypiSource::Registry { version, .. } = &requirement.source else {
+            tracing::warn!(
+                "'{}' is not installed from a registry, which cannot be exported to spack. Skipping dependency",
+                name.as_source()
+            );
+            continue;
+        };
+        let versions = match version {
+            VersionOrStar::Star => Some(String::new()),
+            VersionOrStar::Version(specifiers) => pypi_versions(specifiers),
+        };
+        if versions.is_none() {
+            tracing::warn!(
+                "The version of '{}' cannot be expressed in spack. Exporting it without a version",
+                name.as_source()
+            );
+        }
+        spack_env.push(spack_spec(
+            &format!("py-{}", name.as_normalized()),
+            versions,
+        ));
+    }
+
+    Ok(spack_env)
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let workspace = WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .locate()?;
+    let environment = workspace.environment_from_name_or_env_var(args.environment)?;
+    let platform = match args.platform {
+        Some(subdir) => (&workspace)
+            .workspace_manifest()
+            .workspace
+            .platforms
+            .iter()
+            .find(|p| p.subdir() == subdir)
+            .cloned()
+            .ok_or_else(|| {
+                miette::miette!("workspace does not define a platform with subdir '{subdir}'")
+            })?,
+        None => environment
+            .best_declared_platform()
+            .cloned()
+            .ok_or_else(|| {
+                miette::miette!(
+                    "no platform supported by environment '{}' matches the current system",
+                    environment.name()
+                )
+            })?,
+    };
+
+    let spack_env = build_spack_env(
+        &platform,
+        &environment,
+        workspace.config().global_channel_config(),
+    )?;
+
+    if let Some(output_path) = args.output_path {
+        fs_err::write(&output_path, spack_env.to_yaml_string())
+            .into_diagnostic()
+            .with_context(|| "failed to write spack environment")?;
+    } else {
+        print!("{}", spack_env.to_yaml_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, str::FromStr};
+
+    use pixi_core::Workspace;
+    use rattler_conda_types::ParseStrictness;
+
+    use super::*;
+
+    #[test]
+    fn test_export_spack_env() {
+        let toml = r#"
+        [workspace]
+        name = "spack"
+        channels = ["conda-forge"]
+        platforms = ["linux-64"]
+
+        [dependencies]
+        python = "3.12.*"
+        numpy = ">=1.26,<2"
+        cmake = ">=3.20"
+        openmpi = "==4.1.6"
+        some-conda-only-package = "*"
+
+        [pypi-dependencies]
+        rich = ">=13.8.0, <14"
+        local-package = { path = "./local" }
+        "#;
+        let workspace = Workspace::from_str(Path::new("pixi.toml"), toml).unwrap();
+        let environment = workspace.default_environment();
+        let platform = environment.best_declared_platform().cloned().unwrap();
+
+        let spack_env = build_spack_env(
+            &platform,
+            &environment,
+            workspace.config().global_channel_config(),
+        )
+        .unwrap();
+        insta::assert_snapshot!("test_export_spack_env", spack_env.to_yaml_string());
+    }
+
+    #[test]
+    fn test_spack_versions() {
+        let conda = |spec: &str| {
+            conda_versions(&VersionSpec::from_str(spec, ParseStrictness::Lenient).unwrap())
+        };
+        assert_eq!(conda("*").as_deref(), Some(""));
+        assert_eq!(conda("3.12.*").as_deref(), Some("3.12"));
+        assert_eq!(conda("==1.2.3").as_deref(), Some("=1.2.3"));
+        assert_eq!(conda(">=1.2,<=2").as_deref(), Some("1.2:2"));
+        assert_eq!(conda(">=1.26,<2.0").as_deref(), Some("1.26:1"));
+        assert_eq!(conda("<1.27").as_deref(), Some(":1.26"));
+        assert_eq!(conda("1.2.*|>=2").as_deref(), Some("1.2,2:"));
+        assert_eq!(conda(">1.2"), None);
+
+        let pypi = |spec: &str| pypi_versions(&VersionSpecifiers::from_str(spec).unwrap());
+        assert_eq!(pypi(">=13.8.0, <14").as_deref(), Some("13.8.0:13"));
+        assert_eq!(pypi("==1.0.*").as_deref(), Some("1.0"));
+        assert_eq!(pypi("~=1.0"), None);
+    }
+}
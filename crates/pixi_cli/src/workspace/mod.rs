@@ -11,6 +11,7 @@ pub mod name;
 pub mod platform;
 pub mod register;
 pub mod requires_pixi;
+pub mod validate;
 pub mod version;
 
 #[derive(Debug, Parser)]
@@ -25,6 +26,7 @@ pub enum Command {
     Name(name::Args),
     Register(register::Args),
     RequiresPixi(requires_pixi::Args),
+    Validate(validate::Args),
 }
 
 /// Modify the workspace configuration file through the command line.
@@ -49,6 +51,7 @@ pub async fn execute(cmd: Args) -> miette::Result<()> {
         Command::Name(args) => name::execute(args).await?,
         Command::Register(args) => register::execute(args).await?,
         Command::RequiresPixi(args) => requires_pixi::execute(args).await?,
+        Command::Validate(args) => validate::execute(args).await?,
     };
     Ok(())
 }
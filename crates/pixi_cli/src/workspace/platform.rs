@@ -251,18 +251,29 @@ fn parse_add_positional(input: &str) -> miette::Result<(PixiPlatformName, Platfo
         let name = PixiPlatformName::try_from(name)
             .into_diagnostic()
             .map_err(|e| miette::miette!("invalid platform name '{name}': {e}"))?;
-        let subdir = Platform::from_str(subdir)
-            .into_diagnostic()
-            .map_err(|e| miette::miette!("'{subdir}' is not a valid conda subdir: {e}"))?;
-        Ok((name, subdir))
+        Ok((name, parse_subdir(subdir)?))
     } else {
-        let subdir = Platform::from_str(input)
-            .into_diagnostic()
-            .map_err(|e| miette::miette!("'{input}' is not a valid conda subdir: {e}"))?;
+        let subdir = parse_subdir(input)?;
         Ok((subdir.into(), subdir))
     }
 }
 
+/// Parse a conda subdir, listing the known subdirs when it is not valid.
+fn parse_subdir(subdir: &str) -> miette::Result<Platform> {
+    Platform::from_str(subdir).map_err(|e| {
+        miette::miette!(
+            help = format!(
+                "valid subdirs are: {}",
+                Platform::all()
+                    .map(|platform| platform.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            "'{subdir}' is not a valid conda subdir: {e}"
+        )
+    })
+}
+
 #[derive(Parser, Debug, Default)]
 pub struct AddArgs {
     /// Platforms to add, optionally followed by raw virtual-package specs.
@@ -1035,4 +1046,23 @@ mod tests {
             .unwrap_err();
         assert!(err.to_string().contains("more than once"), "{err}");
     }
+
+    #[test]
+    fn parse_add_positional_lists_valid_subdirs() {
+        let (name, subdir) = parse_add_positional("gpu-linux=linux-64").unwrap();
+        assert_eq!(name.as_str(), "gpu-linux");
+        assert_eq!(subdir, Platform::Linux64);
+
+        let err = parse_add_positional("linux-65").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'linux-65' is not a valid conda subdir"),
+            "{err}"
+        );
+        let help = err.help().unwrap().to_string();
+        assert!(
+            help.contains("linux-64") && help.contains("osx-arm64"),
+            "{help}"
+        );
+    }
 }
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use miette::IntoDiagnostic;
+use pixi_core::{WorkspaceLocator, WorkspaceLocatorError};
+use pixi_manifest::{AssociateProvenance, ManifestProvenance};
+
+use crate::cli_config::WorkspaceConfig;
+
+/// Validate the workspace manifest and report all the problems that are found.
+///
+/// Exits with a non-zero exit code if the manifest contains any error.
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(flatten)]
+    pub config_source: pixi_config::ConfigSourceCli,
+
+    #[clap(flatten)]
+    pub workspace_config: WorkspaceConfig,
+
+    /// Also report dependencies that are defined more than once, e.g. both as
+    /// a conda and a PyPI dependency, as errors.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    // Use the regular discovery to find the manifest. If the manifest itself
+    // fails to parse we still know which file to validate.
+    let manifest_path = match WorkspaceLocator::for_cli()
+        .with_global_config_source(args.config_source.source())
+        .with_search_start(args.workspace_config.workspace_locator_start())
+        .with_emit_warnings(false)
+        .locate()
+    {
+        Ok(workspace) => workspace.workspace.provenance.path,
+        Err(WorkspaceLocatorError::Toml(err)) => PathBuf::from(err.source.name()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let provenance = ManifestProvenance::from_path(manifest_path.clone()).into_diagnostic()?;
+    let contents = provenance.read().into_diagnostic()?.into_inner();
+    pixi_manifest::validate_manifest(contents.with_provenance(provenance), args.strict)?;
+
+    eprintln!(
+        "{}Manifest {} is valid",
+        console::style(console::Emoji("✔ ", "")).green(),
+        manifest_path.display()
+    );
+    Ok(())
+}
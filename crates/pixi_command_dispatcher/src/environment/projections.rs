@@ -101,6 +101,7 @@ mod tests {
     fn spec_with_channels(channels: Vec<ChannelUrl>) -> EnvironmentSpec {
         EnvironmentSpec {
             channels,
+            channel_subdirs: Default::default(),
             build_environment: BuildEnvironment {
                 host_platform: Platform::Linux64,
                 host_virtual_packages: Vec::new(),
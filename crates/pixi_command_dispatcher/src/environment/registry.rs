@@ -128,6 +128,7 @@ mod tests {
     fn empty_spec() -> EnvironmentSpec {
         EnvironmentSpec {
             channels: Vec::new(),
+            channel_subdirs: Default::default(),
             build_environment: BuildEnvironment {
                 host_platform: Platform::Linux64,
                 host_virtual_packages: Vec::new(),
@@ -1,8 +1,11 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
 
 use pixi_spec::ResolvedExcludeNewer;
 use pixi_utils::variants::VariantConfig;
-use rattler_conda_types::ChannelUrl;
+use rattler_conda_types::{ChannelUrl, Platform};
 use rattler_solve::ChannelPriority;
 
 use crate::BuildEnvironment;
@@ -22,6 +25,10 @@ use crate::BuildEnvironment;
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EnvironmentSpec {
     pub channels: Vec<ChannelUrl>,
+    /// The subdirs to fetch for the channels that restrict them. Channels
+    /// that are not in the map are fetched for the target platform and
+    /// `noarch`.
+    pub channel_subdirs: BTreeMap<ChannelUrl, Vec<Platform>>,
     pub build_environment: BuildEnvironment,
     pub variants: VariantConfig,
     pub exclude_newer: Option<ResolvedExcludeNewer>,
@@ -37,12 +44,14 @@ impl Hash for EnvironmentSpec {
     fn hash<H: Hasher>(&self, state: &mut H) {
         let Self {
             channels,
+            channel_subdirs,
             build_environment,
             variants,
             exclude_newer,
             channel_priority,
         } = self;
         channels.hash(state);
+        channel_subdirs.hash(state);
         build_environment.hash(state);
         variants.hash(state);
         exclude_newer.hash(state);
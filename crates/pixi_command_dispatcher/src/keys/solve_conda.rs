@@ -7,6 +7,7 @@
 //! limiting and reporter wiring.
 
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     hash::{Hash, Hasher},
     mem,
     sync::Arc,
@@ -23,7 +24,7 @@ use rattler_conda_types::{
     Channel, ChannelConfig, ChannelUrl, GenericVirtualPackage, MatchSpec, PackageName,
     PackageNameMatcher, ParseMatchSpecOptions, Platform, RepodataRevision,
 };
-use rattler_repodata_gateway::GatewayError;
+use rattler_repodata_gateway::{Gateway, GatewayError, RepoData};
 use rattler_solve::{ChannelPriority, SolveError, SolveStrategy};
 use thiserror::Error;
 use tracing::instrument;
@@ -58,6 +59,8 @@ pub struct SolveCondaSpec {
     pub platform: Platform,
     /// Channels to search.
     pub channels: Vec<ChannelUrl>,
+    /// The subdirs to fetch for the channels that restrict them.
+    pub channel_subdirs: BTreeMap<ChannelUrl, Vec<Platform>>,
     /// Virtual packages to pretend are installed.
     pub virtual_packages: Vec<GenericVirtualPackage>,
     /// Solver strategy.
@@ -85,6 +88,7 @@ impl Hash for SolveCondaSpec {
             installed,
             platform,
             channels,
+            channel_subdirs,
             virtual_packages,
             strategy,
             channel_priority,
@@ -103,6 +107,7 @@ impl Hash for SolveCondaSpec {
         installed.hash(state);
         platform.hash(state);
         channels.hash(state);
+        channel_subdirs.hash(state);
         virtual_packages.hash(state);
         mem::discriminant(strategy).hash(state);
         mem::discriminant(channel_priority).hash(state);
@@ -127,6 +132,7 @@ impl PartialEq for SolveCondaSpec {
             && self.installed == other.installed
             && self.platform == other.platform
             && self.channels == other.channels
+            && self.channel_subdirs == other.channel_subdirs
             && self.virtual_packages == other.virtual_packages
             && mem::discriminant(&self.strategy) == mem::discriminant(&other.strategy)
             && mem::discriminant(&self.channel_priority)
@@ -262,26 +268,29 @@ impl Key for SolveCondaKey {
         // `scope_active` (e.g. the pixi-solve or backend-instantiate
         // op that triggered it), so `OperationId::current()` gives
         // the already-started op the gateway query belongs to.
-        let gateway_reporter = OperationId::current().and_then(|op_id| {
-            ctx.global_data()
-                .gateway_reporter()
-                .and_then(|r| r.create_gateway_reporter(op_id))
-        });
-        let mut query = gateway
-            .query(
-                spec.channels.iter().cloned().map(Channel::from_url),
-                [spec.platform, Platform::NoArch],
-                binary_match_specs
-                    .into_iter()
-                    .chain(constraint_match_specs)
-                    .chain(source_repodata_fetch_specs)
-                    .chain(dev_source_fetch_specs),
-            )
-            .recursive(true);
-        if let Some(reporter) = gateway_reporter {
-            query = query.with_reporter(WrappingGatewayReporter(reporter));
-        }
-        let binary_repodata = query.await?;
+        let op_id = OperationId::current();
+        let gateway_reporter = ctx.global_data().gateway_reporter().cloned();
+        let new_reporter = || {
+            op_id.and_then(|op_id| {
+                gateway_reporter
+                    .as_ref()
+                    .and_then(|r| r.create_gateway_reporter(op_id))
+            })
+        };
+        let binary_repodata = fetch_binary_repodata(
+            &gateway,
+            &spec.channels,
+            spec.platform,
+            &spec.channel_subdirs,
+            binary_match_specs
+                .into_iter()
+                .chain(constraint_match_specs)
+                .chain(source_repodata_fetch_specs)
+                .chain(dev_source_fetch_specs)
+                .collect(),
+            new_reporter,
+        )
+        .await?;
         // `binary_repodata.len()` returns the number of subdir buckets,
         // not the number of records. Sum across them to get the real
         // count the solver is about to chew through.
@@ -331,6 +340,107 @@ impl Key for SolveCondaKey {
     }
 }
 
+/// Fetches the binary repodata of the channels for the target platform
+/// and `noarch`, ordered by channel and then by subdir. A channel in
+/// `channel_subdirs` is only fetched for those of the two subdirs it
+/// lists.
+///
+/// A gateway query fetches the same subdirs from every channel, so
+/// with restricted channels every subdir is queried separately. The
+/// recursion of such a query doesn't see the records of the other
+/// subdir, so the queries are repeated with the dependencies of all
+/// fetched records until no new package names show up. The queries
+/// after the first are served from the gateway cache.
+async fn fetch_binary_repodata(
+    gateway: &Gateway,
+    channels: &[ChannelUrl],
+    platform: Platform,
+    channel_subdirs: &BTreeMap<ChannelUrl, Vec<Platform>>,
+    mut specs: Vec<MatchSpec>,
+    new_reporter: impl Fn() -> Option<Box<dyn rattler_repodata_gateway::Reporter>>,
+) -> Result<Vec<RepoData>, GatewayError> {
+    let query = |channels: Vec<ChannelUrl>, subdirs: Vec<Platform>, specs: Vec<MatchSpec>| {
+        let mut query = gateway
+            .query(channels.into_iter().map(Channel::from_url), subdirs, specs)
+            .recursive(true);
+        if let Some(reporter) = new_reporter() {
+            query = query.with_reporter(WrappingGatewayReporter(reporter));
+        }
+        query
+    };
+
+    let subdirs = [platform, Platform::NoArch];
+    if !channels
+        .iter()
+        .any(|channel| channel_subdirs.contains_key(channel))
+    {
+        return query(channels.to_vec(), subdirs.to_vec(), specs).await;
+    }
+
+    let mut names: HashSet<PackageName> = specs
+        .iter()
+        .filter_map(|spec| match &spec.name {
+            PackageNameMatcher::Exact(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    loop {
+        let mut repodata_per_subdir = Vec::with_capacity(subdirs.len());
+        for subdir in subdirs {
+            let subdir_channels = channels
+                .iter()
+                .filter(|channel| {
+                    channel_subdirs
+                        .get(*channel)
+                        .is_none_or(|subdirs| subdirs.contains(&subdir))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            let repodata = if subdir_channels.is_empty() {
+                Vec::new()
+            } else {
+                query(subdir_channels.clone(), vec![subdir], specs.clone()).await?
+            };
+            repodata_per_subdir.push(
+                subdir_channels
+                    .into_iter()
+                    .zip(repodata)
+                    .collect::<HashMap<_, _>>(),
+            );
+        }
+
+        let new_specs = repodata_per_subdir
+            .iter()
+            .flat_map(HashMap::values)
+            .flat_map(RepoData::iter)
+            .flat_map(|record| record.package_record.depends.iter())
+            .filter_map(|depend| {
+                MatchSpec::from_str(
+                    depend,
+                    ParseMatchSpecOptions::lenient().with_repodata_revision(RepodataRevision::V3),
+                )
+                .ok()
+            })
+            .filter(|spec| match &spec.name {
+                PackageNameMatcher::Exact(name) => names.insert(name.clone()),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        if new_specs.is_empty() {
+            return Ok(channels
+                .iter()
+                .flat_map(|channel| {
+                    repodata_per_subdir
+                        .iter_mut()
+                        .filter_map(|repodata| repodata.remove(channel))
+                        .collect::<Vec<_>>()
+                })
+                .collect());
+        }
+        specs.extend(new_specs);
+    }
+}
+
 /// For every assembled `SourceRecord` in `spec.source_repodata`,
 /// emit a `MatchSpec` for each of its non-source depends. Source
 /// deps are filtered via `record.sources()` since they're already
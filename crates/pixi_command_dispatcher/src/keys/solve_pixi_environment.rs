@@ -394,6 +394,7 @@ async fn compute_inner(
                 .collect(),
             platform: env_spec.build_environment.host_platform,
             channels: env_spec.channels.clone(),
+            channel_subdirs: env_spec.channel_subdirs.clone(),
             virtual_packages: env_spec.build_environment.host_virtual_packages.clone(),
             strategy: spec.strategy,
             channel_priority: env_spec.channel_priority,
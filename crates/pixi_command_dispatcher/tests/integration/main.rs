@@ -83,6 +83,7 @@ fn empty_pixi_env_spec() -> SolvePixiEnvironmentSpec {
             "test",
             EnvironmentSpec {
                 channels: Vec::new(),
+                channel_subdirs: Default::default(),
                 build_environment: BuildEnvironment::default(),
                 variants: VariantConfig::default(),
                 exclude_newer: None,
@@ -123,6 +124,7 @@ fn env_ref_of(channels: Vec<ChannelUrl>, build_environment: BuildEnvironment) ->
         "test",
         EnvironmentSpec {
             channels,
+            channel_subdirs: Default::default(),
             build_environment,
             variants: VariantConfig::default(),
             exclude_newer: None,
@@ -1042,6 +1044,7 @@ pub async fn test_dev_source_metadata_with_variants() {
                 "variant-test",
                 EnvironmentSpec {
                     channels: vec![],
+                    channel_subdirs: Default::default(),
                     build_environment: BuildEnvironment::simple(
                         tool_platform,
                         tool_virtual_packages,
@@ -2269,6 +2272,7 @@ pub async fn test_installed_hint_reused_across_variants() {
         "test",
         EnvironmentSpec {
             channels: vec![channel_url.clone()],
+            channel_subdirs: Default::default(),
             build_environment: BuildEnvironment::simple(
                 tool_platform,
                 tool_virtual_packages.clone(),
@@ -2945,6 +2949,7 @@ pub async fn test_solve_pixi_environment_key_dedups_across_ephemeral_env_names()
             name,
             EnvironmentSpec {
                 channels: Vec::new(),
+                channel_subdirs: Default::default(),
                 build_environment: build_env.clone(),
                 variants: VariantConfig::default(),
                 exclude_newer: None,
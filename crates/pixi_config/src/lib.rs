@@ -3,7 +3,7 @@ use std::{
     path::{Path, PathBuf},
     process::{Command, Stdio},
     str::FromStr,
-    sync::{LazyLock, Mutex},
+    sync::{LazyLock, Mutex, OnceLock},
 };
 
 use clap::{ArgAction, Parser};
@@ -149,6 +149,29 @@ static NETFS_REDIRECT_WARNED: LazyLock<Mutex<HashSet<CacheKind>>> =
 /// [`Config::cache_dir_for`] instead.
 static GLOBAL_CACHE_CONFIG: LazyLock<CacheConfig> = LazyLock::new(|| Config::load_global().cache);
 
+/// A cache root that takes precedence over all other cache configuration of
+/// this process, set through [`set_cache_dir_override`].
+static CACHE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Redirects all caches of this process to subdirectories of `path`, which
+/// is created if it does not exist yet.
+///
+/// This is used by the `--cache-dir` flag and takes precedence over the
+/// environment variables and the `[cache]` configuration. The override can
+/// only be set once.
+pub fn set_cache_dir_override(path: &Path) -> miette::Result<()> {
+    fs_err::create_dir_all(path)
+        .into_diagnostic()
+        .wrap_err(format!(
+            "failed to create the cache directory '{}'",
+            path.display()
+        ))?;
+    let path = std::path::absolute(path).into_diagnostic()?;
+    CACHE_DIR_OVERRIDE
+        .set(path)
+        .map_err(|_| miette!("the cache directory can only be overridden once"))
+}
+
 /// Describes where the system + user-level config layer comes from. Built from
 /// [`ConfigSourceCli`] (which mirrors `--no-config` / `--config-file`) and
 /// passed into [`Config::load_global_with`] and [`Config::load_with`].
@@ -224,7 +247,8 @@ pub fn pixi_home() -> Option<PathBuf> {
 // TODO(tim): I think we should move this to another crate, dont know if global
 // config is really correct
 /// Returns the default cache directory.
-/// Most important is the `PIXI_CACHE_DIR` environment variable.
+/// A directory passed with `--cache-dir` takes precedence over everything.
+/// Most important otherwise is the `PIXI_CACHE_DIR` environment variable.
 /// - If that is not set, the `RATTLER_CACHE_DIR` environment variable is used.
 /// - If that is not set, `XDG_CACHE_HOME/pixi` is used when the directory
 ///   exists.
@@ -618,6 +642,9 @@ fn env_netfs_redirect() -> Option<NetfsRedirect> {
 }
 
 fn resolve_cache_kind_dir(cache_cfg: &CacheConfig, kind: CacheKind) -> miette::Result<PathBuf> {
+    if let Some(root) = CACHE_DIR_OVERRIDE.get() {
+        return Ok(root.join(kind.subdir()));
+    }
     // Env vars override TOML for per-kind paths. Setting one bypasses the
     // redirect logic for that kind, mirroring the TOML field's semantics.
     if let Some(p) = env_path_for(kind) {
@@ -667,9 +694,12 @@ fn resolve_cache_kind_dir(cache_cfg: &CacheConfig, kind: CacheKind) -> miette::R
     Ok(redirected)
 }
 
-/// Resolve the cache root, consulting (in order): `PIXI_CACHE_DIR`,
+/// Resolve the cache root, consulting (in order): `--cache-dir`, `PIXI_CACHE_DIR`,
 /// `RATTLER_CACHE_DIR`, `[cache.root]` in config, XDG, rattler default.
 fn resolve_cache_root(cache_cfg: &CacheConfig) -> Option<(PathBuf, CacheDirSource)> {
+    if let Some(root) = CACHE_DIR_OVERRIDE.get() {
+        return Some((root.clone(), CacheDirSource::UserPinned));
+    }
     if let Ok(dir) = std::env::var("PIXI_CACHE_DIR") {
         return Some((PathBuf::from(dir), CacheDirSource::UserPinned));
     }
@@ -697,15 +727,27 @@ pub struct ConfigCli {
     pub auth_file: Option<PathBuf>,
 
     /// Max concurrent network requests, default is `50`
-    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS)]
+    #[arg(
+        long,
+        help_heading = consts::CLAP_CONFIG_OPTIONS,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
     pub concurrent_downloads: Option<usize>,
 
     /// Max concurrent solves, default is the number of CPUs
-    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS)]
+    #[arg(
+        long,
+        help_heading = consts::CLAP_CONFIG_OPTIONS,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
     pub concurrent_solves: Option<usize>,
 
+    /// Max number of retries of a failing network request, default is `3`
+    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS)]
+    pub max_retries: Option<u32>,
+
     /// Set pinning strategy
-    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS, value_enum)]
+    #[arg(long, alias = "pin", help_heading = consts::CLAP_CONFIG_OPTIONS, value_enum)]
     pub pinning_strategy: Option<PinningStrategy>,
 
     /// Specifies whether to use the keyring to look up credentials for PyPI.
@@ -746,13 +788,18 @@ pub struct ConfigCliPrompt {
     /// Do not change the PS1 variable when starting a prompt.
     #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS)]
     change_ps1: Option<bool>,
+
+    /// Do not add the name of the environment to the prompt, same as
+    /// `--change-ps1 false`.
+    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS, conflicts_with = "change_ps1")]
+    no_prompt: bool,
 }
 
 impl From<ConfigCliPrompt> for Config {
     fn from(cli: ConfigCliPrompt) -> Self {
         Self {
             shell: ShellConfig {
-                change_ps1: cli.change_ps1,
+                change_ps1: cli.change_ps1(),
                 ..Default::default()
             },
             ..Default::default()
@@ -761,9 +808,18 @@ impl From<ConfigCliPrompt> for Config {
 }
 
 impl ConfigCliPrompt {
+    /// Returns whether the prompt should be changed, if that was specified.
+    fn change_ps1(&self) -> Option<bool> {
+        if self.no_prompt {
+            Some(false)
+        } else {
+            self.change_ps1
+        }
+    }
+
     pub fn merge_config(self, config: Config) -> Config {
         let mut config = config;
-        config.shell.change_ps1 = self.change_ps1.or(config.shell.change_ps1);
+        config.shell.change_ps1 = self.change_ps1().or(config.shell.change_ps1);
         config
     }
 }
@@ -865,7 +921,7 @@ impl RepodataConfig {
 pub struct ConfigCliActivation {
     /// Do not use the environment activation cache. (default: true except in
     /// experimental mode)
-    #[arg(long, help_heading = consts::CLAP_CONFIG_OPTIONS)]
+    #[arg(long, visible_alias = "no-activation-cache", help_heading = consts::CLAP_CONFIG_OPTIONS)]
     force_activate: bool,
 
     /// Do not source the autocompletion scripts from the environment.
@@ -953,6 +1009,11 @@ pub struct PyPIConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub allow_insecure_host: Vec<String>,
+    /// Allow PyPI packages to overwrite files installed by conda packages,
+    /// instead of warning about it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_clobber: Option<bool>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -1090,6 +1151,12 @@ fn default_max_concurrent_downloads() -> usize {
     50
 }
 
+/// The default number of times a failing network request is retried.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The default number of previous lock files that are kept in the history.
+pub const DEFAULT_LOCK_HISTORY_DEPTH: usize = 5;
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct ConcurrencyConfig {
@@ -1156,6 +1223,7 @@ impl PyPIConfig {
                 .into_iter()
                 .chain(other.allow_insecure_host)
                 .collect(),
+            allow_clobber: other.allow_clobber.or(self.allow_clobber),
         }
     }
 
@@ -1176,6 +1244,7 @@ impl PyPIConfig {
         self.index_url.is_none()
             && self.extra_index_urls.is_empty()
             && self.keyring_provider.is_none()
+            && self.allow_clobber.is_none()
     }
 }
 
@@ -1286,6 +1355,11 @@ impl PinningStrategy {
         };
         Some(constraint)
     }
+
+    /// Returns true if the constraints of this strategy have an upper bound.
+    pub fn has_upper_bound(self) -> bool {
+        !matches!(self, Self::LatestUp | Self::NoPin)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
@@ -1384,6 +1458,18 @@ pub struct Config {
     #[serde(skip_serializing_if = "ConcurrencyConfig::is_default")]
     pub concurrency: ConcurrencyConfig,
 
+    /// The maximum number of times a network request that failed with a
+    /// transient error (e.g. a connection reset or a 5xx response) is retried.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+
+    /// The number of previous lock files that are kept in the lock file
+    /// history of a workspace, so that an update can be rolled back.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_history_depth: Option<usize>,
+
     /// Run the post link scripts
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1463,6 +1549,8 @@ impl Default for Config {
             shell: ShellConfig::default(),
             experimental: ExperimentalConfig::default(),
             concurrency: ConcurrencyConfig::default(),
+            max_retries: None,
+            lock_history_depth: None,
             run_post_link_scripts: None,
             allow_symbolic_links: None,
             allow_hard_links: None,
@@ -1535,6 +1623,7 @@ impl From<ConfigCli> for Config {
                     .concurrent_downloads
                     .unwrap_or(ConcurrencyConfig::default().downloads),
             },
+            max_retries: cli.max_retries,
             tool_platform: None,
             run_post_link_scripts: if cli.run_post_link_scripts {
                 Some(RunPostLinkScripts::Insecure)
@@ -2102,6 +2191,8 @@ impl Config {
             "detached-environments",
             "experimental",
             "experimental.use-environment-activation-cache",
+            "lock-history-depth",
+            "max-retries",
             "mirrors",
             "pinning-strategy",
             "proxy-config",
@@ -2109,6 +2200,7 @@ impl Config {
             "proxy-config.https",
             "proxy-config.non-proxy-hosts",
             "pypi-config",
+            "pypi-config.allow-clobber",
             "pypi-config.allow-insecure-host",
             "pypi-config.extra-index-urls",
             "pypi-config.index-url",
@@ -2176,6 +2268,8 @@ impl Config {
             experimental: self.experimental.merge(other.experimental),
             // Make other take precedence over self to allow for setting the value through the CLI
             concurrency: self.concurrency.merge(other.concurrency),
+            max_retries: other.max_retries.or(self.max_retries),
+            lock_history_depth: other.lock_history_depth.or(self.lock_history_depth),
             run_post_link_scripts: other.run_post_link_scripts.or(self.run_post_link_scripts),
             allow_symbolic_links: other.allow_symbolic_links.or(self.allow_symbolic_links),
             allow_hard_links: other.allow_hard_links.or(self.allow_hard_links),
@@ -2278,6 +2372,17 @@ impl Config {
         self.concurrency.downloads
     }
 
+    /// Retrieve the maximum number of retries of a failing network request.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES)
+    }
+
+    /// Retrieve the number of previous lock files that are kept.
+    pub fn lock_history_depth(&self) -> usize {
+        self.lock_history_depth
+            .unwrap_or(DEFAULT_LOCK_HISTORY_DEPTH)
+    }
+
     /// The platform to use to install tools.
     pub fn tool_platform(&self) -> Platform {
         self.tool_platform.unwrap_or(Platform::current())
@@ -2388,6 +2493,12 @@ impl Config {
                     "The `force-activate` field is deprecated. Please use the `shell.force-activate` field instead."
                 ));
             }
+            "max-retries" => {
+                self.max_retries = value.map(|v| v.parse()).transpose().into_diagnostic()?;
+            }
+            "lock-history-depth" => {
+                self.lock_history_depth = value.map(|v| v.parse()).transpose().into_diagnostic()?;
+            }
             "tool-platform" => {
                 self.tool_platform = value
                     .as_deref()
@@ -2467,6 +2578,10 @@ impl Config {
                             .into_diagnostic()?
                             .unwrap_or_default();
                     }
+                    "allow-clobber" => {
+                        self.pypi_config.allow_clobber =
+                            value.map(|v| v.parse()).transpose().into_diagnostic()?;
+                    }
                     _ => return Err(err),
                 }
             }
@@ -2927,6 +3042,7 @@ UNUSED = "unused"
             pypi_keyring_provider: Some(KeyringProvider::Subprocess),
             concurrent_solves: Some(8),
             concurrent_downloads: Some(100),
+            max_retries: Some(5),
             run_post_link_scripts: true,
             no_symbolic_links: false,
             no_hard_links: false,
@@ -2942,6 +3058,7 @@ UNUSED = "unused"
             Some(KeyringProvider::Subprocess)
         );
         assert_eq!(config.concurrency.solves, 8);
+        assert_eq!(config.max_retries(), 5);
         assert_eq!(config.concurrency.downloads, 100);
         assert_eq!(
             config.run_post_link_scripts,
@@ -2960,6 +3077,7 @@ UNUSED = "unused"
             pypi_keyring_provider: None,
             concurrent_solves: None,
             concurrent_downloads: None,
+            max_retries: None,
             run_post_link_scripts: false,
             no_symbolic_links: false,
             no_hard_links: false,
@@ -3097,6 +3215,7 @@ UNUSED = "unused"
                 ]),
                 index_url: Some(Url::parse("https://conda.anaconda.org/conda-forge").unwrap()),
                 keyring_provider: Some(KeyringProvider::Subprocess),
+                allow_clobber: Some(true),
             },
             s3_options: HashMap::from([(
                 "bucket1".into(),
@@ -3123,6 +3242,8 @@ UNUSED = "unused"
             allow_ref_links: Some(false),
             proxy_config: ProxyConfig::default(),
             build: BuildConfig::default(),
+            max_retries: Some(5),
+            lock_history_depth: Some(2),
             tool_platform: None,
             cache: CacheConfig {
                 root: Some(PathBuf::from("/some/cache/root")),
@@ -3413,6 +3534,12 @@ UNUSED = "unused"
 
         assert_eq!(config.max_concurrent_downloads(), 1);
 
+        assert_eq!(config.max_retries(), DEFAULT_MAX_RETRIES);
+        config.set("max-retries", Some("0".to_string())).unwrap();
+        assert_eq!(config.max_retries(), 0);
+        config.set("max-retries", None).unwrap();
+        assert_eq!(config.max_retries(), DEFAULT_MAX_RETRIES);
+
         config.set("s3-options.my-bucket", Some(r#"{"endpoint-url": "http://localhost:9000", "force-path-style": true, "region": "auto"}"#.to_string())).unwrap();
         let s3_options = config.s3_options.get("my-bucket").unwrap();
         assert!(
@@ -3498,6 +3625,12 @@ UNUSED = "unused"
             .unwrap();
         assert_eq!(config.pypi_config().allow_insecure_host.len(), 1);
 
+        // Test pypi-config.allow-clobber
+        config
+            .set("pypi-config.allow-clobber", Some("true".to_string()))
+            .unwrap();
+        assert_eq!(config.pypi_config().allow_clobber, Some(true));
+
         // Test proxy-config
         config
             .set(
@@ -87,6 +87,19 @@ pub const PIXI_DIR: &str = match option_env!("PIXI_DIR") {
 };
 /// Environment variable to override the detected platform.
 pub const PIXI_OVERRIDE_PLATFORM: &str = "PIXI_OVERRIDE_PLATFORM";
+/// Environment variable with a comma-separated list of channels that replaces
+/// the channels of the workspace.
+pub const PIXI_CHANNELS: &str = "PIXI_CHANNELS";
+/// Environment variable with a comma-separated list of platforms that
+/// replaces the platforms of the workspace.
+pub const PIXI_PLATFORMS: &str = "PIXI_PLATFORMS";
+/// Environment variable that stores the values the variables had before `pixi
+/// shell-hook` activated an environment, used to restore them on deactivation.
+pub const PIXI_DEACTIVATION_ENV: &str = "PIXI_DEACTIVATION_ENV";
+
+/// Environment variable that is set in every activated pixi environment, used
+/// to detect nested activations.
+pub const PIXI_IN_SHELL_ENV: &str = "PIXI_IN_SHELL";
 /// The default manifest name for the global manifest file in the pixi config directory.
 pub const GLOBAL_MANIFEST_DEFAULT_NAME: &str =
     match option_env!("PIXI_GLOBAL_MANIFEST_DEFAULT_NAME") {
@@ -3,23 +3,26 @@ use crate::{
     environment::EnvironmentHash,
     workspace::{HasWorkspaceRef, PlatformOverrides, PlatformSource},
 };
+use base64::{Engine, engine::general_purpose::STANDARD};
 use fs_err::tokio as tokio_fs;
 use indexmap::IndexMap;
 use itertools::Itertools;
-use miette::IntoDiagnostic;
+use miette::{Context, IntoDiagnostic};
+use pixi_consts::consts;
 use pixi_manifest::EnvironmentName;
 use pixi_manifest::FeaturesExt;
+use rattler_conda_types::Platform;
 use rattler_lock::LockFile;
 use rattler_shell::{
     activation::{
         ActivationError::FailedToRunActivationScript, ActivationVariables, Activator,
-        PathModificationBehavior,
+        PathModificationBehavior, prefix_path_entries,
     },
-    shell::ShellEnum,
+    shell::{ShellEnum, ShellScript},
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 // Setting a base prefix for the pixi package
 const PROJECT_PREFIX: &str = "PIXI_PROJECT_";
@@ -79,7 +82,7 @@ impl Workspace {
                         version.to_string()
                     }),
             ),
-            (String::from("PIXI_IN_SHELL"), String::from("1")),
+            (consts::PIXI_IN_SHELL_ENV.to_string(), String::from("1")),
         ]);
 
         if let Ok(exe_path) = std::env::current_exe() {
@@ -112,10 +115,38 @@ impl Environment<'_> {
                 self.platforms().iter().map(|plat| plat.as_str()).join(","),
             ),
             ("PIXI_PROMPT".to_string(), format!("({prompt}) ")),
+            (
+                "PIXI_PREFIX".to_string(),
+                self.dir().to_string_lossy().into_owned(),
+            ),
         ])
     }
 }
 
+/// The extensions of the activation scripts that the given shell can source.
+fn activation_script_extensions(shell: &ShellEnum) -> &'static [&'static str] {
+    match shell {
+        ShellEnum::Bash(_) => &["sh", "bash"],
+        ShellEnum::Zsh(_) => &["sh", "bash", "zsh"],
+        ShellEnum::Xonsh(_) => &["xsh", "sh"],
+        ShellEnum::Fish(_) => &["fish"],
+        ShellEnum::NuShell(_) => &["nu"],
+        ShellEnum::PowerShell(_) => &["ps1"],
+        ShellEnum::CmdExe(_) => &["bat", "cmd"],
+    }
+}
+
+/// Returns false if the script has the extension of a script for another
+/// shell. Scripts with an unknown extension are passed to every shell.
+fn is_activation_script_for_shell(script: &Path, shell_extensions: &[&str]) -> bool {
+    const SHELL_SCRIPT_EXTENSIONS: &[&str] = &[
+        "sh", "bash", "zsh", "xsh", "fish", "nu", "ps1", "bat", "cmd",
+    ];
+    let extension = script.extension().unwrap_or_default();
+    shell_extensions.iter().any(|ext| extension == *ext)
+        || !SHELL_SCRIPT_EXTENSIONS.iter().any(|ext| extension == *ext)
+}
+
 /// Get the complete activator for the environment.
 /// This method will create an activator for the environment and add the activation scripts from the project.
 /// The activator will be created for the current platform and the default shell.
@@ -159,20 +190,32 @@ pub fn get_activator<'p>(
         );
     }
 
-    // Check if the platform and activation script extension match. For Platform::Windows the extension should be .bat and for All other platforms it should be .sh or .bash.
+    // Scripts that are written for another shell are skipped, this allows
+    // listing e.g. a `.sh`, a `.bat` and a `.ps1` script in the same
+    // `[activation]` table.
+    let shell_extensions = activation_script_extensions(&shell);
+    let (additional_activation_scripts, skipped_scripts): (Vec<_>, Vec<_>) =
+        additional_activation_scripts
+            .into_iter()
+            .partition(|script| is_activation_script_for_shell(script, shell_extensions));
+    for script in skipped_scripts {
+        tracing::debug!(
+            "Skipping activation script '{}' because it is meant for another shell.",
+            script.display(),
+        );
+    }
+
+    // Scripts without a known shell extension are still sourced, but are likely a mistake.
     for script in additional_activation_scripts.iter() {
         let extension = script.extension().unwrap_or_default();
-        if subdir.is_windows() && extension != "bat" {
-            tracing::warn!(
-                "The activation script '{}' does not have the correct extension for the platform '{}'. The extension should be '.bat'.",
-                script.display(),
-                subdir
-            );
-        } else if !subdir.is_windows() && extension != "sh" && extension != "bash" {
+        if !shell_extensions.iter().any(|ext| extension == *ext) {
             tracing::warn!(
-                "The activation script '{}' does not have the correct extension for the platform '{}'. The extension should be '.sh' or '.bash'.",
+                "The activation script '{}' does not have the extension of a script for the activation shell. The extension should be one of: {}.",
                 script.display(),
-                subdir
+                shell_extensions
+                    .iter()
+                    .map(|ext| format!("'.{ext}'"))
+                    .join(", ")
             );
         }
     }
@@ -490,18 +533,77 @@ pub(crate) fn get_clean_environment_variables() -> HashMap<String, String> {
         vec![]
     };
 
+    // Processes fail to start on Windows without some of the system variables.
+    let windows_keys = if cfg!(windows) {
+        vec![
+            "SYSTEMROOT",
+            "SYSTEMDRIVE",
+            "WINDIR",
+            "COMSPEC",
+            "PATHEXT",
+            "TEMP",
+            "TMP",
+            "USERNAME",
+            "USERPROFILE",
+            "HOMEDRIVE",
+            "HOMEPATH",
+            "APPDATA",
+            "LOCALAPPDATA",
+            "PROGRAMDATA",
+            "PROGRAMFILES",
+            "PROGRAMFILES(X86)",
+            "COMPUTERNAME",
+            "NUMBER_OF_PROCESSORS",
+            "PROCESSOR_ARCHITECTURE",
+            "OS",
+        ]
+    } else {
+        vec![]
+    };
+
     let keys = unix_keys
         .into_iter()
         .chain(macos_keys)
-        // .chain(windows_keys)
+        .chain(windows_keys)
         .map(|s| s.to_string().to_uppercase())
         .collect_vec();
 
+    // Variable names are case-insensitive on Windows, e.g. `SYSTEMROOT` is
+    // usually set as `SystemRoot`. Uppercase them there so they can be looked
+    // up by the names above.
     env.into_iter()
-        .filter(|(key, _)| keys.contains(&key.to_string().to_uppercase()))
+        .filter(|(key, _)| keys.contains(&key.to_uppercase()))
+        .map(|(key, value)| {
+            if cfg!(windows) {
+                (key.to_uppercase(), value)
+            } else {
+                (key, value)
+            }
+        })
         .collect::<HashMap<String, String>>()
 }
 
+/// Returns the system directories that are added to the `PATH` of a clean
+/// environment. On Windows these contain the executables that are part of
+/// the operating system, elsewhere nothing is added.
+fn clean_system_paths() -> Vec<PathBuf> {
+    if !cfg!(windows) {
+        return Vec::new();
+    }
+    let Some(system_root) = std::env::var_os("SYSTEMROOT").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    vec![
+        system_root.join("System32"),
+        system_root.clone(),
+        system_root.join("System32").join("Wbem"),
+        system_root
+            .join("System32")
+            .join("WindowsPowerShell")
+            .join("v1.0"),
+    ]
+}
+
 /// Determine the environment variables that need to be set in an interactive shell to make it
 /// function as if the environment has been activated. This method runs the activation scripts from
 /// the environment and stores the environment variables it added, finally it adds environment
@@ -527,28 +629,173 @@ pub(crate) async fn initialize_env_variables(
 
     // Get environment variables from the currently activated shell.
     let current_shell_env_vars = match env_var_behavior {
-        CurrentEnvVarBehavior::Clean if cfg!(windows) => {
-            return Err(miette::miette!(
-                "Currently it's not possible to run a `clean-env` option on Windows."
-            ));
-        }
         CurrentEnvVarBehavior::Clean => get_clean_environment_variables(),
         CurrentEnvVarBehavior::Include => std::env::vars().collect(),
         CurrentEnvVarBehavior::Exclude => HashMap::new(),
     };
 
-    let all_variables: HashMap<String, String> = current_shell_env_vars
+    let mut all_variables: HashMap<String, String> = current_shell_env_vars
         .into_iter()
         .chain(activation_env)
         .collect();
 
+    // The activation replaced the `PATH` of a clean environment, add back the
+    // directories of the system that are required to run commands.
+    let system_paths = clean_system_paths();
+    if matches!(env_var_behavior, CurrentEnvVarBehavior::Clean) && !system_paths.is_empty() {
+        let path_key = all_variables
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case("PATH"))
+            .cloned()
+            .unwrap_or_else(|| "PATH".to_string());
+        let paths = all_variables
+            .get(&path_key)
+            .map(|path| std::env::split_paths(path).collect_vec())
+            .unwrap_or_default();
+        let path = std::env::join_paths(paths.into_iter().chain(system_paths)).into_diagnostic()?;
+        all_variables.insert(path_key, path.to_string_lossy().into_owned());
+    }
+
     Ok(all_variables)
 }
 
+/// Returns the environment variables to run a command in the environment
+/// without activating it.
+///
+/// Only the directories of the prefix that contain executables are prepended
+/// to the `PATH`. Activation scripts are not run and none of the variables
+/// that pixi sets during activation are added, which makes this much cheaper
+/// than [`initialize_env_variables`].
+pub fn get_unactivated_environment_variables(
+    environment: &Environment<'_>,
+    env_var_behavior: CurrentEnvVarBehavior,
+) -> miette::Result<HashMap<String, String>> {
+    let mut env_vars = match env_var_behavior {
+        CurrentEnvVarBehavior::Clean => get_clean_environment_variables(),
+        CurrentEnvVarBehavior::Include => std::env::vars().collect(),
+        CurrentEnvVarBehavior::Exclude => HashMap::new(),
+    };
+
+    let platform = environment
+        .best_declared_platform()
+        .map(|platform| platform.subdir())
+        .unwrap_or_else(Platform::current);
+    let prefix_paths = prefix_path_entries(&environment.dir(), &platform);
+
+    // On Windows the variable is usually spelled `Path`.
+    let path_key = env_vars
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case("PATH"))
+        .cloned()
+        .unwrap_or_else(|| "PATH".to_string());
+    let current_paths = match env_var_behavior {
+        CurrentEnvVarBehavior::Clean => clean_system_paths(),
+        _ => env_vars
+            .get(&path_key)
+            .map(|path| std::env::split_paths(path).collect())
+            .unwrap_or_default(),
+    };
+    let path =
+        std::env::join_paths(prefix_paths.into_iter().chain(current_paths)).into_diagnostic()?;
+    env_vars.insert(path_key, path.to_string_lossy().into_owned());
+
+    Ok(env_vars)
+}
+
+/// Returns an error if the given environment variables belong to a shell in
+/// which a pixi environment is already activated.
+///
+/// Starting a shell from an activated environment stacks the second
+/// activation on top of the first, so variables of both environments end up
+/// in the new shell.
+pub fn ensure_not_activated(env_vars: &HashMap<String, String>) -> miette::Result<()> {
+    if !env_vars.contains_key(consts::PIXI_IN_SHELL_ENV) {
+        return Ok(());
+    }
+    let environment = env_vars
+        .get(&format!("{ENV_PREFIX}NAME"))
+        .map_or("a pixi", String::as_str);
+    let workspace = env_vars
+        .get(&format!("{PROJECT_PREFIX}NAME"))
+        .map(|name| format!(" of '{name}'"))
+        .unwrap_or_default();
+    miette::bail!(
+        help = format!(
+            "run `exit` to leave the active shell first, or unset `{}` to start a nested shell anyway",
+            consts::PIXI_IN_SHELL_ENV
+        ),
+        "the '{environment}' environment{workspace} is already activated in this shell"
+    )
+}
+
+/// The values environment variables had before an environment was activated.
+///
+/// A value of `None` means the variable did not exist before activation and
+/// should be removed on deactivation.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnvironmentSnapshot(BTreeMap<String, Option<String>>);
+
+impl EnvironmentSnapshot {
+    /// Records the prior value of every variable that is changed or added by
+    /// the activation.
+    pub fn from_activation(
+        before: &HashMap<String, String>,
+        activated: &HashMap<String, String>,
+    ) -> Self {
+        Self(
+            activated
+                .iter()
+                .filter(|(key, value)| before.get(*key) != Some(value))
+                .map(|(key, _)| (key.clone(), before.get(key).cloned()))
+                .collect(),
+        )
+    }
+
+    /// Reads the snapshot that was stored in [`consts::PIXI_DEACTIVATION_ENV`]
+    /// by a previous activation, if any.
+    pub fn from_env() -> miette::Result<Option<Self>> {
+        std::env::var(consts::PIXI_DEACTIVATION_ENV)
+            .ok()
+            .map(|encoded| Self::decode(&encoded))
+            .transpose()
+    }
+
+    /// Encodes the snapshot so it can be stored in an environment variable.
+    pub fn encode(&self) -> miette::Result<String> {
+        let json = serde_json::to_vec(self).into_diagnostic()?;
+        Ok(STANDARD.encode(json))
+    }
+
+    /// Decodes a snapshot created with [`EnvironmentSnapshot::encode`].
+    pub fn decode(encoded: &str) -> miette::Result<Self> {
+        let json = STANDARD
+            .decode(encoded)
+            .into_diagnostic()
+            .wrap_err("invalid deactivation environment")?;
+        serde_json::from_slice(&json).into_diagnostic()
+    }
+
+    /// Returns a script for the given shell that restores the variables to
+    /// their prior values and removes the variables that were added.
+    pub fn deactivation_script(&self, shell: ShellEnum) -> miette::Result<String> {
+        let mut script = ShellScript::new(shell, Platform::current());
+        for (key, value) in &self.0 {
+            match value {
+                Some(value) => script.set_env_var(key, value).into_diagnostic()?,
+                None => script.unset_env_var(key).into_diagnostic()?,
+            };
+        }
+        script
+            .unset_env_var(consts::PIXI_DEACTIVATION_ENV)
+            .into_diagnostic()?;
+        script.contents().into_diagnostic()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rattler_conda_types::Platform;
     use std::path::Path;
 
     /// Write a completed-install fingerprint marker for `env_dir` via
@@ -565,6 +812,85 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_activation_scripts_are_selected_by_shell() {
+        let for_shell = |script: &str, shell: ShellEnum| {
+            is_activation_script_for_shell(Path::new(script), activation_script_extensions(&shell))
+        };
+        let bash = || ShellEnum::Bash(rattler_shell::shell::Bash);
+        let powershell = || ShellEnum::PowerShell(Default::default());
+        let cmd = || ShellEnum::CmdExe(rattler_shell::shell::CmdExe);
+
+        assert!(for_shell("setup.sh", bash()));
+        assert!(!for_shell("setup.ps1", bash()));
+        assert!(!for_shell("setup.bat", bash()));
+        assert!(for_shell("setup.ps1", powershell()));
+        assert!(!for_shell("setup.bat", powershell()));
+        assert!(for_shell("setup.bat", cmd()));
+        assert!(!for_shell("setup.sh", cmd()));
+
+        // Scripts with an unknown extension are sourced by every shell.
+        assert!(for_shell("setup.env", bash()));
+        assert!(for_shell("setup.env", cmd()));
+    }
+
+    #[test]
+    fn test_nested_activation_is_rejected() {
+        assert!(ensure_not_activated(&HashMap::new()).is_ok());
+
+        let activated = HashMap::from([
+            (consts::PIXI_IN_SHELL_ENV.to_string(), "1".to_string()),
+            ("PIXI_ENVIRONMENT_NAME".to_string(), "test".to_string()),
+            ("PIXI_PROJECT_NAME".to_string(), "my-project".to_string()),
+        ]);
+        let err = ensure_not_activated(&activated).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the 'test' environment of 'my-project' is already activated in this shell"
+        );
+
+        // Deactivation removes the marker so the environment can be activated again.
+        let script = EnvironmentSnapshot::from_activation(&HashMap::new(), &activated)
+            .deactivation_script(ShellEnum::Bash(rattler_shell::shell::Bash))
+            .unwrap();
+        assert!(
+            script.contains(&format!("unset {}", consts::PIXI_IN_SHELL_ENV)),
+            "{script}"
+        );
+    }
+
+    #[test]
+    fn test_deactivation_restores_prior_values() {
+        let before = HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("HOME".to_string(), "/home/user".to_string()),
+        ]);
+        let activated = HashMap::from([
+            ("PATH".to_string(), "/env/bin:/usr/bin".to_string()),
+            ("HOME".to_string(), "/home/user".to_string()),
+            ("MY_VAR".to_string(), "x".to_string()),
+        ]);
+
+        let snapshot = EnvironmentSnapshot::from_activation(&before, &activated);
+        let snapshot = EnvironmentSnapshot::decode(&snapshot.encode().unwrap()).unwrap();
+        let script = snapshot
+            .deactivation_script(ShellEnum::Bash(rattler_shell::shell::Bash))
+            .unwrap();
+
+        // `PATH` is restored to its exact prior value
+        assert!(script.contains("PATH="), "{script}");
+        assert!(script.contains("/usr/bin"), "{script}");
+        assert!(!script.contains("/env/bin"), "{script}");
+        // Newly added variables are removed
+        assert!(script.contains("unset MY_VAR"), "{script}");
+        assert!(
+            script.contains(&format!("unset {}", consts::PIXI_DEACTIVATION_ENV)),
+            "{script}"
+        );
+        // Unchanged variables are left alone
+        assert!(!script.contains("HOME"), "{script}");
+    }
+
     #[test]
     fn test_metadata_env() {
         let multi_env_workspace = r#"
@@ -588,6 +914,10 @@ mod tests {
 
         assert_eq!(env.get("PIXI_ENVIRONMENT_NAME").unwrap(), "default");
         assert!(env.get("PIXI_ENVIRONMENT_PLATFORMS").is_some());
+        assert_eq!(
+            env.get("PIXI_PREFIX").unwrap(),
+            default_env.dir().to_str().unwrap()
+        );
         assert!(env.get("PIXI_PROMPT").unwrap().contains("pixi"));
 
         let test_env = project.environment("test").unwrap();
@@ -684,6 +1014,24 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn test_get_windows_clean_environment_variables() {
+        let env = get_clean_environment_variables();
+        // Processes can't start without `SYSTEMROOT`, which is usually set as
+        // `SystemRoot`.
+        assert!(env.keys().all(|key| *key == key.to_uppercase()));
+        assert_eq!(
+            env.get("SYSTEMROOT").unwrap(),
+            std::env::var("SYSTEMROOT").as_ref().unwrap()
+        );
+        assert!(
+            clean_system_paths()
+                .iter()
+                .any(|path| path.ends_with("System32"))
+        );
+    }
+
     /// Test that the activation cache is created and used correctly based on the lock file.
     ///
     /// Validates that the activation cache:
@@ -769,6 +1117,58 @@ mod tests {
         assert_eq!(env.get("TEST").unwrap(), "ACTIVATION123");
     }
 
+    /// Editing an activation script invalidates the cached activation, even
+    /// though the list of scripts and the install fingerprint are unchanged.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_activation_cache_based_on_activation_script() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = []
+
+        [activation]
+        scripts = ["activate.sh"]
+        "#;
+        let script = temp_dir.path().join("activate.sh");
+        tokio_fs::write(&script, "export TEST=ACTIVATION123\n")
+            .await
+            .unwrap();
+        let project =
+            Workspace::from_str(temp_dir.path().join("pixi.toml").as_path(), workspace).unwrap();
+        let default_env = project.default_environment();
+        write_fingerprint(&default_env.dir(), "00000000000000fc").await;
+
+        let activate = || {
+            run_activation(
+                &default_env,
+                &CurrentEnvVarBehavior::Include,
+                None,
+                false,
+                true,
+            )
+        };
+        let env = activate().await.unwrap();
+        assert_eq!(env.get("TEST").unwrap(), "ACTIVATION123");
+
+        // The second activation is served from the cache.
+        let cache_file = default_env.activation_cache_file_path();
+        let contents = tokio_fs::read_to_string(&cache_file).await.unwrap();
+        let modified = contents.replace("ACTIVATION123", "CACHED");
+        tokio_fs::write(&cache_file, modified).await.unwrap();
+        let env = activate().await.unwrap();
+        assert_eq!(env.get("TEST").unwrap(), "CACHED");
+
+        // Editing the script runs the activation again.
+        tokio_fs::write(&script, "export TEST=ACTIVATION456\n")
+            .await
+            .unwrap();
+        let env = activate().await.unwrap();
+        assert_eq!(env.get("TEST").unwrap(), "ACTIVATION456");
+    }
+
     /// Activation env vars are part of [`EnvironmentHash::for_activation`]'s
     /// hashed inputs (via `hash_common_inputs`), so adding or
     /// changing one in the manifest invalidates the cache even when
@@ -172,6 +172,13 @@ impl EnvironmentHash {
         let mut hasher = Xxh3::new();
         Self::hash_common_inputs(&mut hasher, run_environment, input_environment_variables);
         installed_fingerprint.as_str().hash(&mut hasher);
+
+        // The contents of the activation scripts, so that editing a script
+        // invalidates the cached activation.
+        let root = run_environment.workspace().root();
+        for script in run_environment.activation_scripts(run_environment.best_declared_platform()) {
+            fs_err::read(root.join(script)).ok().hash(&mut hasher);
+        }
         EnvironmentHash(format!("{:x}", hasher.finish()))
     }
 
@@ -338,6 +345,11 @@ pub(crate) struct EnvironmentFile {
     /// be weaker than [`Self::resolved_platform`]. `None` as above.
     #[serde(default)]
     pub(crate) minimum_supported_platform: Option<PlatformData>,
+    /// The sha256 hash of the `pixi.lock` file the environment was last
+    /// installed or validated with, checked by `pixi verify-lock`. `None` on
+    /// environments written by an older pixi.
+    #[serde(default)]
+    pub(crate) lock_file_hash: Option<String>,
 }
 
 /// The path to the environment file in the `conda-meta` directory of the
@@ -817,6 +829,7 @@ mod tests {
         let parsed: EnvironmentFile = serde_json::from_str(json).expect("legacy file parses");
         assert!(parsed.resolved_platform.is_none());
         assert!(parsed.minimum_supported_platform.is_none());
+        assert!(parsed.lock_file_hash.is_none());
     }
 
     /// `PlatformData` stores the platform's composition (subdir + declared
@@ -0,0 +1,185 @@
+//! The error type for library consumers of pixi.
+//!
+//! Most of pixi reports errors as a [`miette::Report`], which renders well but
+//! is hard to match on. At the public entry points of the library those
+//! reports are sorted into a [`PixiError`], so that a caller can handle the
+//! common failure modes without inspecting the error message. The original
+//! diagnostic is kept, so a [`PixiError`] renders exactly like the report it
+//! was created from.
+
+use std::fmt::{Debug, Display, Formatter};
+
+use miette::Diagnostic;
+use pixi_manifest::LoadManifestsError;
+
+use crate::lock_file::{
+    LockFileOutOfDateError, SolveCondaEnvironmentError, SolvePypiEnvironmentError,
+};
+
+/// An error returned by the public API of pixi.
+pub enum PixiError {
+    /// The manifest could not be read or parsed.
+    ManifestParse(LoadManifestsError),
+
+    /// The requirements of an environment could not be solved.
+    SolveFailed(miette::Report),
+
+    /// The lock file is not up-to-date with the workspace, and it was not
+    /// allowed to be updated.
+    LockOutOfDate(miette::Report),
+
+    /// A channel or package index could not be reached.
+    NetworkError(miette::Report),
+
+    /// Any other error.
+    Other(miette::Report),
+}
+
+impl PixiError {
+    /// The diagnostic this error was created from.
+    fn diagnostic(&self) -> &(dyn Diagnostic + Send + Sync + 'static) {
+        match self {
+            PixiError::ManifestParse(err) => err,
+            PixiError::SolveFailed(report)
+            | PixiError::LockOutOfDate(report)
+            | PixiError::NetworkError(report)
+            | PixiError::Other(report) => report.as_ref(),
+        }
+    }
+}
+
+impl From<LoadManifestsError> for PixiError {
+    fn from(err: LoadManifestsError) -> Self {
+        PixiError::ManifestParse(err)
+    }
+}
+
+impl From<miette::Report> for PixiError {
+    fn from(report: miette::Report) -> Self {
+        let report = match report.downcast::<LoadManifestsError>() {
+            Ok(err) => return PixiError::ManifestParse(err),
+            Err(report) => report,
+        };
+
+        // Network errors are checked first, they are also returned as the
+        // cause of a failed solve when the repodata could not be fetched.
+        let chain = || report.chain();
+        if chain().any(|err| {
+            err.is::<rattler_repodata_gateway::GatewayError>() || err.is::<uv_client::Error>()
+        }) {
+            PixiError::NetworkError(report)
+        } else if chain().any(|err| err.is::<LockFileOutOfDateError>()) {
+            PixiError::LockOutOfDate(report)
+        } else if chain().any(|err| {
+            err.is::<SolveCondaEnvironmentError>() || err.is::<SolvePypiEnvironmentError>()
+        }) {
+            PixiError::SolveFailed(report)
+        } else {
+            PixiError::Other(report)
+        }
+    }
+}
+
+impl Debug for PixiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixiError::ManifestParse(err) => Debug::fmt(err, f),
+            PixiError::SolveFailed(report)
+            | PixiError::LockOutOfDate(report)
+            | PixiError::NetworkError(report)
+            | PixiError::Other(report) => Debug::fmt(report, f),
+        }
+    }
+}
+
+impl Display for PixiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.diagnostic(), f)
+    }
+}
+
+impl std::error::Error for PixiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PixiError::ManifestParse(err) => Some(err),
+            PixiError::SolveFailed(report)
+            | PixiError::LockOutOfDate(report)
+            | PixiError::NetworkError(report)
+            | PixiError::Other(report) => {
+                Some(AsRef::<dyn std::error::Error + Send + Sync>::as_ref(report))
+            }
+        }
+    }
+}
+
+impl Diagnostic for PixiError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.diagnostic().code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.diagnostic().severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.diagnostic().help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.diagnostic().url()
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.diagnostic().source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        self.diagnostic().labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.diagnostic().related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.diagnostic().diagnostic_source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Workspace;
+
+    #[test]
+    fn test_malformed_manifest_is_manifest_parse_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path = temp_dir.path().join("pixi.toml");
+        fs_err::write(&manifest_path, "[workspace\nname = ").unwrap();
+
+        let err = Workspace::load(&manifest_path).unwrap_err();
+        assert!(matches!(err, PixiError::ManifestParse(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_report_is_sorted_by_cause() {
+        let err = PixiError::from(miette::Report::new(LockFileOutOfDateError::new(Vec::new())));
+        assert!(matches!(err, PixiError::LockOutOfDate(_)));
+        assert_eq!(
+            err.to_string(),
+            "lock file not up-to-date with the workspace"
+        );
+
+        let err = PixiError::from(miette::miette!("something else"));
+        assert!(matches!(err, PixiError::Other(_)));
+    }
+
+    #[test]
+    fn test_source_is_the_wrapped_error() {
+        use std::error::Error;
+
+        let err = PixiError::from(miette::Report::new(LockFileOutOfDateError::new(Vec::new())));
+        let source = err.source().expect("the wrapped error is the source");
+        assert!(source.is::<LockFileOutOfDateError>());
+    }
+}
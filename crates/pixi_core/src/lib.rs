@@ -3,6 +3,7 @@
 
 pub mod activation;
 pub mod environment;
+mod error;
 pub mod lock_file;
 pub mod prompt;
 pub mod rayon_primer;
@@ -12,5 +13,6 @@ pub mod workspace;
 pub mod signals;
 
 pub use environment::InstallFilter;
+pub use error::PixiError;
 pub use lock_file::UpdateLockFileOptions;
 pub use workspace::{DependencyType, Workspace, WorkspaceLocator, WorkspaceLocatorError};
@@ -0,0 +1,334 @@
+//! A concise explanation of why a conda solve is unsatisfiable.
+//!
+//! The solver reports a conflict as a tree that contains every option it
+//! tried. For a user that mostly wants to know which of their requested
+//! specs conflict, and through which package, that tree is a wall of text.
+//! [`ConflictExplanation`] condenses it to the requested specs and the
+//! shortest chain of dependencies from each of them to the package they
+//! disagree on.
+//!
+//! The solver only exposes the conflict as that rendered tree, the
+//! [`SolveError::Unsolvable`] variant carries no other information. The
+//! requested packages are therefore matched by name against the tree, and
+//! the specs in it are only read up to the package name.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+use itertools::Itertools;
+use rattler_conda_types::PackageName;
+use rattler_solve::SolveError;
+
+/// The requested specs that conflict and the packages they conflict on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictExplanation {
+    /// For every requested spec that is part of the conflict, the chain of
+    /// specs that leads from it to one of the conflicting packages.
+    pub chains: Vec<Vec<String>>,
+
+    /// The packages for which no compatible version exists.
+    pub conflicting: Vec<PackageName>,
+}
+
+/// Phrases that terminate the spec at the start of a line of the solver
+/// output.
+const SPEC_TERMINATORS: &[&str] = &[
+    ", which",
+    " can be installed",
+    " cannot be installed",
+    " would require",
+    " would constrain",
+    " is excluded",
+    " is locked",
+    " conflicts with",
+];
+
+/// Phrases that mark a line of the solver output as the cause of a conflict.
+const CONFLICT_MARKERS: &[&str] = &[
+    "conflicts with",
+    "no viable options",
+    "No candidates",
+    "is excluded",
+    "is locked",
+];
+
+/// A single line of the solver output.
+struct Node<'a> {
+    depth: usize,
+    parent: Option<usize>,
+    text: &'a str,
+    has_children: bool,
+}
+
+impl Node<'_> {
+    fn spec(&self) -> &str {
+        let text = self
+            .text
+            .strip_prefix("No candidates were found for ")
+            .unwrap_or(self.text);
+        let end = SPEC_TERMINATORS
+            .iter()
+            .filter_map(|terminator| text.find(terminator))
+            .min()
+            .unwrap_or(text.len());
+        text[..end].trim().trim_end_matches(['.', ':'])
+    }
+
+    fn package_name(&self) -> Option<PackageName> {
+        let name = self.spec().split_whitespace().next()?;
+        PackageName::try_from(name).ok()
+    }
+
+    fn is_conflict(&self) -> bool {
+        !self.has_children
+            && CONFLICT_MARKERS
+                .iter()
+                .any(|marker| self.text.contains(marker))
+    }
+}
+
+impl ConflictExplanation {
+    /// Finds the solve error in the chain of sources of the given error and
+    /// explains the conflict between the `requested` packages.
+    pub fn from_error(
+        error: &(dyn std::error::Error + 'static),
+        requested: &HashSet<PackageName>,
+    ) -> Option<Self> {
+        std::iter::successors(Some(error), |error| error.source()).find_map(|error| {
+            let solve_error = error
+                .downcast_ref::<SolveError>()
+                .or_else(|| error.downcast_ref::<Arc<SolveError>>().map(AsRef::as_ref))?;
+            Self::from_solve_error(solve_error, requested)
+        })
+    }
+
+    /// Explains the conflict between the `requested` packages if the solve was
+    /// unsatisfiable.
+    pub fn from_solve_error(error: &SolveError, requested: &HashSet<PackageName>) -> Option<Self> {
+        match error {
+            SolveError::Unsolvable(conflicts) => conflicts
+                .iter()
+                .find_map(|conflict| Self::from_conflict(conflict, requested)),
+            _ => None,
+        }
+    }
+
+    /// Explains the conflict tree of an unsatisfiable solve.
+    fn from_conflict(conflict: &str, requested: &HashSet<PackageName>) -> Option<Self> {
+        let nodes = parse_tree(conflict);
+
+        let conflicting = nodes
+            .iter()
+            .filter(|node| node.is_conflict())
+            .filter_map(Node::package_name)
+            .collect::<HashSet<_>>();
+        if conflicting.is_empty() {
+            return None;
+        }
+
+        // The requested specs are the outermost nodes of a requested package.
+        let chains = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| {
+                node.package_name()
+                    .is_some_and(|name| requested.contains(&name))
+            })
+            .filter(|(_, node)| {
+                !std::iter::successors(node.parent, |&parent| nodes[parent].parent).any(|parent| {
+                    nodes[parent]
+                        .package_name()
+                        .is_some_and(|name| requested.contains(&name))
+                })
+            })
+            .filter_map(|(root, _)| shortest_chain(&nodes, root, &conflicting))
+            .collect_vec();
+        if chains.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            chains,
+            conflicting: conflicting.into_iter().sorted().collect(),
+        })
+    }
+
+    /// The requested specs that are part of the conflict.
+    pub fn requested(&self) -> impl Iterator<Item = &str> {
+        self.chains
+            .iter()
+            .filter_map(|chain| chain.first().map(String::as_str))
+    }
+}
+
+impl Display for ConflictExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let requested = self.requested().collect_vec();
+        let conflicting = quoted_list(self.conflicting.iter().map(PackageName::as_source));
+        if requested.len() == 1 {
+            write!(
+                f,
+                "the requested spec '{}' cannot be installed because of {conflicting}:",
+                requested[0]
+            )?;
+        } else {
+            write!(
+                f,
+                "the requested specs {} conflict on {conflicting}:",
+                quoted_list(requested.iter().copied())
+            )?;
+        }
+        for chain in &self.chains {
+            write!(f, "\n  {}", chain.join(" → "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the items as `'a'`, `'a' and 'b'` or `'a', 'b' and 'c'`.
+fn quoted_list<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    let quoted = items.map(|item| format!("'{item}'")).collect_vec();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => quoted.join(""),
+    }
+}
+
+/// Parses the lines of the tree the solver printed, e.g.
+///
+/// ```text
+/// The following packages are incompatible
+/// ├─ foo * can be installed with any of the following options:
+/// │  └─ foo 1 would require
+/// │     └─ shared ==1, which can be installed with any of the following options:
+/// │        └─ shared 1
+/// └─ bar * cannot be installed because there are no viable options:
+///    └─ bar 1 would require
+///       └─ shared ==2, which cannot be installed because there are no viable options:
+///          └─ shared 2, which conflicts with the versions reported above.
+/// ```
+fn parse_tree(output: &str) -> Vec<Node<'_>> {
+    let mut nodes: Vec<Node<'_>> = Vec::new();
+    let mut ancestors: Vec<usize> = Vec::new();
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        let mut text = line;
+        let mut depth = 0;
+        while let Some(rest) = ["├─ ", "└─ ", "│  ", "   "]
+            .iter()
+            .find_map(|indent| text.strip_prefix(indent))
+        {
+            text = rest;
+            depth += 1;
+        }
+
+        ancestors.truncate(depth);
+        let parent = depth.checked_sub(1).and_then(|_| ancestors.last().copied());
+        if let Some(parent) = parent {
+            nodes[parent].has_children = true;
+        }
+        ancestors.push(nodes.len());
+        nodes.push(Node {
+            depth,
+            parent,
+            text: text.trim(),
+            has_children: false,
+        });
+    }
+    nodes
+}
+
+/// Returns the specs on the shortest path from the given root to a spec of one
+/// of the conflicting packages.
+fn shortest_chain(
+    nodes: &[Node<'_>],
+    root: usize,
+    conflicting: &HashSet<PackageName>,
+) -> Option<Vec<String>> {
+    // The nodes are in pre-order, so the subtree of the root is the range of
+    // nodes that follow it and are nested deeper.
+    let target = (root..nodes.len())
+        .take_while(|&index| index == root || nodes[index].depth > nodes[root].depth)
+        .filter(|&index| {
+            nodes[index]
+                .package_name()
+                .is_some_and(|name| conflicting.contains(&name))
+        })
+        .min_by_key(|&index| nodes[index].depth)?;
+
+    let mut chain = vec![nodes[target].spec().to_string()];
+    let mut index = target;
+    while index != root {
+        index = nodes[index].parent?;
+        chain.push(nodes[index].spec().to_string());
+    }
+    chain.reverse();
+    Some(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requested(names: &[&str]) -> HashSet<PackageName> {
+        names
+            .iter()
+            .map(|name| PackageName::try_from(*name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_explains_shared_dependency_conflict() {
+        let output = "\
+The following packages are incompatible
+├─ foo * can be installed with any of the following options:
+│  └─ foo 1 would require
+│     └─ shared ==1, which can be installed with any of the following options:
+│        └─ shared 1
+└─ bar * cannot be installed because there are no viable options:
+   └─ bar 1 would require
+      └─ shared ==2, which cannot be installed because there are no viable options:
+         └─ shared 2, which conflicts with the versions reported above.";
+
+        let explanation =
+            ConflictExplanation::from_conflict(output, &requested(&["foo", "bar"])).unwrap();
+        assert_eq!(
+            explanation.conflicting,
+            vec![PackageName::try_from("shared").unwrap()]
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "the requested specs 'foo *' and 'bar *' conflict on 'shared':\n  foo * → foo 1 → shared ==1\n  bar * → bar 1 → shared ==2"
+        );
+    }
+
+    #[test]
+    fn test_explains_missing_candidates() {
+        let output = "\
+The following packages are incompatible
+└─ foo >=2 cannot be installed because there are no viable options:
+   └─ No candidates were found for foo >=2.";
+
+        let explanation = ConflictExplanation::from_conflict(output, &requested(&["foo"])).unwrap();
+        assert_eq!(
+            explanation.to_string(),
+            "the requested spec 'foo >=2' cannot be installed because of 'foo':\n  foo >=2"
+        );
+    }
+
+    #[test]
+    fn test_no_explanation_without_tree() {
+        let error = SolveError::Unsolvable(vec!["something went wrong".to_string()]);
+        assert!(ConflictExplanation::from_solve_error(&error, &requested(&["foo"])).is_none());
+    }
+
+    #[test]
+    fn test_no_explanation_for_other_solve_errors() {
+        assert!(
+            ConflictExplanation::from_solve_error(&SolveError::Cancelled, &requested(&["foo"]))
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Previous versions of the lock file of a workspace.
+//!
+//! Every time the lock file is updated the version it replaces is archived in
+//! the `.pixi/lock-history` directory, so that a bad update can be rolled back
+//! with `pixi lock rollback`. Revisions are named after the moment they were
+//! archived, which makes them sort from old to new. Only the newest
+//! `lock-history-depth` revisions are kept.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+
+use crate::Workspace;
+
+/// The name of the history directory inside the `.pixi` directory.
+const LOCK_HISTORY_DIR: &str = "lock-history";
+
+/// The extension of an archived lock file.
+const REVISION_EXTENSION: &str = "lock";
+
+/// A previous version of the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockFileRevision {
+    /// The identifier of the revision, the UTC time at which it was archived.
+    pub id: String,
+
+    /// The path of the archived lock file.
+    pub path: PathBuf,
+}
+
+/// The archived lock files of a workspace.
+#[derive(Debug, Clone)]
+pub struct LockFileHistory {
+    dir: PathBuf,
+}
+
+impl LockFileHistory {
+    /// Returns the history of the given workspace.
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        Self::new(workspace.pixi_dir().join(LOCK_HISTORY_DIR))
+    }
+
+    fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the archived revisions, the newest revision first.
+    pub fn revisions(&self) -> miette::Result<Vec<LockFileRevision>> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut revisions = Vec::new();
+        for entry in fs_err::read_dir(&self.dir).into_diagnostic()? {
+            let path = entry.into_diagnostic()?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(REVISION_EXTENSION) {
+                continue;
+            }
+            if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                revisions.push(LockFileRevision {
+                    id: id.to_string(),
+                    path: path.clone(),
+                });
+            }
+        }
+        revisions.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(revisions)
+    }
+
+    /// Archives the contents of a lock file that is about to be replaced and
+    /// removes the oldest revisions so that at most `depth` are kept.
+    pub(crate) fn archive(&self, contents: &str, depth: usize) -> miette::Result<()> {
+        if depth == 0 {
+            return Ok(());
+        }
+        fs_err::create_dir_all(&self.dir)
+            .into_diagnostic()
+            .context("failed to create the lock file history directory")?;
+
+        let id = Utc::now().format("%Y%m%dT%H%M%S%.6fZ").to_string();
+        let path = self.dir.join(format!("{id}.{REVISION_EXTENSION}"));
+        fs_err::write(&path, contents)
+            .into_diagnostic()
+            .context("failed to archive the previous lock file")?;
+
+        for revision in self.revisions()?.into_iter().skip(depth) {
+            fs_err::remove_file(&revision.path).into_diagnostic()?;
+        }
+        Ok(())
+    }
+
+    /// Restores a revision to the given lock file path. The revision and all
+    /// newer revisions are removed from the history, and the lock file that
+    /// is replaced is archived like any other update, so that the rollback
+    /// itself can be rolled back.
+    pub fn restore(
+        &self,
+        revision: &LockFileRevision,
+        lock_file_path: &Path,
+        depth: usize,
+    ) -> miette::Result<()> {
+        let restored = fs_err::read_to_string(&revision.path)
+            .into_diagnostic()
+            .context("failed to read the archived lock file")?;
+
+        for newer in self
+            .revisions()?
+            .into_iter()
+            .take_while_inclusive(|newer| newer.id != revision.id)
+        {
+            fs_err::remove_file(&newer.path).into_diagnostic()?;
+        }
+
+        if lock_file_path.is_file() {
+            let current = fs_err::read_to_string(lock_file_path).into_diagnostic()?;
+            self.archive(&current, depth)?;
+        }
+
+        fs_err::write(lock_file_path, restored)
+            .into_diagnostic()
+            .context("failed to restore the lock file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_keeps_the_newest_revisions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let history = LockFileHistory::new(temp_dir.path().join(LOCK_HISTORY_DIR));
+        assert!(history.revisions().unwrap().is_empty());
+
+        for contents in ["first", "second", "third"] {
+            history.archive(contents, 2).unwrap();
+        }
+
+        let revisions = history.revisions().unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(fs_err::read_to_string(&revisions[0].path).unwrap(), "third");
+        assert_eq!(
+            fs_err::read_to_string(&revisions[1].path).unwrap(),
+            "second"
+        );
+    }
+
+    #[test]
+    fn test_restore_removes_newer_revisions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let history = LockFileHistory::new(temp_dir.path().join(LOCK_HISTORY_DIR));
+        for contents in ["first", "second", "third"] {
+            history.archive(contents, 5).unwrap();
+        }
+
+        let lock_file_path = temp_dir.path().join("pixi.lock");
+        fs_err::write(&lock_file_path, "current").unwrap();
+        let second = history.revisions().unwrap()[1].clone();
+        history.restore(&second, &lock_file_path, 5).unwrap();
+
+        assert_eq!(fs_err::read_to_string(&lock_file_path).unwrap(), "second");
+
+        // The replaced lock file is archived as the newest revision.
+        let revisions = history.revisions().unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(
+            fs_err::read_to_string(&revisions[0].path).unwrap(),
+            "current"
+        );
+        assert_eq!(fs_err::read_to_string(&revisions[1].path).unwrap(), "first");
+    }
+}
@@ -0,0 +1,432 @@
+//! Metadata about how a lock file was written.
+//!
+//! The metadata is stored as a block of comments at the top of the lock file.
+//! Comments are ignored by the lock file parser, so lock files with metadata
+//! stay readable by pixi versions that predate it, and the metadata never
+//! affects whether the lock file is up-to-date.
+//!
+//! ```yaml
+//! # This file is generated by pixi, do not edit it manually.
+//! # pixi-version: 0.70.2
+//! # channel-priority: strict
+//! # platforms: linux-64, osx-arm64
+//! # generated-at: 2026-01-01
+//! # download-url: https://conda.anaconda.org/conda-forge/noarch/foo-1.0-0.conda -> https://prefix.dev/conda-forge/noarch/foo-1.0-0.conda
+//! version: 6
+//! ...
+//! ```
+//!
+//! A `download-url` line records that a package was downloaded from a mirror,
+//! as the url of the package in the lock file and the url it was downloaded
+//! from. When the lock file is installed without a mirror configured for the
+//! package, only that package is downloaded from the recorded url. The
+//! recorded urls never change the mirror configuration.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
+use miette::{Context, IntoDiagnostic};
+use pixi_consts::consts;
+use pixi_record::UnresolvedPixiRecord;
+use pixi_utils::atomic_write::atomic_write_sync;
+use rattler_conda_types::RepoDataRecord;
+use rattler_conda_types::Version;
+use rattler_lock::LockFile;
+use url::Url;
+
+use crate::Workspace;
+
+/// The first line of the metadata block.
+const HEADER: &str = "# This file is generated by pixi, do not edit it manually.";
+
+const PIXI_VERSION_KEY: &str = "pixi-version";
+const CHANNEL_PRIORITY_KEY: &str = "channel-priority";
+const PLATFORMS_KEY: &str = "platforms";
+const GENERATED_AT_KEY: &str = "generated-at";
+const DOWNLOAD_URL_KEY: &str = "download-url";
+
+/// The metadata recorded at the top of a lock file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockFileMetadata {
+    /// The version of pixi that wrote the lock file.
+    pub pixi_version: Option<String>,
+
+    /// The channel priority of the workspace at the time of the solve.
+    pub channel_priority: Option<String>,
+
+    /// The platforms that were solved for.
+    pub platforms: Vec<String>,
+
+    /// The day the lock file was written. A day rather than a moment, so that
+    /// recreating a lock file with the same content results in the same file.
+    pub generated_at: Option<NaiveDate>,
+
+    /// The urls of the conda packages that were downloaded from a mirror, as
+    /// pairs of the url in the lock file and the url of the mirror.
+    pub download_urls: Vec<(Url, Url)>,
+}
+
+impl LockFileMetadata {
+    /// Creates the metadata for a lock file of the workspace that is written
+    /// by this version of pixi.
+    pub fn new(workspace: &Workspace, lock_file: &LockFile) -> Self {
+        Self {
+            pixi_version: Some(consts::PIXI_VERSION.to_string()),
+            channel_priority: Some(
+                workspace
+                    .workspace_manifest()
+                    .workspace
+                    .channel_priority
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            platforms: lock_file
+                .platforms()
+                .map(|platform| platform.name().to_string())
+                .collect(),
+            generated_at: Some(generated_at()),
+            download_urls: mirrored_download_urls(workspace.config().mirror_map(), lock_file),
+        }
+    }
+
+    /// Parses the metadata from the contents of a lock file. Returns the
+    /// default, empty metadata if the lock file has none.
+    pub fn from_lock_file_contents(contents: &str) -> Self {
+        let mut metadata = Self::default();
+        for line in contents.lines().take_while(|line| line.starts_with('#')) {
+            let Some((key, value)) = line.trim_start_matches('#').split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                PIXI_VERSION_KEY => metadata.pixi_version = Some(value.to_string()),
+                CHANNEL_PRIORITY_KEY => metadata.channel_priority = Some(value.to_string()),
+                PLATFORMS_KEY => {
+                    metadata.platforms = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|platform| !platform.is_empty())
+                        .map(ToString::to_string)
+                        .collect()
+                }
+                GENERATED_AT_KEY => metadata.generated_at = NaiveDate::from_str(value).ok(),
+                DOWNLOAD_URL_KEY => {
+                    let download_url = value.split_once(" -> ").and_then(|(url, download_url)| {
+                        Some((Url::parse(url).ok()?, Url::parse(download_url).ok()?))
+                    });
+                    metadata.download_urls.extend(download_url);
+                }
+                _ => {}
+            }
+        }
+        metadata
+    }
+
+    /// Reads the metadata of the lock file at the given path. Only the
+    /// comments at the top of the file are read.
+    pub fn from_path(path: &Path) -> miette::Result<Self> {
+        let file = fs_err::File::open(path).into_diagnostic()?;
+        let header = BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .take_while(|line| line.starts_with('#'))
+            .map(|line| line + "\n")
+            .collect::<String>();
+        Ok(Self::from_lock_file_contents(&header))
+    }
+
+    /// Returns the url that the package with the given url in the lock file
+    /// was downloaded from, if it was downloaded from a mirror.
+    pub fn download_url(&self, url: &Url) -> Option<&Url> {
+        self.download_urls
+            .iter()
+            .find_map(|(locked, download_url)| (locked == url).then_some(download_url))
+    }
+
+    /// Replaces the urls of the binary records with the urls they were
+    /// downloaded from when the lock file was written. Records with a mirror
+    /// in `mirrors` keep their url, the configured mirror takes precedence.
+    pub(crate) fn apply_download_urls(
+        &self,
+        records: Vec<UnresolvedPixiRecord>,
+        mirrors: &HashMap<Url, Vec<Url>>,
+    ) -> Vec<UnresolvedPixiRecord> {
+        if self.download_urls.is_empty() {
+            return records;
+        }
+        records
+            .into_iter()
+            .map(|record| match record {
+                UnresolvedPixiRecord::Binary(binary) => match self.download_url(&binary.url) {
+                    Some(download_url) if mirror_for(mirrors, &binary.url).is_none() => {
+                        tracing::debug!(
+                            "downloading {} from the recorded url {download_url}",
+                            binary.url
+                        );
+                        UnresolvedPixiRecord::Binary(Arc::new(RepoDataRecord {
+                            url: download_url.clone(),
+                            ..RepoDataRecord::clone(&binary)
+                        }))
+                    }
+                    _ => UnresolvedPixiRecord::Binary(binary),
+                },
+                record => record,
+            })
+            .collect()
+    }
+
+    /// Renders the metadata as the comment block that precedes the lock file.
+    pub fn render(&self) -> String {
+        let mut header = vec![HEADER.to_string()];
+        if let Some(pixi_version) = &self.pixi_version {
+            header.push(format!("# {PIXI_VERSION_KEY}: {pixi_version}"));
+        }
+        if let Some(channel_priority) = &self.channel_priority {
+            header.push(format!("# {CHANNEL_PRIORITY_KEY}: {channel_priority}"));
+        }
+        if !self.platforms.is_empty() {
+            header.push(format!(
+                "# {PLATFORMS_KEY}: {}",
+                self.platforms.iter().join(", ")
+            ));
+        }
+        if let Some(generated_at) = &self.generated_at {
+            header.push(format!("# {GENERATED_AT_KEY}: {generated_at}"));
+        }
+        for (url, download_url) in &self.download_urls {
+            header.push(format!("# {DOWNLOAD_URL_KEY}: {url} -> {download_url}"));
+        }
+        header.into_iter().map(|line| line + "\n").collect()
+    }
+
+    /// Returns true if the lock file was written by a newer version of pixi
+    /// than this one.
+    pub fn is_written_by_newer_pixi(&self) -> bool {
+        let (Some(recorded), Ok(current)) = (
+            self.pixi_version
+                .as_deref()
+                .and_then(|version| Version::from_str(version).ok()),
+            Version::from_str(consts::PIXI_VERSION),
+        ) else {
+            return false;
+        };
+        recorded > current
+    }
+
+    /// Warns if the lock file was written by a newer version of pixi.
+    pub fn warn_if_written_by_newer_pixi(&self, lock_file_path: &Path) {
+        if self.is_written_by_newer_pixi() {
+            tracing::warn!(
+                "'{}' was written by pixi {}, which is newer than this version ({}). Some of its contents may not be understood, consider updating pixi.",
+                lock_file_path.display(),
+                self.pixi_version.as_deref().unwrap_or_default(),
+                consts::PIXI_VERSION
+            );
+        }
+    }
+}
+
+/// Returns the day a lock file is generated at. `SOURCE_DATE_EPOCH` takes
+/// precedence over the current time, so that a lock file can be reproduced
+/// byte for byte on another day.
+fn generated_at() -> NaiveDate {
+    pixi_utils::reproducible::reproducible_mtime()
+        .and_then(|mtime| DateTime::from_timestamp(mtime.unix_seconds(), 0))
+        .unwrap_or_else(Utc::now)
+        .date_naive()
+}
+
+/// Returns the url with a trailing slash, so that it can be used as the prefix
+/// of the urls of the packages of a channel.
+fn with_trailing_slash(url: &Url) -> Url {
+    if url.path().ends_with('/') {
+        url.clone()
+    } else {
+        let mut url = url.clone();
+        url.set_path(&format!("{}/", url.path()));
+        url
+    }
+}
+
+/// Returns the url that a package with the given url is downloaded from
+/// through the first, preferred mirror of its channel in `mirrors`. If the
+/// url matches multiple channels the mirror of the longest one is used.
+fn mirror_for(mirrors: &HashMap<Url, Vec<Url>>, url: &Url) -> Option<Url> {
+    let (rest, mirror) = mirrors
+        .iter()
+        .filter_map(|(channel, mirrors)| {
+            let channel = with_trailing_slash(channel);
+            let rest = url.as_str().strip_prefix(channel.as_str())?;
+            Some((rest, mirrors.first()?))
+        })
+        .min_by_key(|(rest, _)| rest.len())?;
+    with_trailing_slash(mirror).join(rest).ok()
+}
+
+/// Returns the urls of the conda packages of the lock file that are
+/// downloaded from a mirror, together with the url of the mirror.
+fn mirrored_download_urls(
+    mirrors: &HashMap<Url, Vec<Url>>,
+    lock_file: &LockFile,
+) -> Vec<(Url, Url)> {
+    if mirrors.is_empty() {
+        return Vec::new();
+    }
+    lock_file
+        .environments()
+        .flat_map(|(_, environment)| environment.conda_packages_by_platform())
+        .flat_map(|(_, packages)| packages)
+        .filter_map(|package| {
+            let url = package.location().as_url()?;
+            let download_url = mirror_for(mirrors, url)?;
+            Some((url.clone(), download_url))
+        })
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+/// Returns the contents of a lock file without its metadata block.
+fn strip_metadata(contents: &str) -> &str {
+    let mut rest = contents;
+    while rest.starts_with('#') {
+        rest = rest.split_once('\n').map_or("", |(_, rest)| rest);
+    }
+    rest
+}
+
+/// Writes the lock file to the given path, preceded by its metadata.
+///
+/// If the lock file itself did not change the previous file is kept as is,
+/// so that the recorded metadata only changes when the locked content does.
+/// Otherwise the contents of the replaced file are returned, if there was one.
+pub(crate) fn write_lock_file_with_metadata(
+    lock_file: &LockFile,
+    metadata: &LockFileMetadata,
+    path: &Path,
+) -> miette::Result<Option<String>> {
+    let contents = lock_file
+        .render_to_string()
+        .into_diagnostic()
+        .context("failed to render the lock file")?;
+
+    let previous = fs_err::read_to_string(path).ok();
+    let unchanged = previous.as_deref().is_some_and(|previous| {
+        previous.starts_with(HEADER) && strip_metadata(previous) == contents
+    });
+    if unchanged {
+        return Ok(None);
+    }
+
+    atomic_write_sync(path, format!("{}{contents}", metadata.render()))
+        .into_diagnostic()
+        .context("failed to write lock file to disk")?;
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let metadata = LockFileMetadata {
+            pixi_version: Some(consts::PIXI_VERSION.to_string()),
+            channel_priority: Some("strict".to_string()),
+            platforms: vec!["linux-64".to_string(), "osx-arm64".to_string()],
+            generated_at: Some(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            download_urls: vec![(
+                Url::parse("https://conda.anaconda.org/conda-forge/noarch/foo-1.0-0.conda")
+                    .unwrap(),
+                Url::parse("https://prefix.dev/conda-forge/noarch/foo-1.0-0.conda").unwrap(),
+            )],
+        };
+        let contents = format!("{}version: 6\n", metadata.render());
+
+        assert_eq!(
+            LockFileMetadata::from_lock_file_contents(&contents),
+            metadata
+        );
+        assert_eq!(strip_metadata(&contents), "version: 6\n");
+        assert!(!metadata.is_written_by_newer_pixi());
+    }
+
+    #[test]
+    fn test_mirror_for() {
+        let mirrors = HashMap::from([(
+            Url::parse("https://conda.anaconda.org/conda-forge").unwrap(),
+            vec![Url::parse("https://prefix.dev/conda-forge").unwrap()],
+        )]);
+        assert_eq!(
+            mirror_for(
+                &mirrors,
+                &Url::parse("https://conda.anaconda.org/conda-forge/noarch/foo-1.0-0.conda")
+                    .unwrap()
+            )
+            .unwrap()
+            .as_str(),
+            "https://prefix.dev/conda-forge/noarch/foo-1.0-0.conda"
+        );
+        assert!(
+            mirror_for(
+                &mirrors,
+                &Url::parse("https://conda.anaconda.org/bioconda/noarch/bar-1.0-0.conda").unwrap()
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_mirror_for_longest_prefix() {
+        let mirrors = HashMap::from([
+            (
+                Url::parse("https://conda.anaconda.org").unwrap(),
+                vec![Url::parse("https://mirror.example.com").unwrap()],
+            ),
+            (
+                Url::parse("https://conda.anaconda.org/conda-forge").unwrap(),
+                vec![Url::parse("https://prefix.dev/conda-forge").unwrap()],
+            ),
+        ]);
+        assert_eq!(
+            mirror_for(
+                &mirrors,
+                &Url::parse("https://conda.anaconda.org/conda-forge/noarch/foo-1.0-0.conda")
+                    .unwrap()
+            )
+            .unwrap()
+            .as_str(),
+            "https://prefix.dev/conda-forge/noarch/foo-1.0-0.conda"
+        );
+        assert_eq!(
+            mirror_for(
+                &mirrors,
+                &Url::parse("https://conda.anaconda.org/bioconda/noarch/bar-1.0-0.conda").unwrap()
+            )
+            .unwrap()
+            .as_str(),
+            "https://mirror.example.com/bioconda/noarch/bar-1.0-0.conda"
+        );
+    }
+
+    #[test]
+    fn test_metadata_of_newer_pixi() {
+        let metadata =
+            LockFileMetadata::from_lock_file_contents("# pixi-version: 999.0.0\nversion: 6\n");
+        assert!(metadata.is_written_by_newer_pixi());
+    }
+
+    #[test]
+    fn test_lock_file_without_metadata() {
+        let metadata = LockFileMetadata::from_lock_file_contents("version: 6\n");
+        assert_eq!(metadata, LockFileMetadata::default());
+        assert!(!metadata.is_written_by_newer_pixi());
+    }
+}
@@ -1,37 +1,50 @@
+mod conflict;
+mod history;
 mod install_subset;
+mod metadata;
 mod outdated;
 mod package_identifier;
 mod platform_rename;
 mod platform_setup;
+mod python_abi;
 mod records_by_name;
 mod reporter;
 mod resolve;
 mod satisfiability;
+mod state_marker;
+mod unsatisfied_specs;
 mod update;
 mod utils;
 pub mod virtual_packages;
 
 pub use crate::environment::CondaPrefixUpdater;
+pub use conflict::ConflictExplanation;
+pub use history::{LockFileHistory, LockFileRevision};
 pub use install_subset::{FilteredPackages, InstallSubset};
+pub use metadata::LockFileMetadata;
 pub use package_identifier::PypiPackageIdentifier;
 use pixi_install_pypi::LockedPypiRecord;
 use pixi_record::PixiRecord;
 pub use pixi_uv_context::UvResolutionContext;
+pub use python_abi::PythonAbiChange;
 pub use rattler_lock::Verbatim;
 pub use records_by_name::{
     HasNameVersion, PixiRecordsByName, PypiRecordsByName, UnresolvedPixiRecordsByName,
 };
+pub(crate) use resolve::pypi::SolveError as SolvePypiEnvironmentError;
 pub use resolve::pypi::resolve_pypi;
 pub(crate) use satisfiability::resolve_lock_platform;
 pub use satisfiability::{
     Dependency, EnvironmentUnsat, PlatformUnsat, resolve_dev_dependencies,
     verify_environment_satisfiability, verify_platform_satisfiability,
 };
+pub use unsatisfied_specs::{UnsatisfiedSpec, find_unsatisfied_specs};
 pub use update::{
-    LockFileDerivedData, PackageFilterNames, ReinstallEnvironment, ReinstallPackages,
-    SolveCondaEnvironmentError, UpdateContext, UpdateLockFileOptions, UpdateMode, UpdatedPrefix,
+    EnvironmentPackages, LockFileDerivedData, LockFileOutOfDateError, PackageFilterNames,
+    ReinstallEnvironment, ReinstallPackages, SolveCondaEnvironmentError, UpdateContext,
+    UpdateLockFileOptions, UpdateMode, UpdatedPrefix,
 };
-pub use utils::{LockedPackageKind, filter_lock_file};
+pub use utils::{LockedPackageKind, filter_lock_file, lock_file_hash};
 
 pub use utils::IoConcurrencyLimit;
 
@@ -317,6 +317,7 @@ async fn find_unsatisfiable_targets<'p>(
                     // Don't add to disregard_locked_content.
                 }
                 EnvironmentUnsat::ChannelsMismatch
+                | EnvironmentUnsat::ChannelPlatformsMismatch { .. }
                 | EnvironmentUnsat::InvalidChannel(_)
                 | EnvironmentUnsat::ChannelPriorityMismatch { .. }
                 | EnvironmentUnsat::SolveStrategyMismatch { .. }
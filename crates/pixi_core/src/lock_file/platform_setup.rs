@@ -69,6 +69,7 @@ pub(crate) fn build_platform_setup(
         .cloned()
         .map(|c| c.into_base_url(&channel_config))
         .collect::<Result<Vec<_>, _>>()?;
+    let channel_subdirs = environment.channel_subdirs(&channel_config)?;
     let variant_config = environment.workspace().variants(platform)?;
     let virtual_packages: Vec<GenericVirtualPackage> = environment
         .virtual_packages(platform)
@@ -87,6 +88,7 @@ pub(crate) fn build_platform_setup(
         platform.name().to_string(),
         EnvironmentSpec {
             channels,
+            channel_subdirs,
             build_environment,
             variants: variant_config,
             exclude_newer: None,
@@ -0,0 +1,87 @@
+//! Detection of environments whose Python minor version changed when the lock
+//! file was updated.
+//!
+//! Wheels of PyPI packages with compiled extensions are built for a specific
+//! Python ABI, so the PyPI packages of such an environment have to be
+//! reinstalled. That happens when the new Python is installed into the prefix,
+//! this module only reports the change when the lock file is updated.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use pypi_modifiers::pypi_tags::is_python_record;
+use rattler_conda_types::Version;
+use rattler_lock::LockFile;
+
+/// An environment platform whose Python minor version changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonAbiChange {
+    pub environment: String,
+    pub platform: String,
+    pub previous: Version,
+    pub current: Version,
+}
+
+impl Display for PythonAbiChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the Python version of the '{}' environment on {} changed from {} to {}",
+            self.environment, self.platform, self.previous, self.current
+        )
+    }
+}
+
+/// Returns the locked Python version of every environment platform.
+fn locked_python_versions(lock_file: &LockFile) -> HashMap<(String, String), Version> {
+    let mut versions = HashMap::new();
+    for (name, environment) in lock_file.environments() {
+        for (platform, packages) in environment.conda_packages_by_platform() {
+            let python = packages
+                .filter_map(|package| package.record())
+                .find(|record| is_python_record(record));
+            if let Some(record) = python {
+                versions.insert(
+                    (name.to_string(), platform.name().to_string()),
+                    record.version.version().clone(),
+                );
+            }
+        }
+    }
+    versions
+}
+
+/// Returns the environment platforms with PyPI packages whose Python minor
+/// version differs between the `previous` and the `current` lock file.
+pub(crate) fn python_abi_changes(previous: &LockFile, current: &LockFile) -> Vec<PythonAbiChange> {
+    let previous_versions = locked_python_versions(previous);
+    let mut changes = Vec::new();
+    for ((environment, platform), current_version) in locked_python_versions(current) {
+        let Some(previous_version) =
+            previous_versions.get(&(environment.clone(), platform.clone()))
+        else {
+            continue;
+        };
+        if previous_version.as_major_minor() == current_version.as_major_minor() {
+            continue;
+        }
+        let has_pypi_packages = current.environment(&environment).is_some_and(|env| {
+            env.lock_file()
+                .platform(&platform)
+                .is_some_and(|p| env.has_pypi_packages(p))
+        });
+        if !has_pypi_packages {
+            continue;
+        }
+        changes.push(PythonAbiChange {
+            environment,
+            platform,
+            previous: previous_version.clone(),
+            current: current_version,
+        });
+    }
+    changes.sort_by(|a, b| (&a.environment, &a.platform).cmp(&(&b.environment, &b.platform)));
+    changes
+}
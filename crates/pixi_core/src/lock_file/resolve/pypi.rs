@@ -246,10 +246,12 @@ pub enum SolveError {
 }
 
 /// Creates a custom `SolveError` from a `ResolveError`.
-/// to add some extra information about locked conda packages
+/// to add some extra information about locked conda packages and the python
+/// version the packages were resolved for.
 fn create_solve_error(
     error: ResolveError,
     conda_python_packages: &CondaPythonPackages,
+    python_version: &str,
 ) -> SolveError {
     match error {
         ResolveError::NoSolution(no_solution) => {
@@ -269,15 +271,23 @@ fn create_solve_error(
                 })
                 .collect();
 
-            let advice = if conflicting_packages.is_empty() {
-                None
-            } else {
-                Some(format!(
+            let mut advice = Vec::new();
+            if !conflicting_packages.is_empty() {
+                advice.push(format!(
                     "The following PyPI packages have been pinned by the conda solve, and this version may be causing a conflict:\n{}
 See https://pixi.sh/latest/concepts/conda_pypi/#pinned-package-conflicts for more information.",
                     conflicting_packages.join("\n")
-                ))
-            };
+                ));
+            }
+
+            // Distributions that don't support the python version of the
+            // environment are rejected, say which version that is.
+            if packages.iter().any(|name| name.as_ref() == "python") {
+                advice.push(format!(
+                    "The PyPI dependencies are resolved for Python {python_version}, which was selected by the conda solve. Packages without a wheel or source distribution that supports Python {python_version} cannot be installed, consider changing the `python` dependency or the version of the package."
+                ));
+            }
+            let advice = (!advice.is_empty()).then(|| advice.join("\n\n"));
 
             SolveError::NoSolution {
                 source: no_solution,
@@ -457,6 +467,11 @@ pub async fn resolve_pypi(
     .into_diagnostic()
     .context("error creating version specifier for python version")?;
 
+    let python_version = python_record
+        .version()
+        .expect("python record always has a version")
+        .as_str()
+        .to_string();
     let requires_python =
         RequiresPython::from_specifiers(&uv_pep440::VersionSpecifiers::from(python_specifier));
     tracing::debug!(
@@ -486,6 +501,7 @@ pub async fn resolve_pypi(
     let registry_client = {
         let base_client_builder = context.base_client_builder(
             allow_insecure_hosts,
+            &index_locations,
             Some(&marker_environment),
             Connectivity::Online,
         );
@@ -518,6 +534,21 @@ pub async fn resolve_pypi(
                 })
                 .collect::<Result<Vec<_>, _>>()
         }).transpose()?.unwrap_or_default();
+    let pypi_constraints = pypi_options
+        .constraints
+        .iter()
+        .flatten()
+        .map(|(name, spec)| {
+            as_uv_req(spec, name.as_normalized().as_ref(), project_root)
+                .into_diagnostic()
+                .with_context(|| {
+                    format!(
+                        "failed to convert the constraint {name}:{spec} to a uv requirement",
+                        name = name.as_source(),
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     let flat_index = {
         let flat_index_client = FlatIndexClient::new(
@@ -738,7 +769,11 @@ pub async fn resolve_pypi(
 
     let resolver_env = ResolverEnvironment::specific(marker_environment.clone().into());
 
-    let constraints = Constraints::from_requirements(constraints.iter().cloned());
+    // Constrain the packages to the python packages installed by conda and the
+    // constraints of the user. Constraints only apply to packages that are
+    // already part of the resolution, they don't add packages.
+    let constraints =
+        Constraints::from_requirements(constraints.iter().cloned().chain(pypi_constraints));
 
     let overrides = Overrides::from_requirements(dependency_overrides);
 
@@ -844,7 +879,7 @@ pub async fn resolve_pypi(
 
         let resolution = Box::pin(resolver.resolve())
             .await
-            .map_err(|e| create_solve_error(e, &conda_python_packages))?;
+            .map_err(|e| create_solve_error(e, &conda_python_packages, &python_version))?;
 
         let resolution = Resolution::from(resolution);
 
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::Path,
     str::FromStr,
 };
@@ -20,7 +20,7 @@ use uv_distribution_filename::{DistExtension, ExtensionError, SourceDistExtensio
 use super::errors::{
     EnvironmentUnsat, IndexesMismatch, PlatformDefinitionChanged, verify_exclude_newer,
 };
-use crate::workspace::{Environment, grouped_environment::GroupedEnvironment};
+use crate::workspace::{Environment, HasWorkspaceRef, grouped_environment::GroupedEnvironment};
 
 /// Verifies that all the requirements of the specified `environment` can be
 /// satisfied with the packages present in the lock file.
@@ -68,6 +68,15 @@ pub fn verify_environment_satisfiability(
         return Err(EnvironmentUnsat::ChannelsMismatch);
     }
 
+    // Check that the packages of channels that are restricted with `platforms`
+    // were locked from one of the listed subdirs, so that restricting the
+    // platforms of a channel re-solves the environment. The solve group is
+    // solved with the subdirs of all its environments.
+    let channel_subdirs = grouped_env.channel_subdirs(&config)?;
+    if !channel_subdirs.is_empty() {
+        verify_channel_subdirs(&channel_subdirs, &config, locked_environment)?;
+    }
+
     let platforms = environment.platforms();
     let locked_platform_data: Vec<rattler_lock::PlatformData> = locked_environment
         .platforms()
@@ -257,6 +266,40 @@ pub fn verify_environment_satisfiability(
     Ok(())
 }
 
+/// Verifies that every conda package that was locked from a channel in
+/// `channel_subdirs` comes from one of the subdirs listed for that channel.
+fn verify_channel_subdirs(
+    channel_subdirs: &BTreeMap<ChannelUrl, Vec<Platform>>,
+    config: &rattler_conda_types::ChannelConfig,
+    locked_environment: rattler_lock::Environment<'_>,
+) -> Result<(), EnvironmentUnsat> {
+    for (_platform, packages) in locked_environment.conda_packages_by_platform() {
+        for package in packages {
+            let Some(binary) = package.as_binary() else {
+                continue;
+            };
+            let Some(channel) = binary.channel.as_ref().map(ToString::to_string) else {
+                continue;
+            };
+            let channel_url = NamedChannelOrUrl::from_str(&channel)
+                .unwrap_or_else(|_err| NamedChannelOrUrl::Name(channel.clone()))
+                .into_base_url(config)?;
+            let Some(subdirs) = channel_subdirs.get(&channel_url) else {
+                continue;
+            };
+            let subdir = &binary.package_record.subdir;
+            if !subdirs.iter().any(|platform| platform.as_str() == subdir) {
+                return Err(EnvironmentUnsat::ChannelPlatformsMismatch {
+                    package: binary.package_record.name.as_source().to_string(),
+                    channel,
+                    subdir: subdir.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 struct PypiWheelTagsCheck {
     platform_wheel_tags: HashMap<Platform, Tags>,
 }
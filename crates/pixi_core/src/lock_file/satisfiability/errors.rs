@@ -29,6 +29,15 @@ pub enum EnvironmentUnsat {
     #[error("channels were extended with additional lower-priority channels")]
     ChannelsExtended,
 
+    #[error(
+        "the package '{package}' was locked from the subdir '{subdir}' of '{channel}', which is not one of the platforms of the channel"
+    )]
+    ChannelPlatformsMismatch {
+        package: String,
+        channel: String,
+        subdir: String,
+    },
+
     #[error("platform(s) '{platforms}' present in the lock file but not in the environment", platforms = .0.iter().map(|p| p.as_str()).join(", ")
     )]
     AdditionalPlatformsInLockFile(HashSet<PixiPlatformName>),
@@ -617,6 +617,26 @@ async fn verify_package_platform_satisfiability(
         .collect::<Result<indexmap::IndexMap<_, _>, _>>()
         .map_err(CommandDispatcherError::Failed)?;
 
+    // retrieve the constraints on pypi packages
+    // map it to (name => requirement) for later matching
+    let pypi_constraints = ctx
+        .environment
+        .pypi_options()
+        .constraints
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, req)| -> Result<_, Box<PlatformUnsat>> {
+            let uv_req = as_uv_req(&req, name.as_source(), ctx.project_root).map_err(|e| {
+                Box::new(PlatformUnsat::AsPep508Error(
+                    name.as_normalized().clone(),
+                    e,
+                ))
+            })?;
+            Ok((uv_req.name.clone(), uv_req))
+        })
+        .collect::<Result<indexmap::IndexMap<_, _>, _>>()
+        .map_err(CommandDispatcherError::Failed)?;
+
     // Find the python interpreter from the list of conda packages. Note that this
     // refers to the locked python interpreter, it might not match the specs
     // from the environment. That is ok because we will find that out when we
@@ -921,6 +941,19 @@ async fn verify_package_platform_satisfiability(
                                 .cloned()
                                 .unwrap_or(requirement);
 
+                            // the locked package must also satisfy the constraint on it
+                            if let Some(constraint) = pypi_constraints.get(&requirement.name)
+                                && let Err(err) = pypi_satisfies_requirement(
+                                    constraint,
+                                    record,
+                                    ctx.project_root,
+                                    origin,
+                                    &locked_indexes,
+                                )
+                            {
+                                delayed_pypi_error.get_or_insert(err);
+                            }
+
                             if requirement.is_editable() {
                                 if let Err(err) =
                                     pypi_satisfies_editable(&requirement, record, ctx.project_root)
@@ -604,6 +604,7 @@ async fn read_local_package_metadata(
     let registry_client = {
         let base_client_builder = ctx.uv_context.base_client_builder(
             allow_insecure_hosts.clone(),
+            &index_locations,
             Some(&marker_environment),
             Connectivity::Online,
         );
@@ -0,0 +1,98 @@
+//! A marker that records the state of the workspace at the moment its lock
+//! file was last verified to be up-to-date.
+//!
+//! Verifying that the lock file satisfies the manifest requires loading and
+//! comparing every locked package. When neither the manifest, the lock file
+//! nor the channel configuration changed since the last successful
+//! verification, the marker allows skipping that work entirely.
+
+use std::{
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use pixi_consts::consts;
+use rattler_lock::LockFile;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::Workspace;
+
+/// The name of the marker file inside the `.pixi` directory.
+const MARKER_FILE_NAME: &str = "lock-file-state";
+
+/// A hash of everything that determines whether the lock file satisfies the
+/// workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceStateHash(String);
+
+impl WorkspaceStateHash {
+    /// Computes the hash of the current state of the workspace and its lock
+    /// file.
+    ///
+    /// Returns `None` if the state cannot be captured, e.g. because the lock
+    /// file contains packages that are built from source. Their up-to-dateness
+    /// depends on files outside of the manifest and the lock file.
+    pub(crate) fn from_workspace(workspace: &Workspace, lock_file: &LockFile) -> Option<Self> {
+        if contains_source_packages(lock_file) {
+            return None;
+        }
+
+        let manifest = fs_err::read_to_string(&workspace.workspace.provenance.path).ok()?;
+        let locked = fs_err::read_to_string(workspace.lock_file_path()).ok()?;
+
+        let mut hasher = Xxh3::new();
+        consts::PIXI_VERSION.hash(&mut hasher);
+        manifest.hash(&mut hasher);
+        locked.hash(&mut hasher);
+        format!("{:?}", workspace.channel_config()).hash(&mut hasher);
+
+        // Virtual package overrides change which locked packages are usable.
+        let mut overrides = std::env::vars()
+            .filter(|(key, _)| key.starts_with("CONDA_OVERRIDE_"))
+            .collect::<Vec<_>>();
+        overrides.sort();
+        overrides.hash(&mut hasher);
+
+        Some(Self(format!("{:x}", hasher.finish())))
+    }
+
+    /// Returns true if the marker of the workspace records this state.
+    pub(crate) fn matches_marker(&self, workspace: &Workspace) -> bool {
+        fs_err::read_to_string(marker_path(workspace))
+            .is_ok_and(|contents| contents.trim() == self.0)
+    }
+
+    /// Records this state in the marker of the workspace.
+    ///
+    /// The marker is only written if the `.pixi` directory already exists, it
+    /// is a cache so failing to write it is not an error.
+    pub(crate) fn write_marker(&self, workspace: &Workspace) {
+        if !workspace.pixi_dir().is_dir() {
+            return;
+        }
+        if let Err(err) = fs_err::write(marker_path(workspace), &self.0) {
+            tracing::debug!("failed to write the lock file state marker: {err}");
+        }
+    }
+}
+
+fn marker_path(workspace: &Workspace) -> PathBuf {
+    workspace.pixi_dir().join(MARKER_FILE_NAME)
+}
+
+/// Returns true if any environment of the lock file contains a conda source
+/// package or a PyPI package that is installed from a local path.
+fn contains_source_packages(lock_file: &LockFile) -> bool {
+    lock_file.environments().any(|(_, environment)| {
+        lock_file.platforms().any(|platform| {
+            environment
+                .conda_packages(platform)
+                .is_some_and(|mut packages| packages.any(|package| package.as_source().is_some()))
+                || environment
+                    .pypi_packages(platform)
+                    .is_some_and(|mut packages| {
+                        packages.any(|package| package.location().as_url().is_none())
+                    })
+        })
+    })
+}
@@ -0,0 +1,224 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use pixi_manifest::{EnvironmentName, FeaturesExt, HasWorkspaceManifest, PixiPlatformName};
+use pixi_pypi_spec::{PixiPypiSource, PixiPypiSpec, PypiPackageName};
+use pixi_uv_conversions::as_uv_req;
+use rattler_conda_types::{ChannelUrl, NamedChannelOrUrl};
+use rattler_lock::{LockFile, LockedPackage, PypiPackageData};
+use uv_distribution_types::RequirementSource;
+
+use crate::{
+    Workspace,
+    lock_file::resolve_lock_platform,
+    workspace::{Environment, grouped_environment::GroupedEnvironment},
+};
+
+/// A requirement of the manifest that is not satisfied by the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsatisfiedSpec {
+    /// The environment is not present in the lock file.
+    MissingEnvironment(EnvironmentName),
+
+    /// The channels of the environment differ from the locked channels.
+    ChannelsChanged(EnvironmentName),
+
+    /// The platform of the environment is not present in the lock file.
+    MissingPlatform(EnvironmentName, PixiPlatformName),
+
+    /// None of the locked packages matches the conda dependency.
+    Conda {
+        environment: EnvironmentName,
+        platform: PixiPlatformName,
+        spec: String,
+    },
+
+    /// None of the locked packages matches the PyPI dependency.
+    Pypi {
+        environment: EnvironmentName,
+        platform: PixiPlatformName,
+        spec: String,
+    },
+}
+
+impl Display for UnsatisfiedSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsatisfiedSpec::MissingEnvironment(environment) => write!(
+                f,
+                "environment '{}' is missing from the lock file",
+                environment.as_str()
+            ),
+            UnsatisfiedSpec::ChannelsChanged(environment) => write!(
+                f,
+                "the channels of environment '{}' changed",
+                environment.as_str()
+            ),
+            UnsatisfiedSpec::MissingPlatform(environment, platform) => write!(
+                f,
+                "platform '{platform}' of environment '{}' is missing from the lock file",
+                environment.as_str()
+            ),
+            UnsatisfiedSpec::Conda {
+                environment,
+                platform,
+                spec,
+            }
+            | UnsatisfiedSpec::Pypi {
+                environment,
+                platform,
+                spec,
+            } => write!(
+                f,
+                "'{spec}' is not satisfied by the locked packages of environment '{}' on '{platform}'",
+                environment.as_str()
+            ),
+        }
+    }
+}
+
+/// Checks that every conda and PyPI dependency of the manifest is matched by a
+/// locked package, for every environment and platform, and returns the
+/// requirements that are not.
+///
+/// Unlike the full satisfiability check this does not solve or fetch
+/// anything, which makes it a cheap way to find out why a lock file is out of
+/// date. Conda source dependencies are not checked because their metadata is
+/// only known after building them, and PyPI dependencies that are not
+/// installed from an index only need a locked package with the same name.
+pub fn find_unsatisfied_specs(workspace: &Workspace, lock_file: &LockFile) -> Vec<UnsatisfiedSpec> {
+    workspace
+        .environments()
+        .into_iter()
+        .flat_map(|environment| unsatisfied_environment_specs(&environment, lock_file))
+        .collect()
+}
+
+fn unsatisfied_environment_specs(
+    environment: &Environment<'_>,
+    lock_file: &LockFile,
+) -> Vec<UnsatisfiedSpec> {
+    let name = environment.name().clone();
+    let Some(locked_environment) = lock_file.environment(name.as_str()) else {
+        return vec![UnsatisfiedSpec::MissingEnvironment(name)];
+    };
+
+    let config = environment.channel_config();
+    let channels: Result<Vec<ChannelUrl>, _> = GroupedEnvironment::from(environment.clone())
+        .channels()
+        .into_iter()
+        .map(|channel| channel.clone().into_base_url(&config))
+        .try_collect();
+    let locked_channels: Result<Vec<ChannelUrl>, _> = locked_environment
+        .channels()
+        .iter()
+        .map(|c| {
+            NamedChannelOrUrl::from_str(&c.url)
+                .unwrap_or_else(|_err| NamedChannelOrUrl::Name(c.url.clone()))
+                .into_base_url(&config)
+        })
+        .try_collect();
+    if !matches!((channels, locked_channels), (Ok(channels), Ok(locked)) if channels == locked) {
+        return vec![UnsatisfiedSpec::ChannelsChanged(name)];
+    }
+
+    let workspace_manifest = environment.workspace_manifest();
+    let mut unsatisfied = Vec::new();
+    for platform_name in environment.platforms() {
+        let Some(platform) = workspace_manifest
+            .workspace
+            .platform_by_name(&platform_name)
+        else {
+            continue;
+        };
+        let Some(lock_platform) =
+            resolve_lock_platform(lock_file, &platform_name, workspace_manifest)
+        else {
+            unsatisfied.push(UnsatisfiedSpec::MissingPlatform(
+                name.clone(),
+                platform_name,
+            ));
+            continue;
+        };
+
+        let locked_packages = locked_environment
+            .packages(lock_platform)
+            .into_iter()
+            .flatten()
+            .filter_map(LockedPackage::as_conda)
+            .collect_vec();
+
+        for (package_name, spec) in environment
+            .combined_dependencies(Some(platform))
+            .into_specs()
+        {
+            if spec.is_source() {
+                continue;
+            }
+            let Ok(match_spec) = spec.to_match_spec(&package_name, &config) else {
+                continue;
+            };
+            if !locked_packages
+                .iter()
+                .any(|package| package.satisfies(&match_spec))
+            {
+                unsatisfied.push(UnsatisfiedSpec::Conda {
+                    environment: name.clone(),
+                    platform: platform_name.clone(),
+                    spec: match_spec.to_string(),
+                });
+            }
+        }
+
+        let locked_pypi_packages = locked_environment
+            .pypi_packages(lock_platform)
+            .into_iter()
+            .flatten()
+            .collect_vec();
+        for (package_name, spec) in environment.pypi_dependencies(Some(platform)).into_specs() {
+            let satisfied = locked_pypi_packages
+                .iter()
+                .filter(|package| package.name() == package_name.as_normalized())
+                .any(|package| pypi_version_satisfies(environment, &package_name, &spec, package));
+            if !satisfied {
+                unsatisfied.push(UnsatisfiedSpec::Pypi {
+                    environment: name.clone(),
+                    platform: platform_name.clone(),
+                    spec: format!("{} {spec}", package_name.as_source()),
+                });
+            }
+        }
+    }
+    unsatisfied
+}
+
+/// Returns true if the locked version of a PyPI package matches the version
+/// specifiers of a dependency that is installed from an index. Other
+/// dependencies are satisfied by any locked version.
+fn pypi_version_satisfies(
+    environment: &Environment<'_>,
+    package_name: &PypiPackageName,
+    spec: &PixiPypiSpec,
+    package: &PypiPackageData,
+) -> bool {
+    if !matches!(spec.source, PixiPypiSource::Registry { .. }) {
+        return true;
+    }
+    let Ok(requirement) = as_uv_req(
+        spec,
+        package_name.as_source(),
+        environment.workspace().root(),
+    ) else {
+        return true;
+    };
+    let RequirementSource::Registry { specifier, .. } = requirement.source else {
+        return true;
+    };
+    package
+        .version()
+        .and_then(|version| uv_pep440::Version::from_str(&version.to_string()).ok())
+        .is_some_and(|version| specifier.contains(&version))
+}
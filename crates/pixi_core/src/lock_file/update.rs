@@ -39,7 +39,7 @@ use pixi_manifest::{
     ChannelPriority, EnvironmentName, FeaturesExt, HasWorkspaceManifest, PixiPlatform,
     PixiPlatformName,
 };
-use pixi_progress::global_multi_progress;
+use pixi_progress::{global_multi_progress, timing};
 use pixi_record::{LockFileResolver, ParseLockFileError, PixiRecord, UnresolvedPixiRecord};
 use pixi_utils::{prefix::Prefix, variants::VariantConfig};
 use pixi_uv_context::UvResolutionContext;
@@ -51,7 +51,9 @@ use pypi_mapping::{self, PurlDerivationClient};
 use pypi_modifiers::pypi_marker_env::determine_marker_environment;
 use rattler::package_cache::PackageCache;
 use rattler_conda_types::{Arch, GenericVirtualPackage, PackageName, ParseChannelError, Platform};
-use rattler_lock::{LockFile, LockedPackage, ParseCondaLockError};
+use rattler_lock::{
+    CondaPackageData, LockFile, LockedPackage, ParseCondaLockError, PypiPackageData,
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::Semaphore;
@@ -60,20 +62,25 @@ use uv_install_wheel::LinkMode;
 use uv_normalize::ExtraName;
 
 use super::{
-    CondaPrefixUpdater, InstallSubset, PixiRecordsByName, PypiRecordsByName,
-    UnresolvedPixiRecordsByName, outdated::OutdatedEnvironments, resolve_lock_platform,
+    CondaPrefixUpdater, InstallSubset, LockFileHistory, LockFileMetadata, PixiRecordsByName,
+    PypiRecordsByName, UnresolvedPixiRecordsByName, find_unsatisfied_specs,
+    metadata::write_lock_file_with_metadata,
+    outdated::OutdatedEnvironments,
+    python_abi::{PythonAbiChange, python_abi_changes},
+    resolve_lock_platform,
+    state_marker::WorkspaceStateHash,
     utils::IoConcurrencyLimit,
 };
 use crate::{
-    Workspace,
+    PixiError, Workspace,
     activation::CurrentEnvVarBehavior,
     environment::{
         CondaPrefixUpdated, EnvironmentFile, InstallFilter, LockFileUsage, LockedEnvironmentHash,
-        PerEnvironmentAndPlatform, PerGroup, PerGroupAndPlatform, PlatformData,
+        PerEnvironmentAndPlatform, PerGroup, PerGroupAndPlatform, PlatformData, PythonStatus,
         read_environment_file, write_environment_file,
     },
     lock_file::{
-        self,
+        self, ConflictExplanation,
         reporter::SolveProgressBar,
         virtual_packages::{
             compute_minimal_required_platforms, validate_system_meets_environment_requirements,
@@ -121,6 +128,34 @@ struct LockFileVersionMismatchError {
     help_message: String,
 }
 
+/// Error for when the lock file is not up-to-date with the workspace and may
+/// not be updated.
+#[derive(Debug, Error, Diagnostic)]
+#[error("lock file not up-to-date with the workspace")]
+pub struct LockFileOutOfDateError {
+    /// The requirements of the workspace that the lock file does not satisfy.
+    pub unsatisfied: Vec<String>,
+    #[help]
+    help_message: Option<String>,
+}
+
+impl LockFileOutOfDateError {
+    pub fn new(unsatisfied: Vec<String>) -> Self {
+        let help_message = (!unsatisfied.is_empty()).then(|| {
+            format!(
+                "the lock file does not satisfy the workspace:\n{}",
+                unsatisfied
+                    .iter()
+                    .format_with("\n", |spec, f| f(&format_args!("  - {spec}")))
+            )
+        });
+        Self {
+            unsatisfied,
+            help_message,
+        }
+    }
+}
+
 impl LockFileLoadResult {
     /// Extract the lock file, treating version mismatch as an error.
     ///
@@ -233,10 +268,23 @@ impl Workspace {
     /// that needs to be done to update the lock file. The tasks are awaited
     /// in a specific order to make sure that we can start instantiating
     /// prefixes as soon as possible.
+    ///
+    /// Errors are returned as a [`PixiError`], which distinguishes a failed
+    /// solve, a lock file that is out of date and network failures.
     pub async fn update_lock_file(
         &self,
         progress: Option<Arc<pixi_reporters::TopLevelProgress>>,
         options: UpdateLockFileOptions,
+    ) -> Result<(LockFileDerivedData<'_>, bool), PixiError> {
+        self.update_lock_file_inner(progress, options)
+            .await
+            .map_err(PixiError::from)
+    }
+
+    async fn update_lock_file_inner(
+        &self,
+        progress: Option<Arc<pixi_reporters::TopLevelProgress>>,
+        options: UpdateLockFileOptions,
     ) -> miette::Result<(LockFileDerivedData<'_>, bool)> {
         let lock_file_result = self.load_lock_file().await?;
 
@@ -307,6 +355,20 @@ impl Workspace {
             return Ok((derived, false));
         }
 
+        // Skip the verification if nothing changed since the lock file was last
+        // found to be up-to-date.
+        let state_hash = WorkspaceStateHash::from_workspace(self, &derived.lock_file);
+        if !needs_format_upgrade
+            && let Some(state_hash) = &state_hash
+            && state_hash.matches_marker(self)
+        {
+            tracing::info!(
+                "the lock file is up-to-date, the workspace did not change since it was last verified"
+            );
+            derived.workspace_unchanged = true;
+            return Ok((derived, false));
+        }
+
         // Check which environments are out of date.
         let resolver = derived.resolver()?;
         let mut outdated = OutdatedEnvironments::from_workspace_and_lock_file(
@@ -326,6 +388,9 @@ impl Workspace {
                 );
             } else {
                 tracing::info!("the lock file is up-to-date");
+                if let Some(state_hash) = state_hash {
+                    state_hash.write_marker(self);
+                }
             }
 
             // If no-environment is outdated we can return early. Pass the
@@ -370,7 +435,11 @@ impl Workspace {
         // If the lock file is out of date, but we're not allowed to update it, we
         // should exit.
         if !options.lock_file_usage.allow_updates() {
-            miette::bail!("lock file not up-to-date with the workspace");
+            let unsatisfied = find_unsatisfied_specs(self, &derived.lock_file);
+            return Err(LockFileOutOfDateError::new(
+                unsatisfied.iter().map(ToString::to_string).collect(),
+            )
+            .into());
         }
 
         // The environments whose conda dependencies are about to be re-solved.
@@ -385,8 +454,11 @@ impl Workspace {
             ..
         } = derived;
 
+        // Keep the previous lock file around to detect Python ABI changes.
+        let previous_lock_file = lock_file.clone();
+
         // Construct an update context and perform the actual update.
-        let lock_file_derived_data = UpdateContext::builder(self, command_dispatcher)?
+        let mut lock_file_derived_data = UpdateContext::builder(self, command_dispatcher)?
             .with_package_cache(package_cache)
             .with_no_install(options.no_install)
             .with_outdated_environments(outdated)
@@ -403,10 +475,27 @@ impl Workspace {
             &lock_file_derived_data.lock_file,
         );
 
+        // Installed wheels can be incompatible with a new Python minor version.
+        // The PyPI packages are reinstalled when the new Python is installed
+        // into the prefix, which may only happen with a later `pixi install`.
+        let abi_changes =
+            python_abi_changes(&previous_lock_file, &lock_file_derived_data.lock_file);
+        for change in &abi_changes {
+            tracing::warn!(
+                "{change}, all PyPI packages of the environment will be reinstalled because installed wheels may not match the new Python ABI"
+            );
+        }
+        lock_file_derived_data.python_abi_changes = abi_changes;
+
         // Write the lock file to disk
 
         if options.lock_file_usage != LockFileUsage::DryRun {
             lock_file_derived_data.write_to_disk()?;
+            if let Some(state_hash) =
+                WorkspaceStateHash::from_workspace(self, &lock_file_derived_data.lock_file)
+            {
+                state_hash.write_marker(self);
+            }
         }
 
         Ok((lock_file_derived_data, true))
@@ -430,38 +519,44 @@ impl Workspace {
         if lock_file_path.is_file() {
             // Spawn a background task because loading the file might be IO bound.
             tokio::task::spawn_blocking(move || {
-                LockFile::from_path(&lock_file_path)
-                    .map(|lock| {
-                        // Rewrite locked platform names to match the manifest's
-                        // current platforms by identity (subdir + customised
-                        // virtual packages). A user who renames an entry in
-                        // pixi.toml shouldn't have to re-solve to use the
-                        // existing locked packages, and downstream code
-                        // (satisfiability, environment lookup, install) sees
-                        // the workspace-current names directly.
-                        crate::lock_file::platform_rename::align_platform_names(
-                            lock,
-                            &manifest,
-                            &workspace_root,
-                        )
-                    })
-                    .map(LockFileLoadResult::Loaded)
-                    .or_else(|err| match err {
-                        ParseCondaLockError::IncompatibleVersion {
-                            lock_file_version,
-                            max_supported_version,
-                        } => Ok(LockFileLoadResult::VersionMismatch {
-                            lock_file_version,
-                            max_supported_version,
-                        }),
-                        _ => Err(miette::miette!(err)),
-                    })
-                    .wrap_err_with(|| {
-                        format!(
-                            "Failed to load lock file from `{}`",
-                            lock_file_path.display()
-                        )
-                    })
+                let load = || {
+                    // The metadata is parsed from the same contents as the lock file.
+                    let contents = fs_err::read_to_string(&lock_file_path).into_diagnostic()?;
+                    LockFileMetadata::from_lock_file_contents(&contents)
+                        .warn_if_written_by_newer_pixi(&lock_file_path);
+                    LockFile::from_str_with_base_directory(&contents, lock_file_path.parent())
+                        .map(|lock| {
+                            // Rewrite locked platform names to match the manifest's
+                            // current platforms by identity (subdir + customised
+                            // virtual packages). A user who renames an entry in
+                            // pixi.toml shouldn't have to re-solve to use the
+                            // existing locked packages, and downstream code
+                            // (satisfiability, environment lookup, install) sees
+                            // the workspace-current names directly.
+                            crate::lock_file::platform_rename::align_platform_names(
+                                lock,
+                                &manifest,
+                                &workspace_root,
+                            )
+                        })
+                        .map(LockFileLoadResult::Loaded)
+                        .or_else(|err| match err {
+                            ParseCondaLockError::IncompatibleVersion {
+                                lock_file_version,
+                                max_supported_version,
+                            } => Ok(LockFileLoadResult::VersionMismatch {
+                                lock_file_version,
+                                max_supported_version,
+                            }),
+                            _ => Err(miette::miette!(err)),
+                        })
+                };
+                load().wrap_err_with(|| {
+                    format!(
+                        "Failed to load lock file from `{}`",
+                        lock_file_path.display()
+                    )
+                })
             })
             .await
             .unwrap_or_else(|e| Err(e).into_diagnostic())
@@ -469,6 +564,64 @@ impl Workspace {
             Ok(LockFileLoadResult::Loaded(LockFile::default()))
         }
     }
+
+    /// Loads the lock file and returns the locked packages of a single
+    /// environment and platform.
+    ///
+    /// The lock file is not updated, so the packages might be out of date with
+    /// the manifest.
+    pub async fn load_environment_packages(
+        &self,
+        environment: &EnvironmentName,
+        platform: &str,
+    ) -> miette::Result<EnvironmentPackages> {
+        let lock_file = self.load_lock_file().await?.into_lock_file()?;
+        let locked_environment = lock_file.environment(environment.as_str()).ok_or_else(|| {
+            miette::miette!(
+                help = "run `pixi lock` to update the lock file",
+                "the lock file does not contain the environment '{}'",
+                environment.fancy_display()
+            )
+        })?;
+        let Some(lock_platform) = lock_file.platform(platform) else {
+            miette::bail!(
+                help = format!(
+                    "the lock file contains the platforms: {}",
+                    lock_file
+                        .platforms()
+                        .map(|p| p.name().to_string())
+                        .join(", ")
+                ),
+                "the lock file does not contain the platform '{platform}'"
+            );
+        };
+
+        Ok(EnvironmentPackages {
+            conda: locked_environment
+                .conda_packages(lock_platform)
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+            pypi: locked_environment
+                .pypi_packages(lock_platform)
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+/// The locked packages of a single environment and platform, see
+/// [`Workspace::load_environment_packages`].
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentPackages {
+    /// The conda packages, both binary and source packages.
+    pub conda: Vec<CondaPackageData>,
+
+    /// The PyPI packages.
+    pub pypi: Vec<PypiPackageData>,
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -488,6 +641,10 @@ pub enum SolveCondaEnvironmentError {
         #[source]
         #[diagnostic_source]
         source: Box<SolvePixiEnvironmentError>,
+        /// A concise explanation of the conflict, if the solve was
+        /// unsatisfiable.
+        #[help]
+        explanation: Option<ConflictExplanation>,
     },
 
     #[error(
@@ -606,10 +763,23 @@ pub struct LockFileDerivedData<'p> {
         Arc<lock_file::outdated::PypiEnvironmentBuildCache>,
     >,
 
+    /// Whether the workspace and the lock file did not change since the lock
+    /// file was last verified. In that case prefixes whose environment file
+    /// matches the lock file are not verified again.
+    pub workspace_unchanged: bool,
+
+    /// The environment platforms whose Python minor version changed with this
+    /// update of the lock file, a warning is emitted for each of them.
+    pub python_abi_changes: Vec<PythonAbiChange>,
+
     /// Lazily-built resolver for `lock_file`. Built once on first access to
     /// [`Self::resolver`] and reused across all downstream consumers. Kept
     /// private so all interaction goes through the accessor method.
     resolver: once_cell::sync::OnceCell<Arc<LockFileResolver>>,
+
+    /// The metadata of the lock file on disk. Read once on first access to
+    /// [`Self::lock_file_metadata`].
+    lock_file_metadata: once_cell::sync::OnceCell<LockFileMetadata>,
 }
 
 /// The mode to use when updating a prefix.
@@ -649,7 +819,10 @@ impl<'p> LockFileDerivedData<'p> {
             command_dispatcher,
             glob_hash_cache,
             build_caches: Default::default(),
+            workspace_unchanged: false,
+            python_abi_changes: Default::default(),
             resolver: Default::default(),
+            lock_file_metadata: Default::default(),
         }
     }
 
@@ -665,6 +838,14 @@ impl<'p> LockFileDerivedData<'p> {
             .cloned()
     }
 
+    /// Returns the metadata of the lock file on disk, reading its header on
+    /// first access. A lock file that cannot be read has no metadata.
+    pub fn lock_file_metadata(&self) -> &LockFileMetadata {
+        self.lock_file_metadata.get_or_init(|| {
+            LockFileMetadata::from_path(&self.workspace.lock_file_path()).unwrap_or_default()
+        })
+    }
+
     /// Seeds the resolver cache with a pre-built `Arc`, but only if the
     /// cache is still empty. Used inside the update flow to forward a
     /// resolver that was already built for the same lock file, so later
@@ -685,10 +866,19 @@ impl<'p> LockFileDerivedData<'p> {
             self.workspace.workspace_manifest(),
             self.workspace.root(),
         );
-        lock_file
-            .to_path(&lock_file_path)
-            .into_diagnostic()
-            .context("failed to write lock file to disk")
+        let metadata = LockFileMetadata::new(self.workspace, &self.lock_file);
+        let previous = write_lock_file_with_metadata(&lock_file, &metadata, &lock_file_path)?;
+
+        // Keep the replaced lock file around so the update can be rolled back.
+        if let Some(previous) = previous {
+            let depth = self.workspace.config().lock_history_depth();
+            if let Err(err) =
+                LockFileHistory::from_workspace(self.workspace).archive(&previous, depth)
+            {
+                tracing::warn!("failed to archive the previous lock file: {err}");
+            }
+        }
+        Ok(())
     }
 
     /// Consumes this instance, dropping any resources that are not needed
@@ -778,7 +968,12 @@ impl<'p> LockFileDerivedData<'p> {
         // Check if the prefix is already up-to-date by validating the hash with the
         // environment file
         let hash = self.locked_environment_hash(environment)?;
-        if update_mode == UpdateMode::QuickValidate
+        let unchanged_full_install = self.workspace_unchanged
+            && self.target_platform.is_none()
+            && matches!(reinstall_packages, ReinstallPackages::None)
+            && !filter.filter_active()
+            && self.prefix_contains_locked_conda_packages(environment);
+        if (update_mode == UpdateMode::QuickValidate || unchanged_full_install)
             && let Some(prefix) = self.cached_prefix(environment, &hash)
         {
             return prefix;
@@ -810,6 +1005,9 @@ impl<'p> LockFileDerivedData<'p> {
                 environment_lock_file_hash: hash,
                 resolved_platform,
                 minimum_supported_platform,
+                lock_file_hash: lock_file::lock_file_hash(
+                    &environment.workspace().lock_file_path(),
+                )?,
             },
         )?;
 
@@ -842,6 +1040,43 @@ impl<'p> LockFileDerivedData<'p> {
         Ok(prefix)
     }
 
+    /// Cheaply checks that the `conda-meta` directory of the prefix contains a
+    /// record for every locked conda package of the environment, which catches
+    /// prefixes that were modified outside of pixi.
+    fn prefix_contains_locked_conda_packages(&self, environment: &Environment<'p>) -> bool {
+        let Some(lock_platform) = self
+            .install_platform(environment)
+            .and_then(|platform| self.lock_file.platform(platform.name().as_str()))
+        else {
+            return false;
+        };
+        let Some(locked_environment) = self.lock_file.environment(environment.name().as_str())
+        else {
+            return false;
+        };
+        let Ok(entries) = fs_err::read_dir(environment.dir().join(consts::CONDA_META_DIR)) else {
+            return false;
+        };
+        let installed: HashSet<String> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+
+        locked_environment
+            .conda_packages(lock_platform)
+            .into_iter()
+            .flatten()
+            .filter_map(|package| package.record())
+            .all(|record| {
+                installed.contains(&format!(
+                    "{}-{}-{}.json",
+                    record.name.as_normalized(),
+                    record.version,
+                    record.build
+                ))
+            })
+    }
+
     fn cached_prefix(
         &self,
         environment: &Environment<'p>,
@@ -886,6 +1121,23 @@ impl<'p> LockFileDerivedData<'p> {
                     "Environment '{}' is up-to-date with lock file hash",
                     environment.name().fancy_display()
                 );
+
+                // The prefix matches the current lock file, record that for
+                // `pixi verify-lock` even if the lock file changed for other
+                // environments.
+                let lock_file_hash =
+                    lock_file::lock_file_hash(&environment.workspace().lock_file_path())
+                        .ok()
+                        .flatten();
+                if environment_file.lock_file_hash != lock_file_hash {
+                    let environment_file = EnvironmentFile {
+                        lock_file_hash,
+                        ..environment_file
+                    };
+                    if let Err(err) = write_environment_file(&environment.dir(), environment_file) {
+                        return Some(Err(err));
+                    }
+                }
                 return Some(Ok(Prefix::new(environment.dir())));
             }
         }
@@ -971,7 +1223,12 @@ impl<'p> LockFileDerivedData<'p> {
                     });
 
                 let resolver = self.resolver()?;
-                let pixi_records = locked_packages_to_unresolved_records(conda_packages, &resolver);
+                let pixi_records = locked_packages_to_unresolved_records(
+                    self.workspace,
+                    self.lock_file_metadata(),
+                    conda_packages,
+                    &resolver,
+                );
 
                 // Get the manifest's pypi dependencies for this environment to look up editability.
                 // The lock file always stores editable=false, so we apply the actual
@@ -1031,8 +1288,28 @@ impl<'p> LockFileDerivedData<'p> {
                     .filter_map(|r| to_uv_normalize(&r.name).ok())
                     .collect::<HashSet<_>>();
 
+                // Installed wheels may not match the ABI of a new Python minor
+                // version. The status comes from the conda transaction of the
+                // prefix, so this also holds if the lock file was updated by
+                // an earlier command.
+                let abi_changed = match &python_status {
+                    PythonStatus::Changed { old, new } if !pypi_records.is_empty() => {
+                        tracing::warn!(
+                            "the Python version of the '{}' environment changed from {}.{} to {}.{}, all PyPI packages of the environment are reinstalled",
+                            environment.name(),
+                            old.short_version.0,
+                            old.short_version.1,
+                            new.short_version.0,
+                            new.short_version.1,
+                        );
+                        true
+                    }
+                    _ => false,
+                };
+
                 // Figure out uv reinstall
                 let (uv_reinstall, uv_packages) = match reinstall_packages {
+                    _ if abi_changed => (Some(true), None),
                     ReinstallPackages::None => (Some(false), None),
                     ReinstallPackages::All => (Some(true), None),
                     ReinstallPackages::Some(pkgs) => (
@@ -1092,6 +1369,7 @@ impl<'p> LockFileDerivedData<'p> {
                         index_strategy: index_strategy.as_ref(),
                         exclude_newer: &pypi_exclude_newer,
                         skip_wheel_filename_check,
+                        allow_clobber: self.workspace.allow_clobber(environment),
                         link_mode: Some(derive_link_mode(
                             workspace_config.allow_symbolic_links,
                             workspace_config.allow_hard_links,
@@ -1219,7 +1497,12 @@ impl<'p> LockFileDerivedData<'p> {
                 // directly to the installer which builds them using
                 // variant-based output matching.
                 let resolver = self.resolver()?;
-                let mut records = locked_packages_to_unresolved_records(packages, &resolver);
+                let mut records = locked_packages_to_unresolved_records(
+                    self.workspace,
+                    self.lock_file_metadata(),
+                    packages,
+                    &resolver,
+                );
 
                 // Reify pre-v7 source envs in the records before
                 // handing them to install. Pre-v7 lock files don't
@@ -1329,14 +1612,20 @@ impl PackageFilterNames {
     }
 }
 
+/// Converts the locked conda packages to the records to install. Packages
+/// that the lock file records as downloaded from a mirror are downloaded from
+/// that url, unless a mirror is configured for them.
 fn locked_packages_to_unresolved_records(
+    workspace: &Workspace,
+    metadata: &LockFileMetadata,
     conda_packages: Vec<&'_ LockedPackage>,
     resolver: &LockFileResolver,
 ) -> Vec<UnresolvedPixiRecord> {
-    conda_packages
+    let records = conda_packages
         .into_iter()
         .filter_map(|pkg| resolver.get_for_package(pkg))
-        .collect()
+        .collect();
+    metadata.apply_download_urls(records, workspace.config().mirror_map())
 }
 
 /// Warns for every conda extra requested in the manifest that the resolved
@@ -2453,6 +2742,11 @@ impl<'p> UpdateContext<'p> {
                         .set(Arc::new(records))
                         .expect("records should not be solved twice");
 
+                    timing::record(
+                        timing::Phase::Solve,
+                        format!("conda {} {}", group_name.as_str(), platform),
+                        duration,
+                    );
                     match group_name {
                         GroupedEnvironmentName::Group(_) => {
                             tracing::info!(
@@ -2490,6 +2784,11 @@ impl<'p> UpdateContext<'p> {
                         .set(Arc::new(records))
                         .expect("records should not be solved twice");
 
+                    timing::record(
+                        timing::Phase::Solve,
+                        format!("pypi {} {}", group_name.as_str(), platform),
+                        duration,
+                    );
                     match group_name {
                         GroupedEnvironmentName::Group(_) => {
                             tracing::info!(
@@ -2681,7 +2980,10 @@ impl<'p> UpdateContext<'p> {
             command_dispatcher: self.command_dispatcher,
             glob_hash_cache: self.glob_hash_cache,
             build_caches: self.outdated_envs.build_caches,
+            workspace_unchanged: false,
+            python_abi_changes: Default::default(),
             resolver: Default::default(),
+            lock_file_metadata: Default::default(),
         })
     }
 }
@@ -2794,6 +3096,7 @@ async fn spawn_solve_conda_environment_task(
 
     // Get the dependencies for this platform
     let dependencies = group.combined_dependencies(pixi_platform);
+    let requested = dependencies.names().cloned().collect::<HashSet<_>>();
 
     // Get the dev dependencies for this platform
     let dev_dependencies = group.combined_dev_dependencies(pixi_platform);
@@ -2868,6 +3171,10 @@ async fn spawn_solve_conda_environment_task(
         .collect::<Result<Vec<_>, _>>()
         .map_err(SolveCondaEnvironmentError::from)
         .map_err(CommandDispatcherError::Failed)?;
+    let channel_subdirs = group
+        .channel_subdirs(&channel_config)
+        .map_err(SolveCondaEnvironmentError::from)
+        .map_err(CommandDispatcherError::Failed)?;
 
     // Determine the build variants
     let pixi_platform =
@@ -2905,6 +3212,7 @@ async fn spawn_solve_conda_environment_task(
         platform.to_string(),
         EnvironmentSpec {
             channels,
+            channel_subdirs,
             build_environment: BuildEnvironment::simple(subdir, virtual_packages),
             variants: pixi_utils::variants::VariantConfig {
                 variant_configuration,
@@ -2941,6 +3249,7 @@ async fn spawn_solve_conda_environment_task(
         .map_err_into_dispatcher(|source| SolveCondaEnvironmentError::SolveFailed {
             environment_name: group_name.clone(),
             platform: platform.clone(),
+            explanation: ConflictExplanation::from_error(&source, &requested),
             source: Box::new(source),
         })?;
     let mut records: Vec<PixiRecord> = (*records_arc).clone();
@@ -3063,6 +3372,9 @@ async fn spawn_extract_environment_task(
             .into_iter()
             .filter_map(|c| c.clone().into_base_url(&channel_config).ok())
             .collect();
+        let channel_subdirs = environment
+            .channel_subdirs(&channel_config)
+            .into_diagnostic()?;
 
         let VariantConfig {
             variant_configuration,
@@ -3096,6 +3408,7 @@ async fn spawn_extract_environment_task(
             platform.to_string(),
             EnvironmentSpec {
                 channels,
+                channel_subdirs,
                 build_environment,
                 variants: pixi_utils::variants::VariantConfig {
                     variant_configuration,
@@ -3452,9 +3765,58 @@ fn is_editable_from_manifest(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
+
     use pixi_manifest::PyPiDependencies;
     use pixi_pypi_spec::PixiPypiSpec;
 
+    #[tokio::test]
+    async fn test_load_environment_packages() {
+        let project_dir = Path::new(env!("CARGO_WORKSPACE_DIR"))
+            .join("tests/data/mock-projects/test-project-export");
+        let workspace = Workspace::from_path(&project_dir.join("pixi.toml")).unwrap();
+        let lock_file = LockFile::from_path(&project_dir.join("pixi.lock")).unwrap();
+        let locked_environment = lock_file.environment("default").unwrap();
+        let lock_platform = lock_file.platform("linux-64").unwrap();
+
+        let packages = workspace
+            .load_environment_packages(&EnvironmentName::Default, "linux-64")
+            .await
+            .unwrap();
+        assert_eq!(
+            packages
+                .conda
+                .iter()
+                .map(|package| package.location().to_string())
+                .collect_vec(),
+            locked_environment
+                .conda_packages(lock_platform)
+                .unwrap()
+                .map(|package| package.location().to_string())
+                .collect_vec()
+        );
+        assert_eq!(
+            packages
+                .pypi
+                .iter()
+                .map(|package| package.name().to_string())
+                .collect_vec(),
+            locked_environment
+                .pypi_packages(lock_platform)
+                .unwrap()
+                .map(|package| package.name().to_string())
+                .collect_vec()
+        );
+        assert!(!packages.pypi.is_empty());
+
+        assert!(
+            workspace
+                .load_environment_packages(&EnvironmentName::Default, "win-arm64")
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_format_unknown_extra_warning() {
         let available = ["test".to_string(), "docs".to_string()]
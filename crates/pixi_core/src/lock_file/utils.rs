@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::{io::ErrorKind, path::Path, sync::Arc};
 
+use miette::IntoDiagnostic;
 use pixi_manifest::{FeaturesExt, PixiPlatformName};
 use pixi_record::{LockFileResolver, UnresolvedPixiRecord};
 use rattler_lock::{LockFile, LockFileBuilder, LockedPackage};
@@ -27,6 +28,19 @@ impl From<IoConcurrencyLimit> for Arc<Semaphore> {
     }
 }
 
+/// Returns the sha256 hash of the lock file at the given path as a hex string,
+/// or `None` if the lock file doesn't exist.
+pub fn lock_file_hash(path: &Path) -> miette::Result<Option<String>> {
+    match fs_err::read(path) {
+        Ok(contents) => {
+            let hash = rattler_digest::compute_bytes_digest::<rattler_digest::Sha256>(contents);
+            Ok(Some(format!("{hash:x}")))
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).into_diagnostic(),
+    }
+}
+
 /// Identifies a locked package by name and ecosystem, without committing to
 /// an owning representation. Lets [`filter_lock_file`] call its predicate for
 /// both top-level packages and the transitive build/host entries inside source
@@ -12,8 +12,31 @@ pub fn zsh_hook() -> &'static str {
 }
 
 /// Sets default pixi prompt for posix shells
+///
+/// The original prompt is only remembered the first time, so activating
+/// another environment replaces the indicator instead of stacking them.
 pub fn posix_prompt(env_name: &str) -> String {
-    format!("export PS1=\"({env_name}) ${{PS1:-}}\"")
+    format!(
+        "if [ -z \"${{_PIXI_OLD_PS1+x}}\" ]; then\n\
+         \x20   _PIXI_OLD_PS1=\"${{PS1:-}}\"\n\
+         fi\n\
+         export PS1={}\"$_PIXI_OLD_PS1\"",
+        posix_quote(&format!("({env_name}) "))
+    )
+}
+
+/// Quotes a string in single quotes so a posix shell doesn't expand it.
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Restores the prompt of posix shells
+pub fn posix_prompt_reset() -> String {
+    r#"if [ -n "${_PIXI_OLD_PS1+x}" ]; then
+    export PS1="$_PIXI_OLD_PS1"
+    unset _PIXI_OLD_PS1
+fi"#
+    .to_string()
 }
 
 /// Sets default pixi prompt for the fish shell
@@ -22,7 +45,7 @@ pub fn fish_prompt(env_name: &str) -> String {
         r#"
         function __pixi_add_prompt
             set_color -o green
-            echo -n "({env_name}) "
+            echo -n {prompt}
             set_color normal
         end
 
@@ -30,18 +53,19 @@ pub fn fish_prompt(env_name: &str) -> String {
             functions -c fish_prompt __fish_prompt_orig
         end
 
-        if functions -q fish_right_prompt
-            if not functions -q __fish_right_prompt_orig
+        if not functions -q __fish_right_prompt_orig
+            if functions -q fish_right_prompt
                 functions -c fish_right_prompt __fish_right_prompt_orig
-            end
-        else
-            function __fish_right_prompt_orig
+            else
                 # Placeholder function for when fish_right_prompt does not exist
-                echo ""
+                function __fish_right_prompt_orig
+                    echo ""
+                end
+                set -g __pixi_right_prompt_placeholder 1
             end
         end
 
-        function return_last_status
+        function __pixi_return_last_status
             return $argv
         end
 
@@ -50,7 +74,7 @@ pub fn fish_prompt(env_name: &str) -> String {
             if set -q PIXI_LEFT_PROMPT
                 __pixi_add_prompt
             end
-            return_last_status $last_status
+            __pixi_return_last_status $last_status
             __fish_prompt_orig
         end
 
@@ -60,35 +84,149 @@ pub fn fish_prompt(env_name: &str) -> String {
             end
             __fish_right_prompt_orig
         end
-        "#
+        "#,
+        prompt = fish_quote(&format!("({env_name}) "))
     )
 }
 
+/// Quotes a string in single quotes so the fish shell doesn't expand it.
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+/// Restores the prompt functions of the fish shell
+pub fn fish_prompt_reset() -> String {
+    r#"
+        if functions -q __fish_prompt_orig
+            functions -e fish_prompt
+            functions -c __fish_prompt_orig fish_prompt
+            functions -e __fish_prompt_orig
+        end
+
+        if functions -q __fish_right_prompt_orig
+            functions -e fish_right_prompt
+            if set -q __pixi_right_prompt_placeholder
+                set -e __pixi_right_prompt_placeholder
+            else
+                functions -c __fish_right_prompt_orig fish_right_prompt
+            end
+            functions -e __fish_right_prompt_orig
+        end
+
+        functions -e __pixi_add_prompt __pixi_return_last_status
+        "#
+    .to_string()
+}
+
 /// Sets default pixi prompt for the xonsh shell
-pub fn xonsh_prompt() -> String {
-    // Xonsh' default prompt can find the environment for some reason.
-    "".to_string()
+///
+/// Xonsh' default prompt already shows the `env_name` field, so the field is
+/// replaced rather than prepending the environment to the prompt as well.
+pub fn xonsh_prompt(env_name: &str) -> String {
+    // A python string literal is quoted the same way as a fish one.
+    format!(
+        "if '__pixi_old_env_name' not in globals():\n\
+         \x20   __pixi_old_env_name = $PROMPT_FIELDS['env_name']\n\
+         $PROMPT_FIELDS['env_name'] = {}",
+        fish_quote(&format!("({env_name}) "))
+    )
+}
+
+/// Restores the `env_name` prompt field of the xonsh shell
+pub fn xonsh_prompt_reset() -> String {
+    "if '__pixi_old_env_name' in globals():\n\
+     \x20   $PROMPT_FIELDS['env_name'] = __pixi_old_env_name\n\
+     \x20   del __pixi_old_env_name"
+        .to_string()
 }
 
 /// Sets default pixi prompt for the powershell
 pub fn powershell_prompt(env_name: &str) -> String {
     format!(
-        "$old_prompt = $function:prompt\n\
-         function prompt {{\"({env_name}) $($old_prompt.Invoke())\"}}"
+        "if (-not (Test-Path variable:global:_PIXI_OLD_PROMPT)) {{ $global:_PIXI_OLD_PROMPT = $function:prompt }}\n\
+         function global:prompt {{'{prompt}' + \"$($global:_PIXI_OLD_PROMPT.Invoke())\"}}",
+        prompt = format!("({env_name}) ").replace('\'', "''")
     )
 }
 
+/// Restores the prompt function of the powershell
+pub fn powershell_prompt_reset() -> String {
+    "if (Test-Path variable:global:_PIXI_OLD_PROMPT) {\n\
+     \x20   $function:global:prompt = $global:_PIXI_OLD_PROMPT\n\
+     \x20   Remove-Variable -Name _PIXI_OLD_PROMPT -Scope Global\n\
+     }"
+    .to_string()
+}
+
 /// Sets default pixi prompt for the Nu shell
+///
+/// The original prompt command, which is either a closure or a string, is only
+/// remembered the first time.
 pub fn nu_prompt(env_name: &str) -> String {
     format!(
-        "let old_prompt = $env.PROMPT_COMMAND; \
-         $env.PROMPT_COMMAND = {{|| echo $\"\\({env_name}\\) (do $old_prompt)\"}}"
+        "if '_PIXI_OLD_PROMPT_COMMAND' not-in $env {{ \
+         $env._PIXI_OLD_PROMPT_COMMAND = ($env.PROMPT_COMMAND? | default '') }}; \
+         $env.PROMPT_COMMAND = {{|| \
+         let old_prompt = $env._PIXI_OLD_PROMPT_COMMAND; \
+         let old_prompt = if ($old_prompt | describe) starts-with 'closure' {{ do $old_prompt }} else {{ $old_prompt }}; \
+         {prompt} + ($old_prompt | into string) }}",
+        prompt = nu_quote(&format!("({env_name}) "))
     )
 }
 
+/// Quotes a string in double quotes for the Nu shell.
+fn nu_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\""))
+}
+
+/// Restores the prompt command of the Nu shell
+pub fn nu_prompt_reset() -> String {
+    "if '_PIXI_OLD_PROMPT_COMMAND' in $env { \
+     $env.PROMPT_COMMAND = $env._PIXI_OLD_PROMPT_COMMAND; \
+     hide-env _PIXI_OLD_PROMPT_COMMAND }"
+        .to_string()
+}
+
 /// Sets default pixi prompt for the cmd.exe command prompt
+///
+/// `_PIXI_PROMPT_ACTIVE` records that the original prompt was remembered,
+/// `_PIXI_OLD_PROMPT` is empty if `PROMPT` was not set and cmd.exe shows its
+/// default prompt.
 pub fn cmd_prompt(env_name: &str) -> String {
-    format!(r"@PROMPT ({env_name}) $P$G")
+    let prompt = cmd_prompt_escape(&format!("({env_name}) "));
+    format!(
+        "@IF NOT DEFINED _PIXI_PROMPT_ACTIVE (SET \"_PIXI_PROMPT_ACTIVE=1\" & SET \"_PIXI_OLD_PROMPT=%PROMPT%\")\n\
+         @IF DEFINED _PIXI_OLD_PROMPT PROMPT {prompt}%_PIXI_OLD_PROMPT%\n\
+         @IF NOT DEFINED _PIXI_OLD_PROMPT PROMPT {prompt}$P$G"
+    )
+}
+
+/// Escapes the characters that `PROMPT` or a batch file would otherwise
+/// interpret.
+fn cmd_prompt_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '$' => escaped.push_str("$$"),
+            '&' => escaped.push_str("$A"),
+            '|' => escaped.push_str("$B"),
+            '<' => escaped.push_str("$L"),
+            '>' => escaped.push_str("$G"),
+            '%' => escaped.push_str("%%"),
+            '^' => escaped.push_str("^^"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Restores the prompt of the cmd.exe command prompt that was active before
+/// [`cmd_prompt`]
+pub fn cmd_prompt_reset() -> String {
+    "@IF DEFINED _PIXI_PROMPT_ACTIVE SET \"PROMPT=%_PIXI_OLD_PROMPT%\"\n\
+     @SET _PIXI_OLD_PROMPT=\n\
+     @SET _PIXI_PROMPT_ACTIVE="
+        .to_string()
 }
 
 /// Returns appropriate hook function for configured shell
@@ -108,11 +246,25 @@ pub fn shell_prompt(shell: &ShellEnum, prompt_name: &str) -> String {
         ShellEnum::Bash(_) => posix_prompt(prompt_name),
         ShellEnum::Zsh(_) => posix_prompt(prompt_name),
         ShellEnum::Fish(_) => fish_prompt(prompt_name),
-        ShellEnum::Xonsh(_) => xonsh_prompt(),
+        ShellEnum::Xonsh(_) => xonsh_prompt(prompt_name),
         ShellEnum::CmdExe(_) => cmd_prompt(prompt_name),
     }
 }
 
+/// Returns the snippet that removes the pixi prompt of [`shell_prompt`] again
+/// for configured shell.
+pub fn shell_prompt_reset(shell: &ShellEnum) -> String {
+    match shell {
+        ShellEnum::NuShell(_) => nu_prompt_reset(),
+        ShellEnum::PowerShell(_) => powershell_prompt_reset(),
+        ShellEnum::Bash(_) => posix_prompt_reset(),
+        ShellEnum::Zsh(_) => posix_prompt_reset(),
+        ShellEnum::Fish(_) => fish_prompt_reset(),
+        ShellEnum::Xonsh(_) => xonsh_prompt_reset(),
+        ShellEnum::CmdExe(_) => cmd_prompt_reset(),
+    }
+}
+
 /// Returns prompt name for given project and environment
 pub fn prompt_name(project_name: &str, environment_name: &EnvironmentName) -> String {
     match environment_name {
@@ -120,3 +272,70 @@ pub fn prompt_name(project_name: &str, environment_name: &EnvironmentName) -> St
         EnvironmentName::Named(name) => format!("{project_name}:{name}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_name_is_not_expanded() {
+        let name = "it's $HOME";
+        assert!(posix_prompt(name).contains(r#"export PS1='(it'\''s $HOME) '"$_PIXI_OLD_PS1""#));
+        assert!(fish_prompt(name).contains(r"echo -n '(it\'s $HOME) '"));
+        assert!(powershell_prompt(name).contains("{'(it''s $HOME) ' + "));
+        assert!(cmd_prompt("a&b>c").contains("PROMPT (a$Ab$Gc) %_PIXI_OLD_PROMPT%"));
+        assert!(xonsh_prompt(name).contains(r"$PROMPT_FIELDS['env_name'] = '(it\'s $HOME) '"));
+    }
+
+    #[test]
+    fn test_prompt_reset_restores_original_prompt() {
+        use rattler_shell::shell::{Bash, CmdExe, Fish, NuShell, PowerShell, Xonsh, Zsh};
+
+        // For every shell, where the prompt snippet remembers the original
+        // prompt and where the reset snippet restores it.
+        let shells = [
+            (
+                ShellEnum::Bash(Bash),
+                r#"_PIXI_OLD_PS1="${PS1:-}""#,
+                r#"export PS1="$_PIXI_OLD_PS1""#,
+            ),
+            (
+                ShellEnum::Zsh(Zsh),
+                r#"_PIXI_OLD_PS1="${PS1:-}""#,
+                r#"export PS1="$_PIXI_OLD_PS1""#,
+            ),
+            (
+                ShellEnum::Fish(Fish),
+                "functions -c fish_prompt __fish_prompt_orig",
+                "functions -c __fish_prompt_orig fish_prompt",
+            ),
+            (
+                ShellEnum::Xonsh(Xonsh),
+                "__pixi_old_env_name = $PROMPT_FIELDS['env_name']",
+                "$PROMPT_FIELDS['env_name'] = __pixi_old_env_name",
+            ),
+            (
+                ShellEnum::PowerShell(PowerShell::default()),
+                "$global:_PIXI_OLD_PROMPT = $function:prompt",
+                "$function:global:prompt = $global:_PIXI_OLD_PROMPT",
+            ),
+            (
+                ShellEnum::NuShell(NuShell),
+                "$env._PIXI_OLD_PROMPT_COMMAND = ($env.PROMPT_COMMAND? | default '')",
+                "$env.PROMPT_COMMAND = $env._PIXI_OLD_PROMPT_COMMAND",
+            ),
+            (
+                ShellEnum::CmdExe(CmdExe),
+                r#"SET "_PIXI_OLD_PROMPT=%PROMPT%""#,
+                r#"SET "PROMPT=%_PIXI_OLD_PROMPT%""#,
+            ),
+        ];
+        for (shell, remembered, restored) in shells {
+            let prompt = shell_prompt(&shell, "env");
+            assert!(prompt.contains("(env) "), "{prompt}");
+            assert!(prompt.contains(remembered), "{prompt}");
+            let reset = shell_prompt_reset(&shell);
+            assert!(reset.contains(restored), "{reset}");
+        }
+    }
+}
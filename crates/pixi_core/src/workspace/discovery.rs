@@ -11,7 +11,10 @@ use pixi_manifest::{
 use thiserror::Error;
 
 use crate::workspace::WorkspaceRegistry;
-use crate::workspace::{Workspace, WorkspaceRegistryError};
+use crate::workspace::{
+    EnvironmentOverrideError, Workspace, WorkspaceRegistryError,
+    env_overrides::apply_environment_overrides,
+};
 
 /// Defines where the search for the workspace should start.
 #[derive(Debug, Clone, Default)]
@@ -139,6 +142,10 @@ pub enum WorkspaceLocatorError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     InvalidRequiresPixi(#[from] Box<pixi_manifest::InvalidRequiresPixiError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    EnvironmentOverride(#[from] EnvironmentOverrideError),
 }
 
 impl WorkspaceLocator {
@@ -305,6 +312,12 @@ impl WorkspaceLocator {
             );
         }
 
+        // Replace the channels and platforms with the ones from the environment.
+        let mut discovered_manifests = discovered_manifests;
+        if self.consider_environment {
+            apply_environment_overrides(&mut discovered_manifests.workspace.value)?;
+        }
+
         let workspace = Workspace::from_manifests(discovered_manifests, &self.global_config_source);
 
         Ok(workspace)
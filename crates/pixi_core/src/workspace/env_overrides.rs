@@ -0,0 +1,206 @@
+//! Overrides of the workspace channels and platforms from environment
+//! variables.
+//!
+//! `PIXI_CHANNELS` and `PIXI_PLATFORMS` contain comma-separated lists that
+//! replace the `channels` and `platforms` of the workspace when it is located
+//! for a CLI command, without editing the manifest. An entry of
+//! `PIXI_PLATFORMS` is either the name of a platform declared in the manifest
+//! or a conda subdir.
+//!
+//! The channels of features are still added to the overridden channels, the
+//! platforms of features are restricted to the overridden platforms.
+
+use indexmap::IndexSet;
+use miette::Diagnostic;
+use pixi_consts::consts;
+use pixi_manifest::{PixiPlatform, PrioritizedChannel, WorkspaceManifest};
+use rattler_conda_types::{NamedChannelOrUrl, Platform};
+use thiserror::Error;
+
+/// An environment variable contains an entry that is not a valid channel or
+/// platform.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid {kind} '{entry}' in {var}")]
+#[diagnostic(help("{var} must be a comma-separated list of {kind}s"))]
+pub struct EnvironmentOverrideError {
+    pub var: &'static str,
+    pub kind: &'static str,
+    pub entry: String,
+}
+
+/// Returns the non-empty entries of a comma-separated list.
+fn entries(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Replaces the channels and platforms of the workspace with the values of
+/// the `PIXI_CHANNELS` and `PIXI_PLATFORMS` environment variables, if set.
+pub(crate) fn apply_environment_overrides(
+    manifest: &mut WorkspaceManifest,
+) -> Result<(), EnvironmentOverrideError> {
+    apply_overrides(manifest, |var| std::env::var(var).ok())
+}
+
+fn apply_overrides(
+    manifest: &mut WorkspaceManifest,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<(), EnvironmentOverrideError> {
+    if let Some(value) = lookup(consts::PIXI_CHANNELS) {
+        let channels = entries(&value)
+            .map(|entry| {
+                entry
+                    .parse::<NamedChannelOrUrl>()
+                    .map(PrioritizedChannel::from)
+                    .map_err(|_| EnvironmentOverrideError {
+                        var: consts::PIXI_CHANNELS,
+                        kind: "channel",
+                        entry: entry.to_string(),
+                    })
+            })
+            .collect::<Result<IndexSet<_>, _>>()?;
+        if channels.is_empty() {
+            return Err(EnvironmentOverrideError {
+                var: consts::PIXI_CHANNELS,
+                kind: "channel",
+                entry: value,
+            });
+        }
+        tracing::debug!("using the channels from {}", consts::PIXI_CHANNELS);
+        manifest.workspace.channels = channels;
+    }
+
+    if let Some(value) = lookup(consts::PIXI_PLATFORMS) {
+        let declared = &manifest.workspace.platforms;
+        let platforms = entries(&value)
+            .map(|entry| {
+                declared
+                    .iter()
+                    .find(|platform| platform.name().as_str() == entry)
+                    .cloned()
+                    .or_else(|| {
+                        entry
+                            .parse::<Platform>()
+                            .ok()
+                            .map(PixiPlatform::from_subdir)
+                    })
+                    .ok_or_else(|| EnvironmentOverrideError {
+                        var: consts::PIXI_PLATFORMS,
+                        kind: "platform",
+                        entry: entry.to_string(),
+                    })
+            })
+            .collect::<Result<IndexSet<_>, _>>()?;
+        if platforms.is_empty() {
+            return Err(EnvironmentOverrideError {
+                var: consts::PIXI_PLATFORMS,
+                kind: "platform",
+                entry: value,
+            });
+        }
+        tracing::debug!("using the platforms from {}", consts::PIXI_PLATFORMS);
+        for feature in manifest.features.values_mut() {
+            if let Some(feature_platforms) = &mut feature.platforms {
+                feature_platforms.retain(|name| platforms.iter().any(|p| p.name() == name));
+            }
+        }
+        manifest.workspace.platforms = platforms;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pixi_manifest::FeatureName;
+
+    use super::*;
+    use crate::Workspace;
+
+    fn manifest() -> WorkspaceManifest {
+        let workspace = Workspace::from_str(
+            Path::new("pixi.toml"),
+            r#"
+        [workspace]
+        name = "pixi"
+        channels = ["conda-forge"]
+        platforms = ["linux-64", "osx-arm64", "win-64"]
+
+        [feature.unix]
+        platforms = ["linux-64", "osx-arm64"]
+        "#,
+        )
+        .unwrap();
+        workspace.workspace.value
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut manifest = manifest();
+        apply_overrides(&mut manifest, |var| match var {
+            "PIXI_CHANNELS" => Some("bioconda, https://prefix.dev/my-channel".to_string()),
+            "PIXI_PLATFORMS" => Some("linux-64,linux-aarch64".to_string()),
+            _ => None,
+        })
+        .unwrap();
+
+        let channels = manifest
+            .workspace
+            .channels
+            .iter()
+            .map(|channel| channel.channel.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            channels,
+            [
+                "bioconda".parse::<NamedChannelOrUrl>().unwrap(),
+                "https://prefix.dev/my-channel".parse().unwrap()
+            ]
+        );
+        let platforms = manifest
+            .workspace
+            .platforms
+            .iter()
+            .map(|platform| platform.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(platforms, ["linux-64", "linux-aarch64"]);
+        let unix = manifest.features.get(&FeatureName::from("unix")).unwrap();
+        assert_eq!(
+            unix.platforms
+                .iter()
+                .flatten()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>(),
+            ["linux-64"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_platform() {
+        let mut manifest = manifest();
+        let error = apply_overrides(&mut manifest, |var| {
+            (var == "PIXI_PLATFORMS").then(|| "linux-64,not-a-platform".to_string())
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid platform 'not-a-platform' in PIXI_PLATFORMS"
+        );
+    }
+
+    #[test]
+    fn test_empty_overrides() {
+        for (var, kind) in [("PIXI_CHANNELS", "channel"), ("PIXI_PLATFORMS", "platform")] {
+            let mut manifest = manifest();
+            let error = apply_overrides(&mut manifest, |name| {
+                (name == var).then(|| " , ".to_string())
+            })
+            .unwrap_err();
+            assert_eq!(error.to_string(), format!("invalid {kind} ' , ' in {var}"));
+        }
+    }
+}
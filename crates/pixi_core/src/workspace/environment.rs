@@ -9,8 +9,9 @@ use indexmap::IndexMap;
 use itertools::Either;
 use pixi_consts::consts;
 use pixi_manifest::{
-    self as manifest, EnvironmentName, Feature, FeatureName, FeaturesExt, HasFeaturesIter,
-    HasWorkspaceManifest, PixiPlatform, PixiPlatformName, Task, TaskName, WorkspaceManifest,
+    self as manifest, DotenvFile, EnvironmentName, Feature, FeatureName, FeaturesExt,
+    HasFeaturesIter, HasWorkspaceManifest, PixiPlatform, PixiPlatformName, Task, TaskName,
+    WorkspaceManifest,
 };
 use rattler_conda_types::{ChannelConfig, GenericVirtualPackage, Platform};
 
@@ -98,8 +99,12 @@ impl<'p> Environment<'p> {
             })
     }
 
-    /// Returns the directory where this environment is stored.
+    /// Returns the directory where this environment is stored, which is the
+    /// custom prefix if one was set with [`Workspace::with_prefix_override`].
     pub fn dir(&self) -> std::path::PathBuf {
+        if let Some(prefix) = self.workspace.prefix_override(&self.environment.name) {
+            return prefix.to_path_buf();
+        }
         self.workspace
             .environments_dir()
             .join(self.environment.name.as_str())
@@ -120,6 +125,16 @@ impl<'p> Environment<'p> {
         }
     }
 
+    /// The hash of the lock file this environment was last installed with, as
+    /// recorded in its `conda-meta/pixi` marker file. `None` when the
+    /// environment isn't installed yet or was written by an older pixi.
+    pub fn installed_lock_file_hash(&self) -> Option<String> {
+        match crate::environment::read_environment_file(&self.dir()) {
+            Ok(Some(file)) => file.lock_file_hash,
+            _ => None,
+        }
+    }
+
     /// The name of the workspace platform this environment was last installed
     /// for, recovered by matching the resolved platform in `conda-meta/pixi`
     /// (subdir + virtual packages) against the declared platforms. Lets `pixi
@@ -419,6 +434,21 @@ impl<'p> Environment<'p> {
             })
     }
 
+    /// Returns the `.env` files that are loaded before running a task in this
+    /// environment.
+    ///
+    /// Like for [`Self::activation_env`] the features that are defined first
+    /// take precedence, so their files come last.
+    pub fn activation_dotenv(&self, platform: Option<&PixiPlatform>) -> Vec<DotenvFile> {
+        self.features()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .flat_map(|f| f.activation_dotenv(platform))
+            .cloned()
+            .collect()
+    }
+
     /// Validates that the given platform is supported by this environment.
     fn validate_platform_support(
         &self,
@@ -169,6 +169,11 @@ pub struct UnknownTask<'p> {
     pub task_name: TaskName,
 }
 
+/// An error that occurs when more than one dependency type is requested.
+#[derive(Debug, Error, Diagnostic)]
+#[error("only one of `--host`, `--build` and `--pypi` can be specified")]
+pub struct ConflictingDependencyTypesError;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
 mod discovery;
+mod env_overrides;
 mod environment;
 pub mod errors;
 pub mod grouped_environment;
@@ -9,7 +10,7 @@ mod solve_group;
 pub mod virtual_packages;
 mod workspace_mut;
 
-use self::errors::VariantsError;
+use self::errors::{ConflictingDependencyTypesError, UnsupportedPlatformError, VariantsError};
 #[cfg(not(windows))]
 use std::os::unix::fs::symlink;
 use std::{
@@ -21,12 +22,14 @@ use std::{
 };
 
 use crate::{
+    PixiError,
     activation::{CurrentEnvVarBehavior, initialize_env_variables},
     lock_file::filter_lock_file,
     repodata::Repodata,
 };
 use async_once_cell::OnceCell as AsyncCell;
 pub use discovery::{DiscoveryStart, WorkspaceLocator, WorkspaceLocatorError};
+pub use env_overrides::EnvironmentOverrideError;
 pub use environment::Environment;
 pub use has_project_ref::HasWorkspaceRef;
 use indexmap::Equivalent;
@@ -40,13 +43,14 @@ use pixi_config::{Config, RunPostLinkScripts};
 use pixi_consts::consts;
 use pixi_diff::LockFileDiff;
 use pixi_manifest::{
-    AssociateProvenance, BuildVariantSource, EnvironmentName, Environments, HasWorkspaceManifest,
-    LoadManifestsError, ManifestProvenance, Manifests, PackageManifest, PixiPlatform,
-    PixiPlatformName, SpecType, WithProvenance, WithWarnings, WorkspaceManifest,
+    AssociateProvenance, BuildVariantSource, EnvironmentName, Environments, FeaturesExt,
+    HasWorkspaceManifest, LoadManifestsError, ManifestProvenance, Manifests, PackageManifest,
+    PixiPlatform, PixiPlatformName, SolveStrategy, SpecType, Task, TaskName, WithProvenance,
+    WithWarnings, WorkspaceManifest,
 };
 use pixi_path::AbsPathBuf;
 use pixi_pypi_spec::{PixiPypiSpec, PypiPackageName};
-use pixi_spec::SourceSpec;
+use pixi_spec::{PixiSpec, SourceSpec};
 use pixi_utils::reqwest::build_lazy_reqwest_clients;
 use pixi_utils::{
     reqwest::LazyReqwestClient,
@@ -77,13 +81,31 @@ static CUSTOM_TARGET_DIR_WARN: OnceCell<()> = OnceCell::new();
 static CUSTOM_BUILD_DIR_WARN: OnceCell<()> = OnceCell::new();
 
 /// The dependency types we support
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DependencyType {
     CondaDependency(SpecType),
     PypiDependency,
 }
 
 impl DependencyType {
+    /// Determines the dependency type from the `--host`, `--build` and
+    /// `--pypi` flags. Without any flag the dependencies are run dependencies.
+    ///
+    /// Returns an error if more than one flag is set.
+    pub fn from_args(
+        host: bool,
+        build: bool,
+        pypi: bool,
+    ) -> Result<Self, ConflictingDependencyTypesError> {
+        match (host, build, pypi) {
+            (false, false, false) => Ok(DependencyType::CondaDependency(SpecType::Run)),
+            (true, false, false) => Ok(DependencyType::CondaDependency(SpecType::Host)),
+            (false, true, false) => Ok(DependencyType::CondaDependency(SpecType::Build)),
+            (false, false, true) => Ok(DependencyType::PypiDependency),
+            _ => Err(ConflictingDependencyTypesError),
+        }
+    }
+
     /// Convert to a name used in the manifest
     pub fn name(&self) -> &'static str {
         match self {
@@ -157,6 +179,10 @@ pub struct Workspace {
     // TODO: once https://github.com/rust-lang/rust/issues/109737 is stabilized, switch to OnceLock
     client: OnceCell<(LazyReqwestClient, rattler_networking::LazyClient)>,
 
+    /// A client provided by the embedder of pixi, used instead of the
+    /// authenticated client that pixi would build from the configuration.
+    client_override: Option<LazyClient>,
+
     /// The repodata gateway to use for answering queries about repodata.
     /// This is wrapped in a `OnceLock` to allow for lazy initialization.
     // TODO: once https://github.com/rust-lang/rust/issues/109737 is stabilized, switch to OnceLock
@@ -188,6 +214,14 @@ pub struct Workspace {
 
     /// Optional backend override for testing purposes
     backend_override: Option<BackendOverride>,
+
+    /// Environments that are installed in a custom prefix instead of the
+    /// environments directory, see [`Workspace::with_prefix_override`].
+    prefix_overrides: HashMap<EnvironmentName, PathBuf>,
+
+    /// Whether PyPI packages may overwrite conda files regardless of the
+    /// manifest and config, see [`Workspace::with_allow_clobber`].
+    allow_clobber: bool,
 }
 
 impl Debug for Workspace {
@@ -385,6 +419,7 @@ impl Workspace {
             root,
             manifest_location_name,
             client: Default::default(),
+            client_override: None,
             workspace: manifest.workspace,
             package: manifest.package,
             env_vars,
@@ -394,6 +429,8 @@ impl Workspace {
             repodata_gateway: Default::default(),
             concurrent_downloads_semaphore: OnceCell::default(),
             backend_override: None,
+            prefix_overrides: HashMap::new(),
+            allow_clobber: false,
         }
     }
 
@@ -404,6 +441,12 @@ impl Workspace {
         Self::from_path_with_source(manifest_path, &pixi_config::GlobalConfigSource::Search)
     }
 
+    /// Loads a workspace from a manifest file like [`Workspace::from_path`],
+    /// but returns a [`PixiError`] that library consumers can match on.
+    pub fn load(manifest_path: &Path) -> Result<Self, PixiError> {
+        Ok(Self::from_path(manifest_path)?)
+    }
+
     /// Loads a workspace from a manifest file, using `source` for the global
     /// config layer.
     pub fn from_path_with_source(
@@ -419,15 +462,41 @@ impl Workspace {
     /// Constructs a workspace from a manifest string loaded from a specific
     /// location. Uses the default global-config search.
     pub fn from_str(manifest_path: &Path, content: &str) -> Result<Self, LoadManifestsError> {
+        Self::from_str_with_source(
+            manifest_path,
+            content,
+            &pixi_config::GlobalConfigSource::Search,
+        )
+    }
+
+    /// Constructs a workspace from the contents of a `pixi.toml` in the given
+    /// root directory.
+    ///
+    /// The manifest is not read from disk and the global configuration is not
+    /// loaded, which makes this a cheap way to create a workspace with a
+    /// specific manifest, e.g. in tests. Parse errors are reported exactly as
+    /// when loading the manifest from disk.
+    pub fn from_manifest_str(root: &Path, contents: &str) -> Result<Self, LoadManifestsError> {
+        Self::from_str_with_source(
+            &root.join(consts::WORKSPACE_MANIFEST),
+            contents,
+            &pixi_config::GlobalConfigSource::None,
+        )
+    }
+
+    /// Constructs a workspace from a manifest string loaded from a specific
+    /// location, using `source` for the global config layer.
+    fn from_str_with_source(
+        manifest_path: &Path,
+        content: &str,
+        source: &pixi_config::GlobalConfigSource,
+    ) -> Result<Self, LoadManifestsError> {
         let WithWarnings {
             value: manifests, ..
         } = Manifests::from_workspace_source(
             content.with_provenance(ManifestProvenance::from_path(manifest_path.to_path_buf())?),
         )?;
-        Ok(Self::from_manifests(
-            manifests,
-            &pixi_config::GlobalConfigSource::Search,
-        ))
+        Ok(Self::from_manifests(manifests, source))
     }
 
     /// Initialize empty map of environments variables
@@ -457,6 +526,73 @@ impl Workspace {
         self
     }
 
+    /// Installs the given environment in `prefix` instead of the environments
+    /// directory. A relative path is resolved against the root of the
+    /// workspace. The lock file stays in the workspace.
+    pub fn with_prefix_override(mut self, environment: EnvironmentName, prefix: &Path) -> Self {
+        let prefix = self.root.join(prefix);
+        self.prefix_overrides.insert(environment, prefix);
+        self
+    }
+
+    /// Solves all environments with the given strategy instead of the
+    /// `solve-strategy` of the manifest. The manifest on disk is not changed.
+    ///
+    /// The strategy is recorded in the solve options of the lock file, so a
+    /// workspace without the override considers the lock file out of date
+    /// and re-solves it with the strategy of the manifest.
+    pub fn with_solve_strategy_override(mut self, strategy: SolveStrategy) -> Self {
+        for feature in self.workspace.value.features.values_mut() {
+            feature.solve_strategy = Some(strategy);
+        }
+        self
+    }
+
+    /// Allows PyPI packages to overwrite files installed by conda packages in
+    /// all environments, regardless of the `allow-clobber` pypi-option and
+    /// config.
+    pub fn with_allow_clobber(mut self) -> Self {
+        self.allow_clobber = true;
+        self
+    }
+
+    /// Returns whether PyPI packages may overwrite files installed by conda
+    /// packages in the given environment. The CLI flag takes precedence over
+    /// the `allow-clobber` pypi-option, which takes precedence over the
+    /// config.
+    pub(crate) fn allow_clobber(&self, environment: &Environment<'_>) -> bool {
+        self.allow_clobber
+            || environment
+                .pypi_options()
+                .allow_clobber
+                .or(self.config.pypi_config().allow_clobber)
+                .unwrap_or(false)
+    }
+
+    /// Returns the custom prefix of the given environment, if any.
+    pub(crate) fn prefix_override(&self, environment: &EnvironmentName) -> Option<&Path> {
+        self.prefix_overrides.get(environment).map(PathBuf::as_path)
+    }
+
+    /// Sets the client that is used for all repodata and package downloads
+    /// of this workspace, instead of the authenticated client that pixi
+    /// builds from the configuration.
+    ///
+    /// This allows embedders of pixi to bring their own proxy, certificate or
+    /// middleware setup. The client is used as is, so it should include any
+    /// authentication and retry middleware that is required.
+    ///
+    /// The client only applies to conda requests. PyPI packages are resolved
+    /// and downloaded by uv, which builds its own client from the
+    /// configuration, so the middleware of the injected client is not applied
+    /// to requests to PyPI indexes.
+    pub fn with_client(mut self, client: impl Into<LazyClient>) -> Self {
+        self.client_override = Some(client.into());
+        self.client = OnceCell::new();
+        self.repodata_gateway = OnceCell::new();
+        self
+    }
+
     pub fn modify(self) -> Result<WorkspaceMut, LoadManifestsError> {
         WorkspaceMut::new(self)
     }
@@ -656,6 +792,47 @@ impl Workspace {
             .collect()
     }
 
+    /// Returns the conda dependencies of the default environment as they are
+    /// specified in the manifest.
+    ///
+    /// The run, host and build dependencies of all features of the default
+    /// environment are combined. A package that is specified more than once
+    /// is yielded once for every spec. If the `platform` is `None` no platform
+    /// specific dependencies are taken into consideration.
+    pub fn dependencies(
+        &self,
+        platform: Option<&PixiPlatform>,
+    ) -> impl Iterator<Item = (PackageName, PixiSpec)> + use<> {
+        self.default_environment()
+            .combined_dependencies(platform)
+            .into_specs()
+    }
+
+    /// Returns the PyPI dependencies of the default environment as they are
+    /// specified in the manifest.
+    ///
+    /// A package that is specified more than once is yielded once for every
+    /// spec. If the `platform` is `None` no platform specific dependencies are
+    /// taken into consideration.
+    pub fn pypi_dependencies(
+        &self,
+        platform: Option<&PixiPlatform>,
+    ) -> impl Iterator<Item = (PypiPackageName, PixiPypiSpec)> + use<> {
+        self.default_environment()
+            .pypi_dependencies(platform)
+            .into_specs()
+    }
+
+    /// Returns the tasks of the default environment that are available on the
+    /// given platform. Platform specific tasks take precedence over tasks
+    /// that are defined for all platforms.
+    pub fn tasks<'a>(
+        &'a self,
+        platform: Option<&'a PixiPlatform>,
+    ) -> Result<indexmap::IndexMap<&'a TaskName, &'a Task>, UnsupportedPlatformError> {
+        self.default_environment().tasks(platform)
+    }
+
     /// Returns an environment in this project based on a name or an environment
     /// variable.
     ///
@@ -755,11 +932,13 @@ impl Workspace {
     }
 
     /// Returns a semaphore than can be used to limit the number of concurrent
-    /// according to the user configuration.
+    /// according to the user configuration and the file descriptor limit.
     pub fn concurrent_downloads_semaphore(&self) -> Arc<Semaphore> {
         self.concurrent_downloads_semaphore
             .get_or_init(|| {
-                let max_concurrent_downloads = self.config().max_concurrent_downloads();
+                let max_concurrent_downloads = pixi_utils::rlimit::cap_concurrency_to_rlimit(
+                    self.config().max_concurrent_downloads(),
+                );
                 Arc::new(Semaphore::new(max_concurrent_downloads))
             })
             .clone()
@@ -836,7 +1015,12 @@ impl Workspace {
         &self,
     ) -> miette::Result<&(LazyReqwestClient, rattler_networking::LazyClient)> {
         self.client.get_or_try_init(|| {
-            build_lazy_reqwest_clients(Some(self.config()), Some(self.s3_config.clone()))
+            let (client, authenticated_client) =
+                build_lazy_reqwest_clients(Some(self.config()), Some(self.s3_config.clone()))?;
+            Ok((
+                client,
+                self.client_override.clone().unwrap_or(authenticated_client),
+            ))
         })
     }
 
@@ -1037,10 +1221,26 @@ impl Workspace {
             }
         })
     }
+
+    /// Constructs a new lock file where all the packages of the given
+    /// environments and platforms have been removed, so they are solved
+    /// without any locked preferences.
+    fn unlock_environments(
+        &self,
+        lock_file: &LockFile,
+        affected_environments: HashSet<(&str, PixiPlatformName)>,
+    ) -> LockFile {
+        filter_lock_file(self, lock_file, |env, platform, _package| {
+            !affected_environments.contains(&(env.name().as_str(), platform.clone()))
+        })
+    }
 }
 
 pub struct UpdateDeps {
     pub implicit_constraints: HashMap<String, String>,
+    /// The packages from `implicit_constraints` that were pinned without an
+    /// upper bound, with a bounded constraint to suggest instead.
+    pub unbounded_constraints: HashMap<String, String>,
     pub lock_file_diff: LockFileDiff,
 }
 
@@ -1198,6 +1398,34 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_dependency_type_from_args() {
+        assert_eq!(
+            DependencyType::from_args(false, false, false).unwrap(),
+            DependencyType::CondaDependency(SpecType::Run)
+        );
+        assert_eq!(
+            DependencyType::from_args(true, false, false).unwrap(),
+            DependencyType::CondaDependency(SpecType::Host)
+        );
+        assert_eq!(
+            DependencyType::from_args(false, true, false).unwrap(),
+            DependencyType::CondaDependency(SpecType::Build)
+        );
+        assert_eq!(
+            DependencyType::from_args(false, false, true).unwrap(),
+            DependencyType::PypiDependency
+        );
+        for (host, build, pypi) in [
+            (true, true, false),
+            (true, false, true),
+            (false, true, true),
+            (true, true, true),
+        ] {
+            assert!(DependencyType::from_args(host, build, pypi).is_err());
+        }
+    }
+
     const PROJECT_BOILERPLATE: &str = r#"
         [project]
         name = "foo"
@@ -1379,6 +1607,39 @@ mod tests {
         assert_eq!(workspace.display_name(), "workspace");
     }
 
+    #[test]
+    fn test_workspace_from_manifest_str() {
+        const WORKSPACE_STR: &str = r#"
+        [workspace]
+        name = "foo"
+        channels = ["conda-forge", "https://prefix.dev/bioconda"]
+        platforms = ["linux-64"]
+        "#;
+
+        let workspace =
+            Workspace::from_manifest_str(Path::new("/workspace"), WORKSPACE_STR).unwrap();
+        assert_eq!(workspace.root(), Path::new("/workspace"));
+        let channels = workspace
+            .default_environment()
+            .channels()
+            .into_iter()
+            .cloned()
+            .collect_vec();
+        assert_eq!(
+            channels,
+            [
+                rattler_conda_types::NamedChannelOrUrl::from_str("conda-forge").unwrap(),
+                rattler_conda_types::NamedChannelOrUrl::from_str("https://prefix.dev/bioconda")
+                    .unwrap(),
+            ]
+        );
+
+        let err =
+            Workspace::from_manifest_str(Path::new("/workspace"), "[workspace]\nchannels = 1")
+                .unwrap_err();
+        assert!(matches!(err, LoadManifestsError::Toml(_)), "{err:?}");
+    }
+
     fn format_dependencies(deps: pixi_manifest::CondaDependencies) -> String {
         deps.iter_specs()
             .map(|(name, spec)| format!("{} = {}", name.as_source(), spec.to_toml_value()))
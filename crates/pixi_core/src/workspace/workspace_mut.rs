@@ -23,7 +23,9 @@ use pixi_manifest::{
 };
 use pixi_pypi_spec::{PixiPypiSpec, PypiPackageName};
 use pixi_spec::PixiSpec;
-use rattler_conda_types::{MatchSpec, NamelessMatchSpec, PackageName, Version};
+use rattler_conda_types::{
+    Channel, MatchSpec, Matches, NamelessMatchSpec, PackageName, Platform, Version,
+};
 use rattler_lock::LockFile;
 use toml_edit::DocumentMut;
 
@@ -31,6 +33,7 @@ use crate::{
     Workspace,
     environment::LockFileUsage,
     lock_file::{LockFileDerivedData, ReinstallPackages, UpdateContext, UpdateMode},
+    repodata::Repodata,
     workspace::{
         MatchSpecs, NON_SEMVER_PACKAGES, PypiDeps, SourceSpecs, UpdateDeps,
         grouped_environment::GroupedEnvironment,
@@ -253,6 +256,10 @@ impl WorkspaceMut {
     /// 2. Update the lock file
     /// 3. Given packages without version restrictions will get a semver
     ///    restriction
+    ///
+    /// Packages that are not touched by the new specs keep their locked
+    /// versions where possible. If `no_preserve` is set, the affected
+    /// environments are re-solved from scratch instead.
     #[allow(clippy::too_many_arguments)]
     pub async fn update_dependencies(
         &mut self,
@@ -265,13 +272,16 @@ impl WorkspaceMut {
         targets: &[TargetSelector],
         editable: bool,
         dry_run: bool,
+        no_preserve: bool,
     ) -> Result<Option<UpdateDeps>, miette::Error> {
         let mut conda_specs_to_add_constraints_for = IndexMap::new();
         let mut pypi_specs_to_add_constraints_for = IndexMap::new();
         let mut conda_packages = HashSet::new();
         let mut pypi_packages = HashSet::new();
+        let mut added_conda_specs = Vec::new();
         let channel_config = self.workspace().channel_config();
         for (name, (spec, spec_type)) in match_specs {
+            let full_spec = spec.clone();
             let (_, nameless_spec) = spec.into_nameless();
             let pixi_spec =
                 PixiSpec::from_nameless_matchspec(nameless_spec.clone(), &channel_config);
@@ -289,6 +299,7 @@ impl WorkspaceMut {
                     conda_specs_to_add_constraints_for
                         .insert(name.clone(), (spec_type, nameless_spec));
                 }
+                added_conda_specs.push((name.clone(), full_spec));
                 conda_packages.insert(name);
             }
         }
@@ -392,15 +403,23 @@ impl WorkspaceMut {
             })
             .map(|(e, p)| (e.name().to_string(), p))
             .collect_vec();
-        let unlocked_lock_file = self.workspace().unlock_packages(
-            &original_lock_file,
-            conda_packages,
-            pypi_packages,
-            affect_environment_and_platforms
-                .iter()
-                .map(|(e, p)| (e.as_str(), p.clone()))
-                .collect(),
-        );
+        self.check_conda_specs_availability(&added_conda_specs, &affect_environment_and_platforms)
+            .await?;
+        let affected = affect_environment_and_platforms
+            .iter()
+            .map(|(e, p)| (e.as_str(), p.clone()))
+            .collect();
+        let unlocked_lock_file = if no_preserve {
+            self.workspace()
+                .unlock_environments(&original_lock_file, affected)
+        } else {
+            self.workspace().unlock_packages(
+                &original_lock_file,
+                conda_packages,
+                pypi_packages,
+                affected,
+            )
+        };
         let LockFileDerivedData {
             workspace: _, // We don't need the project here
             lock_file,
@@ -441,6 +460,7 @@ impl WorkspaceMut {
         })?;
 
         let mut implicit_constraints = HashMap::new();
+        let mut unbounded_constraints = HashMap::new();
         if !conda_specs_to_add_constraints_for.is_empty() {
             let conda_constraints = self.update_conda_specs_from_lock_file(
                 &lock_file,
@@ -448,6 +468,7 @@ impl WorkspaceMut {
                 affect_environment_and_platforms.clone(),
                 feature_name,
                 targets,
+                &mut unbounded_constraints,
             )?;
             implicit_constraints.extend(conda_constraints);
         }
@@ -460,6 +481,7 @@ impl WorkspaceMut {
                 feature_name,
                 targets,
                 editable,
+                &mut unbounded_constraints,
             )?;
             implicit_constraints.extend(pypi_constraints);
         }
@@ -521,6 +543,7 @@ impl WorkspaceMut {
 
         Ok(Some(UpdateDeps {
             implicit_constraints,
+            unbounded_constraints,
             lock_file_diff,
         }))
     }
@@ -568,8 +591,125 @@ impl WorkspaceMut {
         Ok(())
     }
 
+    /// Checks that the repodata of every affected platform contains a package
+    /// that matches each of the added conda specs, so a package that is missing
+    /// for some platforms is reported by platform instead of as a failed solve.
+    ///
+    /// If the repodata cannot be fetched the check is skipped and the solve
+    /// reports the problem instead.
+    async fn check_conda_specs_availability(
+        &self,
+        specs: &[(PackageName, MatchSpec)],
+        environment_platforms: &[(String, PixiPlatformName)],
+    ) -> miette::Result<()> {
+        // Virtual packages are never part of the repodata.
+        let specs = specs
+            .iter()
+            .filter(|(name, _)| !name.as_normalized().starts_with("__"))
+            .collect_vec();
+        if specs.is_empty() {
+            return Ok(());
+        }
+
+        let workspace = self.workspace();
+        let gateway = workspace.repodata_gateway()?;
+        let channel_config = workspace.channel_config();
+
+        // For every spec the platforms that do and do not provide it.
+        let mut availability: IndexMap<
+            &PackageName,
+            (Vec<PixiPlatformName>, Vec<PixiPlatformName>),
+        > = IndexMap::new();
+        for (environment_name, platform_name) in environment_platforms {
+            let Some(environment) = workspace.environment(environment_name.as_str()) else {
+                continue;
+            };
+            let Some(platform) = workspace
+                .workspace
+                .value
+                .workspace
+                .platform_by_name(platform_name)
+            else {
+                continue;
+            };
+            let Ok(channels) = environment.channel_urls(&channel_config) else {
+                continue;
+            };
+            let repodata = match gateway
+                .query(
+                    channels.into_iter().map(Channel::from_url),
+                    [platform.subdir(), Platform::NoArch],
+                    specs.iter().map(|(_, spec)| spec.clone()),
+                )
+                .recursive(false)
+                .await
+            {
+                Ok(repodata) => repodata,
+                Err(err) => {
+                    tracing::debug!("skipping the availability check of the added packages: {err}");
+                    return Ok(());
+                }
+            };
+
+            for (name, spec) in &specs {
+                let is_available = repodata
+                    .iter()
+                    .flat_map(|records| records.iter())
+                    .any(|record| spec.matches(&record.package_record));
+                let (available, missing) = availability.entry(name).or_default();
+                let platforms = if is_available { available } else { missing };
+                if !platforms.contains(platform_name) {
+                    platforms.push(platform_name.clone());
+                }
+            }
+        }
+
+        for (name, (available, missing)) in availability {
+            // A platform is only considered missing if no environment provides
+            // the package for it.
+            let missing = missing
+                .into_iter()
+                .filter(|platform| !available.contains(platform))
+                .collect_vec();
+            if missing.is_empty() {
+                continue;
+            }
+            let spec = specs
+                .iter()
+                .find_map(|(spec_name, spec)| (spec_name == name).then_some(spec))
+                .expect("the spec was checked");
+            let message = format!(
+                "'{spec}' is not available for platform(s): {}",
+                missing.iter().format(", ")
+            );
+            if available.is_empty() {
+                return Err(miette::miette!("{message}"));
+            }
+            return Err(miette::miette!(
+                help = format!(
+                    "To only add the dependency for the platforms it is available for, use:\n\n  {}",
+                    console::style(format!(
+                        "pixi add {} {}",
+                        available
+                            .iter()
+                            .map(|platform| format!("--platform {platform}"))
+                            .format(" "),
+                        name.as_source()
+                    ))
+                    .bold()
+                ),
+                "{message}"
+            ));
+        }
+        Ok(())
+    }
+
     /// Update the conda specs of newly added packages based on the contents of
     /// the updated lock file.
+    ///
+    /// Packages that are pinned without an upper bound are recorded in
+    /// `unbounded_constraints`, together with the semver constraint to suggest
+    /// instead.
     fn update_conda_specs_from_lock_file(
         &mut self,
         updated_lock_file: &LockFile,
@@ -577,6 +717,7 @@ impl WorkspaceMut {
         affect_environment_and_platforms: Vec<(String, PixiPlatformName)>,
         feature_name: &FeatureName,
         targets: &[TargetSelector],
+        unbounded_constraints: &mut HashMap<String, String>,
     ) -> miette::Result<HashMap<String, String>> {
         let mut implicit_constraints = HashMap::new();
 
@@ -608,17 +749,24 @@ impl WorkspaceMut {
                 );
                 pinning_strategy = Some(PinningStrategy::Minor);
             }
-            let version_constraint = pinning_strategy
-                .unwrap_or_default()
-                .determine_version_constraint(conda_records.iter().filter_map(|record| {
-                    if record.package_record.name == name {
-                        Some(record.package_record.version.version())
-                    } else {
-                        None
-                    }
-                }));
+            let pinning_strategy = pinning_strategy.unwrap_or_default();
+            let versions = conda_records.iter().filter_map(|record| {
+                if record.package_record.name == name {
+                    Some(record.package_record.version.version())
+                } else {
+                    None
+                }
+            });
+            let version_constraint =
+                pinning_strategy.determine_version_constraint(versions.clone());
 
             if let Some(version_constraint) = version_constraint {
+                if !pinning_strategy.has_upper_bound()
+                    && let Some(bounded) =
+                        PinningStrategy::Semver.determine_version_constraint(versions)
+                {
+                    unbounded_constraints.insert(name.as_source().to_string(), bounded.to_string());
+                }
                 implicit_constraints
                     .insert(name.as_source().to_string(), version_constraint.to_string());
                 let spec = NamelessMatchSpec {
@@ -644,6 +792,11 @@ impl WorkspaceMut {
 
     /// Update the pypi specs of newly added packages based on the contents of
     /// the updated lock file.
+    ///
+    /// Packages that are pinned without an upper bound are recorded in
+    /// `unbounded_constraints`, together with the semver constraint to suggest
+    /// instead.
+    #[allow(clippy::too_many_arguments)]
     fn update_pypi_specs_from_lock_file(
         &mut self,
         updated_lock_file: &LockFile,
@@ -659,6 +812,7 @@ impl WorkspaceMut {
         feature_name: &FeatureName,
         targets: &[TargetSelector],
         editable: bool,
+        unbounded_constraints: &mut HashMap<String, String>,
     ) -> miette::Result<HashMap<String, String>> {
         let mut implicit_constraints = HashMap::new();
 
@@ -690,20 +844,27 @@ impl WorkspaceMut {
 
         // Determine the versions of the packages in the lock file
         for (name, (req, pixi_req, location)) in pypi_specs_to_add_constraints_for {
-            let version_constraint = pinning_strategy.determine_version_constraint(
-                pypi_records
-                    .iter()
-                    .filter_map(|data| {
-                        if data.name() == name.as_normalized() {
-                            data.version()
-                                .and_then(|v| Version::from_str(&v.to_string()).ok())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect_vec()
-                    .iter(),
-            );
+            let versions = pypi_records
+                .iter()
+                .filter_map(|data| {
+                    if data.name() == name.as_normalized() {
+                        data.version()
+                            .and_then(|v| Version::from_str(&v.to_string()).ok())
+                    } else {
+                        None
+                    }
+                })
+                .collect_vec();
+            let version_constraint = pinning_strategy.determine_version_constraint(versions.iter());
+
+            if version_constraint.is_some()
+                && !pinning_strategy.has_upper_bound()
+                && let Some(bounded) = PinningStrategy::Semver
+                    .determine_version_constraint(versions.iter())
+                    .and_then(|spec| VersionSpecifiers::from_str(&spec.to_string()).ok())
+            {
+                unbounded_constraints.insert(name.as_source().to_string(), bounded.to_string());
+            }
 
             let version_spec = version_constraint
                 .and_then(|spec| VersionSpecifiers::from_str(&spec.to_string()).ok());
@@ -6,8 +6,10 @@ mod glob_hash;
 mod glob_hash_cache;
 mod glob_mtime;
 mod glob_set;
+mod name_glob;
 
 pub use glob_hash::{GlobHash, GlobHashError};
 pub use glob_hash_cache::{GlobHashCache, GlobHashKey};
 pub use glob_mtime::{GlobModificationTime, GlobModificationTimeError};
 pub use glob_set::{GlobSet, GlobSetError, Match};
+pub use name_glob::NameGlob;
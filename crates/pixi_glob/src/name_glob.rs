@@ -0,0 +1,54 @@
+//! Globs that match names, like the names of tasks, instead of paths.
+
+use globset::{Glob, GlobMatcher};
+
+/// A glob that matches names instead of paths. `*` matches any sequence of
+/// characters, `?` matches a single character and `[...]` matches one of the
+/// characters in the brackets.
+#[derive(Debug, Clone)]
+pub struct NameGlob(GlobMatcher);
+
+impl NameGlob {
+    /// Parses the pattern. Returns `None` if the pattern has no wildcards, and
+    /// thus only matches itself, or if it is not a valid glob.
+    pub fn new(pattern: &str) -> Option<Self> {
+        if !pattern.contains(['*', '?', '[']) {
+            return None;
+        }
+        let glob = Glob::new(pattern).ok()?;
+        Some(Self(glob.compile_matcher()))
+    }
+
+    /// Returns the pattern of the glob.
+    pub fn pattern(&self) -> &str {
+        self.0.glob().glob()
+    }
+
+    /// Returns true if the name matches the glob.
+    pub fn is_match(&self, name: &str) -> bool {
+        self.0.is_match(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NameGlob;
+
+    #[test]
+    fn test_name_glob() {
+        let matches = |pattern: &str, name: &str| NameGlob::new(pattern).unwrap().is_match(name);
+        assert!(matches("check:*", "check:rust"));
+        assert!(matches("*:py", "lint:py"));
+        assert!(matches("lint:?s", "lint:js"));
+        assert!(matches("lint:[jt]s", "lint:ts"));
+        assert!(matches("*", "build/docs"));
+        assert!(!matches("check:*", "lint:rust"));
+        assert!(!matches("lint:?", "lint:py"));
+    }
+
+    #[test]
+    fn test_name_glob_without_wildcards() {
+        assert!(NameGlob::new("check").is_none());
+        assert!(NameGlob::new("check:[").is_none());
+    }
+}
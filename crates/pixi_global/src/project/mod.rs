@@ -643,6 +643,7 @@ impl Project {
                 env_name.to_string(),
                 EnvironmentSpec {
                     channels: channels.clone(),
+                    channel_subdirs: Default::default(),
                     build_environment: build_environment.clone(),
                     variants: VariantConfig::default(),
                     exclude_newer: None,
@@ -1411,11 +1412,13 @@ impl Project {
     }
 
     /// Returns a semaphore than can be used to limit the number of concurrent
-    /// according to the user configuration.
+    /// according to the user configuration and the file descriptor limit.
     fn concurrent_downloads_semaphore(&self) -> Arc<Semaphore> {
         self.concurrent_downloads_semaphore
             .get_or_init(|| {
-                let max_concurrent_downloads = self.config().max_concurrent_downloads();
+                let max_concurrent_downloads = pixi_utils::rlimit::cap_concurrency_to_rlimit(
+                    self.config().max_concurrent_downloads(),
+                );
                 Arc::new(Semaphore::new(max_concurrent_downloads))
             })
             .clone()
@@ -1516,6 +1519,7 @@ impl Project {
                 pinned_source_spec.to_string(),
                 EnvironmentSpec {
                     channels,
+                    channel_subdirs: Default::default(),
                     build_environment: pixi_command_dispatcher::BuildEnvironment::default(),
                     variants: VariantConfig::default(),
                     exclude_newer: None,
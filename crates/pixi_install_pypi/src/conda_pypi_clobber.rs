@@ -33,6 +33,12 @@ impl ClobberReport {
     pub(crate) fn keys(&self) -> btree_map::Keys<'_, (String, String), Vec<CondaPrefixPath>> {
         self.0.keys()
     }
+
+    /// Returns the prefix-relative paths of all the conda files that are
+    /// overwritten.
+    pub(crate) fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.0.values().flatten().map(CondaPrefixPath::as_path)
+    }
 }
 
 impl fmt::Display for ClobberReport {
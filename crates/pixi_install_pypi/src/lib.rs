@@ -16,10 +16,12 @@ use pixi_manifest::{
     pypi::ResolvedPypiExcludeNewer,
     pypi::pypi_options::{NoBinary, NoBuild, NoBuildIsolation},
 };
-use pixi_progress::await_in_progress;
+use pixi_progress::{ProgressBarPlacement, await_in_progress, json::ProgressEventKind};
 use pixi_python_status::PythonStatus;
 use pixi_record::PixiRecord;
-use pixi_reporters::{UvReporter, UvReporterOptions};
+use pixi_reporters::{
+    TerminalReporter, UvInstallReporter, UvReporter, UvReporterOptions, select_reporter,
+};
 use pixi_utils::prefix::Prefix;
 use pixi_uv_context::UvResolutionContext;
 use pixi_uv_conversions::{
@@ -292,6 +294,10 @@ pub struct PyPIBuildConfig<'a> {
     pub index_strategy: Option<&'a pixi_manifest::pypi::pypi_options::IndexStrategy>,
     pub exclude_newer: &'a ResolvedPypiExcludeNewer,
     pub skip_wheel_filename_check: Option<bool>,
+    /// Whether PyPI packages may overwrite files installed by conda packages.
+    /// When set, the clobbered files are logged and recorded in the install
+    /// summary instead of warned about.
+    pub allow_clobber: bool,
     /// The link mode to use when installing packages.
     /// If `None`, uses the default for the platform (Clone on macOS, Hardlink on Linux).
     pub link_mode: Option<LinkMode>,
@@ -1132,7 +1138,8 @@ impl<'a> PyPIEnvironmentUpdater<'a> {
     /// Check and warn about conflicts between PyPI and Conda packages.
     /// clobbering may occur, so that a PyPI package will overwrite a conda
     /// package this method will notify the user about any potential
-    /// conflicts.
+    /// conflicts. If clobbering is allowed the conflicts are only logged, and
+    /// the clobbered files are recorded in the install summary.
     async fn check_and_warn_about_conflicts(
         &self,
         all_dists: &[CachedDist],
@@ -1170,7 +1177,18 @@ impl<'a> PyPIEnvironmentUpdater<'a> {
         // are
         match pypi_conda_clobber.clobber_on_installation(all_dists.to_vec(), &setup.venv) {
             Ok(Some(clobber_report)) => {
-                tracing::warn!("{clobber_report}");
+                if self.build_config.allow_clobber {
+                    tracing::info!("clobbering is allowed, {clobber_report}");
+                    pixi_progress::install_summary::record_clobbered_files(
+                        clobber_report
+                            .paths()
+                            .map(|path| path.display().to_string()),
+                    );
+                } else {
+                    tracing::warn!(
+                        "{clobber_report}To allow this, set `allow-clobber = true` in the `[pypi-options]` or pass `--allow-clobber`"
+                    );
+                }
 
                 // because we are removing conda packages
                 // we filter the ones we already warn
@@ -1216,10 +1234,15 @@ impl<'a> PyPIEnvironmentUpdater<'a> {
             return Ok(());
         }
 
-        let options = UvReporterOptions::new()
-            .with_length(all_dists.len() as u64)
-            .with_starting_tasks(all_dists.iter().map(|d| format!("{}", d.name())))
-            .with_top_level_message("Installing distributions");
+        let reporter = select_reporter(|| {
+            TerminalReporter::new(
+                pixi_progress::global_multi_progress(),
+                ProgressBarPlacement::Bottom,
+                "installing pypi packages".to_owned(),
+            )
+            .with_osc_report()
+            .with_json_event(ProgressEventKind::Install)
+        });
 
         let start = std::time::Instant::now();
 
@@ -1227,7 +1250,7 @@ impl<'a> PyPIEnvironmentUpdater<'a> {
             uv_installer::Installer::new(&setup.venv, uv_preview::Preview::default())
                 .with_link_mode(self.build_config.link_mode.unwrap_or_default())
                 .with_installer_name(Some(consts::PIXI_UV_INSTALLER.to_string()))
-                .with_reporter(UvReporter::new_arc(options))
+                .with_reporter(Arc::new(UvInstallReporter::new(reporter, &all_dists)))
                 .install(all_dists.clone()),
         )
         .await
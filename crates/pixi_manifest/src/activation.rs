@@ -1,13 +1,56 @@
+use std::path::PathBuf;
+
 use indexmap::IndexMap;
-use toml_span::{DeserError, Value, de_helpers::TableHelper};
+use serde::{Deserialize, Serialize};
+use toml_span::{
+    DeserError, Value,
+    de_helpers::{TableHelper, expected},
+    value::ValueInner,
+};
 
-use pixi_toml::TomlIndexMap;
+use pixi_toml::{TomlFromStr, TomlIndexMap};
 
 #[derive(Default, Clone, Debug)]
 pub struct Activation {
     pub scripts: Option<Vec<String>>,
     /// Environment variables to set before running the scripts.
     pub env: Option<IndexMap<String, String>>,
+    /// `.env` files that are loaded into the environment of every task.
+    pub dotenv: Option<Vec<DotenvFile>>,
+}
+
+/// A `.env` file whose variables are loaded into the environment of a task.
+///
+/// In the manifest this is either a path or a table with a `path` and an
+/// `optional` flag, e.g. `dotenv = [".env", { path = ".env.local", optional
+/// = true }]`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DotenvFile {
+    /// The path of the file, relative to the root of the workspace.
+    pub path: PathBuf,
+
+    /// Whether the file is skipped if it doesn't exist, instead of failing.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl<'de> toml_span::Deserialize<'de> for DotenvFile {
+    fn deserialize(value: &mut Value<'de>) -> Result<Self, DeserError> {
+        match value.take() {
+            ValueInner::String(path) => Ok(DotenvFile {
+                path: PathBuf::from(path.as_ref()),
+                optional: false,
+            }),
+            inner @ ValueInner::Table(_) => {
+                let mut th = TableHelper::new(&mut Value::with_span(inner, value.span))?;
+                let path = th.required::<TomlFromStr<PathBuf>>("path")?.into_inner();
+                let optional = th.optional("optional").unwrap_or(false);
+                th.finalize(None)?;
+                Ok(DotenvFile { path, optional })
+            }
+            other => Err(expected("a string or table", other, value.span).into()),
+        }
+    }
 }
 
 impl<'de> toml_span::Deserialize<'de> for Activation {
@@ -15,10 +58,12 @@ impl<'de> toml_span::Deserialize<'de> for Activation {
         let mut th = TableHelper::new(value)?;
         let scripts = th.optional("scripts");
         let env = th.optional::<TomlIndexMap<_, _>>("env");
+        let dotenv = th.optional("dotenv");
         th.finalize(None)?;
         Ok(Activation {
             scripts,
             env: env.map(TomlIndexMap::into_inner),
+            dotenv,
         })
     }
 }
@@ -32,6 +77,7 @@ mod test {
     fn deserialize_activation() {
         let input = r#"
             scripts = ["echo 'Hello, World!'"]
+            dotenv = [".env", { path = ".env.local", optional = true }]
             [env]
             FOO = "bar"
             "#;
@@ -48,5 +94,18 @@ mod test {
                 "bar".to_string()
             )]))
         );
+        assert_eq!(
+            activation.dotenv,
+            Some(vec![
+                DotenvFile {
+                    path: PathBuf::from(".env"),
+                    optional: false,
+                },
+                DotenvFile {
+                    path: PathBuf::from(".env.local"),
+                    optional: true,
+                },
+            ])
+        );
     }
 }
@@ -1,6 +1,9 @@
+use std::{path::Path, str::FromStr};
+
 use itertools::Itertools;
+use pixi_path::normalize_std;
 use pixi_spec::ExcludeNewer;
-use rattler_conda_types::NamedChannelOrUrl;
+use rattler_conda_types::{NamedChannelOrUrl, Platform};
 use toml_edit::{Table, Value};
 
 /// A channel with an optional priority.
@@ -11,6 +14,9 @@ pub struct PrioritizedChannel {
     pub channel: NamedChannelOrUrl,
     pub priority: Option<i32>,
     pub exclude_newer: Option<ExcludeNewer>,
+    /// The subdirs to fetch from this channel. If not specified, the platform
+    /// that is solved for and `noarch` are fetched.
+    pub platforms: Option<Vec<Platform>>,
 }
 
 impl PrioritizedChannel {
@@ -39,6 +45,7 @@ impl From<NamedChannelOrUrl> for PrioritizedChannel {
             channel: value,
             priority: None,
             exclude_newer: None,
+            platforms: None,
         }
     }
 }
@@ -49,29 +56,145 @@ impl From<(NamedChannelOrUrl, Option<i32>)> for PrioritizedChannel {
             channel: value,
             priority: prio,
             exclude_newer: None,
+            platforms: None,
         }
     }
 }
 
 impl From<PrioritizedChannel> for Value {
     fn from(channel: PrioritizedChannel) -> Self {
-        match (channel.priority, channel.exclude_newer) {
-            (Some(priority), exclude_newer) => {
-                let mut table = Table::new().into_inline_table();
-                table.insert("channel", channel.channel.to_string().into());
-                table.insert("priority", i64::from(priority).into());
-                if let Some(exclude_newer) = exclude_newer {
-                    table.insert("exclude-newer", exclude_newer.to_string().into());
-                }
-                Value::InlineTable(table)
-            }
-            (None, Some(exclude_newer)) => {
-                let mut table = Table::new().into_inline_table();
-                table.insert("channel", channel.channel.to_string().into());
-                table.insert("exclude-newer", exclude_newer.to_string().into());
-                Value::InlineTable(table)
+        if channel.priority.is_none()
+            && channel.exclude_newer.is_none()
+            && channel.platforms.is_none()
+        {
+            return Value::String(toml_edit::Formatted::new(channel.channel.to_string()));
+        }
+
+        let mut table = Table::new().into_inline_table();
+        table.insert("channel", channel.channel.to_string().into());
+        if let Some(priority) = channel.priority {
+            table.insert("priority", i64::from(priority).into());
+        }
+        if let Some(exclude_newer) = channel.exclude_newer {
+            table.insert("exclude-newer", exclude_newer.to_string().into());
+        }
+        if let Some(platforms) = channel.platforms {
+            table.insert(
+                "platforms",
+                Value::Array(platforms.iter().map(Platform::as_str).collect()),
+            );
+        }
+        Value::InlineTable(table)
+    }
+}
+
+/// Returns `path` with an uppercase drive letter if it starts with a lowercase
+/// Windows drive, e.g. `c:/channel`.
+fn uppercase_drive_letter(path: &str) -> Option<String> {
+    let mut chars = path.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(drive), Some(':'), None | Some('/' | '\\')) if drive.is_ascii_lowercase() => {
+            Some(format!("{}{}", drive.to_ascii_uppercase(), &path[1..]))
+        }
+        _ => None,
+    }
+}
+
+/// Uppercases the drive letter of local channels, so that `file:///c:/channel`
+/// and `C:\channel` refer to the same channel. Other channels are returned
+/// unchanged.
+pub(crate) fn canonicalize_local_channel(channel: NamedChannelOrUrl) -> NamedChannelOrUrl {
+    match channel {
+        NamedChannelOrUrl::Url(mut url) if url.scheme() == "file" => {
+            if let Some(path) = url
+                .path()
+                .strip_prefix('/')
+                .and_then(uppercase_drive_letter)
+            {
+                url.set_path(&format!("/{path}"));
             }
-            (None, None) => Value::String(toml_edit::Formatted::new(channel.channel.to_string())),
+            NamedChannelOrUrl::Url(url)
         }
+        NamedChannelOrUrl::Path(path) => uppercase_drive_letter(path.as_str())
+            .and_then(|path| NamedChannelOrUrl::from_str(&path).ok())
+            .unwrap_or(NamedChannelOrUrl::Path(path)),
+        channel => channel,
+    }
+}
+
+/// Returns the form in which to store a channel that was given relative to
+/// `cwd`, e.g. on the command line, in the manifest of the workspace in
+/// `root_dir`.
+///
+/// Relative paths in the manifest are resolved relative to `root_dir`, so a
+/// relative path is rebased from `cwd` onto `root_dir` and written with
+/// forward slashes. Absolute paths and `file://` urls stay absolute.
+pub fn normalize_local_channel(
+    channel: NamedChannelOrUrl,
+    cwd: &Path,
+    root_dir: &Path,
+) -> NamedChannelOrUrl {
+    let channel = canonicalize_local_channel(channel);
+    let NamedChannelOrUrl::Path(path) = &channel else {
+        return channel;
+    };
+    let path = Path::new(path.as_str());
+    if path.has_root() || path.starts_with("~") {
+        return channel;
+    }
+    let Some(relative) =
+        pathdiff::diff_paths(normalize_std(&cwd.join(path)), normalize_std(root_dir))
+    else {
+        return channel;
+    };
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let relative = if relative == ".." || relative.starts_with("../") {
+        relative
+    } else {
+        format!("./{relative}")
+    };
+    NamedChannelOrUrl::from_str(&relative).unwrap_or(channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(channel: &str) -> NamedChannelOrUrl {
+        NamedChannelOrUrl::from_str(channel).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_local_channel() {
+        let root = Path::new("/workspace");
+        let normalize = |value: &str, cwd: &str| {
+            normalize_local_channel(channel(value), Path::new(cwd), root).to_string()
+        };
+
+        assert_eq!(normalize("./channel", "/workspace"), "./channel");
+        assert_eq!(normalize("../channel", "/workspace/sub"), "./channel");
+        assert_eq!(normalize("./channel", "/elsewhere"), "../elsewhere/channel");
+        assert_eq!(normalize("/abs/channel", "/elsewhere"), "/abs/channel");
+        assert_eq!(normalize("conda-forge", "/elsewhere"), "conda-forge");
+        assert_eq!(
+            normalize("file:///abs/channel", "/elsewhere"),
+            "file:///abs/channel"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_drive_letter() {
+        assert_eq!(
+            canonicalize_local_channel(channel("file:///c:/channel")).to_string(),
+            "file:///C:/channel"
+        );
+        assert_eq!(
+            canonicalize_local_channel(channel("c:\\channel")).to_string(),
+            "C:\\channel"
+        );
+        assert_eq!(
+            canonicalize_local_channel(channel("https://c:8080/channel")).to_string(),
+            "https://c:8080/channel"
+        );
     }
 }
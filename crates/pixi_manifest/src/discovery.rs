@@ -398,6 +398,7 @@ impl WorkspaceDiscoverer {
                             "Found manifest in directory: {:?}, continuing further.",
                             provenance.path
                         );
+                        Self::warn_if_ambiguous_manifest(explicit, &provenance);
                         (explicit.parent().map(ToOwned::to_owned), Some(provenance))
                     }
                 }
@@ -660,6 +661,26 @@ impl WorkspaceDiscoverer {
         Ok(None)
     }
 
+    /// Warns if the `pixi.toml` that was picked from an explicitly given
+    /// directory is accompanied by a `pyproject.toml` that also defines a pixi
+    /// workspace.
+    fn warn_if_ambiguous_manifest(dir: &Path, provenance: &ManifestProvenance) {
+        let pyproject_toml_path = dir.join(consts::PYPROJECT_MANIFEST);
+        if provenance.kind != ManifestKind::Pixi
+            || !std::fs::read_to_string(&pyproject_toml_path)
+                .is_ok_and(|source| source.contains("[tool.pixi"))
+        {
+            return;
+        }
+        tracing::warn!(
+            "both '{}' and '{}' define a workspace in '{}', using '{}'",
+            consts::WORKSPACE_MANIFEST,
+            consts::PYPROJECT_MANIFEST,
+            dir.display(),
+            provenance.path.display()
+        );
+    }
+
     /// Discover the workspace manifest in a directory.
     fn provenance_from_dir(dir: &Path) -> Option<ManifestProvenance> {
         let pixi_toml_path = dir.join(consts::WORKSPACE_MANIFEST);
@@ -0,0 +1,343 @@
+//! Detection of dependencies that are defined more than once in a feature in a
+//! way that is most likely a mistake.
+
+use std::fmt::{Display, Formatter};
+
+use pep440_rs::{Operator, VersionSpecifiers};
+use pixi_pypi_spec::{PixiPypiSpec, VersionOrStar};
+use rattler_conda_types::{PackageName, VersionSpec, version_spec::EqualityOperator};
+
+use crate::{Feature, FeatureName, SpecType, TargetSelector, WorkspaceTarget};
+
+/// A dependency that is defined more than once in a feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuplicateDependency {
+    /// A package that is both a conda and a PyPI dependency. The PyPI package
+    /// is installed on top of the conda package and overwrites its files.
+    CondaAndPypi {
+        feature: FeatureName,
+        conda_target: Option<TargetSelector>,
+        pypi_target: Option<TargetSelector>,
+        name: PackageName,
+    },
+
+    /// A target that requires a version of a dependency that cannot satisfy
+    /// the spec of the same dependency in the same table of the base target,
+    /// or the other way around.
+    IncompatibleTarget {
+        feature: FeatureName,
+        target: TargetSelector,
+        /// The name of the table in the manifest, e.g. `pypi-dependencies`.
+        table: &'static str,
+        name: String,
+        spec: String,
+        base_spec: String,
+    },
+}
+
+impl DuplicateDependency {
+    /// A suggestion on how to resolve the duplicate.
+    pub fn help(&self) -> &'static str {
+        match self {
+            DuplicateDependency::CondaAndPypi { .. } => {
+                "the PyPI package is installed on top of the conda package and overwrites its files, remove one of the two"
+            }
+            DuplicateDependency::IncompatibleTarget { .. } => {
+                "on the platforms of the target its spec is used instead of the spec of the base table, make them agree or remove one of them"
+            }
+        }
+    }
+}
+
+impl Display for DuplicateDependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicateDependency::CondaAndPypi {
+                feature,
+                conda_target,
+                pypi_target,
+                name,
+            } => write!(
+                f,
+                "'{}' is both a conda dependency in {} and a PyPI dependency in {}",
+                name.as_source(),
+                table_name(feature, conda_target.as_ref(), "dependencies"),
+                table_name(feature, pypi_target.as_ref(), "pypi-dependencies"),
+            ),
+            DuplicateDependency::IncompatibleTarget {
+                feature,
+                target,
+                table,
+                name,
+                spec,
+                base_spec,
+            } => write!(
+                f,
+                "'{name}' requires '{spec}' in {} which conflicts with '{base_spec}' in {}",
+                table_name(feature, Some(target), table),
+                table_name(feature, None, table),
+            ),
+        }
+    }
+}
+
+/// Returns the name of a dependency table as it is written in the manifest.
+fn table_name(feature: &FeatureName, target: Option<&TargetSelector>, table: &str) -> String {
+    let mut name = String::from("[");
+    if !feature.is_default() {
+        name.push_str(&format!("feature.{feature}."));
+    }
+    if let Some(target) = target {
+        name.push_str(&format!("target.{target}."));
+    }
+    name.push_str(table);
+    name.push(']');
+    name
+}
+
+/// Returns the dependencies of the given features that are defined in both
+/// the conda and the PyPI dependencies, and the target dependencies whose
+/// spec conflicts with the spec of the same table of the base target.
+pub fn duplicate_dependencies<'a>(
+    features: impl IntoIterator<Item = &'a Feature>,
+) -> Vec<DuplicateDependency> {
+    let mut duplicates = Vec::new();
+    for feature in features {
+        let base = feature.targets.default();
+        for (target, selector) in feature.targets.iter() {
+            duplicates.extend(conda_and_pypi(base, target, selector).map(
+                |(name, conda_target)| DuplicateDependency::CondaAndPypi {
+                    feature: feature.name.clone(),
+                    conda_target,
+                    pypi_target: selector.cloned(),
+                    name,
+                },
+            ));
+
+            let Some(selector) = selector else {
+                continue;
+            };
+            let duplicate =
+                |table, name, spec, base_spec| DuplicateDependency::IncompatibleTarget {
+                    feature: feature.name.clone(),
+                    target: selector.clone(),
+                    table,
+                    name,
+                    spec,
+                    base_spec,
+                };
+            for spec_type in SpecType::all() {
+                duplicates.extend(incompatible_conda(base, target, spec_type).map(
+                    |(name, spec, base_spec)| duplicate(spec_type.name(), name, spec, base_spec),
+                ));
+            }
+            duplicates.extend(
+                incompatible_pypi(base, target).map(|(name, spec, base_spec)| {
+                    duplicate("pypi-dependencies", name, spec, base_spec)
+                }),
+            );
+        }
+    }
+    duplicates
+}
+
+/// The PyPI dependencies of `target` that are also a conda dependency of
+/// `target` or of the base target, together with the selector of the target
+/// that defines the conda dependency.
+fn conda_and_pypi<'a>(
+    base: &'a WorkspaceTarget,
+    target: &'a WorkspaceTarget,
+    selector: Option<&'a TargetSelector>,
+) -> impl Iterator<Item = (PackageName, Option<TargetSelector>)> + 'a {
+    let conda_names = move || {
+        [(target, selector), (base, None)]
+            .into_iter()
+            .filter_map(|(target, selector)| Some((target.run_dependencies()?, selector)))
+            .flat_map(|(dependencies, selector)| {
+                dependencies.names().map(move |name| (name, selector))
+            })
+    };
+    target
+        .pypi_dependencies
+        .iter()
+        .flat_map(|dependencies| dependencies.names())
+        .filter_map(move |pypi_name| {
+            // PyPI names are normalized with dashes, conda names may also
+            // contain underscores.
+            let pypi_name = pypi_name.as_normalized().to_string();
+            conda_names()
+                .find(|(conda_name, _)| conda_name.as_normalized().replace('_', "-") == pypi_name)
+                .map(|(conda_name, selector)| (conda_name.clone(), selector.cloned()))
+        })
+}
+
+/// The conda dependencies of `target` that pin a version that doesn't match
+/// the spec of the base target, or the other way around.
+fn incompatible_conda<'a>(
+    base: &'a WorkspaceTarget,
+    target: &'a WorkspaceTarget,
+    spec_type: SpecType,
+) -> impl Iterator<Item = (String, String, String)> + 'a {
+    let conflicts = |pin: &VersionSpec, other: &VersionSpec| match pin {
+        VersionSpec::Exact(EqualityOperator::Equals, version) => !other.matches(version),
+        _ => false,
+    };
+    target
+        .dependencies(spec_type)
+        .into_iter()
+        .flat_map(|dependencies| dependencies.iter_specs())
+        .filter_map(move |(name, spec)| {
+            let spec = spec.as_version_spec()?;
+            let base_spec = base
+                .dependencies(spec_type)?
+                .get(name)?
+                .iter()
+                .find_map(|spec| spec.as_version_spec())?;
+            (conflicts(spec, base_spec) || conflicts(base_spec, spec)).then(|| {
+                (
+                    name.as_source().to_string(),
+                    spec.to_string(),
+                    base_spec.to_string(),
+                )
+            })
+        })
+}
+
+/// The PyPI dependencies of `target` that pin a version that doesn't match
+/// the specifiers of the base target, or the other way around.
+fn incompatible_pypi<'a>(
+    base: &'a WorkspaceTarget,
+    target: &'a WorkspaceTarget,
+) -> impl Iterator<Item = (String, String, String)> + 'a {
+    let specifiers = |spec: &'a PixiPypiSpec| match spec.source.as_version()? {
+        VersionOrStar::Version(specifiers) => Some(specifiers),
+        VersionOrStar::Star => None,
+    };
+    let conflicts = |pin: &VersionSpecifiers, other: &VersionSpecifiers| match &pin[..] {
+        [pin] if *pin.operator() == Operator::Equal => !other.contains(pin.version()),
+        _ => false,
+    };
+    target
+        .pypi_dependencies
+        .iter()
+        .flat_map(|dependencies| dependencies.iter_specs())
+        .filter_map(move |(name, spec)| {
+            let spec = specifiers(spec)?;
+            let base_spec = base
+                .pypi_dependencies
+                .as_ref()?
+                .get(name)?
+                .iter()
+                .find_map(specifiers)?;
+            (conflicts(spec, base_spec) || conflicts(base_spec, spec)).then(|| {
+                (
+                    name.as_source().to_string(),
+                    spec.to_string(),
+                    base_spec.to_string(),
+                )
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::toml::{ExternalWorkspaceProperties, FromTomlStr, PackageDefaults, TomlManifest};
+
+    fn duplicates(pixi_toml: &str) -> Vec<String> {
+        let (manifest, _, _) = TomlManifest::from_toml_str(pixi_toml)
+            .unwrap()
+            .into_workspace_manifest(
+                ExternalWorkspaceProperties::default(),
+                PackageDefaults::default(),
+                Path::new(""),
+            )
+            .unwrap();
+        duplicate_dependencies(manifest.features.values())
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec()
+    }
+
+    #[test]
+    fn test_conda_and_pypi_duplicates() {
+        let duplicates = duplicates(
+            r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ["linux-64"]
+
+        [dependencies]
+        python = "*"
+        ruamel_yaml = "*"
+
+        [pypi-dependencies]
+        ruamel-yaml = "*"
+
+        [feature.test.dependencies]
+        pytest = "*"
+
+        [feature.test.target.linux-64.pypi-dependencies]
+        pytest = "*"
+        "#,
+        );
+        assert_eq!(
+            duplicates,
+            vec![
+                "'ruamel_yaml' is both a conda dependency in [dependencies] and a PyPI dependency in [pypi-dependencies]",
+                "'pytest' is both a conda dependency in [feature.test.dependencies] and a PyPI dependency in [feature.test.target.linux-64.pypi-dependencies]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_incompatible_target_specs() {
+        let duplicates = duplicates(
+            r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ["linux-64", "win-64"]
+
+        [dependencies]
+        python = "3.11.*"
+        numpy = ">=1.26"
+
+        [host-dependencies]
+        cmake = "==3.28.0"
+
+        [pypi-dependencies]
+        requests = "==2.31.0"
+        rich = ">=13"
+
+        [target.win-64.dependencies]
+        python = "==3.10.4"
+        numpy = "<2"
+
+        [target.win-64.host-dependencies]
+        cmake = ">=3.29"
+
+        [feature.test.pypi-dependencies]
+        pytest = ">=8"
+
+        [feature.test.target.linux-64.pypi-dependencies]
+        requests = "==2.30.0"
+        rich = "==13.7.0"
+        pytest = "==7.4.0"
+        "#,
+        );
+        assert_eq!(
+            duplicates,
+            vec![
+                "'python' requires '==3.10.4' in [target.win-64.dependencies] which conflicts with '3.11.*' in [dependencies]",
+                "'cmake' requires '>=3.29' in [target.win-64.host-dependencies] which conflicts with '==3.28.0' in [host-dependencies]",
+                "'pytest' requires '==7.4.0' in [feature.test.target.linux-64.pypi-dependencies] which conflicts with '>=8' in [feature.test.pypi-dependencies]",
+            ]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     pypi::pypi_options::PypiOptions, target::Targets, workspace::ChannelPriority,
     workspace::SolveStrategy,
 };
-use crate::{PixiPlatform, PixiPlatformName};
+use crate::{DotenvFile, PixiPlatform, PixiPlatformName};
 use indexmap::{IndexMap, IndexSet};
 use pixi_pypi_spec::{PixiPypiSpec, PypiPackageName};
 use pixi_spec::PixiSpec;
@@ -362,6 +362,23 @@ impl Feature {
             })
     }
 
+    /// Returns the `.env` files of all targets that match the given
+    /// `platform`, the files of the most specific target come last so their
+    /// variables take precedence.
+    pub fn activation_dotenv<'a>(
+        &'a self,
+        platform: Option<&'a PixiPlatform>,
+    ) -> Vec<&'a DotenvFile> {
+        let mut files = self
+            .targets
+            .resolve(platform)
+            .filter_map(|t| t.activation.as_ref())
+            .filter_map(|a| a.dotenv.as_ref())
+            .collect::<Vec<_>>();
+        files.reverse();
+        files.into_iter().flatten().collect()
+    }
+
     /// Returns true if the feature contains any reference to a pypi
     /// dependencies.
     pub fn has_pypi_dependencies(&self) -> bool {
@@ -1,12 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use chrono::{DateTime, Utc};
 use indexmap::{IndexMap, IndexSet};
 use itertools::{Either, Itertools};
 use miette::Diagnostic;
+use pixi_pypi_spec::PixiPypiSpec;
 use pixi_spec::{ExcludeNewer, ResolvedExcludeNewer};
 use pixi_spec_containers::DependencyMap;
-use rattler_conda_types::{ChannelConfig, ChannelUrl, NamedChannelOrUrl, ParseChannelError};
+use rattler_conda_types::{
+    ChannelConfig, ChannelUrl, NamedChannelOrUrl, ParseChannelError, Platform,
+};
 
 use crate::{
     CondaConstraints, CondaDependencies, PixiPlatform, PixiPlatformName, PrioritizedChannel,
@@ -14,7 +17,10 @@ use crate::{
     dependencies::CondaDevDependencies,
     has_features_iter::HasFeaturesIter,
     has_manifest_ref::HasWorkspaceManifest,
-    pypi::{ResolvedPypiExcludeNewer, pypi_options::PypiOptions},
+    pypi::{
+        ResolvedPypiExcludeNewer,
+        pypi_options::{NoBinary, NoBuild, NoBuildIsolation, PypiOptions},
+    },
     workspace::{ChannelPriority, SolveStrategy},
 };
 
@@ -85,6 +91,35 @@ pub trait FeaturesExt<'source>: HasWorkspaceManifest<'source> + HasFeaturesIter<
             .collect()
     }
 
+    /// Returns the subdirs to fetch for the channels of this collection that
+    /// restrict them with `platforms`. Other channels are not part of the
+    /// map. When a channel is listed several times with different
+    /// `platforms`, the union of them is fetched.
+    fn channel_subdirs(
+        &self,
+        channel_config: &ChannelConfig,
+    ) -> Result<BTreeMap<ChannelUrl, Vec<Platform>>, ParseChannelError> {
+        let channels = self.features().flat_map(|feature| match &feature.channels {
+            Some(channels) => channels,
+            None => &self.workspace_manifest().workspace.channels,
+        });
+
+        let mut channel_subdirs = BTreeMap::new();
+        for channel in channels {
+            let Some(platforms) = &channel.platforms else {
+                continue;
+            };
+            let url = channel.channel.clone().into_base_url(channel_config)?;
+            let subdirs: &mut Vec<Platform> = channel_subdirs.entry(url).or_default();
+            for platform in platforms {
+                if !subdirs.contains(platform) {
+                    subdirs.push(*platform);
+                }
+            }
+        }
+        Ok(channel_subdirs)
+    }
+
     /// Returns the channel priority, error on multiple, different values, return None if
     /// no value is set.
     ///
@@ -324,9 +359,45 @@ pub trait FeaturesExt<'source>: HasWorkspaceManifest<'source> + HasFeaturesIter<
                     .expect("merging of pypi-options should already have been checked")
             });
 
+        // Dependencies can opt out of build isolation, building from source or
+        // installing wheels themselves, which adds them to the corresponding
+        // option.
+        let dependencies_with = |flag: fn(&PixiPypiSpec) -> bool| {
+            self.features()
+                .flat_map(|feature| feature.targets.targets())
+                .filter_map(|target| target.pypi_dependencies.as_ref())
+                .flat_map(|dependencies| dependencies.iter_specs())
+                .filter(move |(_, spec)| flag(spec))
+                .map(|(name, _)| name.as_normalized().clone())
+                .collect::<IndexSet<_>>()
+        };
+        let dependencies_without_isolation = dependencies_with(|spec| spec.no_build_isolation);
+        let dependencies_without_build = dependencies_with(|spec| spec.no_build);
+        let dependencies_without_binary = dependencies_with(|spec| spec.no_binary);
+
         // Overlay features on top of the workspace base: features win on
         // single-assignment fields, lists and union-like fields are merged.
-        base.overlay(&feature_opts)
+        let mut options = base.overlay(&feature_opts);
+        options.no_build_isolation = options
+            .no_build_isolation
+            .union(&NoBuildIsolation::Packages(dependencies_without_isolation));
+        if !dependencies_without_build.is_empty() {
+            options.no_build = Some(
+                options
+                    .no_build
+                    .unwrap_or_default()
+                    .union(&NoBuild::Packages(dependencies_without_build)),
+            );
+        }
+        if !dependencies_without_binary.is_empty() {
+            options.no_binary = Some(
+                options
+                    .no_binary
+                    .unwrap_or_default()
+                    .union(&NoBinary::Packages(dependencies_without_binary)),
+            );
+        }
+        options
     }
 }
 
@@ -3,6 +3,7 @@ mod build_system;
 pub(crate) mod channel;
 mod dependencies;
 mod discovery;
+mod duplicate_dependencies;
 mod environment;
 mod environments;
 mod error;
@@ -24,17 +25,19 @@ mod target;
 pub mod task;
 pub mod toml;
 pub mod utils;
+mod validation;
 mod warning;
 mod workspace;
-pub use activation::Activation;
+pub use activation::{Activation, DotenvFile};
 pub use build_system::BuildBackend;
 pub use build_system::PackageBuild;
-pub use channel::PrioritizedChannel;
+pub use channel::{PrioritizedChannel, normalize_local_channel};
 pub use dependencies::{CondaConstraints, CondaDependencies, PyPiDependencies};
 pub use discovery::{
     DiscoveryStart, ExplicitManifestError, InvalidRequiresPixiError, LoadManifestsError, Manifests,
     PixiVersionMismatchError, WorkspaceDiscoverer, WorkspaceDiscoveryError,
 };
+pub use duplicate_dependencies::{DuplicateDependency, duplicate_dependencies};
 pub use environment::{Environment, EnvironmentName};
 pub use error::{DependencyError, TomlError};
 pub use feature::{Feature, FeatureName};
@@ -62,6 +65,7 @@ pub use system_requirements::{
 pub use target::{PackageTarget, TargetSelector, Targets, WorkspaceTarget};
 pub use task::{Task, TaskName};
 use thiserror::Error;
+pub use validation::{ManifestValidationError, validate_manifest};
 pub use warning::{Warning, WarningWithSource, WithWarnings};
 pub use workspace::{BuildVariantSource, ChannelPriority, SolveStrategy, Workspace};
 
@@ -6,7 +6,7 @@ use pixi_pypi_spec::{PixiPypiSpec, PypiPackageName};
 use pixi_spec::PixiSpec;
 use rattler_conda_types::PackageName;
 use thiserror::Error;
-use toml_edit::{Array, DocumentMut, Item, Table, Value, value};
+use toml_edit::{Array, DocumentMut, Item, Table, TableLike, Value, value};
 
 use crate::{
     FeatureName, ManifestKind, ManifestProvenance, PixiPlatform, PixiPlatformName,
@@ -370,10 +370,13 @@ impl ManifestDocument {
         //  - When explicitly requested
         //  - When a specific platform is requested, as markers are not supported (https://github.com/prefix-dev/pixi/issues/2149)
         //  - When an editable install is requested
+        //  - When the requirement sets how the package is built
         if matches!(self, ManifestDocument::PixiToml(_))
             || matches!(location, Some(PypiDependencyLocation::PixiPypiDependencies))
             || target.is_some()
             || editable.is_some_and(|e| e)
+            || pixi_requirement
+                .is_some_and(|req| req.no_build_isolation || req.no_build || req.no_binary)
         {
             let mut pypi_requirement = match pixi_requirement {
                 Some(existing) => existing.update_requirement(requirement)?,
@@ -514,6 +517,34 @@ impl ManifestDocument {
         Ok(())
     }
 
+    /// Sorts a single dependency table (e.g. `dependencies` or
+    /// `pypi-dependencies`) of a feature and target alphabetically by package
+    /// name. Other tables are left untouched.
+    ///
+    /// Comments are attached to the entry they precede, so they move along
+    /// with it.
+    pub fn sort_dependency_table(
+        &mut self,
+        table: &str,
+        target: Option<&TargetSelector>,
+        feature_name: &FeatureName,
+    ) {
+        let table_name = TableName::new()
+            .with_prefix(self.table_prefix())
+            .with_feature_name(Some(feature_name))
+            .with_target(target.cloned())
+            .with_table(Some(table));
+
+        let mut dependencies = self.manifest_mut().as_table_mut() as &mut dyn TableLike;
+        for key in table_name.as_keys() {
+            match dependencies.get_mut(key).and_then(Item::as_table_like_mut) {
+                Some(table) => dependencies = table,
+                None => return,
+            }
+        }
+        dependencies.sort_values();
+    }
+
     /// Determines the location of a PyPi dependency within the manifest.
     ///
     /// This method checks various sections of the manifest to locate the
@@ -780,6 +811,7 @@ impl ManifestDocument {
     }
 }
 
+/// Sorts the dependency tables that are direct children of the given table.
 #[cfg(test)]
 mod test {
     use super::*;
@@ -825,6 +857,57 @@ hTTPx = ">=0.28.1,<0.29" # Some comment.
         insta::assert_snapshot!(document.to_string());
     }
 
+    #[test]
+    pub fn sort_dependency_table_retains_decoration() {
+        let manifest_content = r#"[workspace]
+channels = []
+platforms = []
+
+[dependencies]
+# The compression library.
+zlib = "*"
+bar = "*" # Some comment.
+
+[pypi-dependencies]
+requests = "*"
+httpx = "*"
+
+[target.linux-64.pypi-dependencies]
+requests = "*"
+httpx = "*"
+"#;
+
+        let mut document = ManifestDocument::PixiToml(TomlDocument::new(
+            DocumentMut::from_str(manifest_content).unwrap(),
+        ));
+        document.sort_dependency_table(consts::DEPENDENCIES, None, &FeatureName::default());
+        document.sort_dependency_table(
+            consts::PYPI_DEPENDENCIES,
+            Some(&TargetSelector::from_str("linux-64").unwrap()),
+            &FeatureName::default(),
+        );
+
+        // Only the tables that were asked for are sorted.
+        insta::assert_snapshot!(document.to_string(), @r#"
+        [workspace]
+        channels = []
+        platforms = []
+
+        [dependencies]
+        bar = "*" # Some comment.
+        # The compression library.
+        zlib = "*"
+
+        [pypi-dependencies]
+        requests = "*"
+        httpx = "*"
+
+        [target.linux-64.pypi-dependencies]
+        httpx = "*"
+        requests = "*"
+        "#);
+    }
+
     /// This test checks that when calling `add_pypi_dependency` with
     /// dependencies that already exist in different locations the
     /// formatting and comments are preserved across all PyPI dependency
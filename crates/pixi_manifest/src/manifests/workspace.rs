@@ -246,6 +246,21 @@ impl WorkspaceManifestMut<'_> {
             miette::bail!("task {} already exists", name);
         }
 
+        self.replace_task(name, task, platform, feature_name)
+    }
+
+    /// Add a task to the project, replacing a task with the same name if it
+    /// already exists.
+    ///
+    /// This function modifies both the workspace and the TOML document. Use
+    /// `ManifestProvenance::save` to persist the changes to disk.
+    pub fn replace_task(
+        &mut self,
+        name: TaskName,
+        task: Task,
+        platform: Option<&PixiPlatform>,
+        feature_name: &FeatureName,
+    ) -> miette::Result<()> {
         // Add the task to the Toml manifest
         self.document
             .add_task(name.as_str(), task.clone(), platform, feature_name)?;
@@ -980,6 +995,25 @@ impl WorkspaceManifestMut<'_> {
         Ok(any_added)
     }
 
+    /// Sorts the dependency table `table` of a feature alphabetically by
+    /// package name, for each of the given platforms or for the default
+    /// target if no platforms are given.
+    ///
+    /// This only modifies the TOML document, the order of the dependencies
+    /// does not affect the workspace.
+    pub fn sort_dependency_table(
+        &mut self,
+        table: &str,
+        platforms: &[PixiPlatformName],
+        feature_name: &FeatureName,
+    ) {
+        for platform_name in to_options(platforms) {
+            let selector = self.platform_target_selector(platform_name.as_ref());
+            self.document
+                .sort_dependency_table(table, selector.as_ref(), feature_name);
+        }
+    }
+
     /// Convert a (possibly absent) workspace platform name into the
     /// [`TargetSelector`] used to key target tables. For platforms whose name
     /// matches the conda subdir and that declare no virtual packages we use
@@ -3190,6 +3224,7 @@ platforms = ["linux-64", "win-64"]
                 channel: NamedChannelOrUrl::Name(String::from("conda-forge")),
                 priority: None,
                 exclude_newer: None,
+                platforms: None,
             }]
             .into_iter()
             .collect::<IndexSet<_>>()
@@ -3206,6 +3241,7 @@ platforms = ["linux-64", "win-64"]
                 channel: NamedChannelOrUrl::Name(String::from("conda-forge")),
                 priority: None,
                 exclude_newer: None,
+                platforms: None,
             }]
             .into_iter()
             .collect::<IndexSet<_>>()
@@ -3224,6 +3260,7 @@ platforms = ["linux-64", "win-64"]
                 channel: NamedChannelOrUrl::Name(String::from("nvidia")),
                 priority: None,
                 exclude_newer: None,
+                platforms: None,
             }]
             .into_iter()
             .collect::<IndexSet<_>>()
@@ -3247,6 +3284,7 @@ platforms = ["linux-64", "win-64"]
                 channel: NamedChannelOrUrl::Name(String::from("nvidia")),
                 priority: None,
                 exclude_newer: None,
+                platforms: None,
             }]
             .into_iter()
             .collect::<IndexSet<_>>()
@@ -3266,11 +3304,13 @@ platforms = ["linux-64", "win-64"]
                     channel: NamedChannelOrUrl::Name(String::from("test")),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 },
                 PrioritizedChannel {
                     channel: NamedChannelOrUrl::Name(String::from("test2")),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 },
             ]
             .into_iter()
@@ -3282,6 +3322,7 @@ platforms = ["linux-64", "win-64"]
             channel: NamedChannelOrUrl::Url("https://custom.com/channel".parse().unwrap()),
             priority: None,
             exclude_newer: None,
+            platforms: None,
         };
         manifest
             .add_channels([custom_channel.clone()], &FeatureName::DEFAULT, false)
@@ -3301,6 +3342,7 @@ platforms = ["linux-64", "win-64"]
             channel: NamedChannelOrUrl::Name(String::from("prioritized")),
             priority: Some(12i32),
             exclude_newer: None,
+            platforms: None,
         };
         manifest
             .add_channels([prioritized_channel1.clone()], &FeatureName::DEFAULT, false)
@@ -3319,6 +3361,7 @@ platforms = ["linux-64", "win-64"]
             channel: NamedChannelOrUrl::Name(String::from("prioritized2")),
             priority: Some(-12i32),
             exclude_newer: None,
+            platforms: None,
         };
         manifest
             .add_channels([prioritized_channel2.clone()], &FeatureName::DEFAULT, false)
@@ -3370,6 +3413,7 @@ platforms = ["linux-64", "win-64"]
                     channel: NamedChannelOrUrl::Name(String::from("conda-forge")),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 }],
                 &FeatureName::DEFAULT,
             )
@@ -3383,6 +3427,7 @@ platforms = ["linux-64", "win-64"]
                     channel: NamedChannelOrUrl::Name(String::from("test_channel")),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 }],
                 &FeatureName::from("test"),
             )
@@ -3405,6 +3450,7 @@ platforms = ["linux-64", "win-64"]
                         channel: NamedChannelOrUrl::Name(String::from("conda-forge")),
                         priority: None,
                         exclude_newer: None,
+                        platforms: None,
                     }],
                     &FeatureName::DEFAULT,
                 )
@@ -3580,11 +3626,13 @@ platforms = ["linux-64", "win-64"]
                     channel: NamedChannelOrUrl::Name(String::from("pytorch")),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 },
                 &PrioritizedChannel {
                     channel: NamedChannelOrUrl::Name(String::from("nvidia")),
                     priority: Some(-1),
                     exclude_newer: None,
+                    platforms: None,
                 },
             ]
         );
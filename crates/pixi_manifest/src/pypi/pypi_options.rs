@@ -171,10 +171,15 @@ pub struct PypiOptions {
     pub no_build: Option<NoBuild>,
     /// Dependency overrides
     pub dependency_overrides: Option<IndexMap<PypiPackageName, PixiPypiSpec>>,
+    /// Constraints on the versions of packages, without adding them as a
+    /// dependency
+    pub constraints: Option<IndexMap<PypiPackageName, PixiPypiSpec>>,
     /// Don't use pre-built wheels all or certain packages
     pub no_binary: Option<NoBinary>,
     /// Skip wheel filename validation
     pub skip_wheel_filename_check: Option<bool>,
+    /// Allow PyPI packages to overwrite files installed by conda packages
+    pub allow_clobber: Option<bool>,
 }
 
 use crate::pypi::merge::{
@@ -192,8 +197,10 @@ impl PypiOptions {
         prerelease_mode: Option<PrereleaseMode>,
         no_build: Option<NoBuild>,
         dependency_overrides: Option<IndexMap<PypiPackageName, PixiPypiSpec>>,
+        constraints: Option<IndexMap<PypiPackageName, PixiPypiSpec>>,
         no_binary: Option<NoBinary>,
         skip_wheel_filename_check: Option<bool>,
+        allow_clobber: Option<bool>,
     ) -> Self {
         Self {
             index_url: index,
@@ -204,8 +211,10 @@ impl PypiOptions {
             prerelease_mode,
             no_build,
             dependency_overrides,
+            constraints,
             no_binary,
             skip_wheel_filename_check,
+            allow_clobber,
         }
     }
 
@@ -270,6 +279,14 @@ impl PypiOptions {
             },
         )?;
 
+        let allow_clobber =
+            merge_single_option(&self.allow_clobber, &other.allow_clobber, |a, b| {
+                PypiOptionsMergeError::MultipleAllowClobber {
+                    first: *a,
+                    second: *b,
+                }
+            })?;
+
         // Ordered lists, deduplicated
         let extra_indexes = merge_list_dedup(&self.extra_index_urls, &other.extra_index_urls);
         let flat_indexes = merge_list_dedup(&self.find_links, &other.find_links);
@@ -282,6 +299,7 @@ impl PypiOptions {
         // Maps with left (self) overriding right (other)
         let dependency_overrides =
             merge_map_override_left(&self.dependency_overrides, &other.dependency_overrides);
+        let constraints = merge_map_override_left(&self.constraints, &other.constraints);
 
         Ok(PypiOptions {
             index_url: index,
@@ -292,8 +310,10 @@ impl PypiOptions {
             prerelease_mode,
             no_build,
             dependency_overrides,
+            constraints,
             no_binary,
             skip_wheel_filename_check,
+            allow_clobber,
         })
     }
 
@@ -304,7 +324,8 @@ impl PypiOptions {
     /// - List fields: concatenated and de-duplicated.
     /// - Union-like fields (`no_build`, `no_binary`, `no_build_isolation`):
     ///   union of both sides.
-    /// - `dependency_overrides`: `other` keys override `self` keys.
+    /// - `dependency_overrides` and `constraints`: `other` keys override `self`
+    ///   keys.
     ///
     /// Unlike [`Self::union`], this never errors on conflicting single-value
     /// fields; `other` simply overrides `self`.
@@ -319,6 +340,7 @@ impl PypiOptions {
             skip_wheel_filename_check: other
                 .skip_wheel_filename_check
                 .or(self.skip_wheel_filename_check),
+            allow_clobber: other.allow_clobber.or(self.allow_clobber),
             extra_index_urls: merge_list_dedup(&self.extra_index_urls, &other.extra_index_urls),
             find_links: merge_list_dedup(&self.find_links, &other.find_links),
             no_build_isolation: self.no_build_isolation.union(&other.no_build_isolation),
@@ -328,6 +350,7 @@ impl PypiOptions {
                 &other.dependency_overrides,
                 &self.dependency_overrides,
             ),
+            constraints: merge_map_override_left(&other.constraints, &self.constraints),
         }
     }
 }
@@ -447,6 +470,10 @@ pub enum PypiOptionsMergeError {
         "multiple skip-wheel-filename-check values are not supported, found both {first} and {second} across multiple pypi options"
     )]
     MultipleSkipWheelFilenameCheck { first: bool, second: bool },
+    #[error(
+        "multiple allow-clobber values are not supported, found both {first} and {second} across multiple pypi options"
+    )]
+    MultipleAllowClobber { first: bool, second: bool },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -556,8 +583,10 @@ mod tests {
                     }),
                 ),
             ])),
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: Some(true),
+            allow_clobber: None,
         };
 
         // Create the second set of options
@@ -588,9 +617,10 @@ mod tests {
                     }),
                 ),
             ])),
-
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Merge the two options
@@ -687,8 +717,10 @@ mod tests {
             prerelease_mode: None,
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Create the second set of options
@@ -701,8 +733,10 @@ mod tests {
             prerelease_mode: None,
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Merge the two options
@@ -723,8 +757,10 @@ mod tests {
             prerelease_mode: None,
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Create the second set of options
@@ -737,8 +773,10 @@ mod tests {
             prerelease_mode: None,
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Merge the two options
@@ -759,8 +797,10 @@ mod tests {
             prerelease_mode: Some(PrereleaseMode::Allow),
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Create the second set of options
@@ -773,8 +813,10 @@ mod tests {
             prerelease_mode: Some(PrereleaseMode::Disallow),
             no_build: Default::default(),
             dependency_overrides: None,
+            constraints: None,
             no_binary: Default::default(),
             skip_wheel_filename_check: None,
+            allow_clobber: None,
         };
 
         // Merge the two options
@@ -782,4 +824,23 @@ mod tests {
         let merged_opts = opts.union(&opts2);
         insta::assert_snapshot!(merged_opts.err().unwrap());
     }
+
+    #[test]
+    fn test_error_on_multiple_allow_clobber() {
+        let opts = PypiOptions {
+            allow_clobber: Some(true),
+            ..Default::default()
+        };
+        let opts2 = PypiOptions {
+            allow_clobber: Some(false),
+            ..Default::default()
+        };
+
+        // Merging the same value is fine
+        assert_eq!(opts.union(&opts).unwrap().allow_clobber, Some(true));
+
+        // This should error because the values conflict
+        let merged_opts = opts.union(&opts2);
+        insta::assert_snapshot!(merged_opts.err().unwrap());
+    }
 }
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use toml_edit::{Array, InlineTable, Item, Table, Value};
 
-use crate::EnvironmentName;
+use crate::{DotenvFile, EnvironmentName};
 
 /// Represents a task name
 #[derive(Debug, Clone, Serialize, Eq, PartialEq, Hash, Ord, PartialOrd, Deserialize)]
@@ -52,6 +52,9 @@ pub struct Dependency {
     pub task_name: TaskName,
     pub args: Option<Vec<DependencyArg>>,
     pub environment: Option<EnvironmentName>,
+    /// Whether the depending task still runs when this dependency fails.
+    #[serde(default)]
+    pub continue_on_error: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -70,6 +73,7 @@ impl Dependency {
             task_name: TaskName(s.to_string()),
             args,
             environment,
+            continue_on_error: false,
         }
     }
 
@@ -78,8 +82,17 @@ impl Dependency {
             task_name: TaskName(s.to_string()),
             args,
             environment: None,
+            continue_on_error: false,
         }
     }
+
+    /// Sets whether the depending task still runs when this dependency
+    /// fails.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
     pub fn render_args(
         &self,
         context: &TaskRenderContext,
@@ -229,6 +242,19 @@ impl Task {
             Task::Alias(_) => Ok(None),
         }
     }
+
+    /// Returns true if the command places the extra command line arguments
+    /// itself through `{{ args }}`. Otherwise they are appended to the
+    /// command.
+    pub fn forwards_args(&self) -> bool {
+        match self {
+            Task::Plain(str) => str.uses_variable(ARGS_VARIABLE),
+            Task::Custom(custom) => custom.templated && custom.cmd.uses_variable(ARGS_VARIABLE),
+            Task::Execute(exe) => exe.cmd.uses_variable(ARGS_VARIABLE),
+            Task::Alias(_) => false,
+        }
+    }
+
     /// Returns the environment variables for the task to run in.
     pub fn env(&self) -> Option<&IndexMap<String, TemplateString>> {
         match self {
@@ -239,6 +265,23 @@ impl Task {
         }
     }
 
+    /// Returns the `.env` files that are loaded before running the task.
+    pub fn dotenv(&self) -> &[DotenvFile] {
+        match self {
+            Task::Execute(exe) => exe.dotenv.as_deref().unwrap_or_default(),
+            _ => &[],
+        }
+    }
+
+    /// Returns the names of the packages or executables that have to be
+    /// installed in the environment to run the task.
+    pub fn requires(&self) -> &[String] {
+        match self {
+            Task::Execute(exe) => exe.requires.as_deref().unwrap_or_default(),
+            _ => &[],
+        }
+    }
+
     /// Returns the working directory for the task to run in.
     pub fn working_directory(&self) -> Option<&Path> {
         match self {
@@ -276,6 +319,23 @@ impl Task {
         }
     }
 
+    /// True if the environment should be activated before running this task.
+    pub fn activation(&self) -> bool {
+        match self {
+            Task::Execute(execute) => execute.activation,
+            _ => true,
+        }
+    }
+
+    /// Returns the shell that should run the command of this task, or `None`
+    /// if the default shell should be used.
+    pub fn shell(&self) -> Option<TaskShell> {
+        match self {
+            Task::Execute(execute) => execute.shell,
+            _ => None,
+        }
+    }
+
     /// Returns the inputs of the task.
     pub fn inputs(&self) -> Option<&GlobPatterns> {
         match self {
@@ -365,6 +425,9 @@ pub struct Execute {
     /// A list of environment variables to set before running the command
     pub env: Option<IndexMap<String, TemplateString>>,
 
+    /// A list of `.env` files to load before running the command
+    pub dotenv: Option<Vec<DotenvFile>>,
+
     /// A default environment to run the task in.
     pub default_environment: Option<EnvironmentName>,
 
@@ -374,10 +437,54 @@ pub struct Execute {
     /// Isolate the task from the running machine
     pub clean_env: bool,
 
+    /// Whether to activate the environment before running the command. When
+    /// disabled only the executables of the environment are put on the
+    /// `PATH`.
+    pub activation: bool,
+
+    /// The shell that runs the command, the cross-platform default shell is
+    /// used if this is not specified.
+    pub shell: Option<TaskShell>,
+
+    /// The packages or executables that have to be installed in the
+    /// environment to run the command.
+    pub requires: Option<Vec<String>>,
+
     /// The arguments to pass to the task
     pub args: Option<Vec<TaskArg>>,
 }
 
+/// The shell that runs the command of a task.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum TaskShell {
+    /// Run the command with `bash -c`.
+    Bash,
+    /// Run the command with `cmd /c`.
+    Cmd,
+    /// Run the command with `powershell -Command`, or `pwsh` outside of
+    /// Windows.
+    #[serde(rename = "powershell")]
+    #[strum(serialize = "powershell")]
+    PowerShell,
+    /// Split the command into arguments and execute it directly, without any
+    /// shell interpretation.
+    None,
+}
+
 impl From<Execute> for Task {
     fn from(value: Execute) -> Self {
         Task::Execute(Box::new(value))
@@ -475,6 +582,9 @@ impl Display for TypedArg {
     }
 }
 
+/// The template variable that holds the extra command line arguments.
+const ARGS_VARIABLE: &str = "args";
+
 // Static default environment name for use in Default impl
 static DEFAULT_ENV: EnvironmentName = EnvironmentName::Default;
 
@@ -515,6 +625,19 @@ impl Default for TaskRenderContext<'_> {
 }
 
 impl<'a> TaskRenderContext<'a> {
+    /// Returns the extra command line arguments that `{{ args }}` expands to,
+    /// or `None` if a typed argument named `args` takes its place.
+    fn placeholder_args(&self) -> Option<&'a [String]> {
+        match self.args {
+            Some(ArgValues::TypedArgs { args, .. })
+                if args.iter().any(|arg| arg.name == ARGS_VARIABLE) =>
+            {
+                None
+            }
+            args => Some(args.map_or(&[][..], ArgValues::extra_args)),
+        }
+    }
+
     /// Builds a MiniJinja context value from this render context.
     ///
     /// The context always includes the pixi system variables.
@@ -529,6 +652,21 @@ impl<'a> TaskRenderContext<'a> {
             _ => HashMap::new(),
         };
 
+        // The extra command line arguments, each one single quoted so it
+        // stays a single word when the command is parsed. A typed argument
+        // with the same name takes precedence.
+        if let Some(extra_args) = self.placeholder_args() {
+            context_map.insert(
+                ARGS_VARIABLE.to_string(),
+                minijinja::Value::from(
+                    extra_args
+                        .iter()
+                        .map(|arg| format!("'{}'", arg.replace('\'', r#"'"'"'"#)))
+                        .join(" "),
+                ),
+            );
+        }
+
         // Create the pixi object with system-provided variables
         let mut pixi_vars: HashMap<String, minijinja::Value> = HashMap::new();
 
@@ -636,6 +774,23 @@ impl TemplateString {
     pub fn source(&self) -> &str {
         &self.0
     }
+
+    /// Returns true if the template references the given variable.
+    pub fn uses_variable(&self, name: &str) -> bool {
+        JINJA_ENV
+            .template_from_str(&self.0)
+            .is_ok_and(|template| template.undeclared_variables(false).contains(name))
+    }
+
+    /// Returns true if the template consists of nothing but the given
+    /// variable, e.g. `{{ args }}`.
+    fn is_variable(&self, name: &str) -> bool {
+        self.0
+            .trim()
+            .strip_prefix("{{")
+            .and_then(|rest| rest.strip_suffix("}}"))
+            .is_some_and(|inner| inner.trim() == name)
+    }
 }
 
 /// A rendered string where placeholders were already replaced by arguments
@@ -747,6 +902,34 @@ impl TemplateStringError {
 pub enum CmdArgs {
     Single(TemplateString),
     Multiple(Vec<TemplateString>),
+    /// Commands that run one after the other in the same environment. A
+    /// command only runs if the previous one succeeded.
+    Sequence(Vec<TemplateString>),
+}
+
+/// Joins the commands of a [`CmdArgs::Sequence`] into a single command line
+/// that stops at the first command that fails.
+/// Renders the arguments of a [`CmdArgs::Multiple`] and quotes each one. An
+/// argument that is only `{{ args }}` expands to the extra command line
+/// arguments, each quoted separately, and to nothing if there are none.
+fn render_multiple(
+    args: &[TemplateString],
+    context: &TaskRenderContext,
+) -> Result<String, TemplateStringError> {
+    let mut rendered_args = Vec::new();
+    for arg in args {
+        match context.placeholder_args() {
+            Some(extra_args) if arg.is_variable(ARGS_VARIABLE) => {
+                rendered_args.extend(extra_args.iter().map(|arg| quote(arg).into_owned()));
+            }
+            _ => rendered_args.push(quote(&arg.render(context)?).into_owned()),
+        }
+    }
+    Ok(rendered_args.join(" "))
+}
+
+fn join_sequence<'a>(commands: impl IntoIterator<Item = &'a str>) -> String {
+    commands.into_iter().map(str::trim).join(" && ")
 }
 
 impl From<Vec<TemplateString>> for CmdArgs {
@@ -769,13 +952,15 @@ impl CmdArgs {
     ) -> Result<Option<Cow<'_, str>>, TemplateStringError> {
         match self {
             CmdArgs::Single(cmd) => Ok(Some(Cow::Owned(cmd.render(context)?))),
-            CmdArgs::Multiple(args) => {
-                let mut rendered_args = Vec::new();
-                for arg in args {
-                    let rendered = arg.render(context)?;
-                    rendered_args.push(quote(&rendered).to_string());
-                }
-                Ok(Some(Cow::Owned(rendered_args.join(" "))))
+            CmdArgs::Multiple(args) => Ok(Some(Cow::Owned(render_multiple(args, context)?))),
+            CmdArgs::Sequence(commands) => {
+                let rendered = commands
+                    .iter()
+                    .map(|command| command.render(context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(Cow::Owned(join_sequence(
+                    rendered.iter().map(String::as_str),
+                ))))
             }
         }
     }
@@ -787,17 +972,17 @@ impl CmdArgs {
     ) -> Result<Option<String>, TemplateStringError> {
         match self {
             CmdArgs::Single(cmd) => cmd.render(context).map(Some),
-            CmdArgs::Multiple(args) => {
-                let rendered_args = args
-                    .iter()
-                    .map(|arg| {
-                        Ok(match arg.render(context) {
-                            Ok(rendered) => quote(&rendered).to_string(),
-                            Err(e) => return Err(e),
-                        })
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Some(rendered_args.join(" ")))
+            CmdArgs::Multiple(args) => render_multiple(&args, context).map(Some),
+            CmdArgs::Sequence(_) => Ok(self.as_single(context)?.map(Cow::into_owned)),
+        }
+    }
+
+    /// Returns true if any of the arguments references the given variable.
+    pub fn uses_variable(&self, name: &str) -> bool {
+        match self {
+            CmdArgs::Single(cmd) => cmd.uses_variable(name),
+            CmdArgs::Multiple(args) | CmdArgs::Sequence(args) => {
+                args.iter().any(|arg| arg.uses_variable(name))
             }
         }
     }
@@ -808,6 +993,9 @@ impl CmdArgs {
             CmdArgs::Multiple(args) => Ok(Some(Cow::Owned(
                 args.iter().map(|arg| arg.source().to_string()).join(" "),
             ))),
+            CmdArgs::Sequence(commands) => Ok(Some(Cow::Owned(join_sequence(
+                commands.iter().map(TemplateString::source),
+            )))),
         }
     }
 }
@@ -835,6 +1023,11 @@ impl Display for Task {
                 CmdArgs::Multiple(mult) => {
                     write!(f, "{}", mult.iter().map(|arg| arg.source()).join(" "))?
                 }
+                CmdArgs::Sequence(commands) => write!(
+                    f,
+                    "{}",
+                    join_sequence(commands.iter().map(TemplateString::source))
+                )?,
             },
             _ => {}
         };
@@ -905,6 +1098,14 @@ impl From<Task> for Item {
                             Value::Array(Array::from_iter(cmd_strs.iter().map(|arg| arg.source()))),
                         );
                     }
+                    CmdArgs::Sequence(commands) => {
+                        table.insert(
+                            "commands",
+                            Value::Array(Array::from_iter(
+                                commands.iter().map(|command| command.source()),
+                            )),
+                        );
+                    }
                 }
 
                 if let Some(args) = &process.args {
@@ -943,32 +1144,40 @@ impl From<Task> for Item {
                     table.insert(
                         "depends-on",
                         Value::Array(Array::from_iter(process.depends_on.iter().map(|dep| {
-                            match &dep.args {
-                                Some(args) if !args.is_empty() => {
-                                    let mut table = Table::new().into_inline_table();
-                                    table.insert("task", dep.task_name.to_string().into());
-                                    table.insert(
-                                        "args",
-                                        Value::Array(Array::from_iter(args.iter().map(|arg| {
-                                            match arg {
-                                                DependencyArg::Positional(val) => {
-                                                    Value::from(val.source().to_string())
-                                                }
-                                                DependencyArg::Named(name, val) => {
-                                                    let mut table = InlineTable::new();
-                                                    table.insert(
-                                                        name,
-                                                        Value::from(val.source().to_string()),
-                                                    );
-                                                    Value::InlineTable(table)
-                                                }
+                            let args = dep.args.as_ref().filter(|args| !args.is_empty());
+                            if args.is_none() && dep.environment.is_none() && !dep.continue_on_error
+                            {
+                                return Value::from(dep.task_name.to_string());
+                            }
+                            let mut table = Table::new().into_inline_table();
+                            table.insert("task", dep.task_name.to_string().into());
+                            if let Some(args) = args {
+                                table.insert(
+                                    "args",
+                                    Value::Array(Array::from_iter(args.iter().map(
+                                        |arg| match arg {
+                                            DependencyArg::Positional(val) => {
+                                                Value::from(val.source().to_string())
                                             }
-                                        }))),
-                                    );
-                                    Value::InlineTable(table)
-                                }
-                                _ => Value::from(dep.task_name.to_string()),
+                                            DependencyArg::Named(name, val) => {
+                                                let mut table = InlineTable::new();
+                                                table.insert(
+                                                    name,
+                                                    Value::from(val.source().to_string()),
+                                                );
+                                                Value::InlineTable(table)
+                                            }
+                                        },
+                                    ))),
+                                );
+                            }
+                            if let Some(env) = &dep.environment {
+                                table.insert("environment", env.to_string().into());
                             }
+                            if dep.continue_on_error {
+                                table.insert("continue-on-error", true.into());
+                            }
+                            Value::InlineTable(table)
                         }))),
                     );
                 }
@@ -981,9 +1190,50 @@ impl From<Task> for Item {
                         Value::InlineTable(env.iter().map(|(k, v)| (k, v.source())).collect()),
                     );
                 }
+                if let Some(dotenv) = &process.dotenv {
+                    table.insert(
+                        "dotenv",
+                        Value::Array(Array::from_iter(dotenv.iter().map(|file| {
+                            let path = file.path.to_string_lossy().to_string();
+                            if !file.optional {
+                                return Value::from(path);
+                            }
+                            let mut table = InlineTable::new();
+                            table.insert("path", path.into());
+                            table.insert("optional", true.into());
+                            Value::InlineTable(table)
+                        }))),
+                    );
+                }
                 if let Some(description) = &process.description {
                     table.insert("description", description.into());
                 }
+                if !process.activation {
+                    table.insert("activation", false.into());
+                }
+                if let Some(shell) = process.shell {
+                    table.insert("shell", shell.to_string().into());
+                }
+                if let Some(requires) = &process.requires {
+                    table.insert("requires", Value::Array(Array::from_iter(requires.iter())));
+                }
+                if let Some(inputs) = &process.inputs {
+                    table.insert(
+                        "inputs",
+                        Value::Array(Array::from_iter(inputs.iter().map(|input| input.source()))),
+                    );
+                }
+                if let Some(outputs) = &process.outputs {
+                    table.insert(
+                        "outputs",
+                        Value::Array(Array::from_iter(
+                            outputs.iter().map(|output| output.source()),
+                        )),
+                    );
+                }
+                if process.clean_env {
+                    table.insert("clean-env", true.into());
+                }
                 Item::Value(Value::InlineTable(table))
             }
             Task::Alias(alias) => {
@@ -1040,6 +1290,10 @@ impl From<Task> for Item {
                             dep_table.insert("environment", env.to_string().into());
                         }
 
+                        if dep.continue_on_error {
+                            dep_table.insert("continue-on-error", true.into());
+                        }
+
                         deps.push(Value::InlineTable(dep_table));
                     }
                     table.insert("depends-on", Value::Array(Array::from_iter(deps)));
@@ -1075,6 +1329,10 @@ impl From<Task> for Item {
                             table.insert("environment", env.to_string().into());
                         }
 
+                        if dep.continue_on_error {
+                            table.insert("continue-on-error", true.into());
+                        }
+
                         array.push(Value::InlineTable(table));
                     }
 
@@ -1098,8 +1356,8 @@ mod tests {
     use insta::assert_snapshot;
     use rattler_conda_types::Platform;
 
-    use crate::PixiPlatform;
     use crate::task::{Alias, Dependency, DependencyArg, Task};
+    use crate::{PixiPlatform, WithWarnings, toml::FromTomlStr};
 
     use super::quote;
 
@@ -1136,6 +1394,22 @@ mod tests {
         assert_snapshot!(toml.to_string(), @r###"[{ task = "depTask", args = ["foo", { bar = "baz" }] }]"###);
     }
 
+    #[test]
+    fn test_table_from_execute_keeps_all_fields() {
+        let source = r#"
+            commands = ["make", "make install"]
+            depends-on = [{ task = "setup", environment = "test" }]
+            inputs = ["src/*"]
+            outputs = ["build/*"]
+            clean-env = true
+        "#;
+        let task = <WithWarnings<Task> as FromTomlStr>::from_toml_str(source)
+            .unwrap()
+            .value;
+        let toml = toml_edit::Item::from(task);
+        assert_snapshot!(toml.to_string(), @r###"{ commands = ["make", "make install"], depends-on = [{ task = "setup", environment = "test" }], inputs = ["src/*"], outputs = ["build/*"], clean-env = true }"###);
+    }
+
     #[test]
     fn test_template_string_pixi_vars_always_available() {
         // pixi variables should always be available, even without typed args
@@ -1165,6 +1439,55 @@ mod tests {
         assert_eq!(rendered, format!("echo {}", Platform::current()));
     }
 
+    #[test]
+    fn test_template_string_args_placeholder() {
+        let t = TemplateString::from("pytest {{ args }} tests/");
+        assert!(t.uses_variable("args"));
+        assert!(!TemplateString::from("pytest tests/").uses_variable("args"));
+        assert!(!TemplateString::from("echo {{ '{{ args }}' }}").uses_variable("args"));
+
+        let free_args = ArgValues::FreeFormArgs(vec!["-k".into(), "foo bar".into()]);
+        let context = TaskRenderContext {
+            args: Some(&free_args),
+            ..TaskRenderContext::default()
+        };
+        assert_eq!(t.render(&context).unwrap(), "pytest '-k' 'foo bar' tests/");
+
+        // Without extra arguments the placeholder renders empty.
+        assert_eq!(
+            t.render(&TaskRenderContext::default()).unwrap(),
+            "pytest  tests/"
+        );
+    }
+
+    #[test]
+    fn test_cmd_args_multiple_args_placeholder() {
+        let cmd = CmdArgs::Multiple(vec![
+            TemplateString::from("pytest"),
+            TemplateString::from("{{ args }}"),
+            TemplateString::from("tests/"),
+        ]);
+
+        // Every extra argument becomes its own quoted word.
+        let free_args = ArgValues::FreeFormArgs(vec!["-k".into(), "foo bar".into()]);
+        let context = TaskRenderContext {
+            args: Some(&free_args),
+            ..TaskRenderContext::default()
+        };
+        assert_eq!(
+            cmd.as_single(&context).unwrap().unwrap(),
+            r#"pytest -k "foo bar" tests/"#
+        );
+
+        // Without extra arguments no empty argument is inserted.
+        assert_eq!(
+            cmd.into_single(&TaskRenderContext::default())
+                .unwrap()
+                .unwrap(),
+            "pytest tests/"
+        );
+    }
+
     #[test]
     fn test_template_string_undefined_var_errors() {
         // Undefined user variables should cause an error
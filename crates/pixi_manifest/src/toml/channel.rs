@@ -1,9 +1,9 @@
 use std::str::FromStr;
 
-use crate::PrioritizedChannel;
+use crate::{PrioritizedChannel, channel::canonicalize_local_channel};
 use pixi_spec::ExcludeNewer;
-use pixi_toml::TomlFromStr;
-use rattler_conda_types::NamedChannelOrUrl;
+use pixi_toml::{TomlFromStr, TomlWith};
+use rattler_conda_types::{NamedChannelOrUrl, Platform};
 use serde::{Serialize, Serializer};
 use toml_span::de_helpers::expected;
 use toml_span::{DeserError, ErrorKind, Value, de_helpers::TableHelper, value::ValueInner};
@@ -16,6 +16,7 @@ use toml_span::{DeserError, ErrorKind, Value, de_helpers::TableHelper, value::Va
 /// channel = "some-channel"
 /// channel = "https://prefix.dev/some-channel"
 /// channel = { channel = "some-channel", priority = 10 }
+/// channel = { channel = "some-channel", platforms = ["linux-64", "noarch"] }
 /// ```
 #[derive(Debug)]
 pub enum TomlPrioritizedChannel {
@@ -31,6 +32,7 @@ impl From<TomlPrioritizedChannel> for PrioritizedChannel {
                 channel,
                 priority: None,
                 exclude_newer: None,
+                platforms: None,
             },
         }
     }
@@ -38,12 +40,11 @@ impl From<TomlPrioritizedChannel> for PrioritizedChannel {
 
 impl From<PrioritizedChannel> for TomlPrioritizedChannel {
     fn from(channel: PrioritizedChannel) -> Self {
-        if channel.priority.is_some() || channel.exclude_newer.is_some() {
-            TomlPrioritizedChannel::Map(PrioritizedChannel {
-                channel: channel.channel,
-                priority: channel.priority,
-                exclude_newer: channel.exclude_newer,
-            })
+        if channel.priority.is_some()
+            || channel.exclude_newer.is_some()
+            || channel.platforms.is_some()
+        {
+            TomlPrioritizedChannel::Map(channel)
         } else {
             TomlPrioritizedChannel::Str(channel.channel)
         }
@@ -80,7 +81,9 @@ impl<'de> toml_span::Deserialize<'de> for TomlPrioritizedChannel {
                     span: value.span,
                     line_info: None,
                 })?;
-                Ok(TomlPrioritizedChannel::Str(name))
+                Ok(TomlPrioritizedChannel::Str(canonicalize_local_channel(
+                    name,
+                )))
             }
             inner @ ValueInner::Table(_) => {
                 let mut th = TableHelper::new(&mut toml_span::Value::with_span(inner, value.span))?;
@@ -89,11 +92,15 @@ impl<'de> toml_span::Deserialize<'de> for TomlPrioritizedChannel {
                 let exclude_newer = th
                     .optional::<TomlFromStr<ExcludeNewer>>("exclude-newer")
                     .map(TomlFromStr::into_inner);
+                let platforms = th
+                    .optional::<TomlWith<_, Vec<TomlFromStr<Platform>>>>("platforms")
+                    .map(TomlWith::into_inner);
                 th.finalize(None)?;
                 Ok(TomlPrioritizedChannel::Map(PrioritizedChannel {
-                    channel: channel.into_inner(),
+                    channel: canonicalize_local_channel(channel.into_inner()),
                     priority,
                     exclude_newer,
+                    platforms,
                 }))
             }
             other => Err(expected("a string or table", other, value.span).into()),
@@ -147,6 +154,7 @@ mod test {
                     ),
                     priority: None,
                     exclude_newer: None,
+                    platforms: None,
                 },
             ),
         }
@@ -172,6 +180,7 @@ mod test {
                         10,
                     ),
                     exclude_newer: None,
+                    platforms: None,
                 },
             ),
         }
@@ -199,6 +208,36 @@ mod test {
                             0ns,
                         ),
                     ),
+                    platforms: None,
+                },
+            ),
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_with_platforms() {
+        let channel = TopLevel::from_toml_str(
+            r#"
+        channel = { channel = "some-channel", platforms = ["linux-64", "noarch"] }
+        "#,
+        )
+        .unwrap();
+        assert_debug_snapshot!(channel, @r###"
+        TopLevel {
+            channel: Map(
+                PrioritizedChannel {
+                    channel: Name(
+                        "some-channel",
+                    ),
+                    priority: None,
+                    exclude_newer: None,
+                    platforms: Some(
+                        [
+                            Linux64,
+                            NoArch,
+                        ],
+                    ),
                 },
             ),
         }
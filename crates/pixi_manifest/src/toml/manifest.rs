@@ -22,6 +22,7 @@ use crate::{
     Activation, Environment, EnvironmentName, Environments, Feature, FeatureName,
     KnownPreviewFeature, PixiPlatform, PixiPlatformName, SolveGroups, SystemRequirements,
     TargetSelector, Targets, Task, TaskName, TomlError, Warning, WithWarnings, WorkspaceManifest,
+    duplicate_dependencies::duplicate_dependencies,
     environment::EnvironmentIdx,
     error::{FeatureNotEnabled, GenericError},
     manifests::PackageManifest,
@@ -447,6 +448,18 @@ impl TomlManifest {
 
         migrate_system_requirements_to_platforms(&mut workspace, &mut features, &feature_sysreqs)?;
 
+        // A package that is defined more than once is often a mistake, a PyPI
+        // package that is also a conda dependency clobbers its files.
+        warnings.extend(
+            duplicate_dependencies(features.values())
+                .into_iter()
+                .map(|duplicate| {
+                    Warning::from(
+                        GenericError::new(duplicate.to_string()).with_help(duplicate.help()),
+                    )
+                }),
+        );
+
         let workspace_manifest = WorkspaceManifest {
             workspace,
             features,
@@ -1556,6 +1569,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_conda_and_pypi_duplicate_warning() {
+        let warnings = expect_parse_warnings(
+            r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ['linux-64']
+
+        [dependencies]
+        numpy = "*"
+
+        [pypi-dependencies]
+        numpy = "*"
+        "#,
+        );
+        assert!(
+            warnings.contains("'numpy' is both a conda dependency in [dependencies] and a PyPI"),
+            "{warnings}"
+        );
+    }
+
     #[test]
     fn test_unused_features() {
         assert_snapshot!(expect_parse_warnings(
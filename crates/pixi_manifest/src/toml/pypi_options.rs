@@ -130,10 +130,14 @@ impl<'de> toml_span::Deserialize<'de> for PypiOptions {
         let dependency_overrides = th
             .optional::<TomlIndexMap<_, _>>("dependency-overrides")
             .map(TomlIndexMap::into_inner);
+        let constraints = th
+            .optional::<TomlIndexMap<_, _>>("constraints")
+            .map(TomlIndexMap::into_inner);
 
         let no_binary = th.optional::<NoBinary>("no-binary");
 
         let skip_wheel_filename_check = th.optional::<bool>("skip-wheel-filename-check");
+        let allow_clobber = th.optional::<bool>("allow-clobber");
 
         th.finalize(None)?;
 
@@ -146,8 +150,10 @@ impl<'de> toml_span::Deserialize<'de> for PypiOptions {
             prerelease_mode,
             no_build,
             dependency_overrides,
+            constraints,
             no_binary,
             skip_wheel_filename_check,
+            allow_clobber,
         })
     }
 }
@@ -277,6 +283,9 @@ mod test {
 
                  [dependency-overrides]
                  numpy = ">=2.0.0"
+
+                 [constraints]
+                 urllib3 = "<2"
              "#;
         let deserialized_options: PypiOptions = PypiOptions::from_toml_str(toml_str).unwrap();
         assert_eq!(
@@ -302,8 +311,16 @@ mod test {
                         index: None,
                     })
                 )]),),
+                constraints: Some(indexmap::IndexMap::from_iter([(
+                    PypiPackageName::from_str("urllib3").unwrap(),
+                    pixi_pypi_spec::PixiPypiSpec::new(pixi_pypi_spec::PixiPypiSource::Registry {
+                        version: pixi_pypi_spec::VersionOrStar::from_str("<2").unwrap(),
+                        index: None,
+                    })
+                )]),),
                 no_binary: Default::default(),
                 skip_wheel_filename_check: None,
+                allow_clobber: None,
             },
         );
     }
@@ -322,6 +339,7 @@ mod test {
         prerelease-mode = "allow"
         no-build = true
         no-binary = ["package1", "package2"]
+        allow-clobber = true
         "#;
         let options = PypiOptions::from_toml_str(input).unwrap();
         assert_debug_snapshot!(options);
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::str::FromStr;
 
 use itertools::Itertools;
-use pixi_toml::{TomlFromStr, TomlIndexMap};
+use pixi_toml::{TomlEnum, TomlFromStr, TomlIndexMap};
 use toml_span::{
     DeserError, ErrorKind, Value,
     de_helpers::{TableHelper, expected},
@@ -13,7 +13,7 @@ use crate::{
     EnvironmentName, Task, TaskName, WithWarnings,
     task::{
         Alias, ArgName, CmdArgs, Dependency, DependencyArg, Execute, GlobPatterns, TaskArg,
-        TemplateString,
+        TaskShell, TemplateString,
     },
     warning::Deprecation,
 };
@@ -139,8 +139,13 @@ impl<'de> toml_span::Deserialize<'de> for TomlTask {
                             let environment = th
                                 .optional::<TomlFromStr<EnvironmentName>>("environment")
                                 .map(TomlFromStr::into_inner);
+                            let continue_on_error =
+                                th.optional::<bool>("continue-on-error").unwrap_or(false);
 
-                            deps.push(Dependency::new(&name, args, environment));
+                            deps.push(
+                                Dependency::new(&name, args, environment)
+                                    .with_continue_on_error(continue_on_error),
+                            );
                         }
                         value => return Err(expected("table", value, item.span).into()),
                     }
@@ -155,7 +160,20 @@ impl<'de> toml_span::Deserialize<'de> for TomlTask {
             inner => return Err(expected("string or table", inner, value.span).into()),
         };
 
-        let cmd = th.optional("cmd");
+        let cmd = match (
+            th.optional::<CmdArgs>("cmd"),
+            th.optional::<Vec<TemplateString>>("commands"),
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(DeserError::from(toml_span::Error {
+                    kind: ErrorKind::Custom("`cmd` and `commands` cannot both be set".into()),
+                    span: value.span,
+                    line_info: None,
+                }));
+            }
+            (cmd, None) => cmd,
+            (None, Some(commands)) => Some(CmdArgs::Sequence(commands)),
+        };
         let mut warnings = Vec::new();
 
         let mut depends_on = |th: &mut TableHelper| {
@@ -177,9 +195,12 @@ impl<'de> toml_span::Deserialize<'de> for TomlTask {
                                     let environment = th
                                         .optional::<TomlFromStr<EnvironmentName>>("environment")
                                         .map(TomlFromStr::into_inner);
+                                    let continue_on_error =
+                                        th.optional::<bool>("continue-on-error").unwrap_or(false);
                                     th.finalize(None)?;
 
-                                    Ok(Dependency::new(&name, args, environment))
+                                    Ok(Dependency::new(&name, args, environment)
+                                        .with_continue_on_error(continue_on_error))
                                 }
                                 inner => Err(expected("string or table", inner, span).into()),
                             }
@@ -242,11 +263,17 @@ impl<'de> toml_span::Deserialize<'de> for TomlTask {
             let env = th
                 .optional::<TomlIndexMap<_, _>>("env")
                 .map(TomlIndexMap::into_inner);
+            let dotenv = th.optional("dotenv");
             let default_environment = th
                 .optional::<TomlFromStr<EnvironmentName>>("default-environment")
                 .map(TomlFromStr::into_inner);
             let description = th.optional("description");
             let clean_env = th.optional("clean-env").unwrap_or(false);
+            let activation = th.optional("activation").unwrap_or(true);
+            let shell = th
+                .optional::<TomlEnum<TaskShell>>("shell")
+                .map(TomlEnum::into_inner);
+            let requires = th.optional("requires");
             let args = th.optional::<Vec<TaskArg>>("args");
 
             let mut have_default = false;
@@ -273,9 +300,13 @@ impl<'de> toml_span::Deserialize<'de> for TomlTask {
                 depends_on,
                 cwd,
                 env,
+                dotenv,
                 default_environment,
                 description,
                 clean_env,
+                activation,
+                shell,
+                requires,
                 args,
             }))
         } else {
@@ -396,6 +427,45 @@ mod test {
         ), @"test, depends-on = 'foo with args'");
     }
 
+    #[test]
+    fn test_commands() {
+        let task = <TomlTask as crate::toml::FromTomlStr>::from_toml_str(
+            r#"
+            commands = ["ruff check", "pytest"]
+        "#,
+        )
+        .unwrap()
+        .value;
+        let Task::Execute(execute) = &task else {
+            panic!("expected an execute task");
+        };
+        assert!(matches!(&execute.cmd, CmdArgs::Sequence(commands) if commands.len() == 2));
+        assert_eq!(task.to_string(), "ruff check && pytest");
+
+        let error = expect_parse_failure(
+            r#"
+            cmd = "test"
+            commands = ["ruff check", "pytest"]
+        "#,
+        );
+        assert!(
+            error.contains("`cmd` and `commands` cannot both be set"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_depends_on_continue_on_error() {
+        let input = r#"
+            cmd = "test"
+            depends-on = ["a", { task = "b", continue-on-error = true }]
+        "#;
+        let parsed = TomlTask::from_toml_str(input).unwrap();
+        let depends_on = parsed.value.depends_on();
+        assert!(!depends_on[0].continue_on_error);
+        assert!(depends_on[1].continue_on_error);
+    }
+
     #[test]
     fn test_task_arg_with_choices() {
         let input = r#"
@@ -0,0 +1,281 @@
+//! Validation of a manifest that, unlike regular loading, does not stop at the
+//! first problem but collects everything that is wrong with it.
+
+use std::{collections::HashSet, sync::Arc};
+
+use miette::{Diagnostic, NamedSource};
+use thiserror::Error;
+use toml_span::{Deserialize, Span, Value};
+
+use crate::{
+    ManifestKind, TomlError, WithProvenance,
+    duplicate_dependencies::duplicate_dependencies,
+    error::GenericError,
+    pyproject::PyProjectManifest,
+    toml::{ExternalWorkspaceProperties, PackageDefaults, TomlManifest},
+};
+
+/// All the problems that were found while validating a manifest.
+#[derive(Debug, Error, Diagnostic)]
+#[error("found {} problem(s) in {}", .errors.len(), .source.name())]
+pub struct ManifestValidationError {
+    #[source_code]
+    pub source: NamedSource<Arc<str>>,
+
+    #[related]
+    pub errors: Vec<TomlError>,
+}
+
+/// Validates the given manifest source and returns all the problems that were
+/// found, or `Ok` if the manifest is valid.
+///
+/// In addition to the errors that would be reported when loading the
+/// manifest, this also reports tasks that depend on tasks that are not
+/// defined anywhere in the manifest. If `strict` is set, the duplicate
+/// dependencies that are otherwise only warned about are reported as errors.
+pub fn validate_manifest<S: AsRef<str>>(
+    WithProvenance {
+        value: source,
+        provenance,
+    }: WithProvenance<S>,
+    strict: bool,
+) -> Result<(), ManifestValidationError> {
+    let errors = collect_errors(source.as_ref(), provenance.kind, &provenance.path, strict);
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ManifestValidationError {
+        source: NamedSource::new(
+            provenance.path.to_string_lossy(),
+            Arc::from(source.as_ref()),
+        )
+        .with_language(provenance.kind.language()),
+        errors,
+    })
+}
+
+fn collect_errors(
+    source: &str,
+    kind: ManifestKind,
+    path: &std::path::Path,
+    strict: bool,
+) -> Vec<TomlError> {
+    let mut toml = match toml_span::parse(source) {
+        Ok(toml) => toml,
+        Err(e) => return vec![TomlError::from(e)],
+    };
+
+    // Check the task dependencies before deserializing because deserializing
+    // consumes the values.
+    let pixi_table = match kind {
+        ManifestKind::Pixi | ManifestKind::MojoProject => Some(&toml),
+        ManifestKind::Pyproject => toml.pointer("/tool/pixi"),
+    };
+    let mut errors = pixi_table
+        .map(unknown_task_dependencies)
+        .unwrap_or_default();
+
+    let manifest_dir = path.parent().expect("a file must have a parent");
+    let result = match kind {
+        ManifestKind::Pixi | ManifestKind::MojoProject => {
+            TomlManifest::deserialize(&mut toml).map(|manifest| {
+                manifest.into_workspace_manifest(
+                    ExternalWorkspaceProperties::default(),
+                    PackageDefaults::default(),
+                    manifest_dir,
+                )
+            })
+        }
+        ManifestKind::Pyproject => PyProjectManifest::deserialize(&mut toml)
+            .map(|manifest| manifest.into_workspace_manifest(manifest_dir)),
+    };
+
+    match result {
+        Ok(Ok((manifest, _, _))) => {
+            if strict {
+                errors.extend(
+                    duplicate_dependencies(manifest.features.values())
+                        .into_iter()
+                        .map(|duplicate| {
+                            GenericError::new(duplicate.to_string())
+                                .with_help(duplicate.help())
+                                .into()
+                        }),
+                );
+            }
+        }
+        Ok(Err(e)) => errors.push(e),
+        Err(e) => errors.extend(e.errors.into_iter().map(TomlError::from)),
+    }
+
+    errors.sort_by_key(error_offset);
+    errors
+}
+
+/// Returns the offset of the first label of the error, used to report the
+/// errors in the order in which they appear in the manifest.
+fn error_offset(error: &TomlError) -> usize {
+    error
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .map_or(usize::MAX, |label| label.offset())
+}
+
+/// Finds all the `depends-on` entries that refer to a task that is not
+/// defined in any feature or target of the manifest.
+fn unknown_task_dependencies(manifest: &Value<'_>) -> Vec<TomlError> {
+    let mut task_tables = Vec::new();
+    collect_task_tables(manifest, &mut task_tables);
+    if let Some(features) = manifest.pointer("/feature").and_then(Value::as_table) {
+        for feature in features.values() {
+            collect_task_tables(feature, &mut task_tables);
+        }
+    }
+
+    let defined: HashSet<&str> = task_tables
+        .iter()
+        .flat_map(|tasks| tasks.keys())
+        .map(|key| key.name.as_ref())
+        .collect();
+
+    let mut errors = Vec::new();
+    for tasks in &task_tables {
+        for (name, task) in tasks.iter() {
+            for (dependency, span) in task_dependencies(task) {
+                if !defined.contains(dependency) {
+                    errors.push(
+                        GenericError::new(format!(
+                            "task '{}' depends on '{dependency}' which is not defined",
+                            name.name
+                        ))
+                        .with_span(span.into())
+                        .with_span_label("unknown task")
+                        .into(),
+                    );
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Collects the `tasks` table of the given feature-like table and of all of its
+/// targets.
+fn collect_task_tables<'a, 'de>(
+    table: &'a Value<'de>,
+    task_tables: &mut Vec<&'a toml_span::value::Table<'de>>,
+) {
+    task_tables.extend(table.pointer("/tasks").and_then(Value::as_table));
+    if let Some(targets) = table.pointer("/target").and_then(Value::as_table) {
+        task_tables.extend(
+            targets
+                .values()
+                .filter_map(|target| target.pointer("/tasks").and_then(Value::as_table)),
+        );
+    }
+}
+
+/// Returns the names and spans of the tasks the given raw task depends on.
+fn task_dependencies<'a>(task: &'a Value<'_>) -> Vec<(&'a str, Span)> {
+    let depends_on = if let Some(aliases) = task.as_array() {
+        aliases.iter().collect::<Vec<_>>()
+    } else {
+        match task
+            .pointer("/depends-on")
+            .or_else(|| task.pointer("/depends_on"))
+        {
+            Some(value) => match value.as_array() {
+                Some(array) => array.iter().collect(),
+                None => vec![value],
+            },
+            None => Vec::new(),
+        }
+    };
+
+    depends_on
+        .into_iter()
+        .filter_map(|dependency| match dependency.as_str() {
+            Some(name) => Some((name, dependency.span)),
+            None => dependency
+                .pointer("/task")
+                .and_then(|task| Some((task.as_str()?, task.span))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_reports_all_problems() {
+        let source = r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ["linux-64", "bogus-platform"]
+
+        [tasks]
+        build = "echo build"
+        test = { cmd = "echo test", depends-on = ["build", "missing"] }
+        "#;
+
+        let errors = collect_errors(source, ManifestKind::Pixi, Path::new("/pixi.toml"), false);
+        let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert!(messages[0].contains("bogus-platform"), "{messages:?}");
+        assert_eq!(
+            messages[1],
+            "task 'test' depends on 'missing' which is not defined"
+        );
+    }
+
+    #[test]
+    fn test_valid_manifest() {
+        let source = r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ["linux-64"]
+
+        [tasks]
+        build = "echo build"
+
+        [feature.test.tasks]
+        test = { cmd = "echo test", depends-on = ["build"] }
+        "#;
+
+        assert!(
+            collect_errors(source, ManifestKind::Pixi, Path::new("/pixi.toml"), false).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_strict_reports_duplicate_dependencies() {
+        let source = r#"
+        [workspace]
+        name = "foo"
+        channels = []
+        platforms = ["linux-64"]
+
+        [dependencies]
+        numpy = "*"
+
+        [pypi-dependencies]
+        numpy = "*"
+        "#;
+
+        let path = Path::new("/pixi.toml");
+        assert!(collect_errors(source, ManifestKind::Pixi, path, false).is_empty());
+        let errors = collect_errors(source, ManifestKind::Pixi, path, true);
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "'numpy' is both a conda dependency in [dependencies] and a PyPI dependency in [pypi-dependencies]"
+            ]
+        );
+    }
+}
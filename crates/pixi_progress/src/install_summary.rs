@@ -0,0 +1,161 @@
+//! Counts of what an install did, for `pixi install --json-summary`.
+//!
+//! The install reporter records the conda packages that were added, removed
+//! and updated, and whether the archives came from the package cache or had
+//! to be downloaded, and the conda files that PyPI packages were allowed to
+//! overwrite. Nothing is recorded unless the summary is enabled.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Whether the summary is collected.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The counts that were recorded so far.
+static SUMMARY: Mutex<InstallSummary> = parking_lot::const_mutex(InstallSummary::new());
+
+/// Enables collecting the summary and clears what an earlier install in the
+/// same process recorded.
+pub fn enable() {
+    *SUMMARY.lock() = InstallSummary::new();
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if the summary is collected.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What the installs of a command did.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct InstallSummary {
+    /// The packages that were not installed before.
+    pub added: usize,
+    /// The packages that were uninstalled.
+    pub removed: usize,
+    /// The packages that were replaced by another version or build, or were
+    /// reinstalled.
+    pub updated: usize,
+    /// The number of bytes of the archives that were downloaded.
+    pub bytes_downloaded: u64,
+    /// The packages that were installed from the package cache.
+    pub cache_hits: usize,
+    /// The prefix-relative paths of the conda files that were overwritten by
+    /// PyPI packages because clobbering was allowed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub clobbered_files: Vec<String>,
+}
+
+impl InstallSummary {
+    const fn new() -> Self {
+        Self {
+            added: 0,
+            removed: 0,
+            updated: 0,
+            bytes_downloaded: 0,
+            cache_hits: 0,
+            clobbered_files: Vec::new(),
+        }
+    }
+
+    /// Serializes the summary, with the duration of the command, as a single
+    /// json object.
+    pub fn to_json(&self, duration: Duration) -> String {
+        serde_json::to_string(&SummaryEvent {
+            summary: self,
+            duration_seconds: duration.as_secs_f64(),
+        })
+        .expect("the summary is always serializable")
+    }
+}
+
+/// The json representation of an [`InstallSummary`].
+#[derive(Serialize)]
+struct SummaryEvent<'a> {
+    #[serde(flatten)]
+    summary: &'a InstallSummary,
+    duration_seconds: f64,
+}
+
+/// Runs `f` on the summary if it is collected.
+fn update(f: impl FnOnce(&mut InstallSummary)) {
+    if is_enabled() {
+        f(&mut SUMMARY.lock());
+    }
+}
+
+/// Records the operations of a transaction.
+pub fn record_operations(added: usize, removed: usize, updated: usize) {
+    update(|summary| {
+        summary.added += added;
+        summary.removed += removed;
+        summary.updated += updated;
+    });
+}
+
+/// Records a package archive that was downloaded.
+pub fn record_download(bytes: u64) {
+    update(|summary| summary.bytes_downloaded += bytes);
+}
+
+/// Records a package that was found in the package cache.
+pub fn record_cache_hit() {
+    update(|summary| summary.cache_hits += 1);
+}
+
+/// Records the conda files that PyPI packages were allowed to overwrite.
+pub fn record_clobbered_files(paths: impl IntoIterator<Item = String>) {
+    update(|summary| summary.clobbered_files.extend(paths));
+}
+
+/// Returns the summary that was recorded so far.
+pub fn recorded() -> InstallSummary {
+    SUMMARY.lock().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_json() {
+        let summary = InstallSummary {
+            added: 3,
+            removed: 1,
+            updated: 2,
+            bytes_downloaded: 1024,
+            cache_hits: 4,
+            clobbered_files: Vec::new(),
+        };
+        assert_eq!(
+            summary.to_json(Duration::from_millis(1500)),
+            "{\"added\":3,\"removed\":1,\"updated\":2,\"bytes_downloaded\":1024,\"cache_hits\":4,\"duration_seconds\":1.5}"
+        );
+    }
+
+    #[test]
+    fn test_enable_clears_the_summary() {
+        enable();
+        record_operations(1, 0, 0);
+        record_cache_hit();
+        enable();
+        assert_eq!(recorded(), InstallSummary::default());
+    }
+
+    #[test]
+    fn test_summary_json_with_clobbered_files() {
+        let summary = InstallSummary {
+            clobbered_files: vec!["lib/python3.12/site-packages/README.txt".to_string()],
+            ..InstallSummary::default()
+        };
+        assert_eq!(
+            summary.to_json(Duration::ZERO),
+            "{\"added\":0,\"removed\":0,\"updated\":0,\"bytes_downloaded\":0,\"cache_hits\":0,\"clobbered_files\":[\"lib/python3.12/site-packages/README.txt\"],\"duration_seconds\":0.0}"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Machine readable progress reporting.
+//!
+//! When enabled, every finished solve, download and install step is written to
+//! `stderr` as a single line of JSON. This allows tools like editor plugins to
+//! follow the progress of pixi without having to parse the progress bars.
+
+use std::{
+    io::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+
+/// Whether json progress events are emitted.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables emitting json progress events to `stderr`.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if json progress events are emitted.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The kind of step a [`ProgressEvent`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressEventKind {
+    Solve,
+    Download,
+    Install,
+}
+
+/// A single progress event, emitted when a step has finished.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressEvent<'a> {
+    pub event: ProgressEventKind,
+    pub package: &'a str,
+    /// The number of steps of this kind that have finished.
+    pub done: u64,
+    /// The total number of steps of this kind that are known.
+    pub total: u64,
+}
+
+impl ProgressEvent<'_> {
+    /// Serializes the event as a single line of json.
+    pub fn to_json_line(&self) -> String {
+        let mut line =
+            serde_json::to_string(self).expect("progress events are always serializable");
+        line.push('\n');
+        line
+    }
+}
+
+/// Writes the event to `stderr` if json progress is enabled.
+///
+/// The line is flushed immediately so consumers receive the event while pixi
+/// keeps working.
+pub fn emit(event: ProgressEvent<'_>) {
+    if !is_enabled() {
+        return;
+    }
+    emit_line(&event.to_json_line());
+}
+
+/// Writes a line of json to `stderr` and flushes it.
+pub(crate) fn emit_line(line: &str) {
+    let mut stderr = std::io::stderr().lock();
+    let _ = stderr.write_all(line.as_bytes());
+    let _ = stderr.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json_line() {
+        let event = ProgressEvent {
+            event: ProgressEventKind::Download,
+            package: "bar",
+            done: 1,
+            total: 10,
+        };
+        assert_eq!(
+            event.to_json_line(),
+            "{\"event\":\"download\",\"package\":\"bar\",\"done\":1,\"total\":10}\n"
+        );
+    }
+}
@@ -1,6 +1,10 @@
+pub mod install_summary;
+pub mod json;
 pub mod osc;
 mod placement;
+pub mod quiet;
 pub mod style;
+pub mod timing;
 
 use std::{
     borrow::Cow,
@@ -29,6 +33,18 @@ macro_rules! println {
     }};
 }
 
+/// Like [`println!`], but for informational status messages, e.g. what was
+/// added or installed. In [`quiet`] mode nothing is printed, so warnings and
+/// errors should use [`println!`] instead.
+#[macro_export]
+macro_rules! println_status {
+    ($($arg:tt)*) => {{
+        if !$crate::quiet::is_enabled() {
+            $crate::println!($($arg)*)
+        }
+    }};
+}
+
 /// Returns a global instance of [`indicatif::MultiProgress`].
 ///
 /// Although you can always create an instance yourself any logging will
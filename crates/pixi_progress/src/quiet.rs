@@ -0,0 +1,17 @@
+//! Quiet mode, in which pixi prints no informational status messages, see
+//! [`crate::println_status`]. Warnings and errors are still printed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether quiet mode is enabled.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables quiet mode.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if quiet mode is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
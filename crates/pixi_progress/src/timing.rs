@@ -0,0 +1,172 @@
+//! Durations of the phases of a command.
+//!
+//! The solve, download and link phases record how long they took through
+//! [`record`]. When timings are enabled the durations are collected and can be
+//! printed as a summary at the end of a command. When json progress is
+//! enabled every recorded duration is also emitted as a json line, next to the
+//! regular [`crate::json`] progress events.
+
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::json;
+
+/// Whether timings are collected.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The timings that were recorded so far.
+static TIMINGS: Mutex<Vec<Timing>> = parking_lot::const_mutex(Vec::new());
+
+/// Enables collecting timings.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if timings are collected.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A phase of a command whose duration is recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Solve,
+    Download,
+    Link,
+}
+
+impl Phase {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Solve => "solve",
+            Phase::Download => "download",
+            Phase::Link => "link",
+        }
+    }
+}
+
+/// The duration of a single phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub phase: Phase,
+    /// Describes what the phase worked on, e.g. the solved environment.
+    pub label: String,
+    pub duration: Duration,
+}
+
+/// The json representation of a [`Timing`].
+#[derive(Serialize)]
+struct TimingEvent<'a> {
+    event: &'static str,
+    phase: Phase,
+    label: &'a str,
+    seconds: f64,
+}
+
+impl Timing {
+    /// Serializes the timing as a single line of json.
+    pub fn to_json_line(&self) -> String {
+        let mut line = serde_json::to_string(&TimingEvent {
+            event: "timing",
+            phase: self.phase,
+            label: &self.label,
+            seconds: self.duration.as_secs_f64(),
+        })
+        .expect("timings are always serializable");
+        line.push('\n');
+        line
+    }
+}
+
+/// Records the duration of a phase.
+pub fn record(phase: Phase, label: impl Into<String>, duration: Duration) {
+    if !is_enabled() && !json::is_enabled() {
+        return;
+    }
+    let timing = Timing {
+        phase,
+        label: label.into(),
+        duration,
+    };
+    if json::is_enabled() {
+        json::emit_line(&timing.to_json_line());
+    }
+    if is_enabled() {
+        TIMINGS.lock().push(timing);
+    }
+}
+
+/// Returns the timings that were recorded so far.
+pub fn recorded() -> Vec<Timing> {
+    TIMINGS.lock().clone()
+}
+
+/// Formats the given timings as a human readable summary, one phase per
+/// line.
+pub fn summary(timings: &[Timing]) -> String {
+    let mut summary = String::from("Timings:\n");
+    if timings.is_empty() {
+        summary.push_str("  nothing was solved, downloaded or linked\n");
+        return summary;
+    }
+    let label_width = timings
+        .iter()
+        .map(|timing| timing.label.len())
+        .max()
+        .unwrap_or_default();
+    for timing in timings {
+        let _ = writeln!(
+            summary,
+            "  {:<8}  {:<label_width$}  {:.2?}",
+            timing.phase.as_str(),
+            timing.label,
+            timing.duration
+        );
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_json_line() {
+        let timing = Timing {
+            phase: Phase::Solve,
+            label: "default linux-64".to_string(),
+            duration: Duration::from_millis(1500),
+        };
+        assert_eq!(
+            timing.to_json_line(),
+            "{\"event\":\"timing\",\"phase\":\"solve\",\"label\":\"default linux-64\",\"seconds\":1.5}\n"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let timings = [
+            Timing {
+                phase: Phase::Solve,
+                label: "conda default linux-64".to_string(),
+                duration: Duration::from_millis(1500),
+            },
+            Timing {
+                phase: Phase::Link,
+                label: "3 packages".to_string(),
+                duration: Duration::from_millis(20),
+            },
+        ];
+        assert_eq!(
+            summary(&timings),
+            "Timings:\n  solve     conda default linux-64  1.50s\n  link      3 packages              20.00ms\n"
+        );
+    }
+}
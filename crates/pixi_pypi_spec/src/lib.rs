@@ -153,6 +153,17 @@ pub struct PixiPypiSpec {
     /// The source for this package.
     #[serde(flatten)]
     pub source: PixiPypiSource,
+    /// Whether the package is built against the environment instead of an
+    /// isolated build environment.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_build_isolation: bool,
+    /// Whether the package may only be installed from a pre-built wheel.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_build: bool,
+    /// Whether the package is always built from source instead of installed
+    /// from a pre-built wheel.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_binary: bool,
 }
 
 /// The type of parse error that occurred when parsing match spec.
@@ -201,6 +212,9 @@ impl From<PixiPypiSource> for PixiPypiSpec {
             extras: Vec::new(),
             source,
             env_markers: MarkerTree::default(),
+            no_build_isolation: false,
+            no_build: false,
+            no_binary: false,
         }
     }
 }
@@ -221,6 +235,9 @@ impl PixiPypiSpec {
             extras,
             source,
             env_markers,
+            no_build_isolation: false,
+            no_build: false,
+            no_binary: false,
         }
     }
 
@@ -325,6 +342,11 @@ impl PixiPypiSpec {
 
         updated.env_markers.or(requirement.marker.clone());
 
+        // Preserve how the package is built
+        updated.no_build_isolation = self.no_build_isolation;
+        updated.no_build = self.no_build;
+        updated.no_binary = self.no_binary;
+
         Ok(updated)
     }
 }
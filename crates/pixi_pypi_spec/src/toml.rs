@@ -21,6 +21,8 @@ pub enum SpecConversion {
     VersionWithNonDetailedKeys { non_detailed_keys: String },
     #[error("Exactly one of `url`, `path`, `git`, or `version` must be specified")]
     MultipleVersionSpecifiers,
+    #[error("`no-build` and `no-binary` cannot both be set")]
+    NoBuildWithNoBinary,
     #[error(transparent)]
     InvalidSubdirectory(#[from] SubdirectoryError),
 }
@@ -80,6 +82,10 @@ struct RawPyPiRequirement {
 
     // Pinned index
     pub index: Option<Url>,
+
+    no_build_isolation: bool,
+    no_build: bool,
+    no_binary: bool,
 }
 
 impl RawPyPiRequirement {
@@ -114,7 +120,13 @@ impl RawPyPiRequirement {
             return Err(SpecConversion::VersionWithNonDetailedKeys { non_detailed_keys });
         }
 
-        let req = match (self.url, self.path, self.git, self.index) {
+        if self.no_build && self.no_binary {
+            return Err(SpecConversion::NoBuildWithNoBinary);
+        }
+
+        let (no_build_isolation, no_build, no_binary) =
+            (self.no_build_isolation, self.no_build, self.no_binary);
+        let mut req = match (self.url, self.path, self.git, self.index) {
             (Some(url), None, None, None) => PixiPypiSpec::with_extras_and_markers(
                 PixiPypiSource::Url {
                     url,
@@ -175,6 +187,9 @@ impl RawPyPiRequirement {
                 return Err(SpecConversion::MultipleVersionSpecifiers);
             }
         };
+        req.no_build_isolation = no_build_isolation;
+        req.no_build = no_build;
+        req.no_binary = no_binary;
 
         Ok(req)
     }
@@ -219,6 +234,10 @@ impl<'de> toml_span::Deserialize<'de> for RawPyPiRequirement {
             .map(TomlFromStr::into_inner)
             .unwrap_or_default();
 
+        let no_build_isolation = th.optional("no-build-isolation").unwrap_or_default();
+        let no_build = th.optional("no-build").unwrap_or_default();
+        let no_binary = th.optional("no-binary").unwrap_or_default();
+
         th.finalize(None)?;
 
         Ok(RawPyPiRequirement {
@@ -234,6 +253,9 @@ impl<'de> toml_span::Deserialize<'de> for RawPyPiRequirement {
             subdirectory,
             index,
             marker,
+            no_build_isolation,
+            no_build,
+            no_binary,
         })
     }
 }
@@ -298,6 +320,15 @@ impl From<PixiPypiSpec> for toml_edit::Value {
             }
         }
 
+        fn insert_flag(table: &mut toml_edit::InlineTable, key: &str, enabled: bool) {
+            if enabled {
+                table.insert(
+                    key,
+                    toml_edit::Value::Boolean(toml_edit::Formatted::new(true)),
+                );
+            }
+        }
+
         fn insert_index(table: &mut toml_edit::InlineTable, index: &Option<Url>) {
             if let Some(index) = index {
                 table.insert(
@@ -310,12 +341,17 @@ impl From<PixiPypiSpec> for toml_edit::Value {
         let extras = &val.extras;
         let markers = &val.env_markers;
 
-        match &val.source {
+        let mut value = match &val.source {
             // Simple version string (no extras, no index)
             PixiPypiSource::Registry { version, index }
-                if extras.is_empty() && index.is_none() && markers.is_true() =>
+                if extras.is_empty()
+                    && index.is_none()
+                    && markers.is_true()
+                    && !val.no_build_isolation
+                    && !val.no_build
+                    && !val.no_binary =>
             {
-                toml_edit::Value::from(version.to_string())
+                return toml_edit::Value::from(version.to_string());
             }
             // Registry with extras or index
             PixiPypiSource::Registry { version, index } => {
@@ -418,7 +454,13 @@ impl From<PixiPypiSpec> for toml_edit::Value {
                 insert_markers(&mut table, markers);
                 toml_edit::Value::InlineTable(table.to_owned())
             }
+        };
+        if let Some(table) = value.as_inline_table_mut() {
+            insert_flag(table, "no-build-isolation", val.no_build_isolation);
+            insert_flag(table, "no-build", val.no_build);
+            insert_flag(table, "no-binary", val.no_binary);
         }
+        value
     }
 }
 
@@ -638,11 +680,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_deserialize_pypi_no_build_isolation() {
+        let requirement = from_toml_str::<TomlIndexMap<pep508_rs::PackageName, PixiPypiSpec>>(
+            r#"foo = { path = "../numpy-test", no-build-isolation = true }"#,
+        )
+        .unwrap()
+        .into_inner();
+        let spec = requirement.first().unwrap().1;
+        assert!(spec.no_build_isolation);
+        assert_eq!(
+            toml_edit::Value::from(spec.clone()).to_string(),
+            r#"{ path = "../numpy-test", no-build-isolation = true }"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_pypi_no_build() {
+        let requirement = from_toml_str::<TomlIndexMap<pep508_rs::PackageName, PixiPypiSpec>>(
+            r#"foo = { version = ">=1.0", no-build = true }"#,
+        )
+        .unwrap()
+        .into_inner();
+        let spec = requirement.first().unwrap().1;
+        assert!(spec.no_build);
+        assert!(!spec.no_binary);
+        assert_eq!(
+            toml_edit::Value::from(spec.clone()).to_string(),
+            r#"{ version = ">=1.0", no-build = true }"#
+        );
+
+        let input = r#"foo = { version = ">=1.0", no-build = true, no-binary = true }"#;
+        assert!(
+            from_toml_str::<TomlIndexMap<pep508_rs::PackageName, PixiPypiSpec>>(input).is_err()
+        );
+    }
+
     #[test]
     fn test_deserialize_fail_on_unknown() {
         let input = r#"foo = { borked = "bork"}"#;
         assert_snapshot!(format_parse_error(input, from_toml_str::<TomlIndexMap::<pep508_rs::PackageName, PixiPypiSpec>>(input).unwrap_err()), @r#"
-         × Unexpected keys, expected only 'version', 'extras', 'path', 'editable', 'git', 'branch', 'tag', 'rev', 'url', 'subdirectory', 'index', 'env-markers'
+         × Unexpected keys, expected only 'version', 'extras', 'path', 'editable', 'git', 'branch', 'tag', 'rev', 'url', 'subdirectory', 'index', 'env-markers', 'no-build-isolation', 'no-build', 'no-binary'
           ╭─[pixi.toml:1:9]
         1 │ foo = { borked = "bork"}
           ·         ───┬──
@@ -668,6 +746,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_pypi_from_url_round_trip() {
+        let input = r#"{ url = "https://example.com/foo-1.0-py3-none-any.whl#sha256=abc123", extras = ["bar"] }"#;
+        let requirement = from_toml_str::<TomlIndexMap<pep508_rs::PackageName, PixiPypiSpec>>(
+            &format!("foo = {input}"),
+        )
+        .unwrap()
+        .into_inner();
+        let spec = requirement.first().unwrap().1;
+        assert_eq!(spec.as_url().and_then(Url::fragment), Some("sha256=abc123"));
+        assert_eq!(toml_edit::Value::from(spec.clone()).to_string(), input);
+    }
+
     #[test]
     fn test_deserialize_pypi_from_git() {
         let requirement = from_toml_str::<TomlIndexMap<pep508_rs::PackageName, PixiPypiSpec>>(
@@ -8,7 +8,10 @@ use std::{
 
 use indicatif::{MultiProgress, ProgressBar, ProgressState, ProgressStyle, style::ProgressTracker};
 use parking_lot::RwLock;
-use pixi_progress::ProgressBarPlacement;
+use pixi_progress::{
+    ProgressBarPlacement,
+    json::{ProgressEvent, ProgressEventKind},
+};
 use rattler_conda_types::RepoDataRecord;
 
 #[derive(Clone)]
@@ -219,6 +222,7 @@ impl BuildDownloadVerifyReporter {
             panic!("Expected entry to be in downloading state, actual: {entry:?}");
         };
         entry.state = EntryState::Finished { download: None };
+        emit_download_event(&entries, index);
         drop(entries);
         self.update();
     }
@@ -283,6 +287,7 @@ impl BuildDownloadVerifyReporter {
                 total_bytes.unwrap_or(*bytes_downloaded),
             )),
         };
+        emit_download_event(&entries, index);
         drop(entries);
         self.update();
     }
@@ -309,8 +314,9 @@ impl BuildDownloadVerifyReporter {
             EntryState::Pending | EntryState::Validating => {
                 entry.state = EntryState::Finished { download: None };
             }
-            _ => {}
+            _ => return,
         };
+        emit_download_event(&entries, index);
         drop(entries);
         self.update();
     }
@@ -425,6 +431,18 @@ fn total_duration_and_size<'a>(
     (total, total_size)
 }
 
+/// Emits a json progress event for the entry that became available in the
+/// package cache.
+fn emit_download_event(entries: &HashMap<usize, Entry>, index: usize) {
+    let done = entries.values().filter(|entry| entry.is_finished()).count();
+    pixi_progress::json::emit(ProgressEvent {
+        event: ProgressEventKind::Download,
+        package: &entries[&index].name,
+        done: done as u64,
+        total: entries.len() as u64,
+    });
+}
+
 fn find_max_and_multiple<'a>(
     entries: impl IntoIterator<Item = &'a Entry>,
 ) -> (Option<&'a Entry>, usize, bool) {
@@ -3,6 +3,7 @@ mod git;
 mod main_progress_bar;
 mod release_notes;
 mod repodata_reporter;
+mod reporter;
 mod sync_reporter;
 pub mod uv_reporter;
 
@@ -18,8 +19,10 @@ use pixi_command_dispatcher::{
     InstallPixiEnvironmentSpec, PixiSolveEnvironmentSpec, SolveCondaEnvironmentSpec,
 };
 use pixi_compute_reporters::{OperationId, OperationRegistry};
+use pixi_progress::json::ProgressEventKind;
 pub use release_notes::format_release_notes;
 use repodata_reporter::RepodataReporter;
+pub use reporter::{NoopReporter, Reporter, TerminalReporter, UvInstallReporter, select_reporter};
 use sync_reporter::SyncReporter;
 use uv_configuration::initialize_rayon_once;
 // Re-export the uv_reporter types for external use
@@ -78,7 +81,8 @@ impl TopLevelProgress {
             multi_progress.clone(),
             pixi_progress::ProgressBarPlacement::Before(anchor_pb.clone()),
             "solving".to_owned(),
-        );
+        )
+        .with_json_event(ProgressEventKind::Solve);
         let install_reporter = SyncReporter::new(
             registry.clone(),
             multi_progress.clone(),
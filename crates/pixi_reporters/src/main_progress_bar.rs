@@ -7,7 +7,10 @@ use std::{
 
 use indicatif::{MultiProgress, ProgressBar, ProgressState, ProgressStyle, style::ProgressTracker};
 use parking_lot::RwLock;
-use pixi_progress::ProgressBarPlacement;
+use pixi_progress::{
+    ProgressBarPlacement,
+    json::{ProgressEvent, ProgressEventKind},
+};
 
 #[derive(Clone)]
 pub struct MainProgressBar<T> {
@@ -32,6 +35,9 @@ struct State<T> {
 
     /// Whether to emit OSC 9;4 terminal progress reporting.
     osc_report: bool,
+
+    /// The kind of json progress event to emit when an item finishes.
+    json_event: Option<ProgressEventKind>,
 }
 
 /// A trait for something that can be tracked by the [`MainProgressBar`].
@@ -76,6 +82,7 @@ impl<T: Tracker> MainProgressBar<T> {
                 tracker: Arc::new(RwLock::new(HashMap::new())),
                 next_tracker_id: 0,
                 osc_report: false,
+                json_event: None,
             })),
         }
     }
@@ -86,6 +93,12 @@ impl<T: Tracker> MainProgressBar<T> {
         self
     }
 
+    /// Emit a json progress event of the given kind whenever an item finishes.
+    pub fn with_json_event(self, kind: ProgressEventKind) -> Self {
+        self.inner.write().json_event = Some(kind);
+        self
+    }
+
     /// Called when an item is queued for processing.
     pub fn queued(&self, tracker: T) -> usize {
         let mut state = self.inner.write();
@@ -160,6 +173,18 @@ impl<T: Tracker> State<T> {
             let now = Instant::now();
             tracker.started.get_or_insert(now);
             tracker.finished = Some(now);
+            if let Some(kind) = self.json_event {
+                let done = trackers
+                    .values()
+                    .filter(|item| item.finished.is_some())
+                    .count();
+                pixi_progress::json::emit(ProgressEvent {
+                    event: kind,
+                    package: trackers[&id].tracker.name(),
+                    done: done as u64,
+                    total: trackers.len() as u64,
+                });
+            }
             drop(trackers);
             self.update();
         }
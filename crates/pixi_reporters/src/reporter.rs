@@ -0,0 +1,182 @@
+//! A progress interface that is shared by the conda and the PyPI installs, so
+//! that both report through the same UI and the backend can be swapped, e.g.
+//! for the [`NoopReporter`] in quiet mode.
+
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+
+use indicatif::MultiProgress;
+use pixi_progress::{ProgressBarPlacement, json::ProgressEventKind};
+use uv_distribution_types::{CachedDist, Name};
+use uv_normalize::PackageName;
+
+use crate::main_progress_bar::{MainProgressBar, Tracker};
+
+/// Receives the progress of the items of a step, e.g. the packages that are
+/// installed into an environment.
+pub trait Reporter: Send + Sync {
+    /// Called when an item is queued, returns the id of the item.
+    fn begin(&self, name: &str, size: u64) -> usize;
+
+    /// Called when processing of an item advances, i.e. when it is started.
+    fn advance(&self, id: usize);
+
+    /// Called when processing of an item has finished.
+    fn finish(&self, id: usize);
+
+    /// Called when the step is done, clears the finished items.
+    fn clear(&self);
+}
+
+/// Returns the backend to report the progress of a step through. In quiet
+/// mode that is the [`NoopReporter`], unless json progress is requested,
+/// otherwise the [`TerminalReporter`] created by `terminal`.
+pub fn select_reporter(terminal: impl FnOnce() -> TerminalReporter) -> Arc<dyn Reporter> {
+    if pixi_progress::quiet::is_enabled() && !pixi_progress::json::is_enabled() {
+        Arc::new(NoopReporter)
+    } else {
+        Arc::new(terminal())
+    }
+}
+
+/// A [`Reporter`] that reports nothing.
+pub struct NoopReporter;
+
+impl Reporter for NoopReporter {
+    fn begin(&self, _name: &str, _size: u64) -> usize {
+        0
+    }
+
+    fn advance(&self, _id: usize) {}
+
+    fn finish(&self, _id: usize) {}
+
+    fn clear(&self) {}
+}
+
+/// A [`Reporter`] that draws a progress bar on the terminal.
+pub struct TerminalReporter {
+    progress_bar: MainProgressBar<PackageWithSize>,
+}
+
+impl TerminalReporter {
+    /// Constructs a new instance with the given title and placement.
+    pub fn new(
+        multi_progress: MultiProgress,
+        progress_bar_placement: ProgressBarPlacement,
+        title: String,
+    ) -> Self {
+        Self {
+            progress_bar: MainProgressBar::new(multi_progress, progress_bar_placement, title),
+        }
+    }
+
+    /// Enable OSC 9;4 terminal progress reporting on the progress bar.
+    pub fn with_osc_report(self) -> Self {
+        Self {
+            progress_bar: self.progress_bar.with_osc_report(),
+        }
+    }
+
+    /// Emit a json progress event of the given kind whenever an item finishes.
+    pub fn with_json_event(self, kind: ProgressEventKind) -> Self {
+        Self {
+            progress_bar: self.progress_bar.with_json_event(kind),
+        }
+    }
+}
+
+impl Reporter for TerminalReporter {
+    fn begin(&self, name: &str, size: u64) -> usize {
+        self.progress_bar.queued(PackageWithSize {
+            name: name.to_string(),
+            size,
+        })
+    }
+
+    fn advance(&self, id: usize) {
+        self.progress_bar.start(id)
+    }
+
+    fn finish(&self, id: usize) {
+        self.progress_bar.finish(id)
+    }
+
+    fn clear(&self) {
+        self.progress_bar.clear()
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct PackageWithSize {
+    name: String,
+    size: u64,
+}
+
+impl Tracker for PackageWithSize {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Ord for PackageWithSize {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.size.cmp(&other.size).reverse()
+    }
+}
+
+impl PartialOrd for PackageWithSize {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reports the progress of a uv install through a [`Reporter`].
+pub struct UvInstallReporter {
+    reporter: Arc<dyn Reporter>,
+    ids: HashMap<PackageName, usize>,
+}
+
+impl UvInstallReporter {
+    /// Queues the given distributions on the reporter.
+    pub fn new(reporter: Arc<dyn Reporter>, dists: &[CachedDist]) -> Self {
+        let ids = dists
+            .iter()
+            .map(|dist| (dist.name().clone(), reporter.begin(dist.name().as_ref(), 1)))
+            .collect();
+        Self { reporter, ids }
+    }
+}
+
+impl uv_installer::InstallReporter for UvInstallReporter {
+    fn on_install_progress(&self, wheel: &CachedDist) {
+        if let Some(&id) = self.ids.get(wheel.name()) {
+            self.reporter.advance(id);
+            self.reporter.finish(id);
+        }
+    }
+
+    fn on_install_complete(&self) {
+        self.reporter.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_selects_noop_reporter() {
+        pixi_progress::quiet::enable();
+
+        // The terminal backend is never created, so nothing is drawn.
+        let reporter = select_reporter(|| unreachable!("quiet mode selects the no-op backend"));
+        let id = reporter.begin("python", 10);
+        reporter.advance(id);
+        reporter.finish(id);
+        reporter.clear();
+    }
+}
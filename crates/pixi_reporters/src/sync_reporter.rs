@@ -1,16 +1,20 @@
 use crate::{
     download_verify_reporter::BuildDownloadVerifyReporter,
-    main_progress_bar::{MainProgressBar, Tracker},
+    reporter::{Reporter, TerminalReporter, select_reporter},
 };
 use futures::{Stream, StreamExt};
 use indicatif::MultiProgress;
 use parking_lot::Mutex;
 use pixi_command_dispatcher::{BackendSourceBuildSpec, reporter::BackendSourceBuildReporter};
 use pixi_compute_reporters::{OperationId, OperationRegistry};
-use pixi_progress::ProgressBarPlacement;
-use rattler::install::Transaction;
+use pixi_progress::{ProgressBarPlacement, install_summary, json::ProgressEventKind, timing};
+use rattler::install::{Transaction, TransactionOperation};
 use rattler_conda_types::{PrefixRecord, RepoDataRecord};
-use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::UnboundedReceiver;
 use uv_configuration::initialize_rayon_once;
 
@@ -157,36 +161,58 @@ pub struct CombinedInstallReporterInner {
     cache_entry_id: HashMap<(TransactionId, usize), usize>,
 
     preparing_progress_bar: BuildDownloadVerifyReporter,
-    install_progress_bar: MainProgressBar<PackageWithSize>,
+    install_progress_bar: Arc<dyn Reporter>,
 
     build_output_receiver: Option<UnboundedReceiver<String>>,
+
+    /// The time spent downloading and linking packages per transaction.
+    phase_spans: HashMap<TransactionId, TransactionPhaseSpans>,
+
+    /// The bytes downloaded so far of the cache entries that are being
+    /// downloaded. Entries that are populated without a download are cache
+    /// hits.
+    downloaded_bytes: HashMap<(TransactionId, usize), u64>,
 }
 
-#[derive(PartialEq, Eq)]
-pub struct PackageWithSize {
-    pub name: String,
-    pub size: u64,
+/// The moments the download and link phases of a transaction started and
+/// ended. Packages are downloaded and linked concurrently, so a phase spans
+/// from the first package that started it to the last one that finished it.
+#[derive(Default)]
+struct TransactionPhaseSpans {
+    download: PhaseSpan,
+    link: PhaseSpan,
 }
 
-impl Tracker for PackageWithSize {
-    fn name(&self) -> &str {
-        self.name.as_str()
-    }
+#[derive(Default)]
+struct PhaseSpan {
+    start: Option<Instant>,
+    end: Option<Instant>,
+    count: usize,
+}
 
-    fn size(&self) -> u64 {
-        self.size
+impl PhaseSpan {
+    fn started(&mut self) {
+        self.start.get_or_insert_with(Instant::now);
     }
-}
 
-impl Ord for PackageWithSize {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.size.cmp(&other.size).reverse()
+    fn finished(&mut self) {
+        self.end = Some(Instant::now());
+        self.count += 1;
     }
-}
 
-impl PartialOrd for PackageWithSize {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn record(&self, phase: timing::Phase) {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            let packages = if self.count == 1 {
+                "package"
+            } else {
+                "packages"
+            };
+            timing::record(
+                phase,
+                format!("{} {packages}", self.count),
+                end.checked_duration_since(start).unwrap_or(Duration::ZERO),
+            );
+        }
     }
 }
 
@@ -200,12 +226,15 @@ impl CombinedInstallReporterInner {
             progress_bar_placement.clone(),
             "preparing packages".to_owned(),
         );
-        let link_progress_bar = MainProgressBar::new(
-            multi_progress.clone(),
-            ProgressBarPlacement::After(preparing_progress_bar.progress_bar()),
-            "installing".to_owned(),
-        )
-        .with_osc_report();
+        let link_progress_bar = select_reporter(|| {
+            TerminalReporter::new(
+                multi_progress.clone(),
+                ProgressBarPlacement::After(preparing_progress_bar.progress_bar()),
+                "installing".to_owned(),
+            )
+            .with_osc_report()
+            .with_json_event(ProgressEventKind::Install)
+        });
 
         Self {
             next_id: std::sync::atomic::AtomicUsize::new(0),
@@ -214,6 +243,8 @@ impl CombinedInstallReporterInner {
             operation_link_id: HashMap::new(),
             cache_entry_id: HashMap::new(),
             build_output_receiver: None,
+            phase_spans: HashMap::new(),
+            downloaded_bytes: HashMap::new(),
         }
     }
 
@@ -222,6 +253,18 @@ impl CombinedInstallReporterInner {
         id: TransactionId,
         transaction: &Transaction<PrefixRecord, RepoDataRecord>,
     ) {
+        let (mut added, mut removed, mut updated) = (0, 0, 0);
+        for operation in &transaction.operations {
+            match operation {
+                TransactionOperation::Install(_) => added += 1,
+                TransactionOperation::Remove(_) => removed += 1,
+                TransactionOperation::Change { .. } | TransactionOperation::Reinstall { .. } => {
+                    updated += 1
+                }
+            }
+        }
+        install_summary::record_operations(added, removed, updated);
+
         for (operation_id, operation) in transaction.operations.iter().enumerate() {
             if let Some(record) = operation
                 .record_to_install()
@@ -229,10 +272,10 @@ impl CombinedInstallReporterInner {
             {
                 self.operation_link_id.insert(
                     (id, operation_id),
-                    self.install_progress_bar.queued(PackageWithSize {
-                        name: record.package_record.name.as_normalized().to_string(),
-                        size: record.package_record.size.unwrap_or(1),
-                    }),
+                    self.install_progress_bar.begin(
+                        record.package_record.name.as_normalized(),
+                        record.package_record.size.unwrap_or(1),
+                    ),
                 );
             }
             if let Some(record) = operation.record_to_install() {
@@ -268,28 +311,36 @@ impl CombinedInstallReporterInner {
             .on_validation_complete(validation_id);
     }
 
-    fn on_download_start(&mut self, _id: TransactionId, cache_entry: usize) -> usize {
+    fn on_download_start(&mut self, id: TransactionId, cache_entry: usize) -> usize {
+        self.phase_spans.entry(id).or_default().download.started();
+        self.downloaded_bytes.insert((id, cache_entry), 0);
         self.preparing_progress_bar.on_download_start(cache_entry);
         cache_entry
     }
 
     fn on_download_progress(
         &mut self,
-        _id: TransactionId,
+        id: TransactionId,
         cache_entry: usize,
         progress: u64,
         total: Option<u64>,
     ) {
+        self.downloaded_bytes.insert((id, cache_entry), progress);
         self.preparing_progress_bar
             .on_download_progress(cache_entry, progress, total);
     }
 
-    fn on_download_completed(&mut self, _id: TransactionId, cache_entry: usize) {
+    fn on_download_completed(&mut self, id: TransactionId, cache_entry: usize) {
+        self.phase_spans.entry(id).or_default().download.finished();
         self.preparing_progress_bar
             .on_download_complete(cache_entry);
     }
 
-    fn on_populate_cache_complete(&mut self, _id: TransactionId, cache_entry: usize) {
+    fn on_populate_cache_complete(&mut self, id: TransactionId, cache_entry: usize) {
+        match self.downloaded_bytes.remove(&(id, cache_entry)) {
+            Some(bytes) => install_summary::record_download(bytes),
+            None => install_summary::record_cache_hit(),
+        }
         self.preparing_progress_bar.on_entry_finished(cache_entry);
     }
 
@@ -300,7 +351,7 @@ impl CombinedInstallReporterInner {
         _record: &PrefixRecord,
     ) -> usize {
         if let Some(&link_id) = self.operation_link_id.get(&(id, operation)) {
-            self.install_progress_bar.start(link_id)
+            self.install_progress_bar.advance(link_id)
         };
         operation
     }
@@ -313,13 +364,16 @@ impl CombinedInstallReporterInner {
         operation: usize,
         _record: &RepoDataRecord,
     ) -> usize {
+        self.phase_spans.entry(id).or_default().link.started();
         if let Some(&link_id) = self.operation_link_id.get(&(id, operation)) {
-            self.install_progress_bar.start(link_id)
+            self.install_progress_bar.advance(link_id)
         };
         operation
     }
 
-    fn on_link_complete(&mut self, _id: TransactionId, _index: usize) {}
+    fn on_link_complete(&mut self, id: TransactionId, _index: usize) {
+        self.phase_spans.entry(id).or_default().link.finished();
+    }
 
     fn on_transaction_operation_complete(&mut self, id: TransactionId, operation: usize) {
         if let Some(link_id) = self.operation_link_id.remove(&(id, operation)) {
@@ -327,7 +381,12 @@ impl CombinedInstallReporterInner {
         }
     }
 
-    fn on_transaction_complete(&mut self, _id: TransactionId) {}
+    fn on_transaction_complete(&mut self, id: TransactionId) {
+        if let Some(spans) = self.phase_spans.remove(&id) {
+            spans.download.record(timing::Phase::Download);
+            spans.link.record(timing::Phase::Link);
+        }
+    }
 }
 
 pub struct InstallReporter {
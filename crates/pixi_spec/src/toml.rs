@@ -1619,6 +1619,7 @@ mod test {
             json!({ "url": "https://conda.anaconda.org/conda-forge/linux-64/21cmfast-3.3.1-py38h0db86a8_1.conda", "sha256": "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3" }),
             json!({ "git": "https://github.com/conda-forge/21cmfast-feedstock" }),
             json!({ "git": "https://github.com/conda-forge/21cmfast-feedstock", "branch": "main" }),
+            json!({ "git": "https://github.com/conda-forge/21cmfast-feedstock", "rev": "8f1b2c3d4e5f60718293a4b5c6d7e8f901234567", "subdirectory": "recipe" }),
             // Source specs with matchspec selectors:
             json!({ "path": "../mypkg", "version": "1.2.3" }),
             json!({ "path": "../mypkg", "version": ">=1.2", "build": "py37_*" }),
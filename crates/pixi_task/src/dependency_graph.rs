@@ -0,0 +1,283 @@
+//! Rendering of the `depends-on` relations between the tasks of an
+//! environment as a Mermaid flowchart or a Graphviz `dot` graph.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+};
+
+use itertools::Itertools;
+use miette::Diagnostic;
+use pixi_manifest::{EnvironmentName, TaskName};
+use thiserror::Error;
+
+use crate::TaskGraph;
+
+/// The tasks form a cycle, which can never be run.
+#[derive(Debug, Error, Diagnostic)]
+#[error("the tasks have a cyclic dependency: {}", .cycle.iter().map(TaskName::as_str).join(" -> "))]
+#[diagnostic(help("remove one of the `depends-on` entries of the cycle"))]
+pub struct TaskCycleError {
+    /// The tasks of the cycle, the first task is repeated at the end.
+    pub cycle: Vec<TaskName>,
+}
+
+/// A node of a [`TaskDependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GraphNode {
+    name: TaskName,
+    /// The environment the task runs in, if that is not the environment of
+    /// the graph.
+    environment: Option<EnvironmentName>,
+    /// Whether the task is an alias, which only runs its dependencies.
+    is_alias: bool,
+}
+
+impl GraphNode {
+    fn label(&self) -> String {
+        match &self.environment {
+            Some(environment) => format!("{} ({environment})", self.name.as_str()),
+            None => self.name.as_str().to_string(),
+        }
+    }
+}
+
+/// The graph of the tasks of an environment, with an edge from every task to
+/// each task it depends on. The nodes are in the order in which the tasks
+/// would run.
+#[derive(Debug, Clone)]
+pub struct TaskDependencyGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl TaskDependencyGraph {
+    /// Constructs the graph of a [`TaskGraph`] of the tasks of `environment`,
+    /// see [`TaskGraph::from_environment`].
+    ///
+    /// Returns an error if the dependencies contain a cycle.
+    pub fn from_task_graph(
+        graph: &TaskGraph<'_>,
+        environment: &EnvironmentName,
+    ) -> Result<Self, TaskCycleError> {
+        let order = graph.topological_order();
+        let positions: HashMap<_, _> = order
+            .iter()
+            .enumerate()
+            .map(|(position, &id)| (id, position))
+            .collect();
+
+        let nodes = order
+            .iter()
+            .map(|&id| {
+                let node = &graph[id];
+                let run_environment = node.run_environment.name();
+                GraphNode {
+                    name: node
+                        .name
+                        .clone()
+                        .expect("the tasks of an environment are named"),
+                    environment: (run_environment != environment).then(|| run_environment.clone()),
+                    is_alias: !node.task.is_executable(),
+                }
+            })
+            .collect_vec();
+
+        let mut edges = Vec::new();
+        for (from, &id) in order.iter().enumerate() {
+            for dependency in &graph[id].dependencies {
+                let to = positions[&dependency.task_id()];
+                if !edges.contains(&(from, to)) {
+                    edges.push((from, to));
+                }
+            }
+        }
+
+        let graph = Self { nodes, edges };
+        graph.check_cycles()?;
+        Ok(graph)
+    }
+
+    /// In the topological order every dependency comes before the task that
+    /// depends on it, unless the dependency is part of a cycle. Returns an
+    /// error with such a cycle.
+    fn check_cycles(&self) -> Result<(), TaskCycleError> {
+        let Some(&(from, to)) = self.edges.iter().find(|(from, to)| to >= from) else {
+            return Ok(());
+        };
+
+        // Find the path back from the dependency to the task.
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::from([to]);
+        while let Some(node) = queue.pop_front() {
+            if node == from {
+                break;
+            }
+            for &(_, next) in self.edges.iter().filter(|(source, _)| *source == node) {
+                if next != to && !previous.contains_key(&next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut path = vec![from];
+        while let Some(&node) = previous.get(path.last().expect("the path is never empty")) {
+            path.push(node);
+        }
+        path.reverse();
+        path.push(to);
+
+        Err(TaskCycleError {
+            cycle: path
+                .into_iter()
+                .map(|node| self.nodes[node].name.clone())
+                .collect(),
+        })
+    }
+
+    /// Renders the graph as a Mermaid flowchart. Concrete tasks are drawn as
+    /// rectangles and aliases as stadiums.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("graph TD\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            let label = node.label().replace('"', "#quot;");
+            if node.is_alias {
+                writeln!(out, "    n{id}([\"{label}\"])").unwrap();
+            } else {
+                writeln!(out, "    n{id}[\"{label}\"]").unwrap();
+            }
+        }
+        for &(from, to) in &self.edges {
+            writeln!(out, "    n{from} --> n{to}").unwrap();
+        }
+        out
+    }
+
+    /// Renders the graph as a Graphviz `dot` graph. Concrete tasks are drawn
+    /// as boxes and aliases as dashed ellipses.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph tasks {\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            let attributes = if node.is_alias {
+                "shape=ellipse, style=dashed"
+            } else {
+                "shape=box"
+            };
+            let label = node.label().replace('"', "\\\"");
+            writeln!(out, "    n{id} [label=\"{label}\", {attributes}];").unwrap();
+        }
+        for &(from, to) in &self.edges {
+            writeln!(out, "    n{from} -> n{to};").unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pixi_core::Workspace;
+
+    use super::*;
+    use crate::SearchEnvironments;
+
+    fn graph(manifest: &str) -> Result<TaskDependencyGraph, TaskCycleError> {
+        let workspace = Workspace::from_str(
+            Path::new("pixi.toml"),
+            &format!(
+                r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64"]
+
+        {manifest}
+        "#
+            ),
+        )
+        .unwrap();
+        let environment = workspace.default_environment();
+        let search_envs =
+            SearchEnvironments::from_opt_env(&workspace, Some(environment.clone()), None);
+        let task_graph =
+            TaskGraph::from_environment(&workspace, &search_envs, &environment).unwrap();
+        TaskDependencyGraph::from_task_graph(&task_graph, environment.name())
+    }
+
+    #[test]
+    fn test_mermaid() {
+        let graph = graph(
+            r#"
+        [tasks]
+        a = { cmd = "echo a", depends-on = ["b"] }
+        b = "echo b"
+        all = { depends-on = ["a", "build-docs"] }
+        "build-docs" = "echo docs"
+        a_b_2 = "echo a_b_2"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            graph.to_mermaid(),
+            r#"graph TD
+    n0["b"]
+    n1["a"]
+    n2["a_b_2"]
+    n3["build-docs"]
+    n4(["all"])
+    n1 --> n0
+    n4 --> n1
+    n4 --> n3
+"#
+        );
+        assert!(
+            graph
+                .to_dot()
+                .contains("    n3 [label=\"build-docs\", shape=box];\n    n4 [label=\"all\", shape=ellipse, style=dashed];\n")
+        );
+        assert!(graph.to_dot().contains("    n4 -> n3;\n"));
+    }
+
+    #[test]
+    fn test_dependency_on_other_environment() {
+        let graph = graph(
+            r#"
+        [tasks]
+        site = { cmd = "echo site", depends-on = [{ task = "serve", environment = "docs" }] }
+
+        [feature.docs.tasks]
+        serve = "echo serve"
+
+        [environments]
+        docs = ["docs"]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            graph.to_mermaid(),
+            r#"graph TD
+    n0["serve (docs)"]
+    n1["site"]
+    n1 --> n0
+"#
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let error = graph(
+            r#"
+        [tasks]
+        a = { cmd = "echo a", depends-on = ["b"] }
+        b = { cmd = "echo b", depends-on = ["a"] }
+        "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the tasks have a cyclic dependency: a -> b -> a"
+        );
+    }
+}
@@ -3,11 +3,14 @@ use std::{
     collections::HashMap,
     ffi::OsString,
     fmt::{Display, Formatter},
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Write,
     path::PathBuf,
 };
 
 use deno_task_shell::{
-    ShellPipeWriter, ShellState, execute_with_pipes, parser::SequentialList, pipe,
+    KillSignal, ShellPipeReader, ShellPipeWriter, ShellState, execute_with_pipes,
+    parser::SequentialList, pipe,
 };
 use fs_err::tokio as tokio_fs;
 use itertools::Itertools;
@@ -15,22 +18,53 @@ use miette::{Context, Diagnostic};
 use pixi_consts::consts;
 use pixi_core::{
     Workspace,
-    activation::CurrentEnvVarBehavior,
+    activation::{CurrentEnvVarBehavior, get_unactivated_environment_variables},
     workspace::get_activated_environment_variables,
     workspace::{Environment, HasWorkspaceRef},
 };
 use pixi_manifest::{
     Task, TaskName,
-    task::{ArgValues, TaskRenderContext, TemplateStringError},
+    task::{ArgValues, TaskRenderContext, TaskShell, TemplateStringError},
 };
 use pixi_progress::await_in_progress;
+use pixi_utils::dotenv::{DotenvError, read_dotenv};
 use rattler_lock::LockFile;
 use thiserror::Error;
 use tokio::task::JoinHandle;
 
+use crate::requirements::{MissingRequirementError, is_installed, locked_package_names};
 use crate::task_graph::{TaskGraph, TaskId};
 use crate::task_hash::{InputHashesError, NameHash, TaskCache, TaskHash};
 
+/// Returns the program and the arguments that run a script with the given
+/// shell. The script is passed as the last argument.
+fn shell_invocation(shell: TaskShell) -> &'static [&'static str] {
+    match shell {
+        TaskShell::Bash => &["bash", "-c"],
+        TaskShell::Cmd => &["cmd", "/d", "/c"],
+        TaskShell::PowerShell if cfg!(windows) => &["powershell", "-NoProfile", "-Command"],
+        TaskShell::PowerShell => &["pwsh", "-NoProfile", "-Command"],
+        TaskShell::None => &[],
+    }
+}
+
+/// Quotes a word so that `deno_task_shell` passes it on verbatim.
+fn deno_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r#"'"'"'"#))
+}
+
+/// Quotes a command line argument so that the shell of the task passes it on
+/// as a single word.
+fn quote_arg(shell: Option<TaskShell>, arg: &str) -> String {
+    match shell {
+        Some(TaskShell::Bash) => format!("'{}'", arg.replace('\'', r"'\''")),
+        Some(TaskShell::PowerShell) => format!("'{}'", arg.replace('\'', "''")),
+        // The arguments end up in a batch file, where `%%` is a literal `%`.
+        Some(TaskShell::Cmd) => format!("\"{}\"", arg.replace('"', "\"\"").replace('%', "%%")),
+        Some(TaskShell::None) | None => deno_quote(arg),
+    }
+}
+
 /// Runs task in project.
 #[derive(Default, Debug)]
 pub struct RunOutput {
@@ -49,6 +83,13 @@ pub enum FailedToParseShellScript {
         task: String,
     },
 
+    #[error("failed to write the script of the task to disk. Task: '{task}'")]
+    WriteScript {
+        #[source]
+        source: std::io::Error,
+        task: String,
+    },
+
     #[error(transparent)]
     #[diagnostic(transparent)]
     ArgumentReplacement(#[from] TemplateStringError),
@@ -67,6 +108,14 @@ pub enum TaskExecutionError {
 
     #[error(transparent)]
     FailedToParseShellScript(#[from] FailedToParseShellScript),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Dotenv(#[from] DotenvError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    MissingRequirement(#[from] MissingRequirementError),
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -150,6 +199,16 @@ impl<'p> ExecutableTask<'p> {
         }
     }
 
+    /// Returns the extra command line arguments that are appended to the
+    /// command.
+    fn appended_args(&self) -> &[String] {
+        if self.task.forwards_args() {
+            &[]
+        } else {
+            self.args.extra_args()
+        }
+    }
+
     /// Returns the task as script.
     ///
     /// Multi-line task strings are passed verbatim to `deno_task_shell`, which
@@ -169,24 +228,54 @@ impl<'p> ExecutableTask<'p> {
             let export = get_export_specific_task_env(self.task.as_ref(), &context)
                 .map_err(FailedToParseShellScript::ArgumentReplacement)?;
 
-            // Append the command line arguments verbatim
-            let extra = self.args.extra_args();
+            // Append the command line arguments verbatim, unless the command
+            // already placed them through `{{ args }}`.
+            let extra = self.appended_args();
+            let shell = self.task.shell();
             let cli_args = if extra.is_empty() {
                 String::new()
             } else {
                 format!(
                     " {}",
-                    extra
-                        .iter()
-                        .format_with(" ", |arg, f| f(&format_args!("'{arg}'")))
+                    extra.iter().map(|arg| quote_arg(shell, arg)).join(" ")
                 )
             };
 
+            let command = match shell {
+                None => format!("{task}{cli_args}"),
+                Some(TaskShell::None) => {
+                    let words = shlex::split(&task).ok_or_else(|| {
+                        FailedToParseShellScript::ParseError {
+                            source: anyhow::anyhow!("the command contains unbalanced quotes"),
+                            task: task.to_string(),
+                        }
+                    })?;
+                    words
+                        .iter()
+                        .chain(extra)
+                        .map(|word| deno_quote(word))
+                        .join(" ")
+                }
+                Some(TaskShell::Cmd) => {
+                    let script = self.write_cmd_script(&format!("{task}{cli_args}"))?;
+                    shell_invocation(TaskShell::Cmd)
+                        .iter()
+                        .map(|word| word.to_string())
+                        .chain([deno_quote(&script.to_string_lossy())])
+                        .join(" ")
+                }
+                Some(shell) => shell_invocation(shell)
+                    .iter()
+                    .map(|word| word.to_string())
+                    .chain([deno_quote(&format!("{task}{cli_args}"))])
+                    .join(" "),
+            };
+
             // Skip the export if it's empty, to avoid leading blank lines.
             let full_script = if export.is_empty() {
-                format!("{task}{cli_args}")
+                command
             } else {
-                format!("{export}\n{task}{cli_args}")
+                format!("{export}\n{command}")
             };
 
             Ok(Some(full_script))
@@ -195,6 +284,28 @@ impl<'p> ExecutableTask<'p> {
         }
     }
 
+    /// Writes the script of a task that runs with `cmd` to a batch file in the
+    /// task cache folder and returns its path.
+    ///
+    /// `cmd /c` does not follow the quoting rules that are used to pass it the
+    /// script as an argument, so a script with quoted words can't be passed
+    /// on the command line. The file is named after its contents, so running
+    /// the same task again reuses it.
+    fn write_cmd_script(&self, script: &str) -> Result<PathBuf, FailedToParseShellScript> {
+        let contents = format!("@echo off\r\n{}\r\n", script.lines().join("\r\n"));
+        let path = self.workspace.task_cache_folder().join(format!(
+            "script-{:016x}.cmd",
+            xxhash_rust::xxh3::xxh3_64(contents.as_bytes())
+        ));
+        fs_err::create_dir_all(self.workspace.task_cache_folder())
+            .and_then(|_| fs_err::write(&path, contents))
+            .map_err(|source| FailedToParseShellScript::WriteScript {
+                source,
+                task: script.to_string(),
+            })?;
+        Ok(path)
+    }
+
     /// Returns a [`SequentialList`] which can be executed by deno task shell.
     /// Returns `None` if the command is not executable like in the case of
     /// an alias.
@@ -233,6 +344,67 @@ impl<'p> ExecutableTask<'p> {
         })
     }
 
+    /// Returns an error for the first package or executable the task
+    /// `requires` that is not installed in the run environment. Packages are
+    /// looked up in the locked packages of the environment, executables in the
+    /// prefix.
+    pub fn check_requirements(&self, lock_file: &LockFile) -> Result<(), MissingRequirementError> {
+        if self.task.requires().is_empty() {
+            return Ok(());
+        }
+        let prefix = self.run_environment.dir();
+        let locked_packages = locked_package_names(lock_file, &self.run_environment);
+        match self
+            .task
+            .requires()
+            .iter()
+            .find(|requirement| !is_installed(&prefix, &locked_packages, requirement))
+        {
+            Some(requirement) => Err(MissingRequirementError {
+                task: self.name().unwrap_or("<anonymous>").to_string(),
+                requirement: requirement.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the command environment with the variables of the `.env` files
+    /// of the environment and of the task added.
+    ///
+    /// Relative paths are resolved against the root of the workspace. The
+    /// variables override the given environment, except for the variables
+    /// that the activation of the manifest sets. The `env` of the task is
+    /// exported by the script itself and therefore overrides them as well.
+    /// `pixi run` doesn't accept variables on the command line, so there is
+    /// nothing else that takes precedence over the `.env` files.
+    pub fn command_env_with_dotenv(
+        &self,
+        command_env: &HashMap<OsString, OsString>,
+    ) -> Result<HashMap<OsString, OsString>, DotenvError> {
+        let platform = self.run_environment.best_declared_platform();
+        let files = self
+            .run_environment
+            .activation_dotenv(platform)
+            .into_iter()
+            .chain(self.task.dotenv().iter().cloned());
+
+        let mut command_env = command_env.clone();
+        let activation_env = self.run_environment.activation_env(platform);
+        for file in files {
+            let path = self.workspace.root().join(&file.path);
+            if file.optional && !path.exists() {
+                tracing::debug!("skipping missing optional '{}'", path.display());
+                continue;
+            }
+            for (key, value) in read_dotenv(&path)? {
+                if !activation_env.contains_key(&key) {
+                    command_env.insert(OsString::from(key), OsString::from(value));
+                }
+            }
+        }
+        Ok(command_env)
+    }
+
     /// Returns the full command that should be executed for this task. This
     /// includes any additional arguments that should be passed to the
     /// command.
@@ -247,7 +419,7 @@ impl<'p> ExecutableTask<'p> {
             .map(|c| c.into_owned());
 
         if let Some(mut cmd) = original_cmd {
-            let extra = self.args.extra_args();
+            let extra = self.appended_args();
             if !extra.is_empty() {
                 cmd.push(' ');
                 cmd.push_str(&extra.join(" "));
@@ -278,6 +450,7 @@ impl<'p> ExecutableTask<'p> {
             });
         };
         let cwd = self.working_directory()?;
+        let command_env = self.command_env_with_dotenv(command_env)?;
         let (stdin, mut stdin_writer) = pipe();
         if let Some(stdin) = input {
             stdin_writer
@@ -287,12 +460,7 @@ impl<'p> ExecutableTask<'p> {
         drop(stdin_writer); // prevent a deadlock by dropping the writer
         let (stdout, stdout_handle) = get_output_writer_and_handle();
         let (stderr, stderr_handle) = get_output_writer_and_handle();
-        let state = ShellState::new(
-            command_env.clone(),
-            cwd,
-            Default::default(),
-            Default::default(),
-        );
+        let state = ShellState::new(command_env, cwd, Default::default(), Default::default());
         let code = execute_with_pipes(script, state, stdin, stdout, stderr).await;
         Ok(RunOutput {
             exit_code: code,
@@ -301,6 +469,73 @@ impl<'p> ExecutableTask<'p> {
         })
     }
 
+    /// Executes the task and forwards its output line by line to the given
+    /// writers, prefixing every line with the colored name of the task.
+    ///
+    /// Returns the exit code of the task.
+    pub async fn execute_with_prefixed_output<O, E>(
+        &self,
+        command_env: &HashMap<OsString, OsString>,
+        kill_signal: KillSignal,
+        stdout: O,
+        stderr: E,
+    ) -> Result<i32, TaskExecutionError>
+    where
+        O: Write + Send + 'static,
+        E: Write + Send + 'static,
+    {
+        let Some(script) = self.as_deno_script()? else {
+            return Ok(0);
+        };
+        let cwd = self.working_directory()?;
+        let command_env = self.command_env_with_dotenv(command_env)?;
+        let (stdout_writer, stdout_handle) =
+            forward_with_prefix(self.output_prefix(console::Style::new()), stdout);
+        let (stderr_writer, stderr_handle) = forward_with_prefix(
+            self.output_prefix(console::Style::new().for_stderr()),
+            stderr,
+        );
+        let state = ShellState::new(command_env, cwd, Default::default(), kill_signal);
+        let code = execute_with_pipes(
+            script,
+            state,
+            ShellPipeReader::stdin(),
+            stdout_writer,
+            stderr_writer,
+        )
+        .await;
+
+        // The writers are dropped when the script finishes, which closes the
+        // pipes and lets the forwarding tasks flush the remaining output.
+        stdout_handle
+            .await
+            .expect("should be able to forward stdout");
+        stderr_handle
+            .await
+            .expect("should be able to forward stderr");
+        Ok(code)
+    }
+
+    /// Returns the prefix that is put in front of every line of output of the
+    /// task. Every task name gets its own color so the output of different
+    /// tasks can be told apart. Anonymous tasks are not prefixed.
+    fn output_prefix(&self, style: console::Style) -> String {
+        const COLORS: [console::Color; 5] = [
+            console::Color::Cyan,
+            console::Color::Magenta,
+            console::Color::Yellow,
+            console::Color::Blue,
+            console::Color::Green,
+        ];
+        let Some(name) = self.name() else {
+            return String::new();
+        };
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        let color = COLORS[(hasher.finish() % COLORS.len() as u64) as usize];
+        format!("{} ", style.fg(color).apply_to(format!("[{name}]")))
+    }
+
     /// Compute the post-run task hash by updating inputs and outputs.
     /// This does not emit any warnings; it only reflects the current filesystem state.
     pub async fn compute_post_run_hash(
@@ -495,6 +730,57 @@ impl Display for ExecutableTaskConsoleDisplay<'_, '_> {
         }
     }
 }
+/// Creates a pipe of which every line written to it is forwarded to `writer`
+/// with the given prefix.
+fn forward_with_prefix<W: Write + Send + 'static>(
+    prefix: String,
+    writer: W,
+) -> (ShellPipeWriter, JoinHandle<()>) {
+    let (reader, pipe_writer) = pipe();
+    let handle = tokio::task::spawn_blocking(move || {
+        let mut writer = PrefixedWriter::new(prefix, writer);
+        if let Err(err) = reader.pipe_to(&mut writer) {
+            tracing::debug!("failed to forward task output: {err}");
+        }
+        let _ = writer.flush();
+    });
+    (pipe_writer, handle)
+}
+
+/// A writer that puts a prefix in front of every line that is written to it.
+struct PrefixedWriter<W> {
+    prefix: String,
+    inner: W,
+    at_line_start: bool,
+}
+
+impl<W> PrefixedWriter<W> {
+    fn new(prefix: String, inner: W) -> Self {
+        Self {
+            prefix,
+            inner,
+            at_line_start: true,
+        }
+    }
+}
+
+impl<W: Write> Write for PrefixedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.at_line_start {
+                self.inner.write_all(self.prefix.as_bytes())?;
+            }
+            self.inner.write_all(line)?;
+            self.at_line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 /// Helper function to create a pipe that we can get the output from.
 fn get_output_writer_and_handle() -> (ShellPipeWriter, JoinHandle<String>) {
     let (reader, writer) = pipe();
@@ -570,6 +856,30 @@ pub async fn get_task_env(
     Ok(activation_env)
 }
 
+/// Determine the environment variables to use when executing a command
+/// without activating the environment. Only the executables of the
+/// environment are added to the `PATH`.
+pub fn get_unactivated_task_env(
+    environment: &Environment<'_>,
+    clean_env: bool,
+) -> miette::Result<HashMap<String, String>> {
+    let env_var_behavior = if clean_env {
+        CurrentEnvVarBehavior::Clean
+    } else {
+        CurrentEnvVarBehavior::Include
+    };
+    let mut task_env = get_unactivated_environment_variables(environment, env_var_behavior)?;
+
+    if let Ok(init_cwd) = std::env::current_dir() {
+        task_env.insert(
+            "INIT_CWD".to_string(),
+            init_cwd.to_string_lossy().to_string(),
+        );
+    }
+
+    Ok(task_env)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -768,6 +1078,88 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn test_as_script_with_shell() {
+        let script = script_for(r#"{ cmd = "echo 'hello world'", shell = "bash" }"#);
+        assert_eq!(script, r#"bash -c 'echo '"'"'hello world'"'"''"#);
+
+        let script = script_for(r#"{ cmd = "echo $HOME 'a b'", shell = "none" }"#);
+        assert_eq!(script, "'echo' '$HOME' 'a b'");
+        assert_eq!(parsed_item_count(&script), 1);
+    }
+
+    #[test]
+    fn test_as_script_with_shell_quotes_args() {
+        let args = ArgValues::FreeFormArgs(vec![
+            "a b".to_string(),
+            "it's \"quoted\"".to_string(),
+            "100%".to_string(),
+        ]);
+        let output = |task_body: &str| {
+            let workspace = workspace_with(&format!("[tasks]\ntest = {task_body}\n"));
+            let mut task = task_from_snippet(&workspace, "test");
+            task.args = args.clone();
+            task.as_script().unwrap().unwrap()
+        };
+
+        let script = output(r#"{ cmd = "echo", shell = "bash" }"#);
+        assert_eq!(
+            shlex::split(&script).unwrap(),
+            ["bash", "-c", r#"echo 'a b' 'it'\''s "quoted"' '100%'"#]
+        );
+
+        let script = output(r#"{ cmd = "echo", shell = "powershell" }"#);
+        assert!(script.ends_with(&deno_quote(r#"echo 'a b' 'it''s "quoted"' '100%'"#)));
+    }
+
+    #[test]
+    fn test_as_script_with_cmd_writes_batch_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = Workspace::from_str(
+            &temp_dir.path().join("pixi.toml"),
+            &format!(
+                "{PROJECT_BOILERPLATE}\n[tasks]\ntest = {{ cmd = \"echo %USERPROFILE%\", shell = \"cmd\" }}\n"
+            ),
+        )
+        .unwrap();
+        let mut task = task_from_snippet(&workspace, "test");
+        task.args = ArgValues::FreeFormArgs(vec!["a b".to_string(), "say \"hi\" 100%".to_string()]);
+        let script = task.as_script().unwrap().unwrap();
+
+        // The script is run from a batch file, so its quotes never reach the
+        // command line of `cmd`.
+        let words = shlex::split(&script).unwrap();
+        assert_eq!(words[..3], ["cmd", "/d", "/c"]);
+        let batch_file = fs_err::read_to_string(&words[3]).unwrap();
+        assert_eq!(
+            batch_file,
+            "@echo off\r\necho %USERPROFILE% \"a b\" \"say \"\"hi\"\" 100%%\"\r\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_without_shell_interpretation() {
+        // Without a shell `$HOME` is passed on literally, so it equals the
+        // quoted string.
+        let run = |script: String| async move {
+            let list = deno_task_shell::parser::parse(script.trim()).unwrap();
+            deno_task_shell::execute(
+                list,
+                std::env::vars_os().collect(),
+                std::env::current_dir().unwrap(),
+                std::collections::HashMap::new(),
+                Default::default(),
+            )
+            .await
+        };
+        let script = script_for(r#"{ cmd = "test $HOME = '$HOME'", shell = "none" }"#);
+        assert_eq!(run(script).await, 0);
+
+        let script = script_for(r#""test $HOME = '$HOME'""#);
+        assert_ne!(run(script).await, 0);
+    }
+
     #[test]
     fn test_as_script_single_line_passthrough() {
         let script = script_for(r#""echo hello""#);
@@ -868,6 +1260,74 @@ exit 0
         assert_ne!(run_script(&script).await, 0);
     }
 
+    #[test]
+    fn test_prefixed_writer() {
+        let mut writer = PrefixedWriter::new("[test] ".to_string(), Vec::new());
+        writer.write_all(b"hello\nwor").unwrap();
+        writer.write_all(b"ld\n\nlast").unwrap();
+        assert_eq!(
+            String::from_utf8(writer.inner).unwrap(),
+            "[test] hello\n[test] world\n[test] \n[test] last"
+        );
+    }
+
+    /// A writer that can still be read after it was moved into the task.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_prefixed_output() {
+        console::set_colors_enabled(false);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_contents = r#"
+            [tasks]
+            greet = "echo hello && echo world"
+            "#;
+        let workspace = Workspace::from_str(
+            &temp_dir.path().join("pixi.toml"),
+            &format!("{PROJECT_BOILERPLATE}\n{file_contents}"),
+        )
+        .unwrap();
+
+        let name = TaskName::from("greet");
+        let environment = workspace.default_environment();
+        let task = environment.task(&name, None).unwrap();
+        let executable_task = ExecutableTask {
+            workspace: &workspace,
+            name: Some(name),
+            task: Cow::Borrowed(task),
+            run_environment: environment.clone(),
+            args: ArgValues::default(),
+            init_cwd: None,
+        };
+
+        let stdout = SharedBuffer::default();
+        let code = executable_task
+            .execute_with_prefixed_output(
+                &HashMap::new(),
+                KillSignal::default(),
+                stdout.clone(),
+                std::io::sink(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(
+            String::from_utf8(stdout.0.lock().unwrap().clone()).unwrap(),
+            "[greet] hello\n[greet] world\n"
+        );
+    }
+
     #[tokio::test]
     async fn test_get_task_env() {
         let file_contents = r#"
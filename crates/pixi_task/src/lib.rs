@@ -1,17 +1,21 @@
+mod dependency_graph;
 mod error;
 mod executable_task;
 mod file_hashes;
+mod requirements;
 mod task_environment;
 mod task_graph;
 mod task_hash;
 
+pub use dependency_graph::{TaskCycleError, TaskDependencyGraph};
 pub use file_hashes::{FileHashes, FileHashesError};
 pub use pixi_manifest::{Task, TaskName};
+pub use requirements::MissingRequirementError;
 pub use task_hash::{ComputationHash, InputHashes, TaskHash};
 
 pub use executable_task::{
     CanSkip, ExecutableTask, FailedToParseShellScript, InvalidWorkingDirectory, RunOutput,
-    TaskExecutionError, get_task_env,
+    TaskExecutionError, get_task_env, get_unactivated_task_env,
 };
 pub use task_environment::{
     AmbiguousTask, FindTaskError, FindTaskSource, SearchEnvironments, TaskAndEnvironment,
@@ -0,0 +1,121 @@
+//! Checks that the packages and executables a task `requires` are installed
+//! in the environment it runs in.
+//!
+//! A requirement is installed when the environment has a locked conda package
+//! or PyPI package with that name, or when its prefix has an executable with
+//! that name in one of its binary folders.
+
+use std::{collections::HashSet, path::Path};
+
+use miette::Diagnostic;
+use pixi_core::workspace::Environment;
+use rattler_lock::LockFile;
+use thiserror::Error;
+
+/// The folders of a prefix that contain executables.
+const BINARY_FOLDERS: &[&str] = if cfg!(windows) {
+    &[
+        "",
+        "Library/mingw-w64/bin",
+        "Library/usr/bin",
+        "Library/bin",
+        "Scripts",
+        "bin",
+    ]
+} else {
+    &["bin"]
+};
+
+/// The extensions an executable can have.
+const EXECUTABLE_EXTENSIONS: &[&str] = if cfg!(windows) {
+    &["exe", "bat", "cmd"]
+} else {
+    &[]
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("task `{task}` requires `{requirement}` which is not installed")]
+#[diagnostic(help(
+    "add it with `pixi add {requirement}`, or with `pixi add --pypi {requirement}` if it is a PyPI package"
+))]
+pub struct MissingRequirementError {
+    pub task: String,
+    pub requirement: String,
+}
+
+/// Normalizes a package name, so that conda and PyPI names can be compared
+/// regardless of case and separators.
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Returns the normalized names of the conda and PyPI packages that are
+/// locked for the environment on its best declared platform.
+pub(crate) fn locked_package_names(
+    lock_file: &LockFile,
+    environment: &Environment<'_>,
+) -> HashSet<String> {
+    let Some(env) = lock_file.environment(environment.name().as_str()) else {
+        return HashSet::new();
+    };
+    environment
+        .best_declared_platform()
+        .and_then(|platform| lock_file.platform(platform.name().as_str()))
+        .and_then(|platform| env.packages(platform))
+        .into_iter()
+        .flatten()
+        .map(|package| normalize(package.name()))
+        .collect()
+}
+
+/// Returns true if an executable with the given name is in one of the binary
+/// folders of the prefix.
+fn has_executable(prefix: &Path, name: &str) -> bool {
+    BINARY_FOLDERS.iter().any(|folder| {
+        let folder = prefix.join(folder);
+        folder.join(name).is_file()
+            || EXECUTABLE_EXTENSIONS
+                .iter()
+                .any(|extension| folder.join(format!("{name}.{extension}")).is_file())
+    })
+}
+
+/// Returns true if a package with the given name is locked, see
+/// [`locked_package_names`], or the prefix contains an executable with that
+/// name.
+pub(crate) fn is_installed(
+    prefix: &Path,
+    locked_packages: &HashSet<String>,
+    requirement: &str,
+) -> bool {
+    locked_packages.contains(&normalize(requirement)) || has_executable(prefix, requirement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_installed() {
+        let prefix = tempfile::tempdir().unwrap();
+        let prefix = prefix.path();
+        let bin = prefix.join(if cfg!(windows) { "Scripts" } else { "bin" });
+        fs_err::create_dir_all(&bin).unwrap();
+        let executable = if cfg!(windows) {
+            "pytest.exe"
+        } else {
+            "pytest"
+        };
+        fs_err::write(bin.join(executable), "").unwrap();
+        let locked_packages: HashSet<String> = ["python-dateutil", "Flask_Cors"]
+            .into_iter()
+            .map(normalize)
+            .collect();
+
+        assert!(is_installed(prefix, &locked_packages, "python-dateutil"));
+        assert!(is_installed(prefix, &locked_packages, "flask-cors"));
+        assert!(is_installed(prefix, &locked_packages, "pytest"));
+        assert!(!is_installed(prefix, &locked_packages, "python"));
+        assert!(!is_installed(prefix, &locked_packages, "ruff"));
+    }
+}
@@ -12,8 +12,8 @@ use pixi_core::{Workspace, workspace::Environment};
 use pixi_manifest::{
     EnvironmentName, Task, TaskName,
     task::{
-        ArgValues, CmdArgs, Custom, TaskArg, TemplateStringError, TypedArg, TypedDependency,
-        TypedDependencyArg,
+        ArgValues, CmdArgs, Custom, Execute, TaskArg, TemplateStringError, TypedArg,
+        TypedDependency, TypedDependencyArg,
     },
 };
 use thiserror::Error;
@@ -70,12 +70,18 @@ pub struct GraphDependency(
     TaskId,
     Option<Vec<TypedDependencyArg>>,
     Option<EnvironmentName>,
+    bool,
 );
 
 impl GraphDependency {
     pub fn task_id(&self) -> TaskId {
         self.0
     }
+
+    /// Whether the depending task still runs when this dependency fails.
+    pub fn continue_on_error(&self) -> bool {
+        self.3
+    }
 }
 
 /// A node in the [`TaskGraph`].
@@ -138,7 +144,7 @@ impl TaskNode<'_> {
         let mut cmd = self.task.as_single_command(context)?;
 
         let extra = self.extra_passthrough_args();
-        if !extra.is_empty() {
+        if !extra.is_empty() && !self.task.forwards_args() {
             // Pass each additional argument verbatim by wrapping it in single quotes
             let formatted_args = format!(" {}", self.format_additional_args());
             cmd = match cmd {
@@ -236,6 +242,15 @@ impl<'p> TaskGraph<'p> {
             (args, true)
         };
 
+        // A single argument with a wildcard, like `check:*`, runs all the tasks
+        // whose name matches it.
+        if prefer_executable == PreferExecutable::TaskFirst
+            && let [pattern] = args.as_slice()
+            && let Some(glob) = NameGlob::new(pattern)
+        {
+            return Self::from_task_glob(project, search_envs, &glob, skip_deps);
+        }
+
         if prefer_executable == PreferExecutable::TaskFirst
             && let Some(name) = args.first()
         {
@@ -270,6 +285,11 @@ impl<'p> TaskGraph<'p> {
 
                     let task_name = args.remove(0);
 
+                    let mut root_args = Some(
+                        args.iter()
+                            .map(|a| TypedDependencyArg::Positional(a.clone()))
+                            .collect(),
+                    );
                     let arg_values = if let Some(task_arguments) = task.args() {
                         // Extract any arguments after `--`. For tasks with typed args, `--`
                         // can appear anywhere after the task name (e.g. after providing all
@@ -283,22 +303,20 @@ impl<'p> TaskGraph<'p> {
                             vec![]
                         };
 
+                        let typed_dep_args =
+                            Self::parse_cli_args(&task_name, task_arguments, &args)?;
+
                         // Check if we don't have more arguments than the task expects
-                        if args.len() > task_arguments.len() {
+                        if typed_dep_args.len() > task_arguments.len() {
                             return Err(TaskGraphError::TooManyArguments(task_name.to_string()));
                         }
 
-                        // TODO: support named arguments from the CLI
-                        let typed_dep_args = args
-                            .iter()
-                            .map(|a| TypedDependencyArg::Positional(a.to_string()))
-                            .collect();
-
                         let merged = Self::merge_args(
                             &TaskName::from(task_name.clone()),
                             Some(&task_arguments.to_vec()),
                             Some(&typed_dep_args),
                         )?;
+                        root_args = Some(typed_dep_args);
 
                         // Combine with any extra args passed after `--`
                         Some(match merged {
@@ -347,11 +365,7 @@ impl<'p> TaskGraph<'p> {
                             args: arg_values,
                             dependencies: vec![],
                         },
-                        Some(
-                            args.iter()
-                                .map(|a| TypedDependencyArg::Positional(a.clone()))
-                                .collect(),
-                        ),
+                        root_args,
                     );
                 }
             }
@@ -402,6 +416,162 @@ impl<'p> TaskGraph<'p> {
         )
     }
 
+    /// Constructs a new instance of a [`TaskGraph`] from a command that is
+    /// passed on the command line with `pixi run --cmd`.
+    ///
+    /// Unlike a custom command the command is treated like the `cmd` of a
+    /// task in the manifest: it is rendered as a template, which makes
+    /// `{{ args }}` available, and `args` are appended to it otherwise. It
+    /// runs in the current directory.
+    pub fn from_inline_cmd<D: TaskDisambiguation<'p>>(
+        project: &'p Workspace,
+        search_envs: &SearchEnvironments<'p, D>,
+        cmd: String,
+        args: Vec<String>,
+    ) -> Result<Self, TaskGraphError> {
+        let run_environment = search_envs
+            .explicit_environment
+            .clone()
+            .unwrap_or_else(|| project.default_environment());
+        let args = match args.first().map(String::as_str) {
+            Some("--") => args[1..].to_vec(),
+            _ => args,
+        };
+
+        let task = Execute {
+            cmd: CmdArgs::Single(cmd.into()),
+            inputs: None,
+            outputs: None,
+            depends_on: vec![],
+            cwd: env::current_dir().ok(),
+            env: None,
+            dotenv: None,
+            default_environment: None,
+            description: None,
+            clean_env: false,
+            activation: true,
+            shell: None,
+            requires: None,
+            args: None,
+        };
+
+        Ok(Self {
+            project,
+            nodes: vec![TaskNode {
+                name: None,
+                task: Cow::Owned(task.into()),
+                run_environment,
+                args: Some(ArgValues::FreeFormArgs(args)),
+                dependencies: vec![],
+            }],
+        })
+    }
+
+    /// Constructs a new instance of a [`TaskGraph`] from all the tasks whose
+    /// name matches the glob.
+    fn from_task_glob<D: TaskDisambiguation<'p>>(
+        project: &'p Workspace,
+        search_envs: &SearchEnvironments<'p, D>,
+        glob: &NameGlob,
+        skip_deps: bool,
+    ) -> Result<Self, TaskGraphError> {
+        let task_names: HashSet<TaskName> = match &search_envs.explicit_environment {
+            Some(environment) => environment.get_filtered_tasks(),
+            None => project
+                .environments()
+                .iter()
+                .flat_map(|environment| environment.get_filtered_tasks())
+                .collect(),
+        };
+
+        let mut roots = Vec::new();
+        for task_name in task_names
+            .into_iter()
+            .filter(|name| glob.is_match(name.as_str()))
+            .sorted()
+        {
+            let (task_env, task) =
+                match search_envs.find_task(task_name.clone(), FindTaskSource::CmdArgs, None) {
+                    Err(FindTaskError::MissingTask(err)) => {
+                        return Err(TaskGraphError::MissingTask(err));
+                    }
+                    Err(FindTaskError::AmbiguousTask(err)) => {
+                        return Err(TaskGraphError::AmbiguousTask(err));
+                    }
+                    Ok(result) => result,
+                };
+            let run_environment = match search_envs.explicit_environment.clone() {
+                Some(explicit_env) if task_env.is_default() => explicit_env,
+                _ => task_env,
+            };
+            let args = Self::merge_args(
+                &task_name,
+                task.args().map(|args| args.to_vec()).as_ref(),
+                None,
+            )?;
+            roots.push((
+                TaskNode {
+                    name: Some(task_name),
+                    task: Cow::Borrowed(task),
+                    run_environment,
+                    args: Some(args),
+                    dependencies: vec![],
+                },
+                None,
+            ));
+        }
+
+        if roots.is_empty() {
+            return Err(TaskGraphError::NoMatchingTasks(glob.pattern().to_string()));
+        }
+
+        if skip_deps {
+            return Ok(Self {
+                project,
+                nodes: roots.into_iter().map(|(node, _)| node).collect(),
+            });
+        }
+
+        Self::from_roots(project, search_envs, roots)
+    }
+
+    /// Constructs a new instance of a [`TaskGraph`] with all the tasks of an
+    /// environment as roots, together with the tasks they depend on. A
+    /// dependency on a task of another environment runs in that environment.
+    pub fn from_environment<D: TaskDisambiguation<'p>>(
+        project: &'p Workspace,
+        search_envs: &SearchEnvironments<'p, D>,
+        environment: &Environment<'p>,
+    ) -> Result<Self, TaskGraphError> {
+        let tasks = environment
+            .tasks(environment.best_declared_platform())
+            .unwrap_or_default();
+        let roots = tasks
+            .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(name, task)| {
+                (
+                    TaskNode {
+                        name: Some(name.clone()),
+                        task: Cow::Borrowed(task),
+                        run_environment: environment.clone(),
+                        // Typed arguments without a default have no value here,
+                        // the dependencies are the same for every value.
+                        args: Self::merge_args(
+                            name,
+                            task.args().map(|args| args.to_vec()).as_ref(),
+                            None,
+                        )
+                        .ok(),
+                        dependencies: vec![],
+                    },
+                    None,
+                )
+            })
+            .collect();
+        Self::from_roots(project, search_envs, roots)
+    }
+
     /// Constructs a new instance of a [`TaskGraph`] from a root task.
     fn from_root<D: TaskDisambiguation<'p>>(
         project: &'p Workspace,
@@ -409,18 +579,31 @@ impl<'p> TaskGraph<'p> {
         root: TaskNode<'p>,
         root_args: Option<Vec<TypedDependencyArg>>,
     ) -> Result<Self, TaskGraphError> {
-        let mut task_name_with_args_to_node: HashMap<TypedDependency, TaskId> =
-            HashMap::from_iter(root.name.clone().into_iter().map(|name| {
-                (
+        Self::from_roots(project, search_environments, vec![(root, root_args)])
+    }
+
+    /// Constructs a new instance of a [`TaskGraph`] from one or more root
+    /// tasks and the arguments they were invoked with.
+    fn from_roots<D: TaskDisambiguation<'p>>(
+        project: &'p Workspace,
+        search_environments: &SearchEnvironments<'p, D>,
+        roots: Vec<(TaskNode<'p>, Option<Vec<TypedDependencyArg>>)>,
+    ) -> Result<Self, TaskGraphError> {
+        let mut task_name_with_args_to_node: HashMap<TypedDependency, TaskId> = HashMap::new();
+        let mut nodes = Vec::with_capacity(roots.len());
+        for (root, root_args) in roots {
+            if let Some(name) = root.name.clone() {
+                task_name_with_args_to_node.insert(
                     TypedDependency {
                         task_name: name,
-                        args: root_args.clone(),
+                        args: root_args,
                         environment: None,
                     },
-                    TaskId(0),
-                )
-            }));
-        let mut nodes = vec![root];
+                    TaskId(nodes.len()),
+                );
+            }
+            nodes.push(root);
+        }
 
         // Iterate over all the nodes in the graph and add them to the graph.
         let mut next_node_to_visit = 0;
@@ -429,7 +612,8 @@ impl<'p> TaskGraph<'p> {
             let dependencies = Vec::from_iter(node.task.depends_on().iter().cloned());
 
             // Collect all dependency data before modifying nodes
-            let mut deps_to_process: Vec<(TypedDependency, Environment<'p>, &Task)> = Vec::new();
+            let mut deps_to_process: Vec<(TypedDependency, bool, Environment<'p>, &Task)> =
+                Vec::new();
 
             // Iterate over all the dependencies of the node and add them to the graph.
             let mut node_dependencies = Vec::with_capacity(dependencies.len());
@@ -441,6 +625,7 @@ impl<'p> TaskGraph<'p> {
                     args: node.args.as_ref(),
                     init_cwd: None,
                 };
+                let continue_on_error = dependency.continue_on_error;
                 let dependency = TypedDependency::from_dependency(&dependency, &context)?;
                 // Check if we visited this node before already.
                 if let Some(&task_id) = task_name_with_args_to_node.get(&dependency) {
@@ -448,6 +633,7 @@ impl<'p> TaskGraph<'p> {
                         task_id,
                         dependency.args.clone(),
                         dependency.environment.clone(),
+                        continue_on_error,
                     ));
                     continue;
                 }
@@ -482,11 +668,11 @@ impl<'p> TaskGraph<'p> {
                 };
 
                 // Store the dependency data for processing later
-                deps_to_process.push((dependency, task_env, task_dependency));
+                deps_to_process.push((dependency, continue_on_error, task_env, task_dependency));
             }
 
             // Process all dependencies after collecting them
-            for (dependency, task_env, task_dependency) in deps_to_process {
+            for (dependency, continue_on_error, task_env, task_dependency) in deps_to_process {
                 // Add the node to the graph
                 let task_id = TaskId(nodes.len());
                 nodes.push(TaskNode {
@@ -509,6 +695,7 @@ impl<'p> TaskGraph<'p> {
                     task_id,
                     dependency.args.clone(),
                     dependency.environment.clone(),
+                    continue_on_error,
                 ));
             }
 
@@ -519,6 +706,47 @@ impl<'p> TaskGraph<'p> {
         Ok(Self { project, nodes })
     }
 
+    /// Parses the arguments of a task with typed arguments from the command
+    /// line. An argument is passed by name as `--<name> <value>` or
+    /// `--<name>=<value>`, any other value is a positional argument.
+    fn parse_cli_args(
+        task_name: &str,
+        task_arguments: &[TaskArg],
+        args: &[String],
+    ) -> Result<Vec<TypedDependencyArg>, TaskGraphError> {
+        let mut parsed = Vec::with_capacity(args.len());
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let named = arg.strip_prefix("--").and_then(|flag| {
+                let (name, value) = match flag.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (flag, None),
+                };
+                task_arguments
+                    .iter()
+                    .find(|task_arg| task_arg.name.as_str() == name)
+                    .map(|task_arg| (task_arg, value))
+            });
+            let Some((task_arg, value)) = named else {
+                parsed.push(TypedDependencyArg::Positional(arg.clone()));
+                continue;
+            };
+            let value = match value {
+                Some(value) => value.to_string(),
+                None => args.next().cloned().ok_or_else(|| MissingArgError {
+                    arg: task_arg.name.as_str().to_string(),
+                    task: task_name.to_string(),
+                    choices: task_arg.choices.as_ref().map(|c| c.join(", ")),
+                })?,
+            };
+            parsed.push(TypedDependencyArg::Named(
+                task_arg.name.as_str().to_string(),
+                value,
+            ));
+        }
+        Ok(parsed)
+    }
+
     fn merge_args(
         task_name: &TaskName,
         task_arguments: Option<&Vec<TaskArg>>,
@@ -673,6 +901,19 @@ impl<'p> TaskGraph<'p> {
             order.push(id);
         }
     }
+
+    /// Returns true if every task that depends on the given task marked that
+    /// dependency as `continue-on-error`, so a failure of the task should not
+    /// stop the tasks that depend on it.
+    pub fn failure_is_tolerated(&self, id: TaskId) -> bool {
+        let mut dependents = self
+            .nodes
+            .iter()
+            .flat_map(|node| node.dependencies.iter())
+            .filter(|dependency| dependency.task_id() == id)
+            .peekable();
+        dependents.peek().is_some() && dependents.all(GraphDependency::continue_on_error)
+    }
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -710,6 +951,10 @@ pub enum TaskGraphError {
 
     #[error(transparent)]
     InvalidArgValue(#[from] InvalidArgValueError),
+
+    #[error("no tasks match the pattern '{0}'")]
+    #[diagnostic(help("run `pixi task list` to see the available tasks"))]
+    NoMatchingTasks(String),
 }
 
 #[cfg(test)]
@@ -1316,6 +1561,35 @@ mod test {
         assert_eq!(commands, vec!["echo debug"]);
     }
 
+    #[test]
+    fn test_named_arg_overrides_default() {
+        let workspace_str = r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64"]
+
+        [tasks.build]
+        cmd = "cargo build --profile {{ target }}"
+        args = [{ arg = "target", default = "debug" }]
+    "#;
+        let commands = TaskGraphTest::new(workspace_str, &["build"]).commands_in_order();
+        assert_eq!(commands, vec!["cargo build --profile debug"]);
+
+        let commands = TaskGraphTest::new(workspace_str, &["build", "--target", "release"])
+            .commands_in_order();
+        assert_eq!(commands, vec!["cargo build --profile release"]);
+
+        let commands =
+            TaskGraphTest::new(workspace_str, &["build", "--target=release"]).commands_in_order();
+        assert_eq!(commands, vec!["cargo build --profile release"]);
+
+        let error = TaskGraphTest::new(workspace_str, &["build", "--target"]).expect_error();
+        assert_matches!(error, TaskGraphError::MissingArgument(err) => {
+            assert_eq!(err.arg, "target");
+        });
+    }
+
     #[test]
     fn test_prefer_executable_always() {
         let workspace_str = r#"
@@ -1409,6 +1683,45 @@ mod test {
         assert_eq!(commands, vec!["echo 'arg1' '--' 'arg2'"]);
     }
 
+    #[test]
+    fn test_args_placeholder() {
+        let workspace_str = r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64"]
+
+        [tasks]
+        test = "pytest {{ args }} tests/"
+    "#;
+        let run_args = &["test", "-k", "foo bar"];
+        let commands = TaskGraphTest::new(workspace_str, run_args).commands_in_order();
+        assert_eq!(commands, vec!["pytest '-k' 'foo bar' tests/"]);
+
+        // Without arguments the placeholder is left empty.
+        let commands = TaskGraphTest::new(workspace_str, &["test"]).commands_in_order();
+        assert_eq!(commands, vec!["pytest  tests/"]);
+    }
+
+    #[test]
+    fn test_args_placeholder_in_cmd_list() {
+        let workspace_str = r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64"]
+
+        [tasks]
+        test = { cmd = ["pytest", "{{ args }}", "tests/"] }
+    "#;
+        let run_args = &["test", "-k", "foo bar"];
+        let commands = TaskGraphTest::new(workspace_str, run_args).commands_in_order();
+        assert_eq!(commands, vec![r#"pytest -k "foo bar" tests/"#]);
+
+        let commands = TaskGraphTest::new(workspace_str, &["test"]).commands_in_order();
+        assert_eq!(commands, vec!["pytest tests/"]);
+    }
+
     #[test]
     fn test_double_dash_first_with_free_form_args() {
         // Task has NO typed args. `--` is the first arg after the task name, so
@@ -1479,4 +1792,73 @@ mod test {
         let commands = TaskGraphTest::new(workspace_str, run_args).commands_in_order();
         assert_eq!(commands, vec!["echo '--verbose'"]);
     }
+
+    #[test]
+    fn test_glob_runs_all_matching_tasks() {
+        let workspace_str = r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64", "osx-64", "win-64", "osx-arm64"]
+
+        [tasks]
+        prepare = "echo prepare"
+        "check:a" = { cmd = "echo a", depends-on = ["check:b"] }
+        "check:b" = { cmd = "echo b", depends-on = ["prepare"] }
+        other = "echo other"
+    "#;
+        let commands = TaskGraphTest::new(workspace_str, &["check:*"]).commands_in_order();
+        assert_eq!(commands, vec!["echo prepare", "echo b", "echo a"]);
+
+        let commands = TaskGraphTest::new(workspace_str, &["check:*"])
+            .skip_deps()
+            .commands_in_order();
+        assert_eq!(commands, vec!["echo a", "echo b"]);
+
+        assert_matches!(
+            TaskGraphTest::new(workspace_str, &["lint:*"]).expect_error(),
+            TaskGraphError::NoMatchingTasks(pattern) if pattern == "lint:*"
+        );
+    }
+
+    #[test]
+    fn test_inline_cmd_is_templated() {
+        let project = Workspace::from_str(
+            Path::new("pixi.toml"),
+            r#"
+        [workspace]
+        name = "pixi"
+        channels = []
+        platforms = ["linux-64", "osx-64", "win-64", "osx-arm64"]
+        "#,
+        )
+        .unwrap();
+        let search_envs = SearchEnvironments::from_opt_env(&project, None, None);
+
+        let render = |cmd: &str, args: &[&str]| {
+            let graph = TaskGraph::from_inline_cmd(
+                &project,
+                &search_envs,
+                cmd.to_string(),
+                args.iter().map(|arg| arg.to_string()).collect(),
+            )
+            .unwrap();
+            let task = &graph[graph.topological_order()[0]];
+            let context = pixi_manifest::task::TaskRenderContext {
+                platform: task.run_environment.best_declared_platform(),
+                environment_name: task.run_environment.name(),
+                manifest_path: Some(&project.workspace.provenance.path),
+                args: task.args.as_ref(),
+                init_cwd: None,
+            };
+            task.full_command(&context).unwrap().unwrap()
+        };
+
+        assert_eq!(render("echo {{ args }}", &["hello"]), "echo 'hello'");
+        assert_eq!(render("echo", &["--", "hello"]), "echo 'hello'");
+        assert_eq!(
+            render("echo {{ pixi.environment.name }}", &[]),
+            "echo default"
+        );
+    }
 }
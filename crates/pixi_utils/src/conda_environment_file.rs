@@ -50,6 +50,10 @@ impl YamlParseError {
     }
 }
 
+/// The top-level fields of a conda environment file that are understood when
+/// importing it. Other fields are ignored with a warning.
+const SUPPORTED_FIELDS: &[&str] = &["name", "channels", "dependencies", "variables"];
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct CondaEnvFile {
     #[serde(default)]
@@ -112,6 +116,19 @@ impl CondaEnvFile {
             s.push_str(&line);
             s.push('\n');
         }
+        if let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str(&s) {
+            for field in mapping
+                .keys()
+                .filter_map(serde_yaml::Value::as_str)
+                .filter(|field| !SUPPORTED_FIELDS.contains(field))
+            {
+                tracing::warn!(
+                    "Ignoring unsupported field '{}' in '{}'",
+                    field,
+                    path.display()
+                );
+            }
+        }
         let env_file: CondaEnvFile = match serde_yaml::from_str(&s) {
             Ok(env_file) => env_file,
             Err(e) => {
@@ -170,6 +187,16 @@ fn parse_dependencies(deps: Vec<CondaEnvDep>) -> miette::Result<ParsedDependenci
                 let pip = pip.unwrap_or_default();
                 pip_deps.extend(
                     pip.iter()
+                        .filter(|dep| {
+                            // Options like `--index-url` or `-r requirements.txt` are not
+                            // requirements themselves.
+                            let is_option = dep.trim_start().starts_with('-');
+                            if is_option {
+                                tracing::warn!("Skipping unsupported pip option: \"{}\"", dep);
+                                tracing::warn!("Please add it to the workspace manually");
+                            }
+                            !is_option
+                        })
                         .map(|dep| {
                             pep508_rs::Requirement::from_str(dep)
                                 .into_diagnostic()
@@ -370,6 +397,39 @@ mod tests {
         assert_eq!(vars, empty_map);
     }
 
+    #[test]
+    fn test_parse_conda_env_file_with_unsupported_fields() {
+        let example_conda_env_file = r#"
+        name: pixi_example_project
+        prefix: /opt/envs/pixi_example_project
+        channels:
+          - conda-forge
+        dependencies:
+          - numpy=1.26
+          - pip:
+            - --extra-index-url https://example.com/simple
+            - requests
+        "#;
+
+        let f = tempfile::NamedTempFile::new().unwrap();
+        let path = f.path();
+        let mut file = fs_err::File::create(path).unwrap();
+        file.write_all(example_conda_env_file.as_bytes()).unwrap();
+
+        let conda_env_file_data = CondaEnvFile::from_path(path).unwrap();
+        let (conda_deps, pip_deps, _) =
+            parse_dependencies(conda_env_file_data.dependencies().clone()).unwrap();
+
+        assert_eq!(
+            conda_deps,
+            vec![MatchSpec::from_str("numpy=1.26", Strict).unwrap()]
+        );
+        assert_eq!(
+            pip_deps,
+            vec![pep508_rs::Requirement::from_str("requests").unwrap()]
+        );
+    }
+
     #[test]
     fn test_parse_conda_env_file_with_variables() {
         let example_conda_env_file = r#"
@@ -0,0 +1,177 @@
+//! Parsing of `.env` files.
+//!
+//! Every line of a `.env` file is either empty, a comment starting with `#`
+//! or an assignment `KEY=value`, optionally prefixed with `export`. Values can
+//! be
+//!
+//! - unquoted, surrounding whitespace and a trailing ` # comment` are removed,
+//! - single quoted, the value is taken literally,
+//! - double quoted, `\n`, `\t`, `\"`, `\\` and `\$` are unescaped and the value
+//!   may span multiple lines.
+//!
+//! Variables in values are not expanded.
+
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum DotenvError {
+    #[error("failed to read '{}'", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid line {line} in '{}': {reason}", path.display())]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        reason: &'static str,
+    },
+}
+
+/// Reads the variables of the `.env` file at the given path.
+pub fn read_dotenv(path: &Path) -> Result<IndexMap<String, String>, DotenvError> {
+    let contents = fs_err::read_to_string(path).map_err(|source| DotenvError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_dotenv(&contents).map_err(|(line, reason)| DotenvError::Parse {
+        path: path.to_path_buf(),
+        line,
+        reason,
+    })
+}
+
+/// Parses the contents of a `.env` file. On failure the line number and the
+/// reason are returned.
+pub fn parse_dotenv(contents: &str) -> Result<IndexMap<String, String>, (usize, &'static str)> {
+    let mut variables = IndexMap::new();
+    let mut lines = contents.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with([' ', '\t']))
+            .map_or(line, str::trim_start);
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err((line_number, "expected `KEY=value`"));
+        };
+        let key = key.trim_end();
+        if key.is_empty()
+            || key.starts_with(|c: char| c.is_ascii_digit())
+            || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err((line_number, "invalid variable name"));
+        }
+
+        let value = value.trim_start();
+        let value = if let Some(rest) = value.strip_prefix('\'') {
+            let Some((value, _)) = rest.split_once('\'') else {
+                return Err((line_number, "unterminated single quote"));
+            };
+            value.to_string()
+        } else if let Some(rest) = value.strip_prefix('"') {
+            // Double quoted values continue on the next lines until the closing
+            // quote.
+            let mut raw = rest.to_string();
+            loop {
+                if let Some(value) = double_quoted(&raw) {
+                    break value;
+                }
+                let Some((_, next)) = lines.next() else {
+                    return Err((line_number, "unterminated double quote"));
+                };
+                raw.push('\n');
+                raw.push_str(next);
+            }
+        } else {
+            let value = match value.find(" #") {
+                Some(comment) => &value[..comment],
+                None => value,
+            };
+            value.trim_end().to_string()
+        };
+        variables.insert(key.to_string(), value);
+    }
+    Ok(variables)
+}
+
+/// Unescapes a double quoted value, `raw` starts after the opening quote.
+/// Returns `None` if the closing quote is missing.
+fn double_quoted(raw: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(c @ ('"' | '\\' | '$')) => value.push(c),
+                Some(c) => {
+                    value.push('\\');
+                    value.push(c);
+                }
+                None => value.push('\\'),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let variables = parse_dotenv(
+            r#"
+# A comment
+FOO=bar
+export EXPORTED = value # trailing comment
+SINGLE='literal $HOME \n'
+DOUBLE="line\nbreak \"quoted\""
+MULTI="first
+second"
+EMPTY=
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            variables.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("FOO".to_string(), "bar".to_string()),
+                ("EXPORTED".to_string(), "value".to_string()),
+                ("SINGLE".to_string(), "literal $HOME \\n".to_string()),
+                ("DOUBLE".to_string(), "line\nbreak \"quoted\"".to_string()),
+                ("MULTI".to_string(), "first\nsecond".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_errors() {
+        assert_eq!(
+            parse_dotenv("FOO=bar\nno assignment"),
+            Err((2, "expected `KEY=value`"))
+        );
+        assert_eq!(parse_dotenv("1FOO=bar"), Err((1, "invalid variable name")));
+        assert_eq!(
+            parse_dotenv("FOO=\"open"),
+            Err((1, "unterminated double quote"))
+        );
+    }
+}
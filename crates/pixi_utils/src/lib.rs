@@ -1,6 +1,7 @@
 pub mod atomic_write;
 pub mod cache;
 pub mod conda_environment_file;
+pub mod dotenv;
 mod environment_fingerprint;
 mod environment_lock;
 pub mod indicatif;
@@ -8,6 +9,7 @@ pub mod prefix;
 mod prefix_guard;
 pub mod reproducible;
 pub mod reqwest;
+pub mod retry;
 pub mod rlimit;
 pub mod tls;
 pub mod variants;
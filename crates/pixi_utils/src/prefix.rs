@@ -1,13 +1,14 @@
 use crate::{is_binary_folder, strip_executable_extension};
 use itertools::Itertools;
 use miette::{Context, Diagnostic, IntoDiagnostic};
-use rattler_conda_types::{PackageName, Platform, PrefixRecord};
+use pixi_consts::consts;
+use rattler_conda_types::{PackageName, Platform, PrefixRecord, prefix_record::PathType};
 use rattler_shell::{
     activation::{ActivationVariables, Activator},
     shell::ShellEnum,
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ffi::OsStr,
     path::{Path, PathBuf},
 };
@@ -23,6 +24,9 @@ pub enum PrefixError {
     #[error("failed to find the designated package '{0}' in the prefix: '{1}'")]
     DesignatedPackageNotFound(String, PathBuf),
 
+    #[error("failed to remove '{1}' from the prefix")]
+    PackageRemovalError(#[source] std::io::Error, PathBuf),
+
     #[error("executing prefix related task failed")]
     #[diagnostic(help("try running the command again, or `pixi clean` to reset the environment"))]
     JoinError,
@@ -46,6 +50,17 @@ impl Prefix {
         &self.root
     }
 
+    /// Returns the `conda-meta` directory that holds the records of the
+    /// installed conda packages.
+    pub fn conda_meta_dir(&self) -> PathBuf {
+        self.root.join(consts::CONDA_META_DIR)
+    }
+
+    /// Returns true if packages have been installed into the prefix.
+    pub fn is_installed(&self) -> bool {
+        self.conda_meta_dir().is_dir()
+    }
+
     /// Runs the activation scripts of the prefix and returns the environment
     /// variables that were modified as part of this process.
     pub async fn run_activation(&self) -> miette::Result<HashMap<String, String>> {
@@ -119,6 +134,129 @@ impl Prefix {
         is_executable::is_executable(absolute_path)
     }
 
+    /// Checks the files of an installed package against the metadata that was
+    /// recorded in its `conda-meta` record when the package was installed.
+    ///
+    /// Every recorded file must exist. Where the metadata contains a size or
+    /// a hash of the file, these must match as well.
+    pub fn verify_package(&self, record: &PrefixRecord) -> PackageVerification {
+        let mut verification = PackageVerification::default();
+        for entry in &record.paths_data.paths {
+            let path = self.root.join(&entry.relative_path);
+            let Ok(metadata) = fs_err::symlink_metadata(&path) else {
+                verification.missing.push(entry.relative_path.clone());
+                continue;
+            };
+
+            // Only regular files have a size and hash that can be compared.
+            if entry.path_type != PathType::HardLink || !metadata.is_file() {
+                continue;
+            }
+
+            // Files that contained a prefix placeholder were rewritten during
+            // the installation, only the hash computed after that is usable.
+            let has_placeholder = entry.prefix_placeholder.is_some();
+            let size_differs = !has_placeholder
+                && entry
+                    .size_in_bytes
+                    .is_some_and(|size| size != metadata.len());
+            let expected_hash = entry
+                .sha256_in_prefix
+                .or(entry.sha256.filter(|_| !has_placeholder));
+            let hash_differs = !size_differs
+                && expected_hash.is_some_and(|expected| {
+                    !rattler_digest::compute_file_digest::<rattler_digest::Sha256>(&path)
+                        .is_ok_and(|actual| actual == expected)
+                });
+            if size_differs || hash_differs {
+                verification.modified.push(entry.relative_path.clone());
+            }
+        }
+        verification
+    }
+
+    /// Returns the size on disk of the files of every given package.
+    ///
+    /// A file that is hard linked to another file in the prefix is only
+    /// counted once, for the first package that contains it.
+    pub fn disk_usage(&self, records: &[PrefixRecord]) -> Vec<PackageDiskUsage> {
+        let mut seen = HashSet::new();
+        records
+            .iter()
+            .map(|record| {
+                let size_in_bytes = record
+                    .files
+                    .iter()
+                    .filter_map(|relative_path| {
+                        let path = self.root.join(relative_path);
+                        let metadata = fs_err::symlink_metadata(&path).ok()?;
+                        if !metadata.is_file() {
+                            return None;
+                        }
+                        seen.insert(file_id(&metadata).unwrap_or_else(|| FileId::Path(path)))
+                            .then_some(metadata.len())
+                    })
+                    .sum();
+                PackageDiskUsage {
+                    name: record.repodata_record.package_record.name.clone(),
+                    size_in_bytes,
+                }
+            })
+            .collect()
+    }
+
+    /// Removes the files and the `conda-meta` records of the given packages
+    /// from the prefix.
+    ///
+    /// Files that are also recorded by one of the `kept` packages are left in
+    /// place. Directories that are empty after removing the files are removed
+    /// as well.
+    pub fn remove_packages(
+        &self,
+        removed: &[PrefixRecord],
+        kept: &[PrefixRecord],
+    ) -> Result<(), PrefixError> {
+        let shared: HashSet<&Path> = kept
+            .iter()
+            .flat_map(|record| record.files.iter().map(PathBuf::as_path))
+            .collect();
+
+        let mut directories = HashSet::new();
+        for record in removed {
+            for relative_path in record
+                .files
+                .iter()
+                .filter(|path| !shared.contains(path.as_path()))
+            {
+                let path = self.root.join(relative_path);
+                remove_if_exists(fs_err::remove_file(&path), &path)?;
+                directories.extend(relative_path.ancestors().skip(1).map(Path::to_path_buf));
+            }
+
+            let package_record = &record.repodata_record.package_record;
+            let meta_path = self.conda_meta_dir().join(format!(
+                "{}-{}-{}.json",
+                package_record.name.as_normalized(),
+                package_record.version,
+                package_record.build
+            ));
+            remove_if_exists(fs_err::remove_file(&meta_path), &meta_path)?;
+        }
+
+        // Remove the deepest directories first, so that their parents can be
+        // empty once their turn comes. Directories that still contain files
+        // are kept.
+        for directory in directories
+            .into_iter()
+            .filter(|directory| !directory.as_os_str().is_empty())
+            .sorted_by_key(|directory| std::cmp::Reverse(directory.components().count()))
+        {
+            let _ = fs_err::remove_dir(self.root.join(directory));
+        }
+
+        Ok(())
+    }
+
     /// Find the designated package in the given [`Prefix`]
     ///
     /// # Returns
@@ -139,6 +277,63 @@ impl Prefix {
     }
 }
 
+/// Treats a file that was already removed as successfully removed.
+fn remove_if_exists(result: std::io::Result<()>, path: &Path) -> Result<(), PrefixError> {
+    match result {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(PrefixError::PackageRemovalError(err, path.to_path_buf()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The result of [`Prefix::verify_package`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PackageVerification {
+    /// Files that are recorded for the package but are missing from the
+    /// prefix.
+    pub missing: Vec<PathBuf>,
+
+    /// Files whose size or hash differs from the recorded metadata.
+    pub modified: Vec<PathBuf>,
+}
+
+impl PackageVerification {
+    /// Returns true if all the files of the package are intact.
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// The result of [`Prefix::disk_usage`] for a single package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDiskUsage {
+    /// The name of the package.
+    pub name: PackageName,
+
+    /// The combined size of the files of the package.
+    pub size_in_bytes: u64,
+}
+
+/// Identifies a file on disk, such that hard links to the same file are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FileId {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    Inode(u64, u64),
+    Path(PathBuf),
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileId::Inode(metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<FileId> {
+    None
+}
+
 #[derive(Debug, Clone)]
 pub struct Executable {
     pub name: String,
@@ -150,3 +345,56 @@ impl Executable {
         Self { name, path }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rattler_conda_types::{
+        PackageRecord, RepoDataRecord, VersionWithSource, package::DistArchiveIdentifier,
+    };
+    use url::Url;
+
+    use super::*;
+
+    fn prefix_record(name: &str, files: &[&str]) -> PrefixRecord {
+        let package_record = PackageRecord::new(
+            name.parse().unwrap(),
+            VersionWithSource::from_str("1.0").unwrap(),
+            "0".to_string(),
+        );
+        let repodata_record = RepoDataRecord {
+            package_record,
+            identifier: DistArchiveIdentifier::try_from_filename(&format!("{name}-1.0-0.conda"))
+                .unwrap(),
+            url: Url::parse(&format!("https://example.com/noarch/{name}-1.0-0.conda")).unwrap(),
+            channel: None,
+        };
+        let mut record = PrefixRecord::from_repodata_record(repodata_record, vec![]);
+        record.files = files.iter().map(PathBuf::from).collect();
+        record
+    }
+
+    #[test]
+    fn test_remove_packages_keeps_shared_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = Prefix::new(dir.path());
+        for file in ["bin/removed", "share/shared", "share/kept"] {
+            let path = dir.path().join(file);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, "").unwrap();
+        }
+        fs_err::create_dir_all(prefix.conda_meta_dir()).unwrap();
+        let meta_path = prefix.conda_meta_dir().join("removed-1.0-0.json");
+        fs_err::write(&meta_path, "{}").unwrap();
+
+        let removed = prefix_record("removed", &["bin/removed", "share/shared"]);
+        let kept = prefix_record("kept", &["share/shared", "share/kept"]);
+        prefix.remove_packages(&[removed], &[kept]).unwrap();
+
+        assert!(!dir.path().join("bin").exists());
+        assert!(!meta_path.exists());
+        assert!(dir.path().join("share/shared").is_file());
+        assert!(dir.path().join("share/kept").is_file());
+    }
+}
@@ -15,16 +15,15 @@ use rattler_networking::{
 };
 use reqwest::Client;
 use reqwest_middleware::{ClientWithMiddleware, Middleware};
-use reqwest_retry::RetryTransientMiddleware;
 use retry_policies::policies::ExponentialBackoff;
 
+use crate::retry::RetryMiddleware;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::tls::Certificates;
 
-/// The default retry policy employed by pixi.
-/// TODO: At some point we might want to make this configurable.
-pub fn default_retry_policy() -> ExponentialBackoff {
-    ExponentialBackoff::builder().build_with_max_retries(3)
+/// The retry policy configured by the user, see [`Config::max_retries`].
+pub fn retry_policy(config: &Config) -> ExponentialBackoff {
+    ExponentialBackoff::builder().build_with_max_retries(config.max_retries())
 }
 
 pub fn mirror_middleware(config: &Config) -> MirrorMiddleware {
@@ -211,9 +210,7 @@ pub fn build_reqwest_middleware_stack(
     // returns a server error (e.g. 500), the retry will go through the mirror
     // middleware again, which will then select a different mirror due to the
     // recorded failure.
-    result.push(Arc::new(RetryTransientMiddleware::new_with_policy(
-        default_retry_policy(),
-    )));
+    result.push(Arc::new(RetryMiddleware::new(retry_policy(config))));
 
     // The mirror middleware is only needed when mirrors are configured.
     if !config.mirror_map().is_empty() {
@@ -0,0 +1,252 @@
+//! A middleware that retries requests that fail with a transient error.
+//!
+//! This is similar to [`reqwest_retry::RetryTransientMiddleware`], but it
+//! honours the `Retry-After` header of `429` and `503` responses and reports
+//! the number of attempts when a request keeps failing. Only requests with an
+//! idempotent method are retried, so that e.g. an upload never runs twice.
+
+use std::time::{Duration, SystemTime};
+
+use http::Extensions;
+use reqwest::{Request, Response, StatusCode, header::RETRY_AFTER};
+use reqwest_middleware::{Middleware, Next};
+use reqwest_retry::{Retryable, default_on_request_failure, default_on_request_success};
+use retry_policies::{RetryDecision, RetryPolicy, policies::ExponentialBackoff};
+
+/// The longest time a `Retry-After` header can make us wait before the next
+/// attempt. Servers that ask for more are retried after this time.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Retries requests that fail with a transient error, see the module
+/// documentation.
+pub struct RetryMiddleware {
+    policy: ExponentialBackoff,
+}
+
+impl RetryMiddleware {
+    pub fn new(policy: ExponentialBackoff) -> Self {
+        Self { policy }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if !req.method().is_idempotent() {
+            return next.run(req, extensions).await;
+        }
+
+        let start_time = SystemTime::now();
+        let mut past_retries = 0;
+        loop {
+            // Requests with a streaming body cannot be cloned, and thus not retried.
+            let Some(attempt) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+            let result = next.clone().run(attempt, extensions).await;
+
+            let retryable = match &result {
+                Ok(response) => default_on_request_success(response),
+                Err(err) => default_on_request_failure(err),
+            };
+            if retryable != Some(Retryable::Transient) {
+                return result;
+            }
+
+            let RetryDecision::Retry { execute_after } =
+                self.policy.should_retry(start_time, past_retries)
+            else {
+                return exhausted(result, past_retries + 1);
+            };
+            let backoff = execute_after
+                .duration_since(SystemTime::now())
+                .unwrap_or_default();
+            let wait = result
+                .as_ref()
+                .ok()
+                .and_then(retry_after)
+                .map_or(backoff, |retry_after| retry_after.min(MAX_RETRY_AFTER));
+
+            tracing::debug!(
+                "retrying request to {} in {wait:?} (attempt {})",
+                req.url(),
+                past_retries + 2,
+            );
+            tokio::time::sleep(wait).await;
+            past_retries += 1;
+        }
+    }
+}
+
+/// Adds the number of attempts to the result of a request that kept failing.
+fn exhausted(
+    result: reqwest_middleware::Result<Response>,
+    attempts: u32,
+) -> reqwest_middleware::Result<Response> {
+    if attempts <= 1 {
+        return result;
+    }
+    match result {
+        Ok(response) => {
+            tracing::warn!(
+                "request to {} failed with status {} after {attempts} attempts",
+                response.url(),
+                response.status()
+            );
+            Ok(response)
+        }
+        Err(err) => {
+            let message = match err.url() {
+                Some(url) => format!("request to {url} failed after {attempts} attempts"),
+                None => format!("request failed after {attempts} attempts"),
+            };
+            Err(reqwest_middleware::Error::Middleware(
+                anyhow::Error::new(err).context(message),
+            ))
+        }
+    }
+}
+
+/// Returns how long the server asked us to wait through the `Retry-After`
+/// header, which is either a number of seconds or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        SystemTime::from(date)
+            .duration_since(SystemTime::now())
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    use reqwest_middleware::ClientBuilder;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Starts a server that answers the first `failures` requests with the
+    /// given response and every later request with `200 OK`. Returns its url
+    /// and the number of requests it received.
+    async fn flaky_server(failures: u32, failure: &'static str) -> (String, Arc<AtomicU32>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicU32::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = [0; 1024];
+                let _ = stream.read(&mut buf).await;
+                let response = if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    failure
+                } else {
+                    "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok"
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, requests)
+    }
+
+    fn client(max_retries: u32) -> reqwest_middleware::ClientWithMiddleware {
+        let policy = ExponentialBackoff::builder()
+            .retry_bounds(Duration::from_millis(1), Duration::from_millis(10))
+            .build_with_max_retries(max_retries);
+        ClientBuilder::new(reqwest::Client::new())
+            .with(RetryMiddleware::new(policy))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let (url, requests) = flaky_server(
+            2,
+            "HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        )
+        .await;
+        let response = client(3).get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_honoured() {
+        let (url, requests) = flaky_server(
+            1,
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 1\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        )
+        .await;
+        let start = std::time::Instant::now();
+        let response = client(3).get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        // The backoff of the policy is at most 10ms, so the wait comes from the
+        // header.
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_max_retries_is_respected() {
+        let (url, requests) = flaky_server(
+            u32::MAX,
+            "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        )
+        .await;
+        let response = client(2).get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_retried() {
+        let (url, requests) = flaky_server(
+            u32::MAX,
+            "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        )
+        .await;
+        let response = client(3).post(&url).body("data").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_reports_attempts() {
+        // Nothing listens on the port once the listener is dropped.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let err = client(2).get(&url).send().await.unwrap_err();
+        assert!(
+            err.to_string().contains("failed after 3 attempts"),
+            "unexpected error: {err}"
+        );
+    }
+}
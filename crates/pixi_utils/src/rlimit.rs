@@ -3,16 +3,32 @@
 #[cfg(not(target_os = "windows"))]
 pub const DESIRED_RLIMIT_NOFILE: u64 = 2048;
 
+/// The number of file descriptors that are kept free for everything that is
+/// not a concurrent download or install, e.g. the cache locks, the lock file
+/// and the logs.
+#[cfg(not(target_os = "windows"))]
+const RESERVED_FILE_DESCRIPTORS: u64 = 64;
+
+/// The number of file descriptors a single concurrent download or install is
+/// assumed to hold open at the same time.
+#[cfg(not(target_os = "windows"))]
+const FILE_DESCRIPTORS_PER_TASK: u64 = 4;
+
 /// Attempt to increase the RLIMIT_NOFILE resource limit to the desired value
 /// for pixi. The desired value is defined by the `DESIRED_RLIMIT_NOFILE`
 /// constant and should suffice for most use cases.
+///
+/// If the hard limit is lower than the desired value, the soft limit is raised
+/// up to the hard limit instead. Returns the resulting soft limit, or `None`
+/// if it could not be determined.
 #[cfg(not(target_os = "windows"))]
-pub fn try_increase_rlimit_to_sensible() {
-    static INIT: std::sync::Once = std::sync::Once::new();
-    INIT.call_once(
+pub fn try_increase_rlimit_to_sensible() -> Option<u64> {
+    static LIMIT: std::sync::OnceLock<Option<u64>> = std::sync::OnceLock::new();
+    *LIMIT.get_or_init(
         || match rlimit::increase_nofile_limit(DESIRED_RLIMIT_NOFILE) {
             Ok(DESIRED_RLIMIT_NOFILE) => {
                 tracing::debug!("Increased RLIMIT_NOFILE to {}", DESIRED_RLIMIT_NOFILE);
+                Some(DESIRED_RLIMIT_NOFILE)
             }
             Ok(lim) => {
                 if lim < DESIRED_RLIMIT_NOFILE {
@@ -28,19 +44,77 @@ pub fn try_increase_rlimit_to_sensible() {
                         lim
                     );
                 }
+                Some(lim)
             }
             Err(err) => {
                 tracing::info!(
                     "Attempted to set RLIMIT_NOFILE to {} failed: {err}",
                     DESIRED_RLIMIT_NOFILE
                 );
+                rlimit::getrlimit(rlimit::Resource::NOFILE)
+                    .ok()
+                    .map(|(soft, _hard)| soft)
             }
         },
-    );
+    )
 }
 
 #[cfg(target_os = "windows")]
-pub fn try_increase_rlimit_to_sensible() {
+pub fn try_increase_rlimit_to_sensible() -> Option<u64> {
     // On Windows, there is no need to increase the RLIMIT_NOFILE resource
     // limit.
+    None
+}
+
+/// Returns the number of concurrent downloads or installs that can run
+/// without running out of file descriptors.
+///
+/// This first tries to raise the RLIMIT_NOFILE limit, if the resulting limit
+/// is still too low for the `requested` concurrency it is reduced to fit in the
+/// available file descriptor budget.
+#[cfg(not(target_os = "windows"))]
+pub fn cap_concurrency_to_rlimit(requested: usize) -> usize {
+    let Some(limit) = try_increase_rlimit_to_sensible() else {
+        return requested;
+    };
+    let capped = concurrency_for_nofile_limit(requested, limit);
+    if capped < requested {
+        tracing::debug!(
+            "Limiting concurrency to {capped} (instead of {requested}) because RLIMIT_NOFILE is {limit}"
+        );
+    }
+    capped
+}
+
+#[cfg(target_os = "windows")]
+pub fn cap_concurrency_to_rlimit(requested: usize) -> usize {
+    // Windows does not limit the number of open files per process in the same
+    // way.
+    requested
+}
+
+/// Returns the highest concurrency, up to `requested`, that fits in a budget
+/// of `limit` file descriptors. At least one task is always allowed.
+#[cfg(not(target_os = "windows"))]
+fn concurrency_for_nofile_limit(requested: usize, limit: u64) -> usize {
+    let budget = limit.saturating_sub(RESERVED_FILE_DESCRIPTORS) / FILE_DESCRIPTORS_PER_TASK;
+    let budget = usize::try_from(budget).unwrap_or(usize::MAX);
+    requested.min(budget).max(1)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrency_is_capped_by_low_limit() {
+        // Plenty of file descriptors, the requested concurrency is used.
+        assert_eq!(concurrency_for_nofile_limit(50, DESIRED_RLIMIT_NOFILE), 50);
+
+        // A low limit reduces the concurrency.
+        assert_eq!(concurrency_for_nofile_limit(50, 128), 16);
+
+        // Even without any budget at least a single task is allowed.
+        assert_eq!(concurrency_for_nofile_limit(50, 16), 1);
+    }
 }
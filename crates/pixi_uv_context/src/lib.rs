@@ -1,10 +1,15 @@
+mod index_credentials;
+
 use std::sync::Arc;
 use std::time::Duration;
 
 use fs_err::create_dir_all;
 use miette::{Context, IntoDiagnostic};
 use pixi_config::{self, CacheKind, Config};
-use pixi_utils::reqwest::{LazyReqwestClient, should_use_system_certs_for_uv, uv_middlewares};
+use pixi_utils::{
+    reqwest::{LazyReqwestClient, should_use_system_certs_for_uv, uv_middlewares},
+    rlimit::cap_concurrency_to_rlimit,
+};
 use pixi_uv_conversions::{ConversionError, to_uv_trusted_host};
 use uv_cache::Cache;
 use uv_client::{
@@ -21,6 +26,10 @@ use uv_preview::Preview;
 use uv_types::{HashStrategy, InFlight};
 use uv_workspace::WorkspaceCache;
 
+pub use index_credentials::{
+    INDEX_TOKEN_ENV_PREFIX, IndexCredentialsMiddleware, index_token_env_var,
+};
+
 /// Objects that are needed for resolutions which can be shared between different resolutions.
 #[derive(Clone)]
 pub struct UvResolutionContext {
@@ -54,7 +63,8 @@ pub struct UvResolutionContext {
     /// HTTP timeout for uv operations, read from UV_HTTP_TIMEOUT,
     /// UV_REQUEST_TIMEOUT, or HTTP_TIMEOUT environment variables.
     pub http_timeout: Option<Duration>,
-    /// HTTP retry count for uv operations, read from UV_HTTP_RETRIES.
+    /// HTTP retry count for uv operations, read from UV_HTTP_RETRIES or the
+    /// `max-retries` configuration.
     pub http_retries: Option<u32>,
 }
 
@@ -148,6 +158,11 @@ fn build_concurrency(config: &Config) -> Concurrency {
     let builds = read_usize_env("UV_CONCURRENT_BUILDS").unwrap_or(defaults.builds);
     let installs = read_usize_env("UV_CONCURRENT_INSTALLS").unwrap_or(defaults.installs);
 
+    // Reduce the concurrency if there are not enough file descriptors
+    // available.
+    let downloads = cap_concurrency_to_rlimit(downloads);
+    let installs = cap_concurrency_to_rlimit(installs);
+
     Concurrency::new(downloads, builds, installs)
 }
 
@@ -188,7 +203,7 @@ impl UvResolutionContext {
             .into_diagnostic()
             .context("failed to parse trusted host")?;
         let http_timeout = read_http_timeout_from_env();
-        let http_retries = read_http_retries_from_env();
+        let http_retries = read_http_retries_from_env().or(Some(config.max_retries()));
         let concurrency = build_concurrency(config);
 
         let preview = Preview::default();
@@ -251,16 +266,36 @@ impl UvResolutionContext {
     ///   `tls_danger_accept_invalid_certs(true)` client for the flagged
     ///   hosts and reqwest 0.13 has no per-host TLS opt-in on a single
     ///   `Client`.
+    ///
+    /// The requests to the indexes in `index_locations` are authenticated with
+    /// the tokens from the environment, see [`IndexCredentialsMiddleware`].
     pub fn base_client_builder<'a>(
         &self,
         allow_insecure_hosts: Vec<TrustedHost>,
+        index_locations: &IndexLocations,
         markers: Option<&'a MarkerEnvironment>,
         connectivity: Connectivity,
     ) -> BaseClientBuilder<'a> {
+        let index_urls = index_locations
+            .allowed_indexes()
+            .into_iter()
+            .chain(index_locations.flat_indexes())
+            .map(|index| {
+                let url: &url::Url = index.url();
+                url
+            });
+        let credentials = IndexCredentialsMiddleware::from_env(index_urls);
+        let mut extra_middleware = self.extra_middleware.clone();
+        if !credentials.is_empty() {
+            // Added last so its credentials win over the ones uv and the
+            // auth middleware found for the same host.
+            extra_middleware.0.push(Arc::new(credentials));
+        }
+
         let mut builder = BaseClientBuilder::default()
             .keyring(self.keyring_provider)
             .connectivity(connectivity)
-            .extra_middleware(self.extra_middleware.clone());
+            .extra_middleware(extra_middleware);
         builder = if allow_insecure_hosts.is_empty() {
             builder
                 .allow_insecure_host(allow_insecure_hosts)
@@ -300,7 +335,7 @@ impl UvResolutionContext {
         connectivity: Connectivity,
     ) -> miette::Result<Arc<RegistryClient>> {
         let base_client_builder =
-            self.base_client_builder(allow_insecure_hosts, markers, connectivity);
+            self.base_client_builder(allow_insecure_hosts, index_locations, markers, connectivity);
 
         let mut uv_client_builder =
             RegistryClientBuilder::new(base_client_builder, self.cache.clone())
@@ -195,6 +195,11 @@ class ChannelInlineTable(StrictBaseModel):
         None,
         description="Override the workspace-level `exclude-newer` cutoff for this channel only",
     )
+    platforms: list[Platform] | None = Field(
+        None,
+        description="The subdirs to fetch from this channel. By default the platform that is solved for and `noarch` are fetched.",
+        examples=[["linux-64", "noarch"], ["noarch"]],
+    )
 
 
 Channel = ChannelName | ChannelInlineTable
@@ -498,6 +503,18 @@ class _PyPIRequirement(StrictBaseModel):
         None,
         description="The [PEP 508 extras](https://peps.python.org/pep-0508/#extras) of the package",
     )
+    no_build_isolation: bool | None = Field(
+        None,
+        description="Build the package against the environment instead of an isolated build environment, this can make the build non-reproducible. Adds the package to `pypi-options.no-build-isolation`.",
+    )
+    no_build: bool | None = Field(
+        None,
+        description="Only install the package from a pre-built wheel, fail instead of building it from source. Adds the package to `pypi-options.no-build`.",
+    )
+    no_binary: bool | None = Field(
+        None,
+        description="Always build the package from source instead of installing a pre-built wheel. Adds the package to `pypi-options.no-binary`.",
+    )
 
 
 class _PyPiGitRequirement(_PyPIRequirement):
@@ -661,6 +678,29 @@ class DependsOn(StrictBaseModel):
     environment: EnvironmentName | None = Field(
         None, description="The environment to use for the task"
     )
+    continue_on_error: bool | None = Field(
+        None,
+        description="Whether to still run the depending task when this dependency fails",
+    )
+
+
+class DotenvFile(StrictBaseModel):
+    """A `.env` file whose variables are loaded before running a task."""
+
+    path: NonEmptyStr = Field(description="The path of the file, relative to the workspace root")
+    optional: bool | None = Field(
+        None,
+        description="Whether the file is skipped when it doesn't exist, instead of failing",
+    )
+
+
+class TaskShell(str, Enum):
+    """The shell that runs the command of a task."""
+
+    bash = "bash"
+    cmd = "cmd"
+    powershell = "powershell"
+    none = "none"
 
 
 class TaskInlineTable(StrictBaseModel):
@@ -670,6 +710,10 @@ class TaskInlineTable(StrictBaseModel):
         None,
         description="A shell command to run the task in the limited, but cross-platform `bash`-like `deno_task_shell`. See the documentation for [supported syntax](https://pixi.sh/latest/environments/advanced_tasks/#syntax)",
     )
+    commands: list[NonEmptyStr] | None = Field(
+        None,
+        description="Shell commands to run one after the other instead of a single `cmd`. A command only runs if the previous one succeeded.",
+    )
     cwd: PathNoBackslash | None = Field(None, description="The working directory to run the task")
     # BREAK: `depends_on` is deprecated, use `depends-on`
     depends_on_deprecated: list[TaskName] | TaskName | None = Field(
@@ -694,6 +738,11 @@ class TaskInlineTable(StrictBaseModel):
         description="A map of environment variables to values, used in the task, these will be overwritten by the shell.",
         examples=[{"key": "value"}, {"ARGUMENT": "value"}],
     )
+    dotenv: list[DotenvFile | NonEmptyStr] | None = Field(
+        None,
+        description="The `.env` files to load before running the task. Variables from later files override earlier ones, the `env` of the task and the activation environment override them all.",
+        examples=[[".env"], [".env", {"path": ".env.local", "optional": True}]],
+    )
     default_environment: EnvironmentName | None = Field(
         None,
         description="A default environment to run the task",
@@ -706,6 +755,19 @@ class TaskInlineTable(StrictBaseModel):
         None,
         description="Whether to run in a clean environment, removing all environment variables except those defined in `env` and by pixi itself.",
     )
+    activation: bool | None = Field(
+        None,
+        description="Whether to activate the environment before running the task. When `false` only the executables of the environment are added to the `PATH`, which makes starting the task faster.",
+    )
+    shell: TaskShell | None = Field(
+        None,
+        description="The shell that runs the command. By default pixi uses its own cross-platform shell. `none` runs the command directly, without any shell interpretation.",
+    )
+    requires: list[NonEmptyStr] | None = Field(
+        None,
+        description="The packages or executables that have to be installed in the environment to run the task. The task fails before running its command if one of them is missing.",
+        examples=[["pytest"], ["ruff", "mypy"]],
+    )
     args: list[TaskArgs | TaskArgName] | None = Field(
         None,
         description="The arguments to a task",
@@ -786,6 +848,11 @@ class Activation(StrictBaseModel):
         description="A map of environment variables to values, used in the activation of the environment. These will be set in the shell. Thus these variables are shell specific. Using '$' might not expand to a value in different shells.",
         examples=[{"key": "value"}, {"ARGUMENT": "value"}],
     )
+    dotenv: list[DotenvFile | NonEmptyStr] | None = Field(
+        None,
+        description="The `.env` files to load before running any task in the environment, before the `.env` files of the task itself.",
+        examples=[[".env"], [".env", {"path": ".env.local", "optional": True}]],
+    )
 
 
 ##################
@@ -964,6 +1031,13 @@ class PyPIOptions(StrictBaseModel):
             {"numpy": ">=1.21.0"},
         ],
     )
+    constraints: dict[PyPIPackageName, PyPIRequirement] | None = Field(
+        None,
+        description="Constraints on the versions of PyPI packages that are part of the resolution, without adding them as a dependency",
+        examples=[
+            {"urllib3": "<2"},
+        ],
+    )
     no_binary: bool | list[PyPIPackageName] | None = Field(
         None,
         description="Don't use pre-built wheels for these packages",
@@ -986,6 +1060,11 @@ class PyPIOptions(StrictBaseModel):
         description="Skip wheel filename validation, allowing installation of wheels with version mismatches between filename and metadata",
         examples=[True, False],
     )
+    allow_clobber: bool | None = Field(
+        None,
+        description="Allow PyPI packages to overwrite files installed by conda packages, instead of warning about it",
+        examples=[True, False],
+    )
 
 
 #######################
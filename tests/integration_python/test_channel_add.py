@@ -1,3 +1,4 @@
+import shutil
 from pathlib import Path
 
 from .common import verify_cli_command
@@ -47,3 +48,41 @@ def test_channel_add_doesnt_update_packages(
         [pixi, "list", "package", "--manifest-path", manifest],
         stdout_contains=["package", "0.1.0"],
     )
+
+
+def test_channel_add_relative_path(pixi: Path, tmp_pixi_workspace: Path, channels: Path) -> None:
+    """Test that a relative channel path is stored relative to the manifest.
+
+    The path is given relative to the current directory, and has to resolve to
+    the same channel when pixi runs from another directory.
+    """
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = """
+    [workspace]
+    name = "test"
+    channels = []
+    platforms = ["linux-64", "osx-64", "osx-arm64", "win-64"]
+    """
+    manifest.write_text(toml)
+    shutil.copytree(
+        channels.joinpath("dummy_channel_1"), tmp_pixi_workspace.joinpath("local-channel")
+    )
+    sub_dir = tmp_pixi_workspace.joinpath("sub")
+    nested_dir = sub_dir.joinpath("nested")
+    nested_dir.mkdir(parents=True)
+
+    verify_cli_command(
+        [pixi, "workspace", "channel", "add", "../local-channel"],
+        cwd=sub_dir,
+    )
+    assert '"./local-channel"' in manifest.read_text()
+
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest, "--no-install", "dummy-a"],
+        cwd=nested_dir,
+    )
+    verify_cli_command(
+        [pixi, "list", "--manifest-path", manifest],
+        cwd=nested_dir,
+        stdout_contains=["dummy-a", "dummy-c"],
+    )
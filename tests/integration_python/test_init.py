@@ -81,6 +81,35 @@ dependencies:
     )
 
 
+def test_pixi_init_import_requirements_txt(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    tmp_pixi_workspace.joinpath("requirements.txt").write_text(
+        """# The pinned dependency
+requests==2.31.0 --hash=sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003f
+
+-r other-requirements.txt
+"""
+    )
+    tmp_pixi_workspace.joinpath("other-requirements.txt").write_text("rich\n")
+
+    verify_cli_command(
+        [pixi, "init", "--import", "requirements.txt"],
+        cwd=tmp_pixi_workspace,
+    )
+
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+
+    assert manifest.is_file()
+
+    assert tomllib.loads(manifest.read_text()) == snapshot(
+        {
+            "workspace": IsPartialDict,
+            "tasks": {},
+            "dependencies": {"python": "*"},
+            "pypi-dependencies": {"requests": "==2.31.0", "rich": "*"},
+        }
+    )
+
+
 @pytest.mark.slow
 def test_pixi_init_pyproject(pixi: Path, tmp_pixi_workspace: Path) -> None:
     manifest_path = tmp_pixi_workspace / "pyproject.toml"
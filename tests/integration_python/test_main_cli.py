@@ -1,9 +1,12 @@
+import http.server
 import json
 import platform
 import shutil
 import sys
+import threading
 import tomllib
 from pathlib import Path
+from typing import Any
 
 import pytest
 import tomli_w
@@ -210,6 +213,47 @@ def test_broken_config(pixi: Path, tmp_pixi_workspace: Path) -> None:
     verify_cli_command([pixi, "info"], env=env, stderr_contains="Ignoring 'invalid-key'")
 
 
+
+def test_init_template(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    pixi_home = tmp_pixi_workspace.joinpath("pixi-home")
+    env = {"PIXI_HOME": str(pixi_home)}
+    templates = pixi_home.joinpath("templates")
+    templates.mkdir(parents=True)
+    templates.joinpath("science.toml").write_text(
+        """
+        channels = ["bioconda"]
+        platforms = ["linux-64", "osx-arm64"]
+
+        [dependencies]
+        numpy = ">=2"
+
+        [tasks]
+        hello = "echo hello"
+        """
+    )
+
+    verify_cli_command(
+        [pixi, "init", "--list-templates"],
+        env=env,
+        stdout_contains=["python (built-in)", "science"],
+    )
+
+    workspace = tmp_pixi_workspace.joinpath("workspace")
+    verify_cli_command([pixi, "init", "--template", "science", workspace], env=env)
+    manifest = tomllib.loads(workspace.joinpath("pixi.toml").read_text())
+    assert manifest["workspace"]["channels"] == ["bioconda"]
+    assert manifest["workspace"]["platforms"] == ["linux-64", "osx-arm64"]
+    assert manifest["dependencies"] == {"numpy": ">=2"}
+    assert manifest["tasks"] == {"hello": "echo hello"}
+
+    verify_cli_command(
+        [pixi, "init", "--template", "unknown", tmp_pixi_workspace.joinpath("other")],
+        ExitCode.FAILURE,
+        env=env,
+        stderr_contains=["unknown template 'unknown'", "python, science"],
+    )
+
+
 @pytest.mark.slow
 def test_search(pixi: Path) -> None:
     verify_cli_command(
@@ -366,6 +410,92 @@ def test_concurrency_flags(
     )
 
 
+def test_shell_refuses_nested_activation(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    manifest_path.write_text(EMPTY_BOILERPLATE_PROJECT)
+
+    verify_cli_command(
+        [pixi, "shell", "--manifest-path", manifest_path],
+        env={
+            "PIXI_IN_SHELL": "1",
+            "PIXI_ENVIRONMENT_NAME": "default",
+            "PIXI_PROJECT_NAME": "outer",
+        },
+        expected_exit_code=ExitCode.FAILURE,
+        stderr_contains="the 'default' environment of 'outer' is already activated",
+    )
+
+
+def test_list_outdated(
+    pixi: Path, tmp_pixi_workspace: Path, multiple_versions_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+
+    verify_cli_command([pixi, "init", "--channel", multiple_versions_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--no-install", "--manifest-path", manifest_path, "package==0.1.0"]
+    )
+
+    # `package` is locked to 0.1.0 while the channel also contains 0.2.0
+    verify_cli_command(
+        [pixi, "list", "--outdated", "--no-install", "--manifest-path", manifest_path],
+        stdout_contains=["package", "0.1.0", "0.2.0"],
+    )
+
+    verify_cli_command(
+        [pixi, "list", "--outdated", "--json", "--no-install", "--manifest-path", manifest_path],
+        stdout_contains=['"latest": "0.2.0"', '"wanted": "0.1.0"'],
+    )
+
+
+@pytest.mark.slow
+def test_list_outdated_pypi(pixi: Path, tmp_pixi_workspace: Path, test_data: Path) -> None:
+    indexes = test_data / "pypi-indexes"
+    servers = []
+    for name in ["multiple-indexes-a", "multiple-indexes-c"]:
+        directory = str(indexes / name / "index")
+
+        class Handler(http.server.SimpleHTTPRequestHandler):
+            def __init__(self, *args: Any, directory: str = directory, **kwargs: Any) -> None:
+                super().__init__(*args, directory=directory, **kwargs)
+
+            def log_message(self, format: str, *args: Any) -> None:
+                pass
+
+        server = http.server.ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+        servers.append(server)
+
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    manifest_path.write_text(f"""
+    [workspace]
+    channels = ["{CONDA_FORGE_CHANNEL}"]
+    platforms = ["{CURRENT_PLATFORM}"]
+
+    [dependencies]
+    python = "3.13.*"
+
+    [pypi-dependencies]
+    foo = "==1.0.0"
+
+    [pypi-options]
+    index-url = "http://127.0.0.1:{servers[0].server_port}"
+    extra-index-urls = ["http://127.0.0.1:{servers[1].server_port}"]
+    """)
+
+    try:
+        verify_cli_command([pixi, "lock", "--manifest-path", manifest_path])
+
+        # foo 1.0.0 is locked from the index, the extra index contains 3.0.0
+        verify_cli_command(
+            [pixi, "list", "--outdated", "--json", "--no-install", "--manifest-path", manifest_path],
+            stdout_contains=['"name": "foo"', '"wanted": "1.0.0"', '"latest": "3.0.0"'],
+        )
+    finally:
+        for server in servers:
+            server.shutdown()
+
+
 @pytest.mark.slow
 def test_cli_config_options(
     pixi: Path, tmp_pixi_workspace: Path, multiple_versions_channel_1: str
@@ -561,6 +691,161 @@ def test_dont_add_broken_dep(pixi: Path, tmp_pixi_workspace: Path, dummy_channel
     assert manifest_content == tmp_pixi_workspace.joinpath("pixi.toml").read_text()
 
 
+def test_add_reports_platforms_without_package(
+    pixi: Path, tmp_pixi_workspace: Path, target_specific_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command(
+        [
+            pixi,
+            "init",
+            "--channel",
+            target_specific_channel_1,
+            "--platform",
+            "linux-64",
+            "--platform",
+            "win-64",
+            tmp_pixi_workspace,
+        ]
+    )
+    manifest_content = manifest_path.read_text()
+
+    # `package-unix` is not available for windows
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "package-unix"],
+        ExitCode.FAILURE,
+        stderr_contains=[
+            "is not available for platform(s): win-64",
+            "pixi add --platform linux-64 package-unix",
+        ],
+    )
+    assert manifest_content == manifest_path.read_text()
+
+    # Restricting the dependency to the available platform works
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest_path,
+            "--no-install",
+            "--platform",
+            "linux-64",
+            "package-unix",
+        ],
+    )
+
+
+def test_add_warns_about_unbounded_pin(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+
+    # Pinning strategies without an upper bound warn and suggest a bound
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest_path,
+            "--no-install",
+            "--pinning-strategy",
+            "latest-up",
+            "dummy-a",
+        ],
+        stderr_contains=[
+            "'dummy-a' was added as '>=0.1.0' which has no upper bound",
+            "dummy-a>=0.1.0,<0.2",
+        ],
+    )
+    assert 'dummy-a = ">=0.1.0"' in manifest_path.read_text()
+
+    # The warning can be silenced
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest_path,
+            "--no-install",
+            "--pinning-strategy",
+            "no-pin",
+            "--no-pin-warning",
+            "dummy-b",
+        ],
+        stderr_excludes="no upper bound",
+    )
+
+    # A bounded strategy pins the dependency without a warning
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest_path,
+            "--no-install",
+            "--pin",
+            "semver",
+            "dummy-c",
+        ],
+        stderr_excludes="no upper bound",
+    )
+    assert 'dummy-c = ">=0.1.0,<0.2"' in manifest_path.read_text()
+
+
+def test_add_dry_run(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    lock_file_path = tmp_pixi_workspace / "pixi.lock"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-a"])
+    manifest_content = manifest_path.read_text()
+    lock_file_content = lock_file_path.read_text()
+
+    # The delta is reported, but nothing is written
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--dry-run", "dummy-b"],
+        stderr_contains=["Dry-run", "dummy-b"],
+    )
+    assert manifest_path.read_text() == manifest_content
+    assert lock_file_path.read_text() == lock_file_content
+
+    # Unavailable packages are still reported
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--dry-run", "does-not-exist"],
+        ExitCode.FAILURE,
+    )
+    assert manifest_path.read_text() == manifest_content
+    assert lock_file_path.read_text() == lock_file_content
+
+
+def test_install_json_summary(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-a"])
+
+    # `dummy-a` depends on `dummy-c`
+    output = verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--json-summary"]
+    )
+    summary = json.loads(output.stdout)
+    assert summary["added"] == 2
+    assert summary["removed"] == 0
+    assert summary["updated"] == 0
+    assert summary["duration_seconds"] >= 0
+
+    # The summary is also printed when nothing changed
+    output = verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--json-summary"]
+    )
+    summary = json.loads(output.stdout)
+    assert summary["added"] == 0
+    assert summary["removed"] == 0
+    assert summary["updated"] == 0
+    assert summary["bytes_downloaded"] == 0
+    assert summary["cache_hits"] == 0
+
+
 def test_list_exits_unsuccessful_on_unknown_pkg(
     pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
 ) -> None:
@@ -612,11 +897,18 @@ def test_pixi_lock(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -
     # Create a new project
     verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
 
+    # Freeze the day the lock file is generated at, so that the recreated lock
+    # file is identical even if the test runs across midnight
+    reproducible = {"SOURCE_DATE_EPOCH": "1700000000"}
+
     # Add a dependency
-    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "dummy-a"])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "dummy-a"], env=reproducible
+    )
 
     # Read the original lock file content
     original_lock_content = lock_file_path.read_text()
+    assert "# generated-at: 2023-11-14" in original_lock_content
 
     # Remove the lock file
     lock_file_path.unlink()
@@ -625,11 +917,20 @@ def test_pixi_lock(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -
     dot_pixi = tmp_pixi_workspace / ".pixi"
     shutil.rmtree(dot_pixi)
 
-    # Run pixi lock to recreate the lock file and validate the return code with --check is 1
+    # Validate that the return code with --check is 1, the missing environment
+    # is found without solving so the lock file is not recreated
     verify_cli_command(
         [pixi, "lock", "--manifest-path", manifest_path, "--check"],
         expected_exit_code=ExitCode.FAILURE,
+        stderr_contains="environment 'default' is missing from the lock file",
+    )
+    assert not lock_file_path.exists()
+
+    # Run pixi lock to recreate the lock file
+    verify_cli_command(
+        [pixi, "lock", "--manifest-path", manifest_path],
         stderr_contains=["+", "dummy-a"],
+        env=reproducible,
     )
 
     # Run pixi lock again to validate that the return code with --check is 0
@@ -902,7 +1203,17 @@ platforms = ["linux-64", "win-64", "osx-64", "osx-arm64"]
 
     # Test simple task alias
     verify_cli_command(
-        [pixi, "task", "alias", "dummy-a", "dummy-b", "dummy-c", "--manifest-path", manifest]
+        [
+            pixi,
+            "task",
+            "alias",
+            "--allow-missing",
+            "dummy-a",
+            "dummy-b",
+            "dummy-c",
+            "--manifest-path",
+            manifest,
+        ]
     )
 
     # Test platform-specific task alias
@@ -911,6 +1222,7 @@ platforms = ["linux-64", "win-64", "osx-64", "osx-arm64"]
             pixi,
             "task",
             "alias",
+            "--allow-missing",
             "--platform",
             "linux-64",
             "linux-alias",
@@ -977,6 +1289,110 @@ test-alias = [{ task = "test" }]
 """)
 
 
+def test_pixi_task_export_import_roundtrip(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    header = """
+        [workspace]
+        name = "test"
+        channels = []
+        platforms = ["linux-64", "win-64", "osx-64", "osx-arm64"]
+        """
+    toml = (
+        header
+        + """
+        [tasks]
+        build = { cmd = "make", cwd = "src", env = { CC = "clang" }, description = "Build it" }
+        test = { cmd = "pytest", depends-on = ["build"] }
+        release = { commands = ["make dist", "make upload"], inputs = ["src/*"] }
+        all = [{ task = "build" }, { task = "test" }]
+
+        [target.linux-64.tasks]
+        linux-only = "echo linux"
+        """
+    )
+    manifest.write_text(toml)
+
+    exported = verify_cli_command(
+        [pixi, "task", "export", "--json", "--manifest-path", manifest]
+    ).stdout
+    tasks_file = tmp_pixi_workspace.joinpath("tasks.json")
+    tasks_file.write_text(exported)
+
+    # Clear the tasks and import them again
+    manifest.write_text(header)
+    verify_cli_command(
+        [pixi, "task", "import", tasks_file, "--manifest-path", manifest],
+        stderr_contains="Imported 5 task(s)",
+    )
+
+    reexported = verify_cli_command(
+        [pixi, "task", "export", "--json", "--manifest-path", manifest]
+    ).stdout
+    tasks = {task["name"]: task for task in json.loads(reexported)}
+    assert list(tasks.values()) == json.loads(exported)
+    # The tasks have the fields of the manifest
+    assert tasks["build"] == {
+        "name": "build",
+        "feature": "default",
+        "cmd": "make",
+        "cwd": "src",
+        "env": {"CC": "clang"},
+        "description": "Build it",
+    }
+    assert tasks["release"]["commands"] == ["make dist", "make upload"]
+    assert tasks["linux-only"]["platform"] == "linux-64"
+    # Aliases stay aliases
+    assert "cmd" not in tasks["all"]
+    assert [dependency["task"] for dependency in tasks["all"]["depends-on"]] == ["build", "test"]
+
+    # Importing again skips the existing tasks unless `--force` is passed
+    verify_cli_command(
+        [pixi, "task", "import", tasks_file, "--manifest-path", manifest],
+        stderr_contains="Skipped task",
+    )
+    verify_cli_command(
+        [pixi, "task", "import", tasks_file, "--force", "--manifest-path", manifest],
+        stderr_excludes="Skipped task",
+    )
+
+
+def test_pixi_task_graph(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+        {EMPTY_BOILERPLATE_PROJECT}
+        [tasks]
+        a = {{ cmd = "echo a", depends-on = ["b"] }}
+        b = "echo b"
+        """)
+
+    verify_cli_command(
+        [pixi, "task", "graph", "--manifest-path", manifest],
+        stdout_contains=["graph TD", 'n0["b"]', 'n1["a"]', "n1 --> n0"],
+    )
+    verify_cli_command(
+        [pixi, "task", "graph", "--format", "dot", "--manifest-path", manifest],
+        stdout_contains=[
+            "digraph tasks",
+            'n0 [label="b", shape=box];',
+            'n1 [label="a", shape=box];',
+            "n1 -> n0;",
+        ],
+    )
+
+    # Cycles are reported instead of rendered
+    manifest.write_text(f"""
+        {EMPTY_BOILERPLATE_PROJECT}
+        [tasks]
+        a = {{ cmd = "echo a", depends-on = ["b"] }}
+        b = {{ cmd = "echo b", depends-on = ["a"] }}
+        """)
+    verify_cli_command(
+        [pixi, "task", "graph", "--manifest-path", manifest],
+        ExitCode.FAILURE,
+        stderr_contains="cyclic dependency",
+    )
+
+
 def test_pixi_task_list_json(pixi: Path, tmp_pixi_workspace: Path) -> None:
     manifest = tmp_pixi_workspace.joinpath("pixi.toml")
     toml = """
@@ -1372,6 +1788,45 @@ def test_quiet_flag_with_rust_log_env(
     )
 
 
+def test_quiet_install_prints_only_errors(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    """With --quiet, a successful install prints nothing, warnings and errors are still printed."""
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "--quiet", "dummy-a"],
+        stderr_excludes="Added",
+    )
+
+    # The reminder that the lock file is out of date is a warning, not a status message.
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest_path,
+            "--no-lock-update",
+            "--quiet",
+            "dummy-b",
+        ],
+        stderr_contains="out of date",
+        stderr_excludes="Added",
+    )
+
+    output = verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--quiet"],
+        stderr_excludes="installed",
+    )
+    assert output.stdout == ""
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--quiet", "-e", "missing"],
+        ExitCode.FAILURE,
+        stderr_contains="missing",
+    )
+
+
 @pytest.mark.slow
 def test_add_url_no_channel(pixi: Path, tmp_pixi_workspace: Path) -> None:
     """
@@ -1489,3 +1944,258 @@ def test_add_url_no_channel(pixi: Path, tmp_pixi_workspace: Path) -> None:
         ],
         stderr_contains="The prefix environment has been installed",
     )
+
+
+@pytest.mark.slow
+def test_json_progress(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-a"],
+    )
+
+    output = verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--progress", "json"],
+    )
+
+    events = [
+        json.loads(line) for line in output.stderr.splitlines() if line.startswith('{"event"')
+    ]
+    downloads = [event for event in events if event["event"] == "download"]
+    assert downloads, output.stderr
+    for event in downloads:
+        assert set(event) == {"event", "package", "done", "total"}
+        assert 0 < event["done"] <= event["total"]
+    assert "dummy-a" in {event["package"] for event in downloads}
+
+
+@pytest.mark.slow
+def test_install_timing(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-a"],
+    )
+    # Remove the lock file so that the install has to solve again
+    (tmp_pixi_workspace / "pixi.lock").unlink()
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--timing"],
+        stderr_contains=["Timings:", "solve", "link"],
+    )
+
+    (tmp_pixi_workspace / "pixi.lock").unlink()
+    output = verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--progress", "json"],
+    )
+    timings = [
+        json.loads(line)
+        for line in output.stderr.splitlines()
+        if line.startswith('{"event":"timing"')
+    ]
+    assert "solve" in {timing["phase"] for timing in timings}, output.stderr
+
+
+@pytest.mark.slow
+def test_install_reuses_verified_state(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "dummy-a"])
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path],
+        stderr_contains="environment has been installed",
+    )
+
+    # Nothing changed, so neither the lock file nor the prefix is verified again
+    verify_cli_command(
+        [pixi, "install", "-vv", "--manifest-path", manifest_path],
+        stderr_contains=[
+            "the workspace did not change since it was last verified",
+            "environment is already up to date",
+        ],
+    )
+
+    # Editing the manifest invalidates the stored state
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-b"],
+    )
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path],
+        stderr_contains="environment has been installed",
+    )
+
+
+@pytest.mark.slow
+def test_install_verify_lock_hash(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "dummy-a"])
+
+    output = verify_cli_command(
+        [pixi, "verify-lock", "--manifest-path", manifest_path],
+        stderr_contains="was installed from the lock file",
+    )
+    lock_hash = output.stdout.strip()
+    assert len(lock_hash) == 64, output.stdout
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--verify-lock-hash", lock_hash],
+    )
+
+    # Changing the lock file no longer matches the recorded hash
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-b"],
+    )
+    verify_cli_command(
+        [pixi, "verify-lock", "--manifest-path", manifest_path],
+        ExitCode.FAILURE,
+        stderr_contains="was installed from a lock file with hash",
+    )
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest_path, "--verify-lock-hash", lock_hash],
+        ExitCode.FAILURE,
+        stderr_contains=f"expected {lock_hash}",
+    )
+
+
+def test_verify_detects_missing_files(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "dummy-a"])
+
+    verify_cli_command(
+        [pixi, "verify", "--manifest-path", manifest_path],
+        stderr_contains="are intact",
+    )
+
+    # Remove one of the files that dummy-a installed
+    prefix = tmp_pixi_workspace / ".pixi" / "envs" / "default"
+    record = next((prefix / "conda-meta").glob("dummy-a-*.json"))
+    removed = json.loads(record.read_text())["files"][0]
+    (prefix / removed).unlink()
+
+    verify_cli_command(
+        [pixi, "verify", "--manifest-path", manifest_path],
+        ExitCode.FAILURE,
+        stderr_contains=["dummy-a", "missing", "failed verification"],
+    )
+
+    verify_cli_command([pixi, "verify", "--repair", "--manifest-path", manifest_path])
+    assert (prefix / removed).exists()
+    verify_cli_command(
+        [pixi, "verify", "--manifest-path", manifest_path],
+        stderr_contains="are intact",
+    )
+
+
+def test_list_size(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", manifest_path, "dummy-a"])
+
+    verify_cli_command(
+        [pixi, "list", "--size", "--manifest-path", manifest_path],
+        stdout_contains=["dummy-a", "Total"],
+    )
+
+    output = verify_cli_command(
+        [pixi, "list", "--size", "--json", "--manifest-path", manifest_path],
+    )
+    usage = json.loads(output.stdout)
+    sizes = {package["name"]: package["size_bytes"] for package in usage["packages"]}
+    assert sizes["dummy-a"] > 0
+    assert usage["total_bytes"] == sum(sizes.values())
+
+
+def test_cache_dir_flag(pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    cache_dir = tmp_pixi_workspace / "custom" / "cache"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "--no-install", "dummy-a"],
+    )
+
+    verify_cli_command(
+        [pixi, "--cache-dir", cache_dir, "install", "--manifest-path", manifest_path],
+    )
+    assert any(path.name.startswith("dummy-a") for path in cache_dir.rglob("*")), list(
+        cache_dir.rglob("*")
+    )
+
+
+def test_manifest_path_directory_prefers_pixi_toml(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command([pixi, "add", "--manifest-path", tmp_pixi_workspace, "dummy-a"])
+
+    # A pyproject.toml next to the pixi.toml that also defines a workspace
+    (tmp_pixi_workspace / "pyproject.toml").write_text(
+        f"""
+[project]
+name = "other"
+version = "0.1.0"
+
+[tool.pixi.workspace]
+channels = ["{dummy_channel_1}"]
+platforms = ["{CURRENT_PLATFORM}"]
+"""
+    )
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", tmp_pixi_workspace],
+        stderr_contains="both 'pixi.toml' and 'pyproject.toml' define a workspace",
+    )
+    assert (tmp_pixi_workspace / ".pixi" / "envs" / "default" / "conda-meta").is_dir()
+    assert any(
+        (tmp_pixi_workspace / ".pixi" / "envs" / "default" / "conda-meta").glob("dummy-a-*.json")
+    )
+
+
+def test_prune_removes_orphaned_packages(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest_path = tmp_pixi_workspace / "pixi.toml"
+    verify_cli_command([pixi, "init", "--channel", dummy_channel_1, tmp_pixi_workspace])
+    verify_cli_command(
+        [pixi, "add", "--manifest-path", manifest_path, "dummy-a", "dummy-b"]
+    )
+
+    prefix = tmp_pixi_workspace / ".pixi" / "envs" / "default"
+    record = next((prefix / "conda-meta").glob("dummy-b-*.json"))
+    orphaned_files = json.loads(record.read_text())["files"]
+    kept_record = next((prefix / "conda-meta").glob("dummy-a-*.json"))
+    kept_files = json.loads(kept_record.read_text())["files"]
+
+    # Remove dummy-b from the lock file, but leave it installed
+    verify_cli_command(
+        [pixi, "remove", "--no-install", "--manifest-path", manifest_path, "dummy-b"]
+    )
+    assert record.exists()
+
+    verify_cli_command(
+        [pixi, "prune", "--dry-run", "--manifest-path", manifest_path],
+        stderr_contains=["would be removed", "dummy-b"],
+        stderr_excludes="dummy-a",
+    )
+    assert record.exists()
+
+    verify_cli_command(
+        [pixi, "prune", "--manifest-path", manifest_path],
+        stderr_contains="Pruned 1 package",
+    )
+    assert not record.exists()
+    assert all(not (prefix / file).exists() for file in orphaned_files)
+    assert all((prefix / file).exists() for file in kept_files)
+
+    verify_cli_command(
+        [pixi, "prune", "--manifest-path", manifest_path],
+        stderr_contains="no packages that are not locked",
+    )
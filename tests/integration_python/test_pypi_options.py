@@ -1,4 +1,10 @@
+import base64
+import http.server
+import os
+import sys
+import threading
 from pathlib import Path
+from typing import Any
 
 import pytest
 import yaml
@@ -25,6 +31,44 @@ def test_no_build_option(pixi: Path, tmp_pixi_workspace: Path) -> None:
     )
 
 
+@pytest.mark.extra_slow
+def test_no_build_per_dependency(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """
+    Tests that `pixi add --pypi --no-build` stores the option on the dependency and
+    that installing fails on the package that would have to be built from source.
+    """
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    [workspace]
+    channels = ["{CONDA_FORGE_CHANNEL}"]
+    platforms = ["{CURRENT_PLATFORM}"]
+
+    [dependencies]
+    python = "3.12.*"
+    """)
+
+    verify_cli_command(
+        [
+            pixi,
+            "add",
+            "--manifest-path",
+            manifest,
+            "--pypi",
+            "--no-build",
+            "--frozen",
+            "--no-install",
+            "sdist==0.0.0",
+        ],
+    )
+    assert 'sdist = { version = "==0.0.0", no-build = true }' in manifest.read_text()
+
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest],
+        ExitCode.FAILURE,
+        stderr_contains="sdist",
+    )
+
+
 @pytest.mark.slow
 def test_pypi_overrides(pixi: Path, tmp_pixi_workspace: Path) -> None:
     """
@@ -80,3 +124,77 @@ def test_pypi_overrides(pixi: Path, tmp_pixi_workspace: Path) -> None:
         for pkg in lock["environments"]["outdated"]["packages"][CURRENT_PLATFORM]
         for v in pkg.values()
     )
+
+
+@pytest.mark.slow
+@pytest.mark.skipif(sys.platform == "win32", reason="the keyring stub is a shell script")
+def test_keyring_provider_authenticates_index(
+    pixi: Path, tmp_pixi_workspace: Path, test_data: Path
+) -> None:
+    """
+    Tests that with the subprocess keyring provider credentials for an index that
+    requires authentication are obtained from `keyring`, and that they do not
+    end up in the lock file.
+    """
+    index_dir = test_data / "pypi-indexes" / "multiple-indexes-a" / "index"
+    password = "keyring-secret"
+    expected_auth = "Basic " + base64.b64encode(f"user:{password}".encode()).decode()
+
+    class AuthenticatedHandler(http.server.SimpleHTTPRequestHandler):
+        def __init__(self, *args: Any, **kwargs: Any) -> None:
+            super().__init__(*args, directory=str(index_dir), **kwargs)
+
+        def do_GET(self) -> None:
+            if self.headers.get("Authorization") != expected_auth:
+                self.send_response(401)
+                self.send_header("WWW-Authenticate", 'Basic realm="index"')
+                self.end_headers()
+                return
+            super().do_GET()
+
+        def log_message(self, format: str, *args: Any) -> None:
+            pass
+
+    server = http.server.ThreadingHTTPServer(("127.0.0.1", 0), AuthenticatedHandler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+
+    # A stub for `keyring get <service> <username>` that prints the password.
+    bin_dir = tmp_pixi_workspace / "bin"
+    bin_dir.mkdir()
+    keyring = bin_dir / "keyring"
+    keyring.write_text(f'#!/bin/sh\nif [ "$1" = "get" ]; then echo "{password}"; fi\n')
+    keyring.chmod(0o755)
+
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    [workspace]
+    channels = ["{CONDA_FORGE_CHANNEL}"]
+    platforms = ["{CURRENT_PLATFORM}"]
+
+    [dependencies]
+    python = "3.13.*"
+
+    [pypi-options]
+    index-url = "http://user@127.0.0.1:{server.server_port}"
+
+    [pypi-dependencies]
+    foo = "*"
+    """)
+
+    try:
+        # Without the keyring the index rejects the requests
+        verify_cli_command(
+            [pixi, "install", "--manifest-path", manifest, "--pypi-keyring-provider=disabled"],
+            ExitCode.FAILURE,
+        )
+
+        verify_cli_command(
+            [pixi, "install", "--manifest-path", manifest, "--pypi-keyring-provider=subprocess"],
+            env={"PATH": f"{bin_dir}{os.pathsep}{os.environ['PATH']}"},
+        )
+    finally:
+        server.shutdown()
+
+    lock = tmp_pixi_workspace.joinpath("pixi.lock").read_text()
+    assert "foo-1.0.0" in lock
+    assert password not in lock
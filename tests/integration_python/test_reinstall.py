@@ -1,4 +1,9 @@
-from .common import skip_if_current_platform_unsupported, verify_cli_command
+from .common import (
+    CONDA_FORGE_CHANNEL,
+    CURRENT_PLATFORM,
+    skip_if_current_platform_unsupported,
+    verify_cli_command,
+)
 import pytest
 from pathlib import Path
 import shutil
@@ -146,3 +151,45 @@ def test_pixi_reinstall_multi_env(pixi: Path, reinstall_workspace: Path) -> None
         stdout_contains="PyPI is number 2",
         env=env,
     )
+
+
+@pytest.mark.extra_slow
+def test_python_minor_change_reinstalls_pypi_packages(
+    pixi: Path, tmp_pixi_workspace: Path, test_data: Path
+) -> None:
+    index = (test_data / "pypi-indexes" / "multiple-indexes-a" / "index").as_uri()
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+
+    def write_manifest(python: str) -> None:
+        manifest.write_text(f"""
+        [workspace]
+        channels = ["{CONDA_FORGE_CHANNEL}"]
+        platforms = ["{CURRENT_PLATFORM}"]
+
+        [dependencies]
+        python = "{python}"
+
+        [pypi-dependencies]
+        foo = "*"
+
+        [pypi-options]
+        index-url = "{index}"
+        """)
+
+    write_manifest("3.12.*")
+    verify_cli_command([pixi, "install", "--manifest-path", manifest])
+
+    # The lock file is updated without installing, the reinstall happens with
+    # the next install
+    write_manifest("3.13.*")
+    verify_cli_command(
+        [pixi, "lock", "--manifest-path", manifest],
+        stderr_contains="all PyPI packages of the environment will be reinstalled",
+    )
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest],
+        stderr_contains="all PyPI packages of the environment are reinstalled",
+    )
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "python", "-c", "import foo"],
+    )
@@ -70,6 +70,120 @@ def test_run_in_shell_environment(pixi: Path, tmp_pixi_workspace: Path) -> None:
     )
 
 
+def test_run_environment_selects_prefix(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """`--environment` runs the task with the PATH of the selected environment."""
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [feature.test.tasks]
+    show-path = "echo $PATH"
+
+    [environments]
+    test = ["test"]
+    """)
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--environment", "test", "show-path"],
+        stdout_contains=str(tmp_pixi_workspace.joinpath(".pixi", "envs", "test")),
+    )
+
+    # The task is not part of the default environment
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--environment", "default", "show-path"],
+        ExitCode.COMMAND_NOT_FOUND,
+        stderr_contains="pixi run -e test show-path",
+    )
+
+
+def test_run_no_activation(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """Without activation only the PATH of the environment is set."""
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [activation.env]
+    ACTIVATED_VAR = "activated"
+
+    [tasks]
+    show = "echo var=$ACTIVATED_VAR path=$PATH"
+    show-unactivated = {{ cmd = "echo var=$ACTIVATED_VAR", activation = false }}
+    """)
+    prefix = str(tmp_pixi_workspace.joinpath(".pixi", "envs", "default"))
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "show"],
+        stdout_contains=["var=activated", prefix],
+    )
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--no-activation", "show"],
+        stdout_contains=["var= ", prefix],
+        stdout_excludes="var=activated",
+    )
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "show-unactivated"],
+        stdout_contains="var=",
+        stdout_excludes="var=activated",
+    )
+
+
+def test_run_with_dotenv(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """Tasks load the variables of their `.env` files."""
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [activation]
+    env = {{ OVERRIDDEN = "activation" }}
+    dotenv = [{{ path = ".env.global", optional = true }}]
+
+    [tasks]
+    show = {{ cmd = "echo foo=$FOO quoted=$QUOTED overridden=$OVERRIDDEN", dotenv = [".env"] }}
+    missing = {{ cmd = "echo unreachable", dotenv = ["missing.env"] }}
+    """)
+    tmp_pixi_workspace.joinpath(".env").write_text(
+        "# comment\nFOO=bar\nexport QUOTED='a b'\nOVERRIDDEN=dotenv\n"
+    )
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "show"],
+        stdout_contains=["foo=bar", "quoted=a b", "overridden=activation"],
+    )
+
+    # The optional global file is loaded before the file of the task.
+    tmp_pixi_workspace.joinpath(".env.global").write_text("FOO=global\n")
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "show"],
+        stdout_contains="foo=bar",
+    )
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "missing"],
+        ExitCode.FAILURE,
+        stderr_contains="missing.env",
+        stdout_excludes="unreachable",
+    )
+
+
+def test_run_with_missing_requirement(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """A task fails before running if a tool it requires is not installed."""
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    manifest.write_text(f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    test = {{ cmd = "echo unreachable", requires = ["pytest"] }}
+    """)
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "test"],
+        ExitCode.FAILURE,
+        stderr_contains=[
+            "task `test` requires `pytest` which is not installed",
+            "pixi add --pypi pytest",
+        ],
+        stdout_excludes="unreachable",
+    )
+
+
 def test_run_platform_not_in_environment_errors(pixi: Path, tmp_pixi_workspace: Path) -> None:
     """A `--platform` the environment doesn't declare is rejected up front --
     before any solve or emulation warning -- with a clear membership error."""
@@ -268,6 +382,110 @@ def test_run_with_activation(pixi: Path, tmp_pixi_workspace: Path) -> None:
     )
 
 
+def test_run_exposes_workspace_variables(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    root = "echo $PIXI_PROJECT_ROOT"
+    prefix = "echo $PIXI_PREFIX"
+    """
+    manifest.write_text(toml)
+
+    output = verify_cli_command([pixi, "run", "--manifest-path", manifest, "root"])
+    assert Path(output.stdout.strip()).resolve() == tmp_pixi_workspace.resolve()
+
+    output = verify_cli_command([pixi, "run", "--manifest-path", manifest, "prefix"])
+    prefix = tmp_pixi_workspace.joinpath(".pixi", "envs", "default")
+    assert Path(output.stdout.strip()).resolve() == prefix.resolve()
+
+
+def test_run_inline_cmd(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [activation.env]
+    INLINE_VAR = "from-activation"
+    """
+    manifest.write_text(toml)
+
+    verify_cli_command(
+        [
+            pixi,
+            "run",
+            "--manifest-path",
+            manifest,
+            "--cmd",
+            "echo $INLINE_VAR {{ pixi.environment.name }} {{ args }}",
+            "hello",
+        ],
+        stdout_contains="from-activation default hello",
+    )
+
+    # The inline command is not added to the manifest
+    assert manifest.read_text() == toml
+
+
+def test_run_suggests_similar_task(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    build = "echo building"
+    test = "echo testing"
+    """
+    manifest.write_text(toml)
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "biuld"],
+        ExitCode.COMMAND_NOT_FOUND,
+        stderr_contains=["Available tasks", "Did you mean 'build'?"],
+        stderr_excludes="'test'",
+    )
+
+    # Unrelated names don't get a suggestion
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "xyzzy"],
+        ExitCode.COMMAND_NOT_FOUND,
+        stderr_excludes="Did you mean",
+    )
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--list"],
+        stderr_contains=["build", "test"],
+    )
+
+
+def test_run_with_activation_scripts_for_all_platforms(
+    pixi: Path, tmp_pixi_workspace: Path
+) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [activation]
+    scripts = ["env_setup.sh", "env_setup.bat"]
+    [tasks]
+    task = "echo $MY_SCRIPT_VAR"
+    """
+    manifest.write_text(toml)
+    tmp_pixi_workspace.joinpath("env_setup.sh").write_text('export MY_SCRIPT_VAR="from sh"\n')
+    tmp_pixi_workspace.joinpath("env_setup.bat").write_text(
+        '@echo off\nset "MY_SCRIPT_VAR=from bat"\n'
+    )
+
+    # Only the script that matches the current platform should be sourced
+    if platform.system() == "Windows":
+        verify_cli_command(
+            [pixi, "run", "--manifest-path", manifest, "task"],
+            stdout_contains="from bat",
+        )
+    else:
+        verify_cli_command(
+            [pixi, "run", "--manifest-path", manifest, "task"],
+            stdout_contains="from sh",
+        )
+
+
 def test_detached_environments_run(pixi: Path, tmp_path: Path, dummy_channel_1: str) -> None:
     tmp_project = tmp_path.joinpath("pixi-project")
     tmp_project.mkdir()
@@ -315,6 +533,33 @@ def test_detached_environments_run(pixi: Path, tmp_path: Path, dummy_channel_1:
     )
 
 
+def test_install_and_run_with_prefix(pixi: Path, tmp_path: Path, dummy_channel_1: str) -> None:
+    tmp_project = tmp_path.joinpath("pixi-project")
+    tmp_project.mkdir()
+    manifest = tmp_project.joinpath("pixi.toml")
+
+    verify_cli_command([pixi, "init", tmp_project, "--channel", dummy_channel_1])
+    verify_cli_command([pixi, "add", "dummy-a", "--no-install", "--manifest-path", manifest])
+    verify_cli_command(
+        [pixi, "task", "add", "--manifest-path", manifest, "path", "echo $PATH"],
+    )
+
+    # A relative prefix is resolved against the workspace root
+    verify_cli_command(
+        [pixi, "install", "--manifest-path", manifest, "--prefix", "custom-prefix"],
+        stderr_contains="custom-prefix",
+    )
+    prefix = tmp_project.joinpath("custom-prefix")
+    assert prefix.joinpath("conda-meta").is_dir()
+    assert not tmp_project.joinpath(".pixi", "envs", "default").exists()
+    assert tmp_project.joinpath("pixi.lock").exists()
+
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--prefix", prefix, "path"],
+        stdout_contains=str(prefix),
+    )
+
+
 def test_run_help(pixi: Path, tmp_pixi_workspace: Path) -> None:
     manifest = tmp_pixi_workspace.joinpath("pixi.toml")
     manifest.write_text(EMPTY_BOILERPLATE_PROJECT)
@@ -1751,6 +1996,48 @@ def test_signal_forwarding(pixi: Path, tmp_pixi_workspace: Path) -> None:
         raise AssertionError("Output file was not created")
 
 
+@pytest.mark.skipif(
+    sys.platform == "win32",
+    reason="Signal handling is different on Windows",
+)
+def test_sigint_stops_task_chain(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    """Test that an interrupted task doesn't start the remaining tasks."""
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    slow = "echo started > started.txt && sleep 30"
+    next = {{ cmd = "echo next > next.txt", depends-on = [{{ task = "slow", continue-on-error = true }}] }}
+    """
+    manifest.write_text(toml)
+
+    # A non-interactive stdin makes pixi forward the SIGINT to the task
+    process = subprocess.Popen(
+        [pixi, "run", "--manifest-path", manifest, "next"],
+        cwd=tmp_pixi_workspace,
+        stdin=subprocess.DEVNULL,
+    )
+
+    started_file = tmp_pixi_workspace.joinpath("started.txt")
+    deadline = time.monotonic() + 30
+    while not started_file.exists():
+        if process.poll() is not None:
+            raise AssertionError(
+                f"pixi exited before the task started (code {process.returncode})"
+            )
+        if time.monotonic() > deadline:
+            process.kill()
+            raise AssertionError("Timed out waiting for the task to start")
+        time.sleep(0.1)
+
+    process.send_signal(signal.SIGINT)
+
+    # pixi exits via SIGINT, which is reported as 130 by a shell
+    exit_code = process.wait(timeout=10)
+    assert exit_code in (130, -signal.SIGINT), f"Process exited with code {exit_code}"
+    assert not tmp_pixi_workspace.joinpath("next.txt").exists()
+
+
 @pytest.mark.parametrize("in_out", ["input", "output"])
 def test_task_inputs_outputs_missing_no_initial_cache(
     pixi: Path, tmp_pixi_workspace: Path, in_out: str
@@ -1792,3 +2079,87 @@ def test_task_inputs_outputs_missing_no_initial_cache(
         stdout_contains="running; no files are created",
         stderr_excludes="cache hit",
     )
+
+
+def test_run_stops_on_failure(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    fail = "exit 3"
+    after = "echo after"
+    all = {{ depends-on = ["fail", "after"] }}
+    """
+    manifest.write_text(toml)
+
+    # By default the first failure stops the run and its exit code is propagated
+    output = subprocess.run(
+        [pixi, "run", "--manifest-path", manifest, "all"], capture_output=True, text=True
+    )
+    assert output.returncode == 3
+    assert "after" not in output.stdout
+
+    # With `--keep-going` the remaining tasks still run
+    output = subprocess.run(
+        [pixi, "run", "--manifest-path", manifest, "--keep-going", "all"],
+        capture_output=True,
+        text=True,
+    )
+    assert output.returncode == 3
+    assert "after" in output.stdout
+    assert "Failed tasks: fail (exit code 3)" in output.stderr
+
+
+def test_run_continue_on_error_dependency(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    toml = f"""
+    {EMPTY_BOILERPLATE_PROJECT}
+    [tasks]
+    optional = "exit 3"
+    main = {{ cmd = "echo main-ran", depends-on = [{{ task = "optional", continue-on-error = true }}] }}
+    """
+    manifest.write_text(toml)
+
+    # The failing dependency is tolerated, the main task still runs and succeeds
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "main"],
+        stdout_contains="main-ran",
+        stderr_contains="task 'optional' failed with exit code 3",
+    )
+
+
+@pytest.mark.slow
+def test_run_updates_stale_lock_file(
+    pixi: Path, tmp_pixi_workspace: Path, dummy_channel_1: str
+) -> None:
+    manifest = tmp_pixi_workspace.joinpath("pixi.toml")
+    lock_file = tmp_pixi_workspace.joinpath("pixi.lock")
+    toml = f"""
+    [workspace]
+    name = "test"
+    channels = ["{dummy_channel_1}"]
+    platforms = ["{CURRENT_PLATFORM}"]
+
+    [tasks]
+    hello = "echo hello"
+    """
+    manifest.write_text(toml)
+    verify_cli_command([pixi, "run", "--manifest-path", manifest, "hello"], stdout_contains="hello")
+    assert "dummy-a" not in lock_file.read_text()
+
+    # Adding a dependency makes the lock file stale, `run` should refresh it
+    manifest.write_text(toml + "\n[dependencies]\ndummy-a = '*'\n")
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "hello"],
+        stdout_contains="hello",
+        stderr_contains="The lock file is stale, updating it",
+    )
+    assert "dummy-a" in lock_file.read_text()
+
+    # With `--locked` a stale lock file is an error that names the missing dependency
+    manifest.write_text(toml + "\n[dependencies]\ndummy-a = '*'\ndummy-b = '*'\n")
+    verify_cli_command(
+        [pixi, "run", "--manifest-path", manifest, "--locked", "hello"],
+        ExitCode.FAILURE,
+        stderr_contains=["lock file not up-to-date", "dummy-b"],
+    )
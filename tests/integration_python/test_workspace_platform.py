@@ -549,6 +549,30 @@ def test_lockfile_records_removed_platform_lazy_pruning(
     assert "linux-64" in lock_names
 
 
+def test_lockfile_platforms_from_env(pixi: Path, tmp_pixi_workspace: Path) -> None:
+    manifest = _seed_workspace(tmp_pixi_workspace)
+    manifest_content = manifest.read_text()
+
+    # `PIXI_PLATFORMS` replaces the platforms of the manifest during the solve
+    verify_cli_command(
+        [pixi, "lock", "--manifest-path", manifest],
+        env={"PIXI_PLATFORMS": "linux-64, osx-arm64"},
+    )
+    lock_names = [
+        p if isinstance(p, str) else p["name"] for p in _lockfile_platforms(tmp_pixi_workspace)
+    ]
+    assert "linux-64" in lock_names
+    assert "osx-arm64" in lock_names
+    assert manifest.read_text() == manifest_content
+
+    verify_cli_command(
+        [pixi, "lock", "--manifest-path", manifest],
+        ExitCode.FAILURE,
+        env={"PIXI_PLATFORMS": "linux-64,not-a-platform"},
+        stderr_contains="invalid platform 'not-a-platform' in PIXI_PLATFORMS",
+    )
+
+
 # ----------------------------------------------------------------------------
 # edit
 # ----------------------------------------------------------------------------